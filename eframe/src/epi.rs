@@ -243,6 +243,15 @@ pub struct NativeOptions {
 
     /// What rendering backend to use.
     pub renderer: Renderer,
+
+    /// If set, the [`egui::RawInput`] of every frame is recorded from the start
+    /// and written to this file (see `egui::recording::InputRecording::to_ron`).
+    ///
+    /// The file is written each time the app state is saved (see [`App::auto_save_interval`])
+    /// and on exit.
+    ///
+    /// Requires the `recording` feature. Ignored on the web.
+    pub input_recording_path: Option<std::path::PathBuf>,
 }
 
 impl Default for NativeOptions {
@@ -265,6 +274,7 @@ impl Default for NativeOptions {
             stencil_buffer: 0,
             hardware_acceleration: HardwareAcceleration::Preferred,
             renderer: Renderer::default(),
+            input_recording_path: None,
         }
     }
 }
@@ -371,6 +381,11 @@ pub struct Frame {
     /// [`egui::PaintCallback`]s.
     #[cfg(feature = "wgpu")]
     pub render_state: Option<egui_wgpu::RenderState>,
+
+    /// Records the input of each frame, if turned on.
+    #[cfg(feature = "recording")]
+    #[doc(hidden)]
+    pub input_recorder: Option<egui::recording::InputRecorder>,
 }
 
 impl Frame {
@@ -446,6 +461,30 @@ impl Frame {
         self.output.drag_window = true;
     }
 
+    /// Start recording the [`egui::RawInput`] of every frame, starting with the next one.
+    ///
+    /// Any previous recording is discarded. See [`egui::recording`].
+    #[cfg(feature = "recording")]
+    pub fn start_recording_input(&mut self) {
+        self.input_recorder = Some(Default::default());
+    }
+
+    /// Stop recording and return what was recorded since [`Self::start_recording_input`].
+    ///
+    /// Returns `None` if we were not recording.
+    #[cfg(feature = "recording")]
+    pub fn stop_recording_input(&mut self) -> Option<egui::recording::InputRecording> {
+        self.input_recorder
+            .take()
+            .map(egui::recording::InputRecorder::finish)
+    }
+
+    /// Are we recording the input (see [`Self::start_recording_input`])?
+    #[cfg(feature = "recording")]
+    pub fn is_recording_input(&self) -> bool {
+        self.input_recorder.is_some()
+    }
+
     /// for integrations only: call once per frame
     #[doc(hidden)]
     pub fn take_app_output(&mut self) -> backend::AppOutput {
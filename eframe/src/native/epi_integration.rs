@@ -53,6 +53,7 @@ pub fn window_builder(
         stencil_buffer: _,        // used in `fn create_display`
         hardware_acceleration: _, // used in `fn create_display`
         renderer: _,              // used in `fn run_native`
+        input_recording_path: _,  // used in `fn run_glow` and `fn run_wgpu`
     } = native_options;
 
     let window_icon = icon_data.clone().and_then(load_icon);
@@ -91,7 +92,7 @@ pub fn window_builder(
     window_builder
 }
 
-fn load_icon(icon_data: epi::IconData) -> Option<winit::window::Icon> {
+pub fn load_icon(icon_data: epi::IconData) -> Option<winit::window::Icon> {
     winit::window::Icon::from_rgba(icon_data.rgba, icon_data.width, icon_data.height).ok()
 }
 
@@ -180,6 +181,9 @@ pub struct EpiIntegration {
     /// When set, it is time to quit
     quit: bool,
     can_drag_window: bool,
+    /// Where to save the input recording, if any.
+    #[cfg(feature = "recording")]
+    input_recording_path: Option<std::path::PathBuf>,
 }
 
 impl EpiIntegration {
@@ -211,6 +215,8 @@ impl EpiIntegration {
             gl,
             #[cfg(feature = "wgpu")]
             render_state,
+            #[cfg(feature = "recording")]
+            input_recorder: None,
         };
 
         if prefer_dark_mode == Some(true) {
@@ -232,14 +238,23 @@ impl EpiIntegration {
             pending_full_output: Default::default(),
             quit: false,
             can_drag_window: false,
+            #[cfg(feature = "recording")]
+            input_recording_path: None,
         }
     }
 
+    /// Record the input of every frame from now on, and write it to `path` on [`Self::save`].
+    #[cfg(feature = "recording")]
+    pub fn record_input_to(&mut self, path: std::path::PathBuf) {
+        self.frame.start_recording_input();
+        self.input_recording_path = Some(path);
+    }
+
     pub fn warm_up(&mut self, app: &mut dyn epi::App, window: &winit::window::Window) {
         crate::profile_function!();
         let saved_memory: egui::Memory = self.egui_ctx.memory().clone();
         self.egui_ctx.memory().set_everything_is_visible(true);
-        let full_output = self.update(app, window);
+        let full_output = self.update(app, window, Default::default());
         self.pending_full_output.append(full_output); // Handle it next frame
         *self.egui_ctx.memory() = saved_memory; // We don't want to remember that windows were huge.
         self.egui_ctx.clear_animations();
@@ -267,15 +282,24 @@ impl EpiIntegration {
         self.egui_winit.on_event(&self.egui_ctx, event);
     }
 
+    /// `viewport_inputs` is the input for the open child viewports (see [`egui::RawInput::viewports`]).
     pub fn update(
         &mut self,
         app: &mut dyn epi::App,
         window: &winit::window::Window,
+        viewport_inputs: egui::ViewportIdMap<egui::RawInput>,
     ) -> egui::FullOutput {
         let frame_start = std::time::Instant::now();
 
         self.frame.info.window_info = read_window_info(window, self.egui_ctx.pixels_per_point());
-        let raw_input = self.egui_winit.take_egui_input(window);
+        let mut raw_input = self.egui_winit.take_egui_input(window);
+        raw_input.viewports = viewport_inputs;
+
+        #[cfg(feature = "recording")]
+        if let Some(recorder) = &mut self.frame.input_recorder {
+            recorder.record(&raw_input);
+        }
+
         let full_output = self.egui_ctx.run(raw_input, |egui_ctx| {
             crate::profile_scope!("App::update");
             app.update(egui_ctx, &mut self.frame);
@@ -327,6 +351,9 @@ impl EpiIntegration {
     }
 
     pub fn save(&mut self, _app: &mut dyn epi::App, _window: &winit::window::Window) {
+        #[cfg(feature = "recording")]
+        self.save_input_recording();
+
         #[cfg(feature = "persistence")]
         if let Some(storage) = self.frame.storage_mut() {
             crate::profile_function!();
@@ -352,6 +379,24 @@ impl EpiIntegration {
             storage.flush();
         }
     }
+
+    #[cfg(feature = "recording")]
+    fn save_input_recording(&self) {
+        let (path, recorder) = match (&self.input_recording_path, &self.frame.input_recorder) {
+            (Some(path), Some(recorder)) => (path, recorder),
+            _ => return,
+        };
+        crate::profile_function!();
+
+        match recorder.recording().to_ron() {
+            Ok(ron) => {
+                if let Err(err) = std::fs::write(path, ron) {
+                    tracing::warn!("Failed to write input recording to {:?}: {}", path, err);
+                }
+            }
+            Err(err) => tracing::warn!("Failed to serialize input recording: {}", err),
+        }
+    }
 }
 
 #[cfg(feature = "persistence")]
@@ -1,5 +1,6 @@
 mod epi_integration;
 pub mod run;
+mod viewports;
 
 /// File storage which can be used by native backends.
 #[cfg(feature = "persistence")]
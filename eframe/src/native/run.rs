@@ -1,21 +1,62 @@
-use super::epi_integration;
+use super::{epi_integration, viewports};
 use crate::epi;
 use egui_winit::winit;
+#[cfg(feature = "glow")]
+use viewports::HasWindow as _;
 
 struct RequestRepaintEvent;
 
+/// A native window with an OpenGL context.
 #[cfg(feature = "glow")]
-#[allow(unsafe_code)]
-fn create_display(
-    native_options: &NativeOptions,
-    window_builder: winit::window::WindowBuilder,
-    event_loop: &winit::event_loop::EventLoop<RequestRepaintEvent>,
-) -> (
-    glutin::WindowedContext<glutin::PossiblyCurrent>,
-    glow::Context,
-) {
-    crate::profile_function!();
+struct GlWindow {
+    /// Only `None` while we are switching which context is current.
+    context: Option<glutin::WindowedContext<glutin::PossiblyCurrent>>,
+}
+
+#[cfg(feature = "glow")]
+impl GlWindow {
+    fn context(&self) -> &glutin::WindowedContext<glutin::PossiblyCurrent> {
+        self.context.as_ref().unwrap()
+    }
 
+    /// With more than one window, call this before painting to a window.
+    #[allow(unsafe_code)]
+    fn make_current(&mut self) {
+        let context = self.context.take().unwrap();
+        if context.is_current() {
+            self.context = Some(context);
+            return;
+        }
+        self.context = Some(match unsafe { context.make_current() } {
+            Ok(context) => context,
+            Err((context, err)) => {
+                tracing::error!("Failed to make OpenGL context current: {}", err);
+                context
+            }
+        });
+    }
+
+    fn resize(&self, physical_size: winit::dpi::PhysicalSize<u32>) {
+        self.context().resize(physical_size);
+    }
+
+    fn swap_buffers(&self) {
+        self.context().swap_buffers().unwrap();
+    }
+}
+
+#[cfg(feature = "glow")]
+impl viewports::HasWindow for GlWindow {
+    fn window(&self) -> &winit::window::Window {
+        self.context().window()
+    }
+}
+
+#[cfg(feature = "glow")]
+fn context_builder<'a, T: glutin::ContextCurrentState>(
+    native_options: &NativeOptions,
+    context_builder: glutin::ContextBuilder<'a, T>,
+) -> glutin::ContextBuilder<'a, T> {
     use crate::HardwareAcceleration;
 
     let hardware_acceleration = match native_options.hardware_acceleration {
@@ -24,14 +65,26 @@ fn create_display(
         HardwareAcceleration::Off => Some(false),
     };
 
+    context_builder
+        .with_hardware_acceleration(hardware_acceleration)
+        .with_depth_buffer(native_options.depth_buffer)
+        .with_multisampling(native_options.multisampling)
+        .with_srgb(true)
+        .with_stencil_buffer(native_options.stencil_buffer)
+        .with_vsync(native_options.vsync)
+}
+
+#[cfg(feature = "glow")]
+#[allow(unsafe_code)]
+fn create_display(
+    native_options: &NativeOptions,
+    window_builder: winit::window::WindowBuilder,
+    event_loop: &winit::event_loop::EventLoop<RequestRepaintEvent>,
+) -> (GlWindow, glow::Context) {
+    crate::profile_function!();
+
     let gl_window = unsafe {
-        glutin::ContextBuilder::new()
-            .with_hardware_acceleration(hardware_acceleration)
-            .with_depth_buffer(native_options.depth_buffer)
-            .with_multisampling(native_options.multisampling)
-            .with_srgb(true)
-            .with_stencil_buffer(native_options.stencil_buffer)
-            .with_vsync(native_options.vsync)
+        context_builder(native_options, glutin::ContextBuilder::new())
             .build_windowed(window_builder, event_loop)
             .unwrap()
             .make_current()
@@ -40,7 +93,43 @@ fn create_display(
 
     let gl = unsafe { glow::Context::from_loader_function(|s| gl_window.get_proc_address(s)) };
 
-    (gl_window, gl)
+    (
+        GlWindow {
+            context: Some(gl_window),
+        },
+        gl,
+    )
+}
+
+/// Create the window of a child viewport, with a context that shares textures etc with `root`.
+///
+/// Leaves the new context current.
+#[cfg(feature = "glow")]
+#[allow(unsafe_code)]
+fn create_child_gl_window(
+    native_options: &NativeOptions,
+    window_builder: winit::window::WindowBuilder,
+    event_loop: &winit::event_loop::EventLoopWindowTarget<RequestRepaintEvent>,
+    root: &GlWindow,
+) -> GlWindow {
+    crate::profile_function!();
+
+    let context = unsafe {
+        context_builder(
+            native_options,
+            glutin::ContextBuilder::new().with_shared_lists(root.context().context()),
+        )
+        // Only the root window waits for vsync, or each window would wait for its own.
+        .with_vsync(false)
+        .build_windowed(window_builder, event_loop)
+        .unwrap()
+        .make_current()
+        .unwrap()
+    };
+
+    GlWindow {
+        context: Some(context),
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -60,7 +149,7 @@ pub fn run_glow(
 
     let window_builder =
         epi_integration::window_builder(native_options, &window_settings).with_title(app_name);
-    let (gl_window, gl) = create_display(native_options, window_builder, &event_loop);
+    let (mut gl_window, gl) = create_display(native_options, window_builder, &event_loop);
     let gl = std::sync::Arc::new(gl);
 
     let mut painter = egui_glow::Painter::new(gl.clone(), None, "")
@@ -75,6 +164,12 @@ pub fn run_glow(
         #[cfg(feature = "wgpu")]
         None,
     );
+    integration.egui_ctx.set_supports_viewports(true);
+
+    #[cfg(feature = "recording")]
+    if let Some(path) = &native_options.input_recording_path {
+        integration.record_input_to(path.clone());
+    }
 
     {
         let event_loop_proxy = egui::mutex::Mutex::new(event_loop.create_proxy());
@@ -97,15 +192,19 @@ pub fn run_glow(
     }
 
     let mut is_focused = true;
+    let mut viewports = viewports::Viewports::<GlWindow>::default();
+    let native_options = native_options.clone();
 
-    event_loop.run(move |event, _, control_flow| {
-        let window = gl_window.window();
-
+    event_loop.run(move |event, event_loop, control_flow| {
         let mut redraw = || {
             #[cfg(feature = "puffin")]
             puffin::GlobalProfiler::lock().new_frame();
             crate::profile_scope!("frame");
 
+            let viewport_inputs = viewports.take_egui_inputs();
+
+            gl_window.make_current();
+            let window = gl_window.window();
             let screen_size_in_pixels: [u32; 2] = window.inner_size().into();
 
             egui_glow::painter::clear(
@@ -119,7 +218,8 @@ pub fn run_glow(
                 needs_repaint,
                 textures_delta,
                 shapes,
-            } = integration.update(app.as_mut(), window);
+                viewports: viewport_outputs,
+            } = integration.update(app.as_mut(), window, viewport_inputs);
 
             integration.handle_platform_output(window, platform_output);
 
@@ -139,9 +239,68 @@ pub fn run_glow(
 
             {
                 crate::profile_scope!("swap_buffers");
-                gl_window.swap_buffers().unwrap();
+                gl_window.swap_buffers();
             }
 
+            {
+                crate::profile_scope!("viewports");
+
+                if !viewport_outputs.is_empty() {
+                    // Vertex array objects are not shared between the contexts of different windows,
+                    // so stop using one as soon as there is more than one window (and GL context):
+                    painter.disable_vertex_array_object();
+                }
+
+                // Dropping a closed viewport closes its window:
+                viewports.update(
+                    event_loop,
+                    &native_options,
+                    painter.max_texture_side(),
+                    &viewport_outputs,
+                    |event_loop, _viewport_id, window_builder| {
+                        create_child_gl_window(
+                            &native_options,
+                            window_builder,
+                            event_loop,
+                            &gl_window,
+                        )
+                    },
+                );
+
+                for (viewport_id, viewport) in viewport_outputs {
+                    let child = match viewports.get_mut(viewport_id) {
+                        Some(child) => child,
+                        None => continue,
+                    };
+                    child.window.make_current();
+                    child.handle_platform_output(
+                        &integration.egui_ctx,
+                        viewport.output.platform_output,
+                    );
+
+                    let screen_size_in_pixels: [u32; 2] = child.window.window().inner_size().into();
+                    egui_glow::painter::clear(
+                        &gl,
+                        screen_size_in_pixels,
+                        app.clear_color(&integration.egui_ctx.style().visuals),
+                    );
+                    let clipped_primitives = integration.egui_ctx.tessellate_with_pixels_per_point(
+                        viewport.output.shapes,
+                        viewport.pixels_per_point,
+                    );
+                    painter.paint_primitives(
+                        screen_size_in_pixels,
+                        viewport.pixels_per_point,
+                        &clipped_primitives,
+                    );
+                    child.window.swap_buffers();
+                }
+
+                gl_window.make_current();
+            }
+
+            let window = gl_window.window();
+
             *control_flow = if integration.should_quit() {
                 winit::event_loop::ControlFlow::Exit
             } else if needs_repaint {
@@ -171,6 +330,30 @@ pub fn run_glow(
             winit::event::Event::RedrawEventsCleared if cfg!(windows) => redraw(),
             winit::event::Event::RedrawRequested(_) if !cfg!(windows) => redraw(),
 
+            winit::event::Event::WindowEvent { window_id, event }
+                if window_id != gl_window.window().id() =>
+            {
+                if let Some(viewport_id) = viewports.viewport_of_window(window_id) {
+                    if let Some(child) = viewports.get_mut(viewport_id) {
+                        match &event {
+                            winit::event::WindowEvent::Resized(physical_size) => {
+                                if physical_size.width > 0 && physical_size.height > 0 {
+                                    child.window.resize(*physical_size);
+                                }
+                            }
+                            winit::event::WindowEvent::ScaleFactorChanged {
+                                new_inner_size,
+                                ..
+                            } => {
+                                child.window.resize(**new_inner_size);
+                            }
+                            _ => {}
+                        }
+                    }
+                    viewports.on_event(&integration.egui_ctx, viewport_id, &event);
+                    gl_window.window().request_redraw();
+                }
+            }
             winit::event::Event::WindowEvent { event, .. } => {
                 match &event {
                     winit::event::WindowEvent::Focused(new_focused) => {
@@ -197,14 +380,16 @@ pub fn run_glow(
                 if integration.should_quit() {
                     *control_flow = winit::event_loop::ControlFlow::Exit;
                 }
-                window.request_redraw(); // TODO(emilk): ask egui if the events warrants a repaint instead
+                gl_window.window().request_redraw(); // TODO(emilk): ask egui if the events warrants a repaint instead
             }
             winit::event::Event::LoopDestroyed => {
-                integration.save(&mut *app, window);
+                integration.save(&mut *app, gl_window.window());
                 app.on_exit(Some(&gl));
                 painter.destroy();
             }
-            winit::event::Event::UserEvent(RequestRepaintEvent) => window.request_redraw(),
+            winit::event::Event::UserEvent(RequestRepaintEvent) => {
+                gl_window.window().request_redraw();
+            }
             _ => (),
         }
     });
@@ -257,6 +442,12 @@ pub fn run_wgpu(
         None,
         Some(render_state.clone()),
     );
+    integration.egui_ctx.set_supports_viewports(true);
+
+    #[cfg(feature = "recording")]
+    if let Some(path) = &native_options.input_recording_path {
+        integration.record_input_to(path.clone());
+    }
 
     {
         let event_loop_proxy = egui::mutex::Mutex::new(event_loop.create_proxy());
@@ -279,8 +470,10 @@ pub fn run_wgpu(
     }
 
     let mut is_focused = true;
+    let mut viewports = viewports::Viewports::<winit::window::Window>::default();
+    let native_options = native_options.clone();
 
-    event_loop.run(move |event, _, control_flow| {
+    event_loop.run(move |event, event_loop, control_flow| {
         let window = &window;
 
         let mut redraw = || {
@@ -288,12 +481,15 @@ pub fn run_wgpu(
             puffin::GlobalProfiler::lock().new_frame();
             crate::profile_scope!("frame");
 
+            let viewport_inputs = viewports.take_egui_inputs();
+
             let egui::FullOutput {
                 platform_output,
                 needs_repaint,
                 textures_delta,
                 shapes,
-            } = integration.update(app.as_mut(), window);
+                viewports: viewport_outputs,
+            } = integration.update(app.as_mut(), window, viewport_inputs);
 
             integration.handle_platform_output(window, platform_output);
 
@@ -309,6 +505,55 @@ pub fn run_wgpu(
                 &textures_delta,
             );
 
+            {
+                crate::profile_scope!("viewports");
+
+                let closed = viewports.update(
+                    event_loop,
+                    &native_options,
+                    painter.max_texture_side().unwrap_or(2048),
+                    &viewport_outputs,
+                    |event_loop, viewport_id, window_builder| {
+                        let window = window_builder.build(event_loop).unwrap();
+                        // SAFETY: the window is only dropped after its surface is removed (below).
+                        #[allow(unsafe_code)]
+                        unsafe {
+                            painter.set_viewport_window(viewport_id, Some(&window));
+                        }
+                        window
+                    },
+                );
+                for (viewport_id, _window) in closed {
+                    #[allow(unsafe_code)]
+                    unsafe {
+                        painter.set_viewport_window(viewport_id, None);
+                    }
+                }
+
+                for (viewport_id, viewport) in viewport_outputs {
+                    let child = match viewports.get_mut(viewport_id) {
+                        Some(child) => child,
+                        None => continue,
+                    };
+                    child.handle_platform_output(
+                        &integration.egui_ctx,
+                        viewport.output.platform_output,
+                    );
+
+                    let clipped_primitives = integration.egui_ctx.tessellate_with_pixels_per_point(
+                        viewport.output.shapes,
+                        viewport.pixels_per_point,
+                    );
+                    painter.paint_viewport_and_update_textures(
+                        viewport_id,
+                        viewport.pixels_per_point,
+                        app.clear_color(&integration.egui_ctx.style().visuals),
+                        &clipped_primitives,
+                        &Default::default(),
+                    );
+                }
+            }
+
             *control_flow = if integration.should_quit() {
                 winit::event_loop::ControlFlow::Exit
             } else if needs_repaint {
@@ -347,6 +592,33 @@ pub fn run_wgpu(
                 painter.set_window(None);
             },
 
+            winit::event::Event::WindowEvent { window_id, event } if window_id != window.id() => {
+                if let Some(viewport_id) = viewports.viewport_of_window(window_id) {
+                    match &event {
+                        winit::event::WindowEvent::Resized(physical_size) => {
+                            if physical_size.width > 0 && physical_size.height > 0 {
+                                painter.on_viewport_window_resized(
+                                    viewport_id,
+                                    physical_size.width,
+                                    physical_size.height,
+                                );
+                            }
+                        }
+                        winit::event::WindowEvent::ScaleFactorChanged {
+                            new_inner_size, ..
+                        } => {
+                            painter.on_viewport_window_resized(
+                                viewport_id,
+                                new_inner_size.width,
+                                new_inner_size.height,
+                            );
+                        }
+                        _ => {}
+                    }
+                    viewports.on_event(&integration.egui_ctx, viewport_id, &event);
+                    window.request_redraw();
+                }
+            }
             winit::event::Event::WindowEvent { event, .. } => {
                 match &event {
                     winit::event::WindowEvent::Focused(new_focused) => {
@@ -378,6 +650,13 @@ pub fn run_wgpu(
             winit::event::Event::LoopDestroyed => {
                 integration.save(&mut *app, window);
 
+                for viewport_id in viewports.viewport_ids() {
+                    #[allow(unsafe_code)]
+                    unsafe {
+                        painter.set_viewport_window(viewport_id, None);
+                    }
+                }
+
                 #[cfg(feature = "glow")]
                 app.on_exit(None);
 
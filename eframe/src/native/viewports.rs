@@ -0,0 +1,216 @@
+//! Native windows for the child viewports of [`egui::Context::show_viewport`].
+
+use crate::epi;
+use egui_winit::winit;
+use winit::event_loop::EventLoopWindowTarget;
+
+use super::epi_integration::points_to_size;
+
+/// Something that has a native window, e.g. a window with a graphics context.
+pub trait HasWindow {
+    fn window(&self) -> &winit::window::Window;
+}
+
+impl HasWindow for winit::window::Window {
+    fn window(&self) -> &winit::window::Window {
+        self
+    }
+}
+
+/// The native window of a child viewport.
+pub struct ChildViewport<W> {
+    /// The window, plus whatever the renderer needs to paint to it.
+    pub window: W,
+    egui_winit: egui_winit::State,
+    builder: egui::ViewportBuilder,
+    close_requested: bool,
+}
+
+/// The native windows of all child viewports.
+///
+/// `W` is the window, plus whatever the renderer needs to paint to it.
+pub struct Viewports<W> {
+    children: egui::ViewportIdMap<ChildViewport<W>>,
+}
+
+impl<W> Default for Viewports<W> {
+    fn default() -> Self {
+        Self {
+            children: Default::default(),
+        }
+    }
+}
+
+impl<W: HasWindow> Viewports<W> {
+    pub fn get_mut(&mut self, viewport_id: egui::ViewportId) -> Option<&mut ChildViewport<W>> {
+        self.children.get_mut(&viewport_id)
+    }
+
+    /// Which viewport has the given window?
+    pub fn viewport_of_window(
+        &self,
+        window_id: winit::window::WindowId,
+    ) -> Option<egui::ViewportId> {
+        self.children
+            .iter()
+            .find(|(_, child)| child.window.window().id() == window_id)
+            .map(|(viewport_id, _)| *viewport_id)
+    }
+
+    #[cfg(feature = "wgpu")]
+    pub fn viewport_ids(&self) -> impl Iterator<Item = egui::ViewportId> + '_ {
+        self.children.keys().copied()
+    }
+
+    /// Feed a window event of the given viewport to egui.
+    pub fn on_event(
+        &mut self,
+        egui_ctx: &egui::Context,
+        viewport_id: egui::ViewportId,
+        event: &winit::event::WindowEvent<'_>,
+    ) {
+        if let Some(child) = self.children.get_mut(&viewport_id) {
+            if matches!(event, winit::event::WindowEvent::CloseRequested) {
+                child.close_requested = true;
+            }
+            child.egui_winit.on_event(egui_ctx, event);
+        }
+    }
+
+    /// The input for all child viewports, for [`egui::RawInput::viewports`].
+    pub fn take_egui_inputs(&mut self) -> egui::ViewportIdMap<egui::RawInput> {
+        self.children
+            .iter_mut()
+            .map(|(viewport_id, child)| {
+                let mut raw_input = child.egui_winit.take_egui_input(child.window.window());
+                raw_input.close_requested = std::mem::take(&mut child.close_requested);
+                (*viewport_id, raw_input)
+            })
+            .collect()
+    }
+
+    /// Open windows for new viewports, close the windows of viewports that are gone,
+    /// and update the windows of the rest.
+    ///
+    /// `create_window` creates the native window of a viewport from a [`winit::window::WindowBuilder`].
+    /// Returns the viewports that were closed.
+    pub fn update<E>(
+        &mut self,
+        event_loop: &EventLoopWindowTarget<E>,
+        native_options: &epi::NativeOptions,
+        max_texture_side: usize,
+        viewports: &egui::ViewportIdMap<egui::ViewportOutput>,
+        mut create_window: impl FnMut(
+            &EventLoopWindowTarget<E>,
+            egui::ViewportId,
+            winit::window::WindowBuilder,
+        ) -> W,
+    ) -> Vec<(egui::ViewportId, W)> {
+        let closed_ids: Vec<egui::ViewportId> = self
+            .children
+            .keys()
+            .filter(|viewport_id| !viewports.contains_key(viewport_id))
+            .copied()
+            .collect();
+        let closed = closed_ids
+            .into_iter()
+            .filter_map(|viewport_id| {
+                let child = self.children.remove(&viewport_id)?;
+                Some((viewport_id, child.window))
+            })
+            .collect();
+
+        for (viewport_id, viewport) in viewports {
+            if let Some(child) = self.children.get_mut(viewport_id) {
+                if child.builder != viewport.builder {
+                    update_window(child.window.window(), &child.builder, &viewport.builder);
+                    child.builder = viewport.builder.clone();
+                }
+            } else {
+                let window_builder = window_builder(native_options, &viewport.builder);
+                let window = create_window(event_loop, *viewport_id, window_builder);
+
+                let mut egui_winit = egui_winit::State::new(event_loop);
+                egui_winit.set_max_texture_side(max_texture_side);
+                egui_winit.set_pixels_per_point(window.window().scale_factor() as f32);
+
+                self.children.insert(
+                    *viewport_id,
+                    ChildViewport {
+                        window,
+                        egui_winit,
+                        builder: viewport.builder.clone(),
+                        close_requested: false,
+                    },
+                );
+            }
+        }
+
+        closed
+    }
+}
+
+impl<W: HasWindow> ChildViewport<W> {
+    pub fn handle_platform_output(
+        &mut self,
+        egui_ctx: &egui::Context,
+        platform_output: egui::PlatformOutput,
+    ) {
+        self.egui_winit
+            .handle_platform_output(self.window.window(), egui_ctx, platform_output);
+    }
+}
+
+fn window_builder(
+    native_options: &epi::NativeOptions,
+    builder: &egui::ViewportBuilder,
+) -> winit::window::WindowBuilder {
+    let egui::ViewportBuilder {
+        title,
+        inner_size,
+        resizable,
+        decorations,
+        always_on_top,
+    } = builder;
+
+    let mut window_builder = winit::window::WindowBuilder::new()
+        .with_title(title)
+        .with_resizable(*resizable)
+        .with_decorations(*decorations)
+        .with_always_on_top(*always_on_top)
+        .with_transparent(native_options.transparent)
+        .with_window_icon(
+            native_options
+                .icon_data
+                .clone()
+                .and_then(super::epi_integration::load_icon),
+        );
+    if let Some(inner_size) = *inner_size {
+        window_builder = window_builder.with_inner_size(points_to_size(inner_size));
+    }
+    window_builder
+}
+
+fn update_window(
+    window: &winit::window::Window,
+    old: &egui::ViewportBuilder,
+    new: &egui::ViewportBuilder,
+) {
+    if old.title != new.title {
+        window.set_title(&new.title);
+    }
+    if old.inner_size != new.inner_size {
+        if let Some(inner_size) = new.inner_size {
+            window.set_inner_size(points_to_size(inner_size));
+        }
+    }
+    if old.resizable != new.resizable {
+        window.set_resizable(new.resizable);
+    }
+    if old.decorations != new.decorations {
+        window.set_decorations(new.decorations);
+    }
+    if old.always_on_top != new.always_on_top {
+        window.set_always_on_top(new.always_on_top);
+    }
+}
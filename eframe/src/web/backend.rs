@@ -20,10 +20,14 @@ pub struct WebInput {
 }
 
 impl WebInput {
-    pub fn new_frame(&mut self, canvas_size: egui::Vec2) -> egui::RawInput {
+    /// `canvas_size` is in browser pixels, which are divided by the [`egui::Context::zoom_factor`].
+    pub fn new_frame(&mut self, canvas_size: egui::Vec2, zoom_factor: f32) -> egui::RawInput {
         egui::RawInput {
-            screen_rect: Some(egui::Rect::from_min_size(Default::default(), canvas_size)),
-            pixels_per_point: Some(native_pixels_per_point()), // We ALWAYS use the native pixels-per-point
+            screen_rect: Some(egui::Rect::from_min_size(
+                Default::default(),
+                canvas_size / zoom_factor,
+            )),
+            pixels_per_point: Some(native_pixels_per_point() * zoom_factor), // We ALWAYS use the native pixels-per-point, times the user zoom
             time: Some(now_sec()),
             ..self.raw.take()
         }
@@ -182,6 +186,8 @@ impl AppRunner {
             gl: Some(painter.gl().clone()),
             #[cfg(feature = "wgpu")]
             render_state: None,
+            #[cfg(feature = "recording")]
+            input_recorder: None,
         };
 
         let needs_repaint: std::sync::Arc<NeedRepaint> = Default::default();
@@ -253,7 +259,14 @@ impl AppRunner {
 
         resize_canvas_to_screen_size(self.canvas_id(), self.app.max_size_points());
         let canvas_size = canvas_size_in_points(self.canvas_id());
-        let raw_input = self.input.new_frame(canvas_size);
+        let raw_input = self
+            .input
+            .new_frame(canvas_size, self.egui_ctx.zoom_factor());
+
+        #[cfg(feature = "recording")]
+        if let Some(recorder) = &mut self.frame.input_recorder {
+            recorder.record(&raw_input);
+        }
 
         let full_output = self.egui_ctx.run(raw_input, |egui_ctx| {
             self.app.update(egui_ctx, &mut self.frame);
@@ -263,6 +276,7 @@ impl AppRunner {
             needs_repaint,
             textures_delta,
             shapes,
+            viewports: _, // child viewports are not supported on web
         } = full_output;
 
         self.handle_platform_output(platform_output);
@@ -316,6 +330,7 @@ impl AppRunner {
             events: _, // already handled
             mutable_text_under_cursor,
             text_cursor_pos,
+            accessibility_tree: _, // no accessibility adapter on web yet
         } = platform_output;
 
         set_cursor_icon(cursor_icon);
@@ -334,7 +349,11 @@ impl AppRunner {
         self.mutable_text_under_cursor = mutable_text_under_cursor;
 
         if self.text_cursor_pos != text_cursor_pos {
-            text_agent::move_text_cursor(text_cursor_pos, self.canvas_id());
+            let zoom_factor = self.egui_ctx.zoom_factor();
+            text_agent::move_text_cursor(
+                text_cursor_pos.map(|pos| (pos.to_vec2() * zoom_factor).to_pos2()),
+                self.canvas_id(),
+            );
             self.text_cursor_pos = text_cursor_pos;
         }
     }
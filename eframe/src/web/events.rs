@@ -241,7 +241,8 @@ pub fn install_canvas_events(runner_container: &AppRunnerContainer) -> Result<()
         "mousedown",
         |event: web_sys::MouseEvent, mut runner_lock| {
             if let Some(button) = button_from_mouse_event(&event) {
-                let pos = pos_from_mouse_event(runner_lock.canvas_id(), &event);
+                let zoom_factor = runner_lock.egui_ctx().zoom_factor();
+                let pos = pos_from_mouse_event(runner_lock.canvas_id(), &event, zoom_factor);
                 let modifiers = runner_lock.input.raw.modifiers;
                 runner_lock
                     .input
@@ -264,7 +265,8 @@ pub fn install_canvas_events(runner_container: &AppRunnerContainer) -> Result<()
         &canvas,
         "mousemove",
         |event: web_sys::MouseEvent, mut runner_lock| {
-            let pos = pos_from_mouse_event(runner_lock.canvas_id(), &event);
+            let zoom_factor = runner_lock.egui_ctx().zoom_factor();
+            let pos = pos_from_mouse_event(runner_lock.canvas_id(), &event, zoom_factor);
             runner_lock
                 .input
                 .raw
@@ -281,7 +283,8 @@ pub fn install_canvas_events(runner_container: &AppRunnerContainer) -> Result<()
         "mouseup",
         |event: web_sys::MouseEvent, mut runner_lock| {
             if let Some(button) = button_from_mouse_event(&event) {
-                let pos = pos_from_mouse_event(runner_lock.canvas_id(), &event);
+                let zoom_factor = runner_lock.egui_ctx().zoom_factor();
+                let pos = pos_from_mouse_event(runner_lock.canvas_id(), &event, zoom_factor);
                 let modifiers = runner_lock.input.raw.modifiers;
                 runner_lock
                     .input
@@ -318,8 +321,13 @@ pub fn install_canvas_events(runner_container: &AppRunnerContainer) -> Result<()
         "touchstart",
         |event: web_sys::TouchEvent, mut runner_lock| {
             let mut latest_touch_pos_id = runner_lock.input.latest_touch_pos_id;
-            let pos =
-                pos_from_touch_event(runner_lock.canvas_id(), &event, &mut latest_touch_pos_id);
+            let zoom_factor = runner_lock.egui_ctx().zoom_factor();
+            let pos = pos_from_touch_event(
+                runner_lock.canvas_id(),
+                &event,
+                &mut latest_touch_pos_id,
+                zoom_factor,
+            );
             runner_lock.input.latest_touch_pos_id = latest_touch_pos_id;
             runner_lock.input.latest_touch_pos = Some(pos);
             let modifiers = runner_lock.input.raw.modifiers;
@@ -346,8 +354,13 @@ pub fn install_canvas_events(runner_container: &AppRunnerContainer) -> Result<()
         "touchmove",
         |event: web_sys::TouchEvent, mut runner_lock| {
             let mut latest_touch_pos_id = runner_lock.input.latest_touch_pos_id;
-            let pos =
-                pos_from_touch_event(runner_lock.canvas_id(), &event, &mut latest_touch_pos_id);
+            let zoom_factor = runner_lock.egui_ctx().zoom_factor();
+            let pos = pos_from_touch_event(
+                runner_lock.canvas_id(),
+                &event,
+                &mut latest_touch_pos_id,
+                zoom_factor,
+            );
             runner_lock.input.latest_touch_pos_id = latest_touch_pos_id;
             runner_lock.input.latest_touch_pos = Some(pos);
             runner_lock
@@ -420,6 +433,9 @@ pub fn install_canvas_events(runner_container: &AppRunnerContainer) -> Result<()
                 _ => 1.0, // DOM_DELTA_PIXEL
             };
 
+            // From browser pixels to egui points:
+            let scroll_multiplier = scroll_multiplier / runner_lock.egui_ctx().zoom_factor();
+
             let mut delta =
                 -scroll_multiplier * egui::vec2(event.delta_x() as f32, event.delta_y() as f32);
 
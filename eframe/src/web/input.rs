@@ -1,11 +1,16 @@
 use super::{canvas_element, canvas_origin, AppRunner};
 
-pub fn pos_from_mouse_event(canvas_id: &str, event: &web_sys::MouseEvent) -> egui::Pos2 {
+/// The position in egui points, taking [`egui::Context::zoom_factor`] into account.
+pub fn pos_from_mouse_event(
+    canvas_id: &str,
+    event: &web_sys::MouseEvent,
+    zoom_factor: f32,
+) -> egui::Pos2 {
     let canvas = canvas_element(canvas_id).unwrap();
     let rect = canvas.get_bounding_client_rect();
     egui::Pos2 {
-        x: event.client_x() as f32 - rect.left() as f32,
-        y: event.client_y() as f32 - rect.top() as f32,
+        x: (event.client_x() as f32 - rect.left() as f32) / zoom_factor,
+        y: (event.client_y() as f32 - rect.top() as f32) / zoom_factor,
     }
 }
 
@@ -30,6 +35,7 @@ pub fn pos_from_touch_event(
     canvas_id: &str,
     event: &web_sys::TouchEvent,
     touch_id_for_pos: &mut Option<egui::TouchId>,
+    zoom_factor: f32,
 ) -> egui::Pos2 {
     let touch_for_pos = if let Some(touch_id_for_pos) = touch_id_for_pos {
         // search for the touch we previously used for the position
@@ -48,26 +54,31 @@ pub fn pos_from_touch_event(
         .or_else(|| event.touches().get(0))
         .map_or(Default::default(), |touch| {
             *touch_id_for_pos = Some(egui::TouchId::from(touch.identifier()));
-            pos_from_touch(canvas_origin(canvas_id), &touch)
+            pos_from_touch(canvas_origin(canvas_id), &touch, zoom_factor)
         })
 }
 
-fn pos_from_touch(canvas_origin: egui::Pos2, touch: &web_sys::Touch) -> egui::Pos2 {
+fn pos_from_touch(
+    canvas_origin: egui::Pos2,
+    touch: &web_sys::Touch,
+    zoom_factor: f32,
+) -> egui::Pos2 {
     egui::Pos2 {
-        x: touch.page_x() as f32 - canvas_origin.x as f32,
-        y: touch.page_y() as f32 - canvas_origin.y as f32,
+        x: (touch.page_x() as f32 - canvas_origin.x as f32) / zoom_factor,
+        y: (touch.page_y() as f32 - canvas_origin.y as f32) / zoom_factor,
     }
 }
 
 pub fn push_touches(runner: &mut AppRunner, phase: egui::TouchPhase, event: &web_sys::TouchEvent) {
     let canvas_origin = canvas_origin(runner.canvas_id());
+    let zoom_factor = runner.egui_ctx().zoom_factor();
     for touch_idx in 0..event.changed_touches().length() {
         if let Some(touch) = event.changed_touches().item(touch_idx) {
             runner.input.raw.events.push(egui::Event::Touch {
                 device_id: egui::TouchDeviceId(0),
                 id: egui::TouchId::from(touch.identifier()),
                 phase,
-                pos: pos_from_touch(canvas_origin, &touch),
+                pos: pos_from_touch(canvas_origin, &touch, zoom_factor),
                 force: touch.force(),
             });
         }
@@ -132,6 +143,9 @@ pub fn translate_key(key: &str) -> Option<egui::Key> {
         "PageUp" => Some(egui::Key::PageUp),
         "PageDown" => Some(egui::Key::PageDown),
 
+        "-" => Some(egui::Key::Minus),
+        "+" | "=" => Some(egui::Key::PlusEquals),
+
         "0" => Some(egui::Key::Num0),
         "1" => Some(egui::Key::Num1),
         "2" => Some(egui::Key::Num2),
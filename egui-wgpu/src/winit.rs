@@ -34,7 +34,9 @@ pub struct Painter<'a> {
     instance: Instance,
     adapter: Option<Adapter>,
     render_state: Option<RenderState>,
-    surface_state: Option<SurfaceState>,
+
+    /// One surface per window, i.e. per viewport (see [`egui::Context::show_viewport`]).
+    surfaces: egui::ViewportIdMap<SurfaceState>,
 }
 
 impl<'a> Painter<'a> {
@@ -68,7 +70,7 @@ impl<'a> Painter<'a> {
             instance,
             adapter: None,
             render_state: None,
-            surface_state: None,
+            surfaces: Default::default(),
         }
     }
 
@@ -121,7 +123,12 @@ impl<'a> Painter<'a> {
         }
     }
 
-    fn configure_surface(&mut self, width_in_pixels: u32, height_in_pixels: u32) {
+    fn configure_surface(
+        &mut self,
+        viewport_id: egui::ViewportId,
+        width_in_pixels: u32,
+        height_in_pixels: u32,
+    ) {
         let render_state = self
             .render_state
             .as_ref()
@@ -137,8 +144,8 @@ impl<'a> Painter<'a> {
         };
 
         let surface_state = self
-            .surface_state
-            .as_mut()
+            .surfaces
+            .get_mut(&viewport_id)
             .expect("Surface state should exist before surface configuration");
         surface_state
             .surface
@@ -172,6 +179,22 @@ impl<'a> Painter<'a> {
     /// surface upon and must remain valid for the lifetime of the created surface. (The surface may
     /// be cleared by passing `None`).
     pub unsafe fn set_window(&mut self, window: Option<&winit::window::Window>) {
+        self.set_viewport_window(egui::ViewportId::ROOT, window);
+    }
+
+    /// Like [`set_window`](Self::set_window), but for the window of a child viewport
+    /// (see [`egui::Context::show_viewport`]).
+    ///
+    /// All viewports share the same device and textures, but each has its own [`wgpu::Surface`].
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`set_window`](Self::set_window).
+    pub unsafe fn set_viewport_window(
+        &mut self,
+        viewport_id: egui::ViewportId,
+        window: Option<&winit::window::Window>,
+    ) {
         match window {
             Some(window) => {
                 let surface = self.instance.create_surface(&window);
@@ -181,15 +204,18 @@ impl<'a> Painter<'a> {
                 let size = window.inner_size();
                 let width = size.width as u32;
                 let height = size.height as u32;
-                self.surface_state = Some(SurfaceState {
-                    surface,
-                    width,
-                    height,
-                });
-                self.configure_surface(width, height);
+                self.surfaces.insert(
+                    viewport_id,
+                    SurfaceState {
+                        surface,
+                        width,
+                        height,
+                    },
+                );
+                self.configure_surface(viewport_id, width, height);
             }
             None => {
-                self.surface_state = None;
+                self.surfaces.remove(&viewport_id);
             }
         }
     }
@@ -206,8 +232,18 @@ impl<'a> Painter<'a> {
     }
 
     pub fn on_window_resized(&mut self, width_in_pixels: u32, height_in_pixels: u32) {
-        if self.surface_state.is_some() {
-            self.configure_surface(width_in_pixels, height_in_pixels);
+        self.on_viewport_window_resized(egui::ViewportId::ROOT, width_in_pixels, height_in_pixels);
+    }
+
+    /// Like [`on_window_resized`](Self::on_window_resized), but for the window of a child viewport.
+    pub fn on_viewport_window_resized(
+        &mut self,
+        viewport_id: egui::ViewportId,
+        width_in_pixels: u32,
+        height_in_pixels: u32,
+    ) {
+        if self.surfaces.contains_key(&viewport_id) {
+            self.configure_surface(viewport_id, width_in_pixels, height_in_pixels);
         } else {
             error!("Ignoring window resize notification with no surface created via Painter::set_window()");
         }
@@ -219,12 +255,31 @@ impl<'a> Painter<'a> {
         clear_color: egui::Rgba,
         clipped_primitives: &[egui::ClippedPrimitive],
         textures_delta: &egui::TexturesDelta,
+    ) {
+        self.paint_viewport_and_update_textures(
+            egui::ViewportId::ROOT,
+            pixels_per_point,
+            clear_color,
+            clipped_primitives,
+            textures_delta,
+        );
+    }
+
+    /// Like [`paint_and_update_textures`](Self::paint_and_update_textures),
+    /// but paints to the window of the given viewport.
+    pub fn paint_viewport_and_update_textures(
+        &mut self,
+        viewport_id: egui::ViewportId,
+        pixels_per_point: f32,
+        clear_color: egui::Rgba,
+        clipped_primitives: &[egui::ClippedPrimitive],
+        textures_delta: &egui::TexturesDelta,
     ) {
         let render_state = match self.render_state.as_mut() {
             Some(rs) => rs,
             None => return,
         };
-        let surface_state = match self.surface_state.as_ref() {
+        let surface_state = match self.surfaces.get(&viewport_id) {
             Some(rs) => rs,
             None => return,
         };
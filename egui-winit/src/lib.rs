@@ -44,6 +44,10 @@ pub struct State {
     current_cursor_icon: egui::CursorIcon,
     /// What egui uses.
     current_pixels_per_point: f32,
+    /// The scale factor of the window, as reported by the OS.
+    native_pixels_per_point: f32,
+    /// See [`egui::Context::zoom_factor`].
+    zoom_factor: f32,
 
     clipboard: clipboard::Clipboard,
     screen_reader: screen_reader::ScreenReader,
@@ -73,6 +77,8 @@ impl State {
             any_pointer_button_down: false,
             current_cursor_icon: egui::CursorIcon::Default,
             current_pixels_per_point: 1.0,
+            native_pixels_per_point: 1.0,
+            zoom_factor: 1.0,
 
             clipboard: clipboard::Clipboard::new(wayland_display),
             screen_reader: screen_reader::ScreenReader::default(),
@@ -96,7 +102,23 @@ impl State {
     ///
     /// Once this has been initialized for a new window then this state will be maintained by handling
     /// [`winit::event::WindowEvent::ScaleFactorChanged`] events.
+    ///
+    /// This is the native scale factor of the window (see [`native_pixels_per_point`]).
+    /// egui will use this multiplied by [`egui::Context::zoom_factor`].
     pub fn set_pixels_per_point(&mut self, pixels_per_point: f32) {
+        self.native_pixels_per_point = pixels_per_point;
+        self.update_pixels_per_point();
+    }
+
+    fn set_zoom_factor(&mut self, zoom_factor: f32) {
+        if self.zoom_factor != zoom_factor {
+            self.zoom_factor = zoom_factor;
+            self.update_pixels_per_point();
+        }
+    }
+
+    fn update_pixels_per_point(&mut self) {
+        let pixels_per_point = self.native_pixels_per_point * self.zoom_factor;
         self.egui_input.pixels_per_point = Some(pixels_per_point);
         self.current_pixels_per_point = pixels_per_point;
     }
@@ -158,9 +180,8 @@ impl State {
         use winit::event::WindowEvent;
         match event {
             WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
-                let pixels_per_point = *scale_factor as f32;
-                self.egui_input.pixels_per_point = Some(pixels_per_point);
-                self.current_pixels_per_point = pixels_per_point;
+                self.zoom_factor = egui_ctx.zoom_factor();
+                self.set_pixels_per_point(*scale_factor as f32);
                 false
             }
             WindowEvent::MouseInput { state, button, .. } => {
@@ -474,9 +495,11 @@ impl State {
             events: _,                    // handled above
             mutable_text_under_cursor: _, // only used in eframe web
             text_cursor_pos,
+            accessibility_tree: _, // for platform accessibility adapters, which should read it before this
         } = platform_output;
 
         self.current_pixels_per_point = egui_ctx.pixels_per_point(); // someone can have changed it to scale the UI
+        self.set_zoom_factor(egui_ctx.zoom_factor()); // the user may have zoomed with the keyboard
 
         self.set_cursor_icon(window, cursor_icon);
 
@@ -592,6 +615,11 @@ fn translate_virtual_key_code(key: winit::event::VirtualKeyCode) -> Option<egui:
         VirtualKeyCode::PageUp => Key::PageUp,
         VirtualKeyCode::PageDown => Key::PageDown,
 
+        VirtualKeyCode::Minus | VirtualKeyCode::NumpadSubtract => Key::Minus,
+        VirtualKeyCode::Equals | VirtualKeyCode::Plus | VirtualKeyCode::NumpadAdd => {
+            Key::PlusEquals
+        }
+
         VirtualKeyCode::Key0 | VirtualKeyCode::Numpad0 => Key::Num0,
         VirtualKeyCode::Key1 | VirtualKeyCode::Numpad1 => Key::Num1,
         VirtualKeyCode::Key2 | VirtualKeyCode::Numpad2 => Key::Num2,
@@ -53,6 +53,7 @@ pub struct Area {
     anchor: Option<(Align2, Vec2)>,
     new_pos: Option<Pos2>,
     drag_bounds: Option<Rect>,
+    trap_focus: bool,
 }
 
 impl Area {
@@ -67,6 +68,7 @@ impl Area {
             new_pos: None,
             anchor: None,
             drag_bounds: None,
+            trap_focus: false,
         }
     }
 
@@ -157,6 +159,15 @@ impl Area {
         self
     }
 
+    /// If `true`, pressing tab or an arrow key won't move the keyboard focus out of the area
+    /// once one of its widgets has focus.
+    ///
+    /// See [`Memory::trap_focus_in_layer`]. Default: `false`.
+    pub fn trap_focus(mut self, trap_focus: bool) -> Self {
+        self.trap_focus = trap_focus;
+        self
+    }
+
     pub(crate) fn get_pivot(&self) -> Align2 {
         if let Some((pivot, _)) = self.anchor {
             pivot
@@ -198,10 +209,15 @@ impl Area {
             new_pos,
             anchor,
             drag_bounds,
+            trap_focus,
         } = self;
 
         let layer_id = LayerId::new(order, id);
 
+        if trap_focus {
+            ctx.memory().trap_focus_in_layer(layer_id);
+        }
+
         let state = ctx.memory().areas.get(id).cloned();
         let is_new = state.is_none();
         if is_new {
@@ -346,6 +362,8 @@ impl Prepared {
             sense,
             enabled,
         );
+        // Use the widgets in the area for keyboard navigation, not the area itself:
+        ctx.memory().set_tab_index(interact_id, -1);
 
         if move_response.dragged() && movable {
             state.pos += ctx.input().pointer.delta();
@@ -171,12 +171,20 @@ impl CollapsingState {
     ) -> Option<InnerResponse<R>> {
         let id = self.id;
         self.show_body_unindented(ui, |ui| {
-            ui.indent(id, |ui| {
+            ui.ctx().accessibility_begin_container(
+                id,
+                crate::accessibility::Role::Disclosure,
+                None,
+                false,
+            );
+            let inner = ui.indent(id, |ui| {
                 // make as wide as the header:
                 ui.expand_to_include_x(header_response.rect.right());
                 add_body(ui)
-            })
-            .inner
+            });
+            ui.ctx()
+                .accessibility_end_container(id, inner.response.rect, false);
+            inner.inner
         })
     }
 
@@ -237,7 +237,7 @@ impl Frame {
 }
 
 impl Prepared {
-    fn paint_rect(&self) -> Rect {
+    pub(crate) fn paint_rect(&self) -> Rect {
         let mut rect = self.content_ui.min_rect();
         rect.min -= self.frame.inner_margin.left_top();
         rect.max += self.frame.inner_margin.right_bottom();
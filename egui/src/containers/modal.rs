@@ -0,0 +1,367 @@
+//! A dialog that blocks the rest of the ui until closed. See [`Modal`].
+
+use std::hash::Hash;
+
+use crate::*;
+
+/// A dialog shown on top of everything else, blocking the ui below it until closed.
+///
+/// The rest of the ui is dimmed, and can't be clicked or hovered.
+/// Keyboard focus is kept within the modal (and the popups and menus opened from it),
+/// so tab and the arrow keys won't reach the widgets below it.
+/// Modals can be nested: a modal opened from within another modal is shown on top of it.
+///
+/// You decide when the modal is open: show it every frame while it should be open.
+/// [`ModalResponse::should_close`] tells you when the user pressed escape
+/// (or clicked outside the modal, if [`Self::close_on_backdrop_click`] is set).
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// # let mut is_open = true;
+/// if is_open {
+///     let response = egui::Modal::new("confirm_quit").show(ctx, |ui| {
+///         ui.label("Do you really want to quit?");
+///         ui.horizontal(|ui| {
+///             if ui.button("Quit").clicked() {
+///                 // …
+///             }
+///             ui.button("Cancel").clicked()
+///         })
+///         .inner
+///     });
+///     if response.inner || response.should_close {
+///         is_open = false;
+///     }
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct Modal {
+    area: Area,
+    frame: Option<Frame>,
+    backdrop_color: Color32,
+    close_on_escape: bool,
+    close_on_backdrop_click: bool,
+}
+
+impl Modal {
+    /// The `id_source` must be unique, and the same every frame.
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            area: Area::new(id_source)
+                .order(Order::Foreground)
+                .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+                .trap_focus(true),
+            frame: None,
+            backdrop_color: Color32::from_black_alpha(128),
+            close_on_escape: true,
+            close_on_backdrop_click: false,
+        }
+    }
+
+    /// Change the background of the modal. Default: [`Frame::window`].
+    pub fn frame(mut self, frame: Frame) -> Self {
+        self.frame = Some(frame);
+        self
+    }
+
+    /// The color painted over everything below the modal. Default: half-transparent black.
+    pub fn backdrop_color(mut self, backdrop_color: Color32) -> Self {
+        self.backdrop_color = backdrop_color;
+        self
+    }
+
+    /// Set [`ModalResponse::should_close`] when the user presses escape. Default: `true`.
+    pub fn close_on_escape(mut self, close_on_escape: bool) -> Self {
+        self.close_on_escape = close_on_escape;
+        self
+    }
+
+    /// Set [`ModalResponse::should_close`] when the user clicks outside of the modal. Default: `false`.
+    pub fn close_on_backdrop_click(mut self, close_on_backdrop_click: bool) -> Self {
+        self.close_on_backdrop_click = close_on_backdrop_click;
+        self
+    }
+
+    /// The layer the contents of the modal are shown in.
+    pub fn layer_id(&self) -> LayerId {
+        self.area.layer()
+    }
+
+    pub fn show<R>(
+        self,
+        ctx: &Context,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> ModalResponse<R> {
+        let layer_id = self.layer_id();
+        let Self {
+            area,
+            frame,
+            backdrop_color,
+            close_on_escape,
+            close_on_backdrop_click,
+        } = self;
+
+        let backdrop = Area::new(area.id.with("backdrop"))
+            .order(Order::Foreground)
+            .fixed_pos(Pos2::ZERO)
+            .movable(false);
+        let backdrop_layer_id = backdrop.layer();
+
+        // Areas that were not visible last frame are moved to the top of their `Order`,
+        // so a newly opened modal ends up on top of everything else, including any other modals.
+        let is_top = {
+            let mut memory = ctx.memory();
+            let top_modal_layer = memory.top_modal_layer();
+            memory.set_modal_layer(layer_id);
+            top_modal_layer.map_or(true, |top| top == layer_id)
+        };
+
+        let backdrop_response = backdrop
+            .show(ctx, |ui| {
+                let screen_rect = ui.ctx().input().screen_rect();
+                ui.painter().rect_filled(screen_rect, 0.0, backdrop_color);
+                ui.allocate_rect(screen_rect, Sense::hover());
+            })
+            .response;
+
+        // Pressing the backdrop moves it to the top, so move the modal along with it
+        // (layers that are moved to the top in the same frame keep their order):
+        let backdrop_pressed = backdrop_response.hovered() && ctx.input().pointer.any_pressed();
+        let below_backdrop = {
+            let memory = ctx.memory();
+            let order = memory.areas.order();
+            let index = |layer_id| order.iter().position(|&l| l == layer_id);
+            index(layer_id) < index(backdrop_layer_id)
+        };
+        if backdrop_pressed || backdrop_response.clicked() || below_backdrop {
+            ctx.move_to_top(layer_id);
+        }
+
+        let frame = frame.unwrap_or_else(|| Frame::window(&ctx.style()));
+        let InnerResponse { inner, response } =
+            area.show(ctx, |ui| frame.show(ui, add_contents).inner);
+
+        let pressed_escape =
+            is_top && close_on_escape && ctx.input_mut().consume_key(Modifiers::NONE, Key::Escape);
+        let should_close = pressed_escape || close_on_backdrop_click && backdrop_response.clicked();
+
+        ModalResponse {
+            inner,
+            response,
+            backdrop_response,
+            should_close,
+        }
+    }
+}
+
+/// The response from showing a [`Modal`].
+pub struct ModalResponse<R> {
+    /// What the user closure returned.
+    pub inner: R,
+
+    /// The response of the area the contents of the modal are shown in.
+    pub response: Response,
+
+    /// The response of the backdrop covering the rest of the ui.
+    pub backdrop_response: Response,
+
+    /// The user pressed escape or clicked the backdrop (see [`Modal::close_on_escape`] and [`Modal::close_on_backdrop_click`]).
+    ///
+    /// It is up to you to stop showing the modal.
+    pub should_close: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn click_at(pos: Pos2) -> Vec<Vec<Event>> {
+        let button = |pressed| Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        vec![
+            vec![Event::PointerMoved(pos)],
+            vec![button(true)],
+            vec![button(false)],
+        ]
+    }
+
+    fn key(key: Key) -> Vec<Vec<Event>> {
+        vec![vec![Event::Key {
+            key,
+            pressed: true,
+            modifiers: Modifiers::NONE,
+        }]]
+    }
+
+    #[derive(Default)]
+    struct App {
+        modal_open: bool,
+        nested_open: bool,
+        background_clicks: usize,
+        background_has_focus: bool,
+        modal_has_focus: bool,
+    }
+
+    impl App {
+        fn ui(&mut self, ctx: &Context) {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = ui.button("Background");
+                self.background_clicks += response.clicked() as usize;
+                self.background_has_focus = response.has_focus();
+            });
+
+            if self.modal_open {
+                let response = Modal::new("modal")
+                    .close_on_backdrop_click(true)
+                    .show(ctx, |ui| {
+                        ui.label("Modal");
+                        self.modal_has_focus = ui.button("In modal").has_focus();
+                    });
+                if response.should_close {
+                    self.modal_open = false;
+                }
+            }
+
+            if self.nested_open {
+                let response = Modal::new("nested").show(ctx, |ui| {
+                    ui.label("Nested");
+                });
+                if response.should_close {
+                    self.nested_open = false;
+                }
+            }
+        }
+
+        fn run(&mut self, ctx: &Context, frames: Vec<Vec<Event>>) {
+            for events in frames.into_iter().chain([vec![], vec![]]) {
+                let raw_input = RawInput {
+                    screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0))),
+                    events,
+                    ..Default::default()
+                };
+                let _ = ctx.run(raw_input, |ctx| self.ui(ctx));
+            }
+        }
+    }
+
+    const BACKGROUND_BUTTON: Pos2 = pos2(20.0, 15.0);
+
+    #[test]
+    fn blocks_pointer_and_focus() {
+        let ctx = Context::default();
+        let mut app = App::default();
+        app.run(&ctx, click_at(BACKGROUND_BUTTON));
+        assert_eq!(app.background_clicks, 1);
+        app.run(&ctx, key(Key::Tab));
+        assert!(app.background_has_focus);
+
+        app.modal_open = true;
+        app.run(&ctx, vec![]);
+        assert!(
+            !app.background_has_focus,
+            "the modal takes away focus from below"
+        );
+
+        app.run(&ctx, key(Key::Tab));
+        assert!(app.modal_has_focus, "tab goes to the modal");
+        app.run(&ctx, key(Key::Tab));
+        assert!(app.modal_has_focus, "and stays there");
+        assert!(!app.background_has_focus);
+
+        app.run(&ctx, click_at(BACKGROUND_BUTTON));
+        assert_eq!(app.background_clicks, 1, "the click hits the backdrop");
+        assert!(!app.modal_open, "which closes the modal");
+
+        app.run(&ctx, click_at(BACKGROUND_BUTTON));
+        assert_eq!(app.background_clicks, 2);
+    }
+
+    #[test]
+    fn escape_closes_the_top_modal() {
+        let ctx = Context::default();
+        let mut app = App {
+            modal_open: true,
+            ..Default::default()
+        };
+        app.run(&ctx, vec![]);
+        app.nested_open = true;
+        app.run(&ctx, vec![]);
+        assert_eq!(
+            ctx.memory().top_modal_layer(),
+            Some(Modal::new("nested").layer_id())
+        );
+
+        app.run(&ctx, click_at(BACKGROUND_BUTTON));
+        assert!(
+            app.modal_open,
+            "the nested modal is on top, and doesn't close on backdrop clicks"
+        );
+
+        app.run(&ctx, key(Key::Escape));
+        assert!(!app.nested_open);
+        assert!(app.modal_open);
+
+        app.run(&ctx, key(Key::Escape));
+        assert!(!app.modal_open);
+        assert_eq!(ctx.memory().top_modal_layer(), None);
+    }
+
+    #[test]
+    fn backdrop_click_keeps_the_modal_on_top() {
+        let mut harness = crate::testing::Harness::new_state(0, |ctx, clicks| {
+            let _ = Modal::new("modal").show(ctx, |ui| {
+                if ui.button("In modal").clicked() {
+                    *clicks += 1;
+                }
+            });
+        });
+        harness.run();
+        harness.run(); // the modal is invisible while it is sized in the first frame
+
+        harness.click_at(pos2(5.0, 5.0), PointerButton::Primary);
+        harness.run();
+        harness.run();
+        harness.click(harness.get_by_label("In modal").id);
+        harness.run();
+        assert_eq!(*harness.state(), 1, "the modal is still above its backdrop");
+    }
+
+    #[test]
+    fn popup_in_modal_can_have_focus() {
+        let mut harness = crate::testing::Harness::new_state(String::new(), |ctx, text| {
+            let _ = Modal::new("modal").show(ctx, |ui| {
+                let response = ui.button("Open popup");
+                let popup_id = ui.make_persistent_id("popup");
+                if response.clicked() {
+                    ui.memory().open_popup(popup_id);
+                }
+                popup_below_widget(ui, popup_id, &response, |ui| {
+                    ui.text_edit_singleline(text);
+                });
+            });
+        });
+        harness.run();
+        harness.run(); // the modal is invisible while it is sized in the first frame
+        harness.click(harness.get_by_label("Open popup").id);
+        harness.run();
+
+        let text_edit = harness.get_by_type(WidgetType::TextEdit).id;
+        harness.ctx().memory().request_focus(text_edit);
+        harness.run();
+        harness.run();
+        assert!(
+            harness.has_focus(text_edit),
+            "the popup is on top of the modal"
+        );
+
+        harness.type_text("hello");
+        harness.run();
+        assert!(harness.has_focus(text_edit));
+        assert_eq!(harness.state(), "hello");
+    }
+}
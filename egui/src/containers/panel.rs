@@ -52,6 +52,15 @@ impl Side {
         }
     }
 
+    /// Left is right in a right-to-left ui.
+    fn for_style(self, style: &Style) -> Self {
+        if style.right_to_left {
+            self.opposite()
+        } else {
+            self
+        }
+    }
+
     fn set_rect_width(self, rect: &mut Rect, width: f32) {
         match self {
             Side::Left => rect.max.x = rect.min.x + width,
@@ -95,11 +104,15 @@ pub struct SidePanel {
 
 impl SidePanel {
     /// `id_source`: Something unique, e.g. `"my_left_panel"`.
+    ///
+    /// In a right-to-left ui ([`crate::Style::right_to_left`]) the panel is shown on the right.
     pub fn left(id_source: impl std::hash::Hash) -> Self {
         Self::new(Side::Left, id_source)
     }
 
     /// `id_source`: Something unique, e.g. `"my_right_panel"`.
+    ///
+    /// In a right-to-left ui ([`crate::Style::right_to_left`]) the panel is shown on the left.
     pub fn right(id_source: impl std::hash::Hash) -> Self {
         Self::new(Side::Right, id_source)
     }
@@ -185,6 +198,7 @@ impl SidePanel {
             default_width,
             width_range,
         } = self;
+        let side = side.for_style(ui.style());
 
         let available_rect = ui.available_rect_before_wrap();
         let mut panel_rect = available_rect;
@@ -237,7 +251,11 @@ impl SidePanel {
             }
         }
 
-        let mut panel_ui = ui.child_ui_with_id_source(panel_rect, Layout::top_down(Align::Min), id);
+        let mut panel_ui = ui.child_ui_with_id_source(
+            panel_rect,
+            Layout::top_down(Align::Min).for_style(ui.style()),
+            id,
+        );
         panel_ui.expand_to_include_rect(panel_rect);
         let frame = frame.unwrap_or_else(|| Frame::side_top_panel(ui.style()));
         let inner_response = frame.show(&mut panel_ui, |ui| {
@@ -297,7 +315,7 @@ impl SidePanel {
         add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
     ) -> InnerResponse<R> {
         let layer_id = LayerId::background();
-        let side = self.side;
+        let side = self.side.for_style(&ctx.style());
         let available_rect = ctx.available_rect();
         let clip_rect = ctx.input().screen_rect();
         let mut panel_ui = Ui::new(ctx.clone(), layer_id, self.id, available_rect, clip_rect);
@@ -522,7 +540,11 @@ impl TopBottomPanel {
             }
         }
 
-        let mut panel_ui = ui.child_ui_with_id_source(panel_rect, Layout::top_down(Align::Min), id);
+        let mut panel_ui = ui.child_ui_with_id_source(
+            panel_rect,
+            Layout::top_down(Align::Min).for_style(ui.style()),
+            id,
+        );
         panel_ui.expand_to_include_rect(panel_rect);
         let frame = frame.unwrap_or_else(|| Frame::side_top_panel(ui.style()));
         let inner_response = frame.show(&mut panel_ui, |ui| {
@@ -657,7 +679,10 @@ impl CentralPanel {
         let Self { frame } = self;
 
         let panel_rect = ui.available_rect_before_wrap();
-        let mut panel_ui = ui.child_ui(panel_rect, Layout::top_down(Align::Min));
+        let mut panel_ui = ui.child_ui(
+            panel_rect,
+            Layout::top_down(Align::Min).for_style(ui.style()),
+        );
 
         let frame = frame.unwrap_or_else(|| Frame::central_panel(ui.style()));
         frame.show(&mut panel_ui, |ui| {
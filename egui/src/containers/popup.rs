@@ -9,6 +9,19 @@ use crate::*;
 pub(crate) struct MonoState {
     last_id: Option<Id>,
     last_size: Vec<Vec2>,
+
+    /// The tooltips shown this frame, and the area in which the pointer keeps them open.
+    ///
+    /// The area is [`Rect::NOTHING`] for tooltips that follow the pointer.
+    open: Vec<(Id, Rect)>,
+    open_previous_frame: Vec<(Id, Rect)>,
+
+    /// The tooltip waiting for [`style::Interaction::tooltip_delay`] to pass, and since when.
+    waiting: Option<(Id, f64)>,
+    waiting_previous_frame: Option<(Id, f64)>,
+
+    /// When a tooltip was last shown, for [`style::Interaction::tooltip_grace_time`].
+    last_shown_time: Option<f64>,
 }
 
 impl MonoState {
@@ -20,6 +33,14 @@ impl MonoState {
         ctx.data().insert_temp(Id::null(), self);
     }
 
+    pub(crate) fn begin_frame(ctx: &Context) {
+        if let Some(mut state) = Self::load(ctx) {
+            state.open_previous_frame = std::mem::take(&mut state.open);
+            state.waiting_previous_frame = state.waiting.take();
+            state.store(ctx);
+        }
+    }
+
     fn tooltip_size(&self, id: Id, index: usize) -> Option<Vec2> {
         if self.last_id == Some(id) {
             self.last_size.get(index).cloned()
@@ -47,6 +68,63 @@ impl MonoState {
     }
 }
 
+/// Was the tooltip shown last frame, with the pointer still over it (or over the widget it belongs to)?
+///
+/// This keeps a tooltip open while the pointer moves from the widget into the tooltip.
+pub(crate) fn is_pointer_over_tooltip(ctx: &Context, tooltip_id: Id) -> bool {
+    let pointer_pos = match ctx.input().pointer.hover_pos() {
+        Some(pointer_pos) => pointer_pos,
+        None => return false,
+    };
+    MonoState::load(ctx).map_or(false, |state| {
+        state
+            .open_previous_frame
+            .iter()
+            .any(|(id, rect)| *id == tooltip_id && rect.contains(pointer_pos))
+    })
+}
+
+/// Call each frame the pointer is over a widget with a tooltip.
+///
+/// Returns `true` once the pointer has been there for [`style::Interaction::tooltip_delay`] seconds,
+/// or right away if the tooltip is already open or another tooltip just closed.
+pub(crate) fn tooltip_delay_passed(ctx: &Context, tooltip_id: Id) -> bool {
+    let now = ctx.input().time;
+    let style::Interaction {
+        tooltip_delay,
+        tooltip_grace_time,
+        ..
+    } = ctx.style().interaction;
+
+    let mut state = MonoState::load(ctx).unwrap_or_default();
+
+    let was_open = state
+        .open_previous_frame
+        .iter()
+        .any(|(id, _)| *id == tooltip_id);
+    let in_grace_time = state
+        .last_shown_time
+        .map_or(false, |time| now - time <= tooltip_grace_time as f64);
+
+    let passed = if was_open || in_grace_time {
+        true
+    } else {
+        let waiting_since = [state.waiting, state.waiting_previous_frame]
+            .into_iter()
+            .flatten()
+            .find(|(id, _)| *id == tooltip_id)
+            .map_or(now, |(_, time)| time);
+        state.waiting = Some((tooltip_id, waiting_since));
+        now - waiting_since >= tooltip_delay as f64
+    };
+    state.store(ctx);
+
+    if !passed {
+        ctx.request_repaint(); // so that we show the tooltip once the delay has passed
+    }
+    passed
+}
+
 // ----------------------------------------------------------------------------
 
 /// Show a tooltip at the current pointer position (if any).
@@ -96,17 +174,28 @@ pub fn show_tooltip_at_pointer<R>(
     id: Id,
     add_contents: impl FnOnce(&mut Ui) -> R,
 ) -> Option<R> {
-    let suggested_pos = ctx
-        .input()
-        .pointer
-        .hover_pos()
-        .map(|pointer_pos| pointer_pos + vec2(16.0, 16.0));
-    show_tooltip_at(ctx, id, suggested_pos, add_contents)
+    let pointer_pos = ctx.input().pointer.hover_pos();
+    // Keep the tooltip away from the pointer, even if it doesn't fit below it:
+    let avoid_rect = pointer_pos.map_or(Rect::NOTHING, |pointer_pos| {
+        Rect::from_center_size(pointer_pos, Vec2::splat(32.0))
+    });
+    show_tooltip_at_avoid_dyn(
+        ctx,
+        id,
+        pointer_pos.map(|pointer_pos| pointer_pos + vec2(16.0, 16.0)),
+        false,
+        avoid_rect,
+        false,
+        Box::new(add_contents),
+    )
 }
 
 /// Show a tooltip under the given area.
 ///
 /// If the tooltip does not fit under the area, it tries to place it above it instead.
+///
+/// The tooltip can contain interactive widgets:
+/// it stays open while the pointer is over `rect` or over the tooltip itself.
 pub fn show_tooltip_for<R>(
     ctx: &Context,
     id: Id,
@@ -125,6 +214,7 @@ pub fn show_tooltip_for<R>(
         Some(position),
         above,
         expanded_rect,
+        true,
         Box::new(add_contents),
     )
 }
@@ -145,24 +235,30 @@ pub fn show_tooltip_at<R>(
         suggested_position,
         above,
         Rect::NOTHING,
+        false,
         Box::new(add_contents),
     )
 }
 
 fn show_tooltip_at_avoid_dyn<'c, R>(
     ctx: &Context,
-    mut id: Id,
+    id: Id,
     suggested_position: Option<Pos2>,
     above: bool,
-    mut avoid_rect: Rect,
+    avoid_rect: Rect,
+    interactive: bool,
     add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
 ) -> Option<R> {
+    let tooltip_id = id;
+    let mut id = id;
     let mut tooltip_rect = Rect::NOTHING;
     let mut count = 0;
+    let mut keep_open_rect = avoid_rect;
+    let mut avoid_rect = avoid_rect;
 
     let stored = ctx.frame_state().tooltip_rect;
 
-    let mut position = if let Some(stored) = stored {
+    let position = if let Some(stored) = stored {
         // if there are multiple tooltips open they should use the same id for the `tooltip_size` caching to work.
         id = stored.id;
         tooltip_rect = stored.rect;
@@ -182,36 +278,34 @@ fn show_tooltip_at_avoid_dyn<'c, R>(
     };
 
     let mut state = MonoState::load(ctx).unwrap_or_default();
-    let expected_size = state.tooltip_size(id, count);
+    let expected_size = state.tooltip_size(id, count).or_else(|| {
+        // The size of this tooltip last time it was shown:
+        let area_id = Area::new(id).layer().id;
+        (count == 0)
+            .then(|| ctx.memory().areas.get(area_id).map(|area| area.size))
+            .flatten()
+    });
     let expected_size = expected_size.unwrap_or_else(|| vec2(64.0, 32.0));
 
-    if above {
-        position.y -= expected_size.y;
-    }
-
-    position = position.at_most(ctx.input().screen_rect().max - expected_size);
+    let position = tooltip_position(
+        ctx.input().screen_rect(),
+        position,
+        expected_size,
+        above,
+        avoid_rect,
+    );
 
-    // check if we intersect the avoid_rect
-    {
-        let new_rect = Rect::from_min_size(position, expected_size);
+    let InnerResponse { inner, response } =
+        show_tooltip_area_dyn(ctx, id, position, interactive, add_contents);
 
-        // Note: We do not use Rect::intersects() since it returns true even if the rects only touch.
-        if new_rect.shrink(1.0).intersects(avoid_rect) {
-            if above {
-                // place below instead:
-                position = avoid_rect.left_bottom();
-            } else {
-                // place above instead:
-                position = Pos2::new(position.x, avoid_rect.min.y - expected_size.y);
-            }
-        }
+    if interactive {
+        keep_open_rect = keep_open_rect.union(response.rect);
+    } else {
+        keep_open_rect = Rect::NOTHING;
     }
-
-    let position = position.at_least(ctx.input().screen_rect().min);
-
-    let InnerResponse { inner, response } = show_tooltip_area_dyn(ctx, id, position, add_contents);
-
     state.set_tooltip_size(id, count, response.rect.size());
+    state.open.push((tooltip_id, keep_open_rect));
+    state.last_shown_time = Some(ctx.input().time);
     state.store(ctx);
 
     ctx.frame_state().tooltip_rect = Some(crate::frame_state::TooltipRect {
@@ -222,6 +316,45 @@ fn show_tooltip_at_avoid_dyn<'c, R>(
     Some(inner)
 }
 
+/// Where to put a tooltip of the given size, so that it is on the screen and not covering `avoid_rect`.
+fn tooltip_position(
+    screen_rect: Rect,
+    mut position: Pos2,
+    size: Vec2,
+    above: bool,
+    avoid_rect: Rect,
+) -> Pos2 {
+    if above {
+        position.y -= size.y;
+    }
+
+    let rect = Rect::from_min_size(position, size);
+    let off_screen = rect.min.y < screen_rect.min.y || screen_rect.max.y < rect.max.y;
+
+    // Note: We do not use Rect::intersects() since it returns true even if the rects only touch.
+    if avoid_rect.is_positive() && (off_screen || rect.shrink(1.0).intersects(avoid_rect)) {
+        // Try the other side of the avoid_rect:
+        let space_above = avoid_rect.min.y - screen_rect.min.y;
+        let space_below = screen_rect.max.y - avoid_rect.max.y;
+        let fits_above = size.y <= space_above;
+        let fits_below = size.y <= space_below;
+        let place_above = if above {
+            fits_above || !fits_below && space_above > space_below
+        } else {
+            !fits_below && (fits_above || space_above > space_below)
+        };
+        position.y = if place_above {
+            avoid_rect.min.y - size.y
+        } else {
+            avoid_rect.max.y
+        };
+    }
+
+    position
+        .at_most(screen_rect.max - size)
+        .at_least(screen_rect.min)
+}
+
 /// Show some text at the current pointer position (if any).
 ///
 /// Most of the time it is easier to use [`Response::on_hover_text`].
@@ -248,13 +381,14 @@ fn show_tooltip_area_dyn<'c, R>(
     ctx: &Context,
     id: Id,
     window_pos: Pos2,
+    interactable: bool,
     add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
 ) -> InnerResponse<R> {
     use containers::*;
     Area::new(id)
         .order(Order::Tooltip)
         .fixed_pos(window_pos)
-        .interactable(false)
+        .interactable(interactable)
         .drag_bounds(Rect::EVERYTHING) // disable clip rect
         .show(ctx, |ui| {
             Frame::popup(&ctx.style())
@@ -301,21 +435,35 @@ pub fn popup_below_widget<R>(
             .show(ui.ctx(), |ui| {
                 // Note: we use a separate clip-rect for this area, so the popup can be outside the parent.
                 // See https://github.com/emilk/egui/issues/825
+                ui.ctx().accessibility_begin_container(
+                    popup_id,
+                    crate::accessibility::Role::Menu,
+                    None,
+                    true,
+                );
                 let frame = Frame::popup(ui.style());
                 let frame_margin = frame.inner_margin + frame.outer_margin;
-                frame
-                    .show(ui, |ui| {
-                        ui.with_layout(Layout::top_down_justified(Align::LEFT), |ui| {
-                            ui.set_width(widget_response.rect.width() - frame_margin.sum().x);
-                            add_contents(ui)
-                        })
-                        .inner
+                let inner_response = frame.show(ui, |ui| {
+                    let layout = Layout::top_down_justified(Align::LEFT).for_style(ui.style());
+                    ui.with_layout(layout, |ui| {
+                        ui.set_width(widget_response.rect.width() - frame_margin.sum().x);
+                        add_contents(ui)
                     })
                     .inner
+                });
+                ui.ctx()
+                    .accessibility_end_container(popup_id, inner_response.response.rect, true);
+                inner_response.inner
             })
             .inner;
 
-        if ui.input().key_pressed(Key::Escape) || widget_response.clicked_elsewhere() {
+        // Consume the escape key, so that it won't also close e.g. a `Modal` the popup is shown in:
+        if ui
+            .ctx()
+            .input_mut()
+            .consume_key(Modifiers::NONE, Key::Escape)
+            || widget_response.clicked_elsewhere()
+        {
             ui.memory().close_popup();
         }
         Some(inner)
@@ -323,3 +471,112 @@ pub fn popup_below_widget<R>(
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::Harness;
+    use crate::*;
+
+    fn tooltip_is_shown<State>(harness: &Harness<'_, State>, text: &str) -> bool {
+        harness
+            .find(|widget| widget.label() == Some(text))
+            .is_some()
+    }
+
+    #[test]
+    fn tooltip_delay_and_grace_time() {
+        let mut harness = Harness::new_ui(|ui| {
+            ui.horizontal(|ui| {
+                ui.button("A").on_hover_text("Tooltip A");
+                ui.button("B").on_hover_text("Tooltip B");
+            });
+            ui.add_space(200.0);
+            ui.label("Nothing");
+        });
+        harness.run();
+
+        harness.hover(harness.get_by_label("A").id);
+        harness.run();
+        assert!(
+            !tooltip_is_shown(&harness, "Tooltip A"),
+            "waiting for the delay"
+        );
+        for _ in 0..40 {
+            harness.run();
+        }
+        assert!(tooltip_is_shown(&harness, "Tooltip A"));
+
+        harness.hover(harness.get_by_label("B").id);
+        harness.run();
+        assert!(
+            tooltip_is_shown(&harness, "Tooltip B"),
+            "shown right away after another tooltip"
+        );
+
+        harness.hover(harness.get_by_label("Nothing").id);
+        for _ in 0..30 {
+            harness.run();
+        }
+        assert!(!tooltip_is_shown(&harness, "Tooltip B"));
+        harness.hover(harness.get_by_label("A").id);
+        harness.run();
+        assert!(
+            !tooltip_is_shown(&harness, "Tooltip A"),
+            "after the grace time we wait again"
+        );
+    }
+
+    #[test]
+    fn interactive_tooltip() {
+        let mut harness = Harness::new_ui_state(0, |ui, clicks| {
+            ui.button("Help").on_hover_ui(|ui| {
+                ui.label("Tooltip");
+                if ui.button("More").clicked() {
+                    *clicks += 1;
+                }
+            });
+            ui.add_space(200.0);
+            ui.label("Nothing");
+        });
+        harness.run();
+
+        harness.hover(harness.get_by_label("Help").id);
+        for _ in 0..40 {
+            harness.run();
+        }
+        assert!(tooltip_is_shown(&harness, "Tooltip"));
+
+        harness.hover(harness.get_by_label("More").id);
+        harness.run();
+        assert!(
+            tooltip_is_shown(&harness, "Tooltip"),
+            "stays open with the pointer over it"
+        );
+        harness.click(harness.get_by_label("More").id);
+        harness.run();
+        assert_eq!(*harness.state(), 1);
+        assert!(tooltip_is_shown(&harness, "Tooltip"));
+
+        harness.hover(harness.get_by_label("Nothing").id);
+        harness.run();
+        assert!(!tooltip_is_shown(&harness, "Tooltip"));
+    }
+
+    #[test]
+    fn tooltip_stays_on_screen() {
+        let screen_rect = Rect::from_min_size(Pos2::ZERO, vec2(400.0, 300.0));
+        let size = vec2(100.0, 50.0);
+
+        let widget = Rect::from_min_size(pos2(10.0, 10.0), vec2(50.0, 20.0));
+        let pos = super::tooltip_position(screen_rect, widget.left_bottom(), size, false, widget);
+        assert_eq!(pos, widget.left_bottom(), "below the widget");
+
+        let widget = Rect::from_min_size(pos2(350.0, 270.0), vec2(50.0, 20.0));
+        let pos = super::tooltip_position(screen_rect, widget.left_bottom(), size, false, widget);
+        assert_eq!(pos, pos2(300.0, 220.0), "above the widget, and moved left");
+
+        let widget = Rect::from_min_size(pos2(10.0, 10.0), vec2(50.0, 20.0));
+        let pos = super::tooltip_position(screen_rect, widget.left_top(), size, true, widget);
+        assert_eq!(pos, widget.left_bottom(), "no space above, so below");
+    }
+}
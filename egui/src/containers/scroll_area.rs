@@ -497,7 +497,14 @@ impl ScrollArea {
         let mut prepared = self.begin(ui);
         let id = prepared.id;
         let inner_rect = prepared.inner_rect;
+        ui.ctx().accessibility_begin_container(
+            id,
+            crate::accessibility::Role::ScrollView,
+            None,
+            false,
+        );
         let inner = add_contents(&mut prepared.content_ui, prepared.viewport);
+        ui.ctx().accessibility_end_container(id, inner_rect, false);
         let state = prepared.end(ui);
         ScrollAreaOutput {
             inner,
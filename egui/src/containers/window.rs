@@ -31,6 +31,7 @@ pub struct Window<'open> {
     scroll: ScrollArea,
     collapsible: bool,
     with_title_bar: bool,
+    detached: bool,
 }
 
 impl<'open> Window<'open> {
@@ -52,6 +53,7 @@ impl<'open> Window<'open> {
             scroll: ScrollArea::neither(),
             collapsible: true,
             with_title_bar: true,
+            detached: false,
         }
     }
 
@@ -77,6 +79,25 @@ impl<'open> Window<'open> {
         self
     }
 
+    /// If `true`, pressing tab or an arrow key won't move the keyboard focus out of the window
+    /// once one of its widgets has focus.
+    ///
+    /// See [`Area::trap_focus`]. Default: `false`.
+    pub fn trap_focus(mut self, trap_focus: bool) -> Self {
+        self.area = self.area.trap_focus(trap_focus);
+        self
+    }
+
+    /// Show the window in a native window of its own, i.e. a child viewport (see [`Context::show_viewport`]).
+    ///
+    /// If the user closes the native window, `*open` is set to `false` (see [`Self::open`]).
+    ///
+    /// If the integration does not support viewports, the window is shown as usual.
+    pub fn detached(mut self, detached: bool) -> Self {
+        self.detached = detached;
+        self
+    }
+
     /// Usage: `Window::new(…).mutate(|w| w.resize = w.resize.auto_expand_width(true))`
     /// Not sure this is a good interface for this.
     pub fn mutate(mut self, mutate: impl Fn(&mut Self)) -> Self {
@@ -256,8 +277,41 @@ impl<'open> Window<'open> {
             scroll,
             collapsible,
             with_title_bar,
+            detached,
         } = self;
 
+        if detached && ctx.supports_viewports() {
+            if matches!(open, Some(false)) {
+                return None;
+            }
+
+            let mut builder = ViewportBuilder::new(title.text());
+            if let Some(state) = ctx.memory().areas.get(area.id) {
+                builder = builder.inner_size(state.size);
+            }
+            let frame = frame.unwrap_or_else(|| Frame::central_panel(&ctx.style()));
+
+            let inner_response = ctx.show_viewport(ViewportId(area.id), builder, |ctx| {
+                if ctx.input().raw.close_requested {
+                    if let Some(open) = open {
+                        *open = false;
+                    }
+                }
+
+                CentralPanel::default().frame(frame).show(ctx, |ui| {
+                    if scroll.has_any_bar() {
+                        scroll.show(ui, add_contents).inner
+                    } else {
+                        add_contents(ui)
+                    }
+                })
+            });
+            return Some(InnerResponse::new(
+                Some(inner_response.inner),
+                inner_response.response,
+            ));
+        }
+
         let frame = frame.unwrap_or_else(|| Frame::window(&ctx.style()));
 
         let is_open = !matches!(open, Some(false)) || ctx.memory().everything_is_visible();
@@ -322,6 +376,13 @@ impl<'open> Window<'open> {
 
         let mut area_content_ui = area.content_ui(ctx);
 
+        ctx.accessibility_begin_container(
+            area_id,
+            crate::accessibility::Role::Window,
+            Some(title.text().to_owned()),
+            true,
+        );
+
         let content_inner = {
             // BEGIN FRAME --------------------------------
             let frame_stroke = frame.stroke;
@@ -360,6 +421,7 @@ impl<'open> Window<'open> {
 
             let outer_rect = frame.end(&mut area_content_ui).rect;
             paint_resize_corner(&mut area_content_ui, &possible, outer_rect, frame_stroke);
+            ctx.accessibility_end_container(area_id, outer_rect, true);
 
             // END FRAME --------------------------------
 
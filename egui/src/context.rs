@@ -29,6 +29,23 @@ impl Default for WrappedTextureManager {
 
 // ----------------------------------------------------------------------------
 
+/// The state that each viewport has its own copy of.
+#[derive(Default)]
+struct ViewportState {
+    input: InputState,
+    frame_state: FrameState,
+    graphics: GraphicLayers,
+    output: PlatformOutput,
+    areas: memory::Areas,
+    interaction: memory::Interaction,
+    window_interaction: Option<window::WindowInteraction>,
+
+    /// Was this viewport shown this frame?
+    used: bool,
+}
+
+// ----------------------------------------------------------------------------
+
 #[derive(Default)]
 struct ContextImpl {
     /// `None` until the start of the first frame.
@@ -52,6 +69,21 @@ struct ContextImpl {
     repaint_requests: u32,
     request_repaint_callbacks: Option<Box<dyn Fn() + Send + Sync>>,
     requested_repaint_last_frame: bool,
+
+    /// If set, every [`Response::widget_info`] call is recorded in [`FrameState::widget_infos`].
+    collect_widget_infos: bool,
+
+    /// Set by the integration if it can show child viewports in their own native windows.
+    supports_viewports: bool,
+
+    /// The viewport we are currently running a frame for.
+    viewport_id: ViewportId,
+
+    /// While running a child viewport, this holds the state of the viewports above it.
+    viewport_stack: Vec<(ViewportId, ViewportState)>,
+
+    /// The state of the child viewports, while we are not running them.
+    viewports: ViewportIdMap<ViewportState>,
 }
 
 impl ContextImpl {
@@ -61,13 +93,34 @@ impl ContextImpl {
         self.input = std::mem::take(&mut self.input)
             .begin_frame(new_raw_input, self.requested_repaint_last_frame);
 
-        if let Some(new_pixels_per_point) = self.memory.new_pixels_per_point.take() {
-            self.input.pixels_per_point = new_pixels_per_point;
+        if let Some(key) = self.memory.interaction.focus.used_arrow_key {
+            // Don't let the newly focused widget react to the key that focused it:
+            self.input.consume_key(Modifiers::NONE, key);
+        }
+
+        let is_root = self.viewport_id == ViewportId::ROOT;
+
+        if is_root {
+            if let Some(new_pixels_per_point) = self.memory.new_pixels_per_point.take() {
+                self.input.pixels_per_point = new_pixels_per_point;
+            }
         }
 
         self.frame_state.begin_frame(&self.input);
 
-        self.update_fonts_mut();
+        if self.memory.options.accessibility_tree {
+            self.frame_state.accessibility_tree =
+                Some(crate::accessibility::AccessibilityTreeBuilder::new(
+                    Id::background(),
+                    self.input.screen_rect(),
+                ));
+        }
+
+        if is_root {
+            // Child viewports use the fonts of the root viewport,
+            // so that we don't rebuild the font atlas for each viewport.
+            self.update_fonts_mut();
+        }
 
         // Ensure we register the background area so panels and background ui can catch clicks:
         let screen_rect = self.input.screen_rect();
@@ -106,6 +159,37 @@ impl ContextImpl {
             }
         }
     }
+
+    fn swap_viewport_state(&mut self, state: &mut ViewportState) {
+        std::mem::swap(&mut self.input, &mut state.input);
+        std::mem::swap(&mut self.frame_state, &mut state.frame_state);
+        std::mem::swap(&mut self.graphics, &mut state.graphics);
+        std::mem::swap(&mut self.output, &mut state.output);
+        std::mem::swap(&mut self.memory.areas, &mut state.areas);
+        std::mem::swap(&mut self.memory.interaction, &mut state.interaction);
+        std::mem::swap(
+            &mut self.memory.window_interaction,
+            &mut state.window_interaction,
+        );
+    }
+
+    /// Switch to the state of a child viewport, remembering the state of the current one.
+    fn begin_viewport(&mut self, viewport_id: ViewportId) {
+        let mut state = self.viewports.remove(&viewport_id).unwrap_or_default();
+        self.swap_viewport_state(&mut state);
+        self.viewport_stack.push((self.viewport_id, state));
+        self.viewport_id = viewport_id;
+    }
+
+    /// Switch back to the viewport that was current before [`Self::begin_viewport`].
+    fn end_viewport(&mut self) {
+        if let Some((parent_id, mut state)) = self.viewport_stack.pop() {
+            self.swap_viewport_state(&mut state);
+            state.used = true;
+            self.viewports.insert(self.viewport_id, state);
+            self.viewport_id = parent_id;
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -223,6 +307,122 @@ impl Context {
     /// ```
     pub fn begin_frame(&self, new_input: RawInput) {
         self.write().begin_frame_mut(new_input);
+        DragAndDrop::begin_frame(self);
+        crate::containers::popup::MonoState::begin_frame(self);
+        if self.options().zoom_with_keyboard {
+            crate::gui_zoom::zoom_with_keyboard(self);
+        }
+    }
+
+    // ---------------------------------------------------------------------
+
+    /// For integrations: call this if you show the child viewports of
+    /// [`FullOutput::viewports`] in their own native windows.
+    pub fn set_supports_viewports(&self, supports_viewports: bool) {
+        self.write().supports_viewports = supports_viewports;
+    }
+
+    /// Can the integration show child viewports in their own native windows?
+    ///
+    /// If not, [`Self::show_viewport`] will show its contents in the current viewport.
+    pub fn supports_viewports(&self) -> bool {
+        self.read().supports_viewports
+    }
+
+    /// The viewport we are currently showing.
+    ///
+    /// This is [`ViewportId::ROOT`], except inside of [`Self::show_viewport`].
+    pub fn viewport_id(&self) -> ViewportId {
+        self.read().viewport_id
+    }
+
+    /// Show a child viewport, i.e. a separate native window.
+    ///
+    /// Call this every frame you want the viewport to be shown.
+    /// Once a frame passes without it being called, the native window is closed.
+    ///
+    /// `add_contents` is called immediately, in a frame of its own with the input of the viewport,
+    /// so you should show your ui with e.g. a [`CentralPanel`].
+    /// The child viewport has its own layers and keyboard focus, but shares [`Memory::data`] with
+    /// the rest of the [`Context`].
+    /// When the user tries to close the native window, [`RawInput::close_requested`] is set
+    /// (in `ctx.input().raw`); it is up to you to stop showing the viewport.
+    ///
+    /// Text in all viewports is rasterized for the [`Self::pixels_per_point`] of the root viewport.
+    ///
+    /// If the integration does not support viewports (see [`Self::supports_viewports`]),
+    /// `add_contents` is run in the current viewport instead.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// # ctx.begin_frame(Default::default());
+    /// let viewport_id = egui::ViewportId::from_hash_of("tools");
+    /// ctx.show_viewport(viewport_id, egui::ViewportBuilder::new("Tools"), |ctx| {
+    ///     egui::CentralPanel::default().show(ctx, |ui| {
+    ///         ui.label("Hello from another window!");
+    ///     });
+    /// });
+    /// # let _ = ctx.end_frame();
+    /// ```
+    pub fn show_viewport<R>(
+        &self,
+        viewport_id: ViewportId,
+        builder: ViewportBuilder,
+        add_contents: impl FnOnce(&Context) -> R,
+    ) -> R {
+        if !self.supports_viewports() {
+            return add_contents(self);
+        }
+
+        let raw_input = {
+            let input = self.input();
+            let mut raw_input = input
+                .raw
+                .viewports
+                .get(&viewport_id)
+                .cloned()
+                .unwrap_or_else(|| RawInput {
+                    screen_rect: builder
+                        .inner_size
+                        .map(|size| Rect::from_min_size(Pos2::ZERO, size)),
+                    pixels_per_point: Some(input.pixels_per_point()),
+                    ..Default::default()
+                });
+            raw_input.time = raw_input.time.or(Some(input.time));
+            raw_input.max_texture_side =
+                raw_input.max_texture_side.or(Some(input.max_texture_side));
+            // Nested viewports find their input here:
+            raw_input.viewports = input.raw.viewports.clone();
+            raw_input.viewports.remove(&viewport_id);
+            raw_input
+        };
+
+        let parent = self.viewport_id();
+        {
+            let ctx_impl = &mut *self.write();
+            ctx_impl.begin_viewport(viewport_id);
+            ctx_impl.begin_frame_mut(raw_input);
+        }
+
+        let ret = add_contents(self);
+
+        let mut output = self.end_frame();
+        let pixels_per_point = self.pixels_per_point();
+        self.write().end_viewport();
+
+        let mut frame_state = self.frame_state();
+        frame_state.viewports.extend(output.viewports.drain());
+        frame_state.viewports.insert(
+            viewport_id,
+            ViewportOutput {
+                parent,
+                builder,
+                pixels_per_point,
+                output,
+            },
+        );
+
+        ret
     }
 
     // ---------------------------------------------------------------------
@@ -320,6 +520,15 @@ impl Context {
     ) -> Response {
         let hovered = hovered && enabled; // can't even hover disabled widgets
 
+        if self
+            .input()
+            .has_accessibility_action(id, crate::accessibility::Action::ScrollIntoView)
+        {
+            let mut frame_state = self.frame_state();
+            frame_state.scroll_target[0] = Some((rect.x_range(), None));
+            frame_state.scroll_target[1] = Some((rect.y_range(), None));
+        }
+
         let mut response = Response {
             ctx: self.clone(),
             layer_id,
@@ -338,7 +547,7 @@ impl Context {
             changed: false, // must be set by the widget itself
         };
 
-        if !enabled || !sense.focusable || !layer_id.allow_interaction() {
+        if !enabled || !sense.focusable {
             // Not interested or allowed input:
             self.memory().surrender_focus(id);
             return response;
@@ -356,7 +565,14 @@ impl Context {
             sense.interactive() || sense.focusable && memory.options.screen_reader;
 
         if interested_in_focus {
-            memory.interested_in_focus(id);
+            memory.interested_in_focus(id, layer_id, rect);
+
+            if memory.interaction.focus.moved_by_keyboard && memory.has_focus(id) {
+                // Make sure the user can see what they moved the focus to:
+                let frame_state = &mut ctx_impl.frame_state;
+                frame_state.scroll_target[0] = Some((rect.x_range(), None));
+                frame_state.scroll_target[1] = Some((rect.y_range(), None));
+            }
         }
 
         if sense.click
@@ -367,6 +583,10 @@ impl Context {
             response.clicked[PointerButton::Primary as usize] = true;
         }
 
+        if sense.click && input.has_accessibility_action(id, crate::accessibility::Action::Click) {
+            response.clicked[PointerButton::Primary as usize] = true;
+        }
+
         if sense.click || sense.drag {
             memory.interaction.click_interest |= hovered && sense.click;
             memory.interaction.drag_interest |= hovered && sense.drag;
@@ -443,6 +663,41 @@ impl Context {
         response
     }
 
+    /// Should [`Response::widget_info`] record every widget in [`FrameState::widget_infos`]?
+    pub(crate) fn set_collect_widget_infos(&self, collect: bool) {
+        self.write().collect_widget_infos = collect;
+    }
+
+    pub(crate) fn is_collecting_widget_infos(&self) -> bool {
+        self.read().collect_widget_infos
+    }
+
+    /// Call from containers (e.g. windows and scroll areas) before showing their contents,
+    /// so that the contents become children of the container in the [`crate::AccessibilityTree`].
+    ///
+    /// Set `is_layer` for things with their own [`LayerId`], like windows and popups,
+    /// which will then become children of the root rather than of the current container.
+    ///
+    /// Must be paired with a call to [`Self::accessibility_end_container`].
+    pub(crate) fn accessibility_begin_container(
+        &self,
+        id: Id,
+        role: crate::accessibility::Role,
+        label: Option<String>,
+        is_layer: bool,
+    ) {
+        if let Some(builder) = &mut self.frame_state().accessibility_tree {
+            builder.begin_container(id, role, label, is_layer);
+        }
+    }
+
+    /// `rect` is the area covered by the container.
+    pub(crate) fn accessibility_end_container(&self, id: Id, rect: Rect, is_layer: bool) {
+        if let Some(builder) = &mut self.frame_state().accessibility_tree {
+            builder.end_container(id, rect, is_layer);
+        }
+    }
+
     /// Get a full-screen painter for a new or existing layer
     pub fn layer_painter(&self, layer_id: LayerId) -> Painter {
         let screen_rect = self.input().screen_rect();
@@ -554,6 +809,38 @@ impl Context {
     pub fn tessellation_options(&self) -> RwLockWriteGuard<'_, TessellationOptions> {
         RwLockWriteGuard::map(self.write(), |c| &mut c.memory.options.tessellation_options)
     }
+
+    /// Format the given shortcut in a human-readable way (e.g. `Ctrl+S` or `⌘S`).
+    ///
+    /// Can be used to get the text for [`Button::shortcut_text`].
+    ///
+    /// On Mac this uses symbols (⌘⇧⌥), if the font supports them.
+    pub fn format_shortcut(&self, shortcut: &KeyboardShortcut) -> String {
+        let is_mac = cfg!(target_os = "macos");
+
+        let can_show_symbols = || {
+            let ModifierNames {
+                alt,
+                ctrl,
+                shift,
+                mac_cmd,
+                ..
+            } = ModifierNames::SYMBOLS;
+
+            let font_id = TextStyle::Body.resolve(&self.style());
+            let fonts = self.fonts();
+            fonts.has_glyphs(&font_id, alt)
+                && fonts.has_glyphs(&font_id, ctrl)
+                && fonts.has_glyphs(&font_id, shift)
+                && fonts.has_glyphs(&font_id, mac_cmd)
+        };
+
+        if is_mac && can_show_symbols() {
+            shortcut.format(&ModifierNames::SYMBOLS, is_mac)
+        } else {
+            shortcut.format(&ModifierNames::NAMES, is_mac)
+        }
+    }
 }
 
 impl Context {
@@ -651,6 +938,37 @@ impl Context {
         self.memory().new_pixels_per_point = Some(pixels_per_point);
     }
 
+    /// The zoom factor chosen by the user, on top of the native scale of the screen.
+    ///
+    /// Defaults to `1.0`. It is persisted in [`Memory`] with the `persistence` feature.
+    ///
+    /// See also [`crate::gui_zoom`].
+    #[inline(always)]
+    pub fn zoom_factor(&self) -> f32 {
+        self.options().zoom_factor
+    }
+
+    /// Set the zoom factor, e.g. when the user picks a zoom level in a menu.
+    ///
+    /// This multiplies the native [`Self::pixels_per_point`] of the screen.
+    /// Will become active at the start of the next frame.
+    ///
+    /// Integrations (such as `egui-winit` and `eframe`) will combine this
+    /// with the scale factor of the OS when setting [`RawInput::pixels_per_point`].
+    ///
+    /// Use [`crate::gui_zoom::zoom_with_keyboard`] to let the user zoom with `Ctrl +`, `Ctrl -` and `Ctrl 0`.
+    /// This is done for you unless `zoom_with_keyboard` in [`crate::Context::options`] is `false`.
+    pub fn set_zoom_factor(&self, zoom_factor: f32) {
+        let old_zoom_factor = self.zoom_factor();
+        if zoom_factor != old_zoom_factor {
+            let pending_pixels_per_point = self.memory().new_pixels_per_point;
+            let pixels_per_point =
+                pending_pixels_per_point.unwrap_or_else(|| self.pixels_per_point());
+            self.set_pixels_per_point(pixels_per_point / old_zoom_factor * zoom_factor);
+            self.options().zoom_factor = zoom_factor;
+        }
+    }
+
     /// Useful for pixel-perfect rendering
     pub(crate) fn round_to_pixel(&self, point: f32) -> f32 {
         let pixels_per_point = self.pixels_per_point();
@@ -780,46 +1098,89 @@ impl Context {
     /// Call at the end of each frame.
     #[must_use]
     pub fn end_frame(&self) -> FullOutput {
+        DragAndDrop::end_frame(self);
+        Toasts::end_frame(self);
+        crate::label_selection::LabelSelectionState::end_frame(self);
+
         if self.input().wants_repaint() {
             self.request_repaint();
         }
 
-        let textures_delta;
+        if self.fonts().is_waiting_for_system_fonts() {
+            // Show the missing characters as soon as the system fonts are loaded.
+            // Not holding the lock, since we may be woken up right away:
+            let fonts = self.fonts().clone();
+            let ctx = self.clone();
+            fonts.when_system_fonts_ready(move || ctx.request_repaint());
+        }
+
+        let is_root = self.viewport_id() == ViewportId::ROOT;
+
+        let mut textures_delta = TexturesDelta::default();
         {
             let ctx_impl = &mut *self.write();
-            ctx_impl
-                .memory
-                .end_frame(&ctx_impl.input, &ctx_impl.frame_state.used_ids);
-
-            let font_image_delta = ctx_impl.fonts.as_ref().unwrap().font_image_delta();
-            if let Some(font_image_delta) = font_image_delta {
+            if is_root {
                 ctx_impl
-                    .tex_manager
-                    .0
-                    .write()
-                    .set(TextureId::default(), font_image_delta);
+                    .memory
+                    .end_frame(&ctx_impl.input, &ctx_impl.frame_state.used_ids);
+            } else {
+                ctx_impl
+                    .memory
+                    .end_viewport_frame(&ctx_impl.input, &ctx_impl.frame_state.used_ids);
             }
 
-            textures_delta = ctx_impl.tex_manager.0.write().take_delta();
+            // All texture changes go out with the root viewport,
+            // which is painted first:
+            if is_root {
+                let font_image_delta = ctx_impl.fonts.as_ref().unwrap().font_image_delta();
+                if let Some(font_image_delta) = font_image_delta {
+                    ctx_impl
+                        .tex_manager
+                        .0
+                        .write()
+                        .set(TextureId::default(), font_image_delta);
+                }
+
+                textures_delta = ctx_impl.tex_manager.0.write().take_delta();
+            }
         };
 
-        let platform_output: PlatformOutput = std::mem::take(&mut self.output());
+        let mut platform_output: PlatformOutput = std::mem::take(&mut self.output());
+
+        let accessibility_tree = self.frame_state().accessibility_tree.take();
+        if let Some(accessibility_tree) = accessibility_tree {
+            let focus = self.memory().focus();
+            platform_output.accessibility_tree = Some(accessibility_tree.finish(focus));
+        }
 
-        let needs_repaint = if self.read().repaint_requests > 0 {
+        let needs_repaint = if !is_root {
+            self.read().repaint_requests > 0
+        } else if self.read().repaint_requests > 0 {
             self.write().repaint_requests -= 1;
             true
         } else {
             false
         };
-        self.write().requested_repaint_last_frame = needs_repaint;
+        if is_root {
+            self.write().requested_repaint_last_frame = needs_repaint;
+        }
 
         let shapes = self.drain_paint_lists();
 
+        let viewports = std::mem::take(&mut self.frame_state().viewports);
+        if is_root {
+            // Forget the child viewports that were not shown this frame:
+            self.write()
+                .viewports
+                .retain(|_, state| std::mem::take(&mut state.used));
+        }
+
         FullOutput {
             platform_output,
             needs_repaint,
             textures_delta,
             shapes,
+            viewports,
         }
     }
 
@@ -833,11 +1194,21 @@ impl Context {
 
     /// Tessellate the given shapes into triangle meshes.
     pub fn tessellate(&self, shapes: Vec<ClippedShape>) -> Vec<ClippedPrimitive> {
+        self.tessellate_with_pixels_per_point(shapes, self.pixels_per_point())
+    }
+
+    /// Tessellate the given shapes into triangle meshes for a given scale factor.
+    ///
+    /// Use this for child viewports, with [`ViewportOutput::pixels_per_point`].
+    pub fn tessellate_with_pixels_per_point(
+        &self,
+        shapes: Vec<ClippedShape>,
+        pixels_per_point: f32,
+    ) -> Vec<ClippedPrimitive> {
         // A tempting optimization is to reuse the tessellation from last frame if the
         // shapes are the same, but just comparing the shapes takes about 50% of the time
         // it takes to tessellate them, so it is not a worth optimization.
 
-        let pixels_per_point = self.pixels_per_point();
         let tessellation_options = *self.tessellation_options();
         let texture_atlas = self.fonts().texture_atlas();
         let font_tex_size = texture_atlas.lock().size();
@@ -1044,6 +1415,16 @@ impl Context {
     }
 }
 
+/// ## Toasts
+impl Context {
+    /// Show a short notification in a corner of the screen. See [`Toasts`].
+    ///
+    /// Returns an id you can use with [`Toasts::dismiss`] and [`Toasts::action_clicked`].
+    pub fn add_toast(&self, toast: Toast) -> ToastId {
+        Toasts::add(self, toast)
+    }
+}
+
 impl Context {
     pub fn settings_ui(&self, ui: &mut Ui) {
         use crate::containers::*;
@@ -0,0 +1,458 @@
+//! A tree describing the ui, for platform accessibility APIs and screen readers.
+//!
+//! Turn it on with `accessibility_tree` in [`crate::Context::options`].
+//! egui will then put a fresh [`AccessibilityTree`] in
+//! [`crate::PlatformOutput::accessibility_tree`] each frame.
+//!
+//! Assistive technology can act on the tree by sending
+//! [`crate::Event::AccessibilityActionRequest`] back to egui.
+
+use crate::{Id, IdMap, Rect, WidgetInfo, WidgetType};
+
+/// What kind of thing an [`AccessibilityNode`] is.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Role {
+    /// The root of the tree, covering the whole screen.
+    Application,
+
+    // Containers:
+    /// A [`crate::Window`].
+    Window,
+    /// A [`crate::ScrollArea`].
+    ScrollView,
+    /// The contents of a menu or a [`crate::ComboBox`].
+    Menu,
+
+    // Widgets:
+    Label,
+    Link,
+    TextInput,
+    Button,
+    CheckBox,
+    RadioButton,
+    /// e.g. a [`crate::SelectableLabel`].
+    ToggleButton,
+    ComboBox,
+    Slider,
+    /// e.g. a [`crate::DragValue`].
+    SpinButton,
+    ColorWell,
+    /// The header of a [`crate::CollapsingHeader`], which is also the parent of its body.
+    Disclosure,
+
+    /// Something that doesn't fit any of the above.
+    Unknown,
+}
+
+impl From<WidgetType> for Role {
+    fn from(typ: WidgetType) -> Self {
+        match typ {
+            WidgetType::Label => Self::Label,
+            WidgetType::Link => Self::Link,
+            WidgetType::TextEdit => Self::TextInput,
+            WidgetType::Button | WidgetType::ImageButton => Self::Button,
+            WidgetType::Checkbox => Self::CheckBox,
+            WidgetType::RadioButton => Self::RadioButton,
+            WidgetType::SelectableLabel => Self::ToggleButton,
+            WidgetType::ComboBox => Self::ComboBox,
+            WidgetType::Slider => Self::Slider,
+            WidgetType::DragValue => Self::SpinButton,
+            WidgetType::ColorButton => Self::ColorWell,
+            WidgetType::CollapsingHeader => Self::Disclosure,
+            WidgetType::Other => Self::Unknown,
+        }
+    }
+}
+
+/// Something assistive technology can ask egui to do with an [`AccessibilityNode`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Action {
+    /// Give the widget keyboard focus.
+    Focus,
+
+    /// Click the widget with the primary button.
+    Click,
+
+    /// Change the value of a slider, drag value or text edit.
+    ///
+    /// The new value is in [`ActionRequest::data`].
+    SetValue,
+
+    /// Scroll all enclosing [`crate::ScrollArea`]s so that the node becomes visible.
+    ScrollIntoView,
+}
+
+/// Extra data for an [`ActionRequest`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ActionData {
+    /// For [`Action::SetValue`] on sliders and drag values.
+    NumericValue(f64),
+
+    /// For [`Action::SetValue`] on text edits.
+    Text(String),
+}
+
+/// A request from assistive technology to perform an [`Action`] on some node.
+///
+/// Sent to egui with [`crate::Event::AccessibilityActionRequest`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ActionRequest {
+    /// The [`Id`] of the [`AccessibilityNode`] to act on.
+    pub target: Id,
+
+    pub action: Action,
+
+    /// Required by [`Action::SetValue`].
+    pub data: Option<ActionData>,
+}
+
+impl ActionRequest {
+    pub fn new(target: Id, action: Action) -> Self {
+        Self {
+            target,
+            action,
+            data: None,
+        }
+    }
+
+    pub fn set_numeric_value(target: Id, value: f64) -> Self {
+        Self {
+            target,
+            action: Action::SetValue,
+            data: Some(ActionData::NumericValue(value)),
+        }
+    }
+
+    pub fn set_text(target: Id, text: impl Into<String>) -> Self {
+        Self {
+            target,
+            action: Action::SetValue,
+            data: Some(ActionData::Text(text.into())),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A widget or container in the [`AccessibilityTree`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AccessibilityNode {
+    pub role: Role,
+
+    /// Where the node is on screen, in points.
+    pub rect: Rect,
+
+    /// `None` only for the root.
+    pub parent: Option<Id>,
+
+    /// In the order they were added.
+    pub children: Vec<Id>,
+
+    /// The text on labels, buttons, checkboxes etc, or the title of a window.
+    pub label: Option<String>,
+
+    pub enabled: bool,
+
+    /// The current value of checkboxes, radio buttons and selectable labels.
+    pub selected: Option<bool>,
+
+    /// The current value of sliders and drag values.
+    pub numeric_value: Option<f64>,
+
+    /// The contents of a text edit.
+    pub text_value: Option<String>,
+
+    /// Selected range of characters in [`Self::text_value`].
+    pub text_selection: Option<std::ops::RangeInclusive<usize>>,
+
+    /// What assistive technology may ask egui to do with this node.
+    pub actions: Vec<Action>,
+}
+
+impl AccessibilityNode {
+    fn new(role: Role, parent: Option<Id>) -> Self {
+        Self {
+            role,
+            rect: Rect::NOTHING,
+            parent,
+            children: Default::default(),
+            label: None,
+            enabled: true,
+            selected: None,
+            numeric_value: None,
+            text_value: None,
+            text_selection: None,
+            actions: Default::default(),
+        }
+    }
+
+    fn set_widget_info(&mut self, info: WidgetInfo) {
+        let WidgetInfo {
+            typ,
+            enabled,
+            label,
+            current_text_value,
+            prev_text_value: _,
+            selected,
+            value,
+            text_selection,
+        } = info;
+
+        self.role = typ.into();
+        self.enabled = enabled;
+        self.label = label;
+        self.text_value = current_text_value;
+        self.selected = selected;
+        self.numeric_value = value;
+        self.text_selection = text_selection;
+    }
+}
+
+/// A description of the ui of a whole frame, keyed by widget [`Id`].
+///
+/// Every node is reachable from [`Self::root`] by following [`AccessibilityNode::children`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AccessibilityTree {
+    /// The [`Role::Application`] node covering the whole screen.
+    pub root: Id,
+
+    pub nodes: IdMap<AccessibilityNode>,
+
+    /// The node with keyboard focus, if any.
+    pub focus: Option<Id>,
+}
+
+impl AccessibilityTree {
+    #[inline]
+    pub fn get(&self, id: Id) -> Option<&AccessibilityNode> {
+        self.nodes.get(&id)
+    }
+
+    /// The children of the given node, in order.
+    pub fn children(&self, id: Id) -> impl Iterator<Item = (Id, &AccessibilityNode)> + '_ {
+        self.get(id)
+            .map(|node| node.children.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|child| Some((*child, self.get(*child)?)))
+    }
+
+    /// Find the first node with the given label.
+    pub fn find_by_label(&self, label: &str) -> Option<(Id, &AccessibilityNode)> {
+        self.nodes
+            .iter()
+            .find(|(_, node)| node.label.as_deref() == Some(label))
+            .map(|(id, node)| (*id, node))
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Builds the [`AccessibilityTree`] during a frame.
+///
+/// Containers are pushed and popped as their contents are shown,
+/// so that all widgets get the innermost open container as their parent.
+#[derive(Clone)]
+pub(crate) struct AccessibilityTreeBuilder {
+    tree: AccessibilityTree,
+    parent_stack: Vec<Id>,
+}
+
+impl AccessibilityTreeBuilder {
+    pub fn new(root: Id, screen_rect: Rect) -> Self {
+        let mut root_node = AccessibilityNode::new(Role::Application, None);
+        root_node.rect = screen_rect;
+
+        let mut nodes = IdMap::default();
+        nodes.insert(root, root_node);
+
+        Self {
+            tree: AccessibilityTree {
+                root,
+                nodes,
+                focus: None,
+            },
+            parent_stack: vec![],
+        }
+    }
+
+    fn current_parent(&self) -> Id {
+        self.parent_stack.last().copied().unwrap_or(self.tree.root)
+    }
+
+    /// Get or insert the node with the given id as a child of the current container.
+    fn node_mut(&mut self, id: Id, role: Role) -> &mut AccessibilityNode {
+        if !self.tree.nodes.contains_key(&id) {
+            let parent = self.current_parent();
+            if let Some(parent_node) = self.tree.nodes.get_mut(&parent) {
+                parent_node.children.push(id);
+            }
+            self.tree
+                .nodes
+                .insert(id, AccessibilityNode::new(role, Some(parent)));
+        }
+        self.tree.nodes.get_mut(&id).unwrap()
+    }
+
+    /// Add (or update) a widget as a child of the current container.
+    pub fn widget(&mut self, id: Id, rect: Rect, info: WidgetInfo, actions: Vec<Action>) {
+        let node = self.node_mut(id, info.typ.into());
+        node.rect = rect;
+        node.set_widget_info(info);
+        node.actions = actions;
+    }
+
+    /// All nodes added until the matching [`Self::end_container`] will be children of this one.
+    ///
+    /// If `is_layer` is true, the container is attached to the root instead of the current container,
+    /// as is the case for windows and popups.
+    pub fn begin_container(&mut self, id: Id, role: Role, label: Option<String>, is_layer: bool) {
+        if is_layer {
+            self.parent_stack.push(self.tree.root);
+        }
+        let node = self.node_mut(id, role);
+        if label.is_some() {
+            node.label = label;
+        }
+        if !node.actions.contains(&Action::ScrollIntoView) {
+            node.actions.push(Action::ScrollIntoView);
+        }
+        self.parent_stack.push(id);
+    }
+
+    /// `rect` is the area covered by the contents of the container.
+    pub fn end_container(&mut self, id: Id, rect: Rect, is_layer: bool) {
+        if let Some(node) = self.tree.nodes.get_mut(&id) {
+            node.rect = node.rect.union(rect);
+        }
+        if let Some(index) = self.parent_stack.iter().rposition(|parent| *parent == id) {
+            // Also pop anything the user forgot to close:
+            self.parent_stack.truncate(index);
+        }
+        if is_layer && self.parent_stack.last() == Some(&self.tree.root) {
+            self.parent_stack.pop();
+        }
+    }
+
+    pub fn finish(self, focus: Option<Id>) -> AccessibilityTree {
+        let mut tree = self.tree;
+        tree.focus = focus.filter(|id| tree.nodes.contains_key(id));
+        tree
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Harness;
+
+    fn tree<'h, State>(harness: &'h Harness<'_, State>) -> &'h AccessibilityTree {
+        harness
+            .output()
+            .platform_output
+            .accessibility_tree
+            .as_ref()
+            .expect("accessibility tree should be on")
+    }
+
+    #[test]
+    fn window_is_parent_of_its_widgets() {
+        let mut harness = Harness::new(|ctx| {
+            crate::Window::new("My window").show(ctx, |ui| {
+                let _ = ui.button("Inside");
+            });
+        });
+        harness.ctx().options().accessibility_tree = true;
+        harness.run();
+
+        let tree = tree(&harness);
+        let (window_id, window) = tree.find_by_label("My window").unwrap();
+        assert_eq!(window.role, Role::Window);
+        assert_eq!(window.parent, Some(tree.root));
+
+        let (button_id, button) = tree.find_by_label("Inside").unwrap();
+        assert_eq!(button.role, Role::Button);
+        assert!(button.actions.contains(&Action::Click));
+        assert!(window.rect.contains_rect(button.rect));
+        assert_eq!(button.parent, Some(window_id));
+        assert!(tree.children(window_id).any(|(id, _)| id == button_id));
+    }
+
+    #[test]
+    fn action_requests() {
+        let mut harness =
+            Harness::new_ui_state((0, 0.0), |ui, (clicks, value): &mut (i32, f64)| {
+                if ui.button("Click me").clicked() {
+                    *clicks += 1;
+                }
+                ui.add(crate::Slider::new(value, 0.0..=10.0).text("Value"));
+            });
+        harness.ctx().options().accessibility_tree = true;
+        harness.run();
+
+        let (button, _) = tree(&harness).find_by_label("Click me").unwrap();
+        harness.push_event(crate::Event::AccessibilityActionRequest(
+            ActionRequest::new(button, Action::Click),
+        ));
+        harness.push_event(crate::Event::AccessibilityActionRequest(
+            ActionRequest::new(button, Action::Focus),
+        ));
+        harness.run();
+        assert_eq!(harness.state().0, 1);
+        assert_eq!(tree(&harness).focus, Some(button));
+
+        let slider = harness.get_by_type(crate::WidgetType::Slider).id;
+        assert_eq!(tree(&harness).get(slider).unwrap().role, Role::Slider);
+        harness.push_event(crate::Event::AccessibilityActionRequest(
+            ActionRequest::set_numeric_value(slider, 7.0),
+        ));
+        harness.run();
+        assert_eq!(harness.state().1, 7.0);
+        assert_eq!(tree(&harness).get(slider).unwrap().numeric_value, Some(7.0));
+    }
+
+    #[test]
+    fn focus_request_for_disabled_widget_is_ignored() {
+        let mut harness = Harness::new_ui(|ui| {
+            ui.add_enabled(false, crate::Button::new("Disabled"));
+            ui.label("Not focusable");
+        });
+        harness.ctx().options().accessibility_tree = true;
+        harness.run();
+
+        for label in ["Disabled", "Not focusable"] {
+            let (id, _) = tree(&harness).find_by_label(label).unwrap();
+            harness.push_event(crate::Event::AccessibilityActionRequest(
+                ActionRequest::new(id, Action::Focus),
+            ));
+            harness.run();
+            assert_eq!(harness.ctx().memory().focus(), None);
+        }
+    }
+
+    #[test]
+    fn set_value_of_disabled_widget_is_ignored() {
+        let mut harness = Harness::new_ui_state((1.0, 2.0), |ui, (slider, drag)| {
+            ui.add_enabled(false, crate::Slider::new(slider, 0.0..=10.0));
+            ui.add_enabled(false, crate::DragValue::new(drag));
+        });
+        harness.run();
+
+        for widget_type in [crate::WidgetType::Slider, crate::WidgetType::DragValue] {
+            let id = harness.get_by_type(widget_type).id;
+            harness.push_event(crate::Event::AccessibilityActionRequest(
+                ActionRequest::set_numeric_value(id, 7.0),
+            ));
+            harness.run();
+        }
+        assert_eq!(*harness.state(), (1.0, 2.0));
+    }
+}
@@ -62,6 +62,16 @@ pub struct RawInput {
     /// Note: when using `eframe` on Windows you need to enable
     /// drag-and-drop support using `eframe::NativeOptions`.
     pub dropped_files: Vec<DroppedFile>,
+
+    /// The user asked to close the native window of this viewport,
+    /// e.g. by clicking its close button.
+    ///
+    /// Set by integrations that support child viewports (see [`crate::Context::show_viewport`]).
+    pub close_requested: bool,
+
+    /// The input of each open child viewport, i.e. each native window
+    /// shown with [`crate::Context::show_viewport`].
+    pub viewports: crate::ViewportIdMap<RawInput>,
 }
 
 impl Default for RawInput {
@@ -76,6 +86,8 @@ impl Default for RawInput {
             events: vec![],
             hovered_files: Default::default(),
             dropped_files: Default::default(),
+            close_requested: false,
+            viewports: Default::default(),
         }
     }
 }
@@ -85,6 +97,7 @@ impl RawInput {
     ///
     /// * [`Self::hovered_files`] is cloned.
     /// * [`Self::dropped_files`] is moved.
+    /// * [`Self::viewports`] is moved.
     pub fn take(&mut self) -> RawInput {
         RawInput {
             screen_rect: self.screen_rect.take(),
@@ -96,6 +109,8 @@ impl RawInput {
             events: std::mem::take(&mut self.events),
             hovered_files: self.hovered_files.clone(),
             dropped_files: std::mem::take(&mut self.dropped_files),
+            close_requested: std::mem::take(&mut self.close_requested),
+            viewports: std::mem::take(&mut self.viewports),
         }
     }
 
@@ -111,6 +126,8 @@ impl RawInput {
             mut events,
             mut hovered_files,
             mut dropped_files,
+            close_requested,
+            viewports,
         } = newer;
 
         self.screen_rect = screen_rect.or(self.screen_rect);
@@ -122,6 +139,13 @@ impl RawInput {
         self.events.append(&mut events);
         self.hovered_files.append(&mut hovered_files);
         self.dropped_files.append(&mut dropped_files);
+        self.close_requested |= close_requested;
+        for (viewport_id, viewport_input) in viewports {
+            self.viewports
+                .entry(viewport_id)
+                .or_default()
+                .append(viewport_input);
+        }
     }
 }
 
@@ -241,6 +265,10 @@ pub enum Event {
         /// The value is in the range from 0.0 (no pressure) to 1.0 (maximum pressure).
         force: f32,
     },
+
+    /// Assistive technology (e.g. a screen reader) wants egui to do something,
+    /// e.g. click a button in the [`crate::accessibility::AccessibilityTree`].
+    AccessibilityActionRequest(crate::accessibility::ActionRequest),
 }
 
 /// Mouse button (or similar for touch input)
@@ -266,7 +294,7 @@ pub const NUM_POINTER_BUTTONS: usize = 5;
 /// State of the modifier keys. These must be fed to egui.
 ///
 /// The best way to compare [`Modifiers`] is by using [`Modifiers::matches`].
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Modifiers {
     /// Either of the alt keys are down (option ⌥ on Mac).
@@ -423,6 +451,88 @@ impl std::ops::BitOr for Modifiers {
     }
 }
 
+// ----------------------------------------------------------------------------
+
+/// Names of different modifier keys.
+///
+/// Used to name modifiers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ModifierNames<'a> {
+    /// Use the short [`Key::symbol_or_name`] for the key?
+    pub is_short: bool,
+
+    pub alt: &'a str,
+    pub ctrl: &'a str,
+    pub shift: &'a str,
+    pub mac_cmd: &'a str,
+
+    /// What goes between the names
+    pub concat: &'a str,
+}
+
+impl ModifierNames<'static> {
+    /// ⌥ ^ ⇧ ⌘ - NOTE: not supported by the default egui font.
+    pub const SYMBOLS: Self = Self {
+        is_short: true,
+        alt: "⌥",
+        ctrl: "^",
+        shift: "⇧",
+        mac_cmd: "⌘",
+        concat: "",
+    };
+
+    /// Alt, Ctrl, Shift, Cmd
+    pub const NAMES: Self = Self {
+        is_short: false,
+        alt: "Alt",
+        ctrl: "Ctrl",
+        shift: "Shift",
+        mac_cmd: "Cmd",
+        concat: "+",
+    };
+}
+
+impl<'a> ModifierNames<'a> {
+    /// Format the modifiers in the order, and with the names, that is customary on the given platform.
+    ///
+    /// [`Modifiers::command`] is shown as ⌘ on Mac, and as Ctrl elsewhere.
+    ///
+    /// ```
+    /// use egui::{ModifierNames, Modifiers};
+    /// let modifiers = Modifiers::COMMAND | Modifiers::SHIFT;
+    /// assert_eq!(ModifierNames::NAMES.format(&modifiers, false), "Ctrl+Shift");
+    /// assert_eq!(ModifierNames::NAMES.format(&modifiers, true), "Shift+Cmd");
+    /// assert_eq!(ModifierNames::SYMBOLS.format(&modifiers, true), "⇧⌘");
+    /// ```
+    pub fn format(&self, modifiers: &Modifiers, is_mac: bool) -> String {
+        let mut s = String::new();
+
+        let mut append_if = |modifier_is_active, modifier_name| {
+            if modifier_is_active {
+                if !s.is_empty() {
+                    s += self.concat;
+                }
+                s += modifier_name;
+            }
+        };
+
+        if is_mac {
+            append_if(modifiers.ctrl, self.ctrl);
+            append_if(modifiers.shift, self.shift);
+            append_if(modifiers.alt, self.alt);
+            append_if(modifiers.mac_cmd || modifiers.command, self.mac_cmd);
+        } else {
+            append_if(modifiers.ctrl || modifiers.command, self.ctrl);
+            append_if(modifiers.alt, self.alt);
+            append_if(modifiers.shift, self.shift);
+        }
+
+        s
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// Keyboard keys.
 ///
 /// Includes all keys egui is interested in (such as `Home` and `End`)
@@ -451,6 +561,12 @@ pub enum Key {
     PageUp,
     PageDown,
 
+    /// The minus key (`-`), either from the main row or from the numpad.
+    Minus,
+
+    /// The plus/equals key (`+` or `=`), either from the main row or from the numpad.
+    PlusEquals,
+
     /// Either from the main row or from the numpad.
     Num0,
     /// Either from the main row or from the numpad.
@@ -522,6 +638,200 @@ pub enum Key {
     F20,
 }
 
+impl Key {
+    /// Emoji or name representing the key
+    pub fn symbol_or_name(self) -> &'static str {
+        // TODO(emilk): add support for more unicode symbols (see for instance https://wincompose.info/).
+        // Before we do we must first make sure they are supported in `Fonts` though,
+        // so perhaps this functions needs to take a `supports_character: impl Fn(char) -> bool` or something.
+        match self {
+            Key::ArrowDown => "⏷",
+            Key::ArrowLeft => "⏴",
+            Key::ArrowRight => "⏵",
+            Key::ArrowUp => "⏶",
+            Key::Minus => "-",
+            Key::PlusEquals => "+",
+            _ => self.name(),
+        }
+    }
+
+    /// Human-readable English name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Key::ArrowDown => "Down",
+            Key::ArrowLeft => "Left",
+            Key::ArrowRight => "Right",
+            Key::ArrowUp => "Up",
+            Key::Escape => "Escape",
+            Key::Tab => "Tab",
+            Key::Backspace => "Backspace",
+            Key::Enter => "Enter",
+            Key::Space => "Space",
+            Key::Insert => "Insert",
+            Key::Delete => "Delete",
+            Key::Home => "Home",
+            Key::End => "End",
+            Key::PageUp => "PageUp",
+            Key::PageDown => "PageDown",
+            Key::Minus => "Minus",
+            Key::PlusEquals => "Plus",
+            Key::Num0 => "0",
+            Key::Num1 => "1",
+            Key::Num2 => "2",
+            Key::Num3 => "3",
+            Key::Num4 => "4",
+            Key::Num5 => "5",
+            Key::Num6 => "6",
+            Key::Num7 => "7",
+            Key::Num8 => "8",
+            Key::Num9 => "9",
+            Key::A => "A",
+            Key::B => "B",
+            Key::C => "C",
+            Key::D => "D",
+            Key::E => "E",
+            Key::F => "F",
+            Key::G => "G",
+            Key::H => "H",
+            Key::I => "I",
+            Key::J => "J",
+            Key::K => "K",
+            Key::L => "L",
+            Key::M => "M",
+            Key::N => "N",
+            Key::O => "O",
+            Key::P => "P",
+            Key::Q => "Q",
+            Key::R => "R",
+            Key::S => "S",
+            Key::T => "T",
+            Key::U => "U",
+            Key::V => "V",
+            Key::W => "W",
+            Key::X => "X",
+            Key::Y => "Y",
+            Key::Z => "Z",
+            Key::F1 => "F1",
+            Key::F2 => "F2",
+            Key::F3 => "F3",
+            Key::F4 => "F4",
+            Key::F5 => "F5",
+            Key::F6 => "F6",
+            Key::F7 => "F7",
+            Key::F8 => "F8",
+            Key::F9 => "F9",
+            Key::F10 => "F10",
+            Key::F11 => "F11",
+            Key::F12 => "F12",
+            Key::F13 => "F13",
+            Key::F14 => "F14",
+            Key::F15 => "F15",
+            Key::F16 => "F16",
+            Key::F17 => "F17",
+            Key::F18 => "F18",
+            Key::F19 => "F19",
+            Key::F20 => "F20",
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A keyboard shortcut, e.g. `Ctrl+Alt+W`.
+///
+/// Can be used with [`crate::InputState::consume_shortcut`]
+/// and [`crate::Context::format_shortcut`].
+///
+/// ```
+/// use egui::{Key, KeyboardShortcut, ModifierNames, Modifiers};
+/// let save = KeyboardShortcut::new(Modifiers::COMMAND, Key::S);
+/// assert_eq!(save.format(&ModifierNames::NAMES, false), "Ctrl+S");
+/// assert_eq!(save.format(&ModifierNames::SYMBOLS, true), "⌘S");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct KeyboardShortcut {
+    pub modifiers: Modifiers,
+    pub key: Key,
+}
+
+impl KeyboardShortcut {
+    pub const fn new(modifiers: Modifiers, key: Key) -> Self {
+        Self { modifiers, key }
+    }
+
+    pub fn format(&self, names: &ModifierNames<'_>, is_mac: bool) -> String {
+        let mut s = names.format(&self.modifiers, is_mac);
+        if !s.is_empty() {
+            s += names.concat;
+        }
+        if names.is_short {
+            s += self.key.symbol_or_name();
+        } else {
+            s += self.key.name();
+        }
+        s
+    }
+
+    /// Would the same key press trigger both shortcuts, on Mac or on any other platform?
+    ///
+    /// ```
+    /// use egui::{Key, KeyboardShortcut, Modifiers};
+    /// let command_s = KeyboardShortcut::new(Modifiers::COMMAND, Key::S);
+    /// let ctrl_s = KeyboardShortcut::new(Modifiers::CTRL, Key::S);
+    /// let mac_cmd_s = KeyboardShortcut::new(Modifiers::MAC_CMD, Key::S);
+    /// assert!(command_s.conflicts_with(&ctrl_s)); // On Windows and Linux
+    /// assert!(command_s.conflicts_with(&mac_cmd_s)); // On Mac
+    /// assert!(!ctrl_s.conflicts_with(&mac_cmd_s));
+    /// ```
+    pub fn conflicts_with(&self, other: &Self) -> bool {
+        self.key == other.key
+            && [false, true].into_iter().any(|is_mac| {
+                match (
+                    pressed_modifiers(self.modifiers, is_mac),
+                    pressed_modifiers(other.modifiers, is_mac),
+                ) {
+                    (Some(a), Some(b)) => a == b,
+                    _ => false,
+                }
+            })
+    }
+}
+
+/// The modifiers that are down when the user presses `pattern` on the given platform,
+/// or `None` if it can't be pressed there.
+fn pressed_modifiers(pattern: Modifiers, is_mac: bool) -> Option<Modifiers> {
+    let Modifiers {
+        alt,
+        ctrl,
+        shift,
+        mac_cmd,
+        command,
+    } = pattern;
+
+    if is_mac {
+        Some(Modifiers {
+            alt,
+            ctrl,
+            shift,
+            mac_cmd: mac_cmd || command,
+            command: mac_cmd || command,
+        })
+    } else if mac_cmd {
+        None
+    } else {
+        Some(Modifiers {
+            alt,
+            ctrl: ctrl || command,
+            shift,
+            mac_cmd: false,
+            command: ctrl || command,
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 impl RawInput {
     pub fn ui(&self, ui: &mut crate::Ui) {
         let Self {
@@ -534,6 +844,8 @@ impl RawInput {
             events,
             hovered_files,
             dropped_files,
+            close_requested,
+            viewports,
         } = self;
 
         ui.label(format!("screen_rect: {:?} points", screen_rect));
@@ -551,6 +863,8 @@ impl RawInput {
         ui.label(format!("modifiers: {:#?}", modifiers));
         ui.label(format!("hovered_files: {}", hovered_files.len()));
         ui.label(format!("dropped_files: {}", dropped_files.len()));
+        ui.label(format!("close_requested: {}", close_requested));
+        ui.label(format!("viewports: {}", viewports.len()));
         ui.scope(|ui| {
             ui.set_min_height(150.0);
             ui.label(format!("events: {:#?}", events))
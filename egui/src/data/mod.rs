@@ -1,4 +1,5 @@
 //! All the data sent between egui and the backend
 
+pub mod accessibility;
 pub mod input;
 pub mod output;
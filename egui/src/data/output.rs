@@ -25,6 +25,16 @@ pub struct FullOutput {
     ///
     /// You can use [`crate::Context::tessellate`] to turn this into triangles.
     pub shapes: Vec<epaint::ClippedShape>,
+
+    /// The child viewports shown this frame with [`crate::Context::show_viewport`].
+    ///
+    /// The integration should show each of these in its own native window,
+    /// and close the windows of viewports that are no longer here.
+    ///
+    /// Child viewports can in turn have child viewports of their own.
+    /// These are all in this one flat map (never in [`crate::ViewportOutput::output`]),
+    /// use [`crate::ViewportOutput::parent`] to find out which viewport showed them.
+    pub viewports: crate::ViewportIdMap<crate::ViewportOutput>,
 }
 
 impl FullOutput {
@@ -35,12 +45,14 @@ impl FullOutput {
             needs_repaint,
             textures_delta,
             shapes,
+            viewports,
         } = newer;
 
         self.platform_output.append(platform_output);
         self.needs_repaint = needs_repaint; // if the last frame doesn't need a repaint, then we don't need to repaint
         self.textures_delta.append(textures_delta);
         self.shapes = shapes; // Only paint the latest
+        self.viewports = viewports; // Only show the latest
     }
 }
 
@@ -72,6 +84,11 @@ pub struct PlatformOutput {
 
     /// Screen-space position of text edit cursor (used for IME).
     pub text_cursor_pos: Option<crate::Pos2>,
+
+    /// A description of the whole ui, for platform accessibility APIs.
+    ///
+    /// Only set if `accessibility_tree` is turned on in [`crate::Context::options`].
+    pub accessibility_tree: Option<crate::accessibility::AccessibilityTree>,
 }
 
 impl PlatformOutput {
@@ -108,6 +125,7 @@ impl PlatformOutput {
             mut events,
             mutable_text_under_cursor,
             text_cursor_pos,
+            accessibility_tree,
         } = newer;
 
         self.cursor_icon = cursor_icon;
@@ -120,6 +138,9 @@ impl PlatformOutput {
         self.events.append(&mut events);
         self.mutable_text_under_cursor = mutable_text_under_cursor;
         self.text_cursor_pos = text_cursor_pos.or(self.text_cursor_pos);
+        if accessibility_tree.is_some() {
+            self.accessibility_tree = accessibility_tree; // Only the latest
+        }
     }
 
     /// Take everything ephemeral (everything except `cursor_icon` currently)
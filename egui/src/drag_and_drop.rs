@@ -0,0 +1,206 @@
+use std::{any::Any, sync::Arc};
+
+use crate::{Context, Id, Key, Modifiers};
+
+/// Tracking of the drag-and-drop payload.
+///
+/// This is a low-level API. For a higher-level API, see:
+/// - [`crate::Ui::dnd_drag_source`]
+/// - [`crate::Ui::dnd_drop_zone`]
+/// - [`crate::Response::dnd_set_drag_payload`]
+/// - [`crate::Response::dnd_hover_payload`]
+/// - [`crate::Response::dnd_release_payload`]
+///
+/// The payload is stored in [`crate::Memory::data`].
+/// It is cleared at the end of the frame in which the pointer is released,
+/// and when the user presses Escape to cancel the drag.
+#[doc(alias = "drag and drop")]
+#[derive(Clone, Default)]
+pub struct DragAndDrop {
+    payload: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+impl DragAndDrop {
+    pub(crate) fn begin_frame(ctx: &Context) {
+        if Self::has_any_payload(ctx) && ctx.input_mut().consume_key(Modifiers::NONE, Key::Escape) {
+            Self::clear_payload(ctx);
+            ctx.memory().interaction.drag_id = None;
+        }
+    }
+
+    pub(crate) fn end_frame(ctx: &Context) {
+        if ctx.input().pointer.any_released() {
+            Self::clear_payload(ctx);
+        }
+    }
+
+    /// Set a drag-and-drop payload.
+    ///
+    /// This can be read by [`Self::payload`] until the pointer is released.
+    pub fn set_payload<Payload>(ctx: &Context, payload: Payload)
+    where
+        Payload: Any + Send + Sync,
+    {
+        ctx.data()
+            .get_temp_mut_or_default::<Self>(Id::null())
+            .payload = Some(Arc::new(payload));
+    }
+
+    /// Clears the payload, setting it to `None`.
+    pub fn clear_payload(ctx: &Context) {
+        ctx.data().remove::<Self>(Id::null());
+    }
+
+    /// Retrieve the payload, if any.
+    ///
+    /// Returns `None` if there is no payload, or if it is not of the requested type.
+    pub fn payload<Payload>(ctx: &Context) -> Option<Arc<Payload>>
+    where
+        Payload: Any + Send + Sync,
+    {
+        let payload = ctx.data().get_temp::<Self>(Id::null())?.payload?;
+        payload.downcast().ok()
+    }
+
+    /// Retrieve and clear the payload, if any.
+    ///
+    /// Returns `None` (and leaves the payload alone) if there is no payload,
+    /// or if it is not of the requested type.
+    pub fn take_payload<Payload>(ctx: &Context) -> Option<Arc<Payload>>
+    where
+        Payload: Any + Send + Sync,
+    {
+        let payload = Self::payload(ctx)?;
+        Self::clear_payload(ctx);
+        Some(payload)
+    }
+
+    /// Are we carrying a payload of the given type?
+    ///
+    /// Returns `true` both during a drag and on the frame the pointer is released
+    /// (if there is a payload).
+    pub fn has_payload_of_type<Payload>(ctx: &Context) -> bool
+    where
+        Payload: Any + Send + Sync,
+    {
+        Self::payload::<Payload>(ctx).is_some()
+    }
+
+    /// Are we carrying a payload?
+    ///
+    /// Returns `true` both during a drag and on the frame the pointer is released
+    /// (if there is a payload).
+    pub fn has_any_payload(ctx: &Context) -> bool {
+        ctx.data()
+            .get_temp::<Self>(Id::null())
+            .map_or(false, |state| state.payload.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Item(usize);
+
+    /// A drag source on the left, and a drop zone on the right.
+    fn drag_and_drop_ui(ctx: &Context, dropped: &mut Vec<usize>, hovered: &mut bool) {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.dnd_drag_source(Id::new("source"), Item(42), |ui| {
+                    ui.label("Drag me");
+                });
+                ui.add_space(100.0);
+                let (response, payload) = ui.dnd_drop_zone::<Item, _>(Frame::none(), |ui| {
+                    ui.label("Drop here");
+                });
+                *hovered = response.response.dnd_hover_payload::<Item>().is_some();
+                dropped.extend(payload.map(|item| item.0));
+            });
+        });
+    }
+
+    fn press_and_move(from: Pos2, to: Pos2) -> Vec<Vec<Event>> {
+        let button = |pos, pressed| Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        vec![
+            vec![Event::PointerMoved(from)],
+            vec![button(from, true)],
+            vec![Event::PointerMoved(from + vec2(10.0, 0.0))],
+            vec![Event::PointerMoved(to)],
+        ]
+    }
+
+    fn run(ctx: &Context, events: Vec<Event>, dropped: &mut Vec<usize>, hovered: &mut bool) {
+        let raw_input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0))),
+            events,
+            ..Default::default()
+        };
+        let _ = ctx.run(raw_input, |ctx| drag_and_drop_ui(ctx, dropped, hovered));
+    }
+
+    #[test]
+    fn drag_and_drop_payload() {
+        let ctx = Context::default();
+        let (mut dropped, mut hovered) = (vec![], false);
+        run(&ctx, vec![], &mut dropped, &mut hovered);
+
+        let source = pos2(20.0, 15.0);
+        let target = pos2(180.0, 15.0);
+        for events in press_and_move(source, target) {
+            run(&ctx, events, &mut dropped, &mut hovered);
+        }
+        assert!(DragAndDrop::has_payload_of_type::<Item>(&ctx));
+        assert!(!DragAndDrop::has_payload_of_type::<String>(&ctx));
+        run(&ctx, vec![], &mut dropped, &mut hovered);
+        assert!(hovered);
+
+        let release = Event::PointerButton {
+            pos: target,
+            button: PointerButton::Primary,
+            pressed: false,
+            modifiers: Default::default(),
+        };
+        run(&ctx, vec![release], &mut dropped, &mut hovered);
+        assert_eq!(dropped, vec![42]);
+        assert!(!DragAndDrop::has_any_payload(&ctx));
+    }
+
+    #[test]
+    fn escape_cancels_drag() {
+        let ctx = Context::default();
+        let (mut dropped, mut hovered) = (vec![], false);
+        run(&ctx, vec![], &mut dropped, &mut hovered);
+
+        let source = pos2(20.0, 15.0);
+        let target = pos2(180.0, 15.0);
+        for events in press_and_move(source, target) {
+            run(&ctx, events, &mut dropped, &mut hovered);
+        }
+        assert!(DragAndDrop::has_any_payload(&ctx));
+
+        let escape = Event::Key {
+            key: Key::Escape,
+            pressed: true,
+            modifiers: Default::default(),
+        };
+        run(&ctx, vec![escape], &mut dropped, &mut hovered);
+        assert!(!DragAndDrop::has_any_payload(&ctx));
+        assert!(!ctx.memory().is_anything_being_dragged());
+
+        let release = Event::PointerButton {
+            pos: target,
+            button: PointerButton::Primary,
+            pressed: false,
+            modifiers: Default::default(),
+        };
+        run(&ctx, vec![release], &mut dropped, &mut hovered);
+        assert!(dropped.is_empty());
+    }
+}
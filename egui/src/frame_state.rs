@@ -41,6 +41,17 @@ pub(crate) struct FrameState {
 
     /// horizontal, vertical
     pub(crate) scroll_target: [Option<(RangeInclusive<f32>, Option<Align>)>; 2],
+
+    /// Every widget that reported itself with [`Response::widget_info`] this frame.
+    ///
+    /// Only collected when turned on with `Context::set_collect_widget_infos`.
+    pub(crate) widget_infos: Vec<crate::testing::WidgetSnapshot>,
+
+    /// Only set if `accessibility_tree` is turned on in [`crate::Context::options`].
+    pub(crate) accessibility_tree: Option<crate::accessibility::AccessibilityTreeBuilder>,
+
+    /// The child viewports shown this frame, including nested ones.
+    pub(crate) viewports: crate::ViewportIdMap<crate::ViewportOutput>,
 }
 
 impl Default for FrameState {
@@ -53,6 +64,9 @@ impl Default for FrameState {
             tooltip_rect: None,
             scroll_delta: Vec2::ZERO,
             scroll_target: [None, None],
+            widget_infos: Default::default(),
+            accessibility_tree: None,
+            viewports: Default::default(),
         }
     }
 }
@@ -67,6 +81,9 @@ impl FrameState {
             tooltip_rect,
             scroll_delta,
             scroll_target,
+            widget_infos,
+            accessibility_tree,
+            viewports,
         } = self;
 
         used_ids.clear();
@@ -76,6 +93,9 @@ impl FrameState {
         *tooltip_rect = None;
         *scroll_delta = input.scroll_delta;
         *scroll_target = [None, None];
+        widget_infos.clear();
+        *accessibility_tree = None;
+        viewports.clear();
     }
 
     /// How much space is still available after panels has been added.
@@ -0,0 +1,157 @@
+//! Helpers for zooming the whole GUI of an app (changing [`Context::zoom_factor`]).
+//!
+//! The zoom factor multiplies the native [`Context::pixels_per_point`] of the screen,
+//! and is persisted in [`crate::Memory`].
+
+use crate::*;
+
+/// The default keyboard shortcuts for zooming the GUI.
+pub mod kb_shortcuts {
+    use super::*;
+
+    /// `Cmd +` / `Ctrl +`.
+    pub const ZOOM_IN: KeyboardShortcut =
+        KeyboardShortcut::new(Modifiers::COMMAND, Key::PlusEquals);
+
+    /// `Cmd Shift +` / `Ctrl Shift +`.
+    ///
+    /// On many keyboard layouts you need to hold down shift to type `+`.
+    pub const ZOOM_IN_SHIFT: KeyboardShortcut = KeyboardShortcut::new(
+        Modifiers {
+            shift: true,
+            ..Modifiers::COMMAND
+        },
+        Key::PlusEquals,
+    );
+
+    /// `Cmd -` / `Ctrl -`.
+    pub const ZOOM_OUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Minus);
+
+    /// `Cmd 0` / `Ctrl 0`.
+    pub const ZOOM_RESET: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Num0);
+}
+
+/// The range of [`Context::zoom_factor`] reachable with [`zoom_in`] and [`zoom_out`].
+pub const ZOOM_RANGE: std::ops::RangeInclusive<f32> = 0.2..=5.0;
+
+/// Let the user scale the GUI (change [`Context::zoom_factor`]) by pressing
+/// Cmd+Plus, Cmd+Minus or Cmd+0, just like in a browser.
+///
+/// This is called for you at the start of each frame
+/// unless `zoom_with_keyboard` in [`crate::Context::options`] is `false`.
+pub fn zoom_with_keyboard(ctx: &Context) {
+    if ctx.input_mut().consume_shortcut(&kb_shortcuts::ZOOM_RESET) {
+        ctx.set_zoom_factor(1.0);
+    } else {
+        if ctx.input_mut().consume_shortcut(&kb_shortcuts::ZOOM_IN)
+            || ctx
+                .input_mut()
+                .consume_shortcut(&kb_shortcuts::ZOOM_IN_SHIFT)
+        {
+            zoom_in(ctx);
+        }
+        if ctx.input_mut().consume_shortcut(&kb_shortcuts::ZOOM_OUT) {
+            zoom_out(ctx);
+        }
+    }
+}
+
+/// Make everything larger by increasing [`Context::zoom_factor`].
+pub fn zoom_in(ctx: &Context) {
+    zoom_by(ctx, 0.1);
+}
+
+/// Make everything smaller by decreasing [`Context::zoom_factor`].
+pub fn zoom_out(ctx: &Context) {
+    zoom_by(ctx, -0.1);
+}
+
+fn zoom_by(ctx: &Context, delta: f32) {
+    let zoom_factor = (ctx.zoom_factor() + delta).clamp(*ZOOM_RANGE.start(), *ZOOM_RANGE.end());
+    let zoom_factor = (zoom_factor * 10.).round() / 10.;
+    ctx.set_zoom_factor(zoom_factor);
+}
+
+/// Show buttons for zooming the GUI, with their keyboard shortcuts.
+///
+/// This is meant to be called from within a menu (See [`Ui::menu_button`]).
+pub fn zoom_menu_buttons(ui: &mut Ui) {
+    if ui
+        .add_enabled(
+            ui.ctx().zoom_factor() < *ZOOM_RANGE.end(),
+            Button::new("Zoom In").shortcut_text(ui.ctx().format_shortcut(&kb_shortcuts::ZOOM_IN)),
+        )
+        .clicked()
+    {
+        zoom_in(ui.ctx());
+        ui.close_menu();
+    }
+
+    if ui
+        .add_enabled(
+            ui.ctx().zoom_factor() > *ZOOM_RANGE.start(),
+            Button::new("Zoom Out")
+                .shortcut_text(ui.ctx().format_shortcut(&kb_shortcuts::ZOOM_OUT)),
+        )
+        .clicked()
+    {
+        zoom_out(ui.ctx());
+        ui.close_menu();
+    }
+
+    if ui
+        .add_enabled(
+            ui.ctx().zoom_factor() != 1.0,
+            Button::new("Reset Zoom")
+                .shortcut_text(ui.ctx().format_shortcut(&kb_shortcuts::ZOOM_RESET)),
+        )
+        .clicked()
+    {
+        ui.ctx().set_zoom_factor(1.0);
+        ui.close_menu();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn run(ctx: &Context, events: Vec<Event>) {
+        let raw_input = RawInput {
+            pixels_per_point: Some(2.0),
+            events,
+            ..Default::default()
+        };
+        let _ = ctx.run(raw_input, |_ctx| {});
+    }
+
+    fn key(key: Key) -> Event {
+        Event::Key {
+            key,
+            pressed: true,
+            modifiers: Modifiers::CTRL | Modifiers::COMMAND,
+        }
+    }
+
+    #[test]
+    fn zoom_with_keyboard() {
+        let ctx = Context::default();
+        run(&ctx, vec![]);
+        assert_eq!(ctx.zoom_factor(), 1.0);
+
+        run(&ctx, vec![key(Key::PlusEquals)]);
+        assert_eq!(ctx.zoom_factor(), 1.1);
+        assert_eq!(ctx.pixels_per_point(), 2.0, "zoom is applied next frame");
+
+        // An integration that knows about zoom would send `2.0 * 1.1` here:
+        let _ = ctx.run(RawInput::default(), |_ctx| {});
+        assert_eq!(ctx.pixels_per_point(), 2.0 * 1.1);
+
+        run(&ctx, vec![key(Key::Num0)]);
+        assert_eq!(ctx.zoom_factor(), 1.0);
+
+        ctx.options().zoom_with_keyboard = false;
+        run(&ctx, vec![key(Key::Minus)]);
+        assert_eq!(ctx.zoom_factor(), 1.0);
+    }
+}
@@ -267,6 +267,23 @@ impl InputState {
         match_found
     }
 
+    /// Check if the given shortcut has been pressed.
+    ///
+    /// If so, `true` is returned and the key pressed is consumed, so that this will only return `true` once.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// const SAVE: egui::KeyboardShortcut =
+    ///     egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::S);
+    /// if ctx.input_mut().consume_shortcut(&SAVE) {
+    ///     // save the document
+    /// }
+    /// ```
+    pub fn consume_shortcut(&mut self, shortcut: &KeyboardShortcut) -> bool {
+        let KeyboardShortcut { modifiers, key } = *shortcut;
+        self.consume_key(modifiers, key)
+    }
+
     /// Was the given key pressed this frame?
     pub fn key_pressed(&self, desired_key: Key) -> bool {
         self.num_presses(desired_key) > 0
@@ -308,6 +325,39 @@ impl InputState {
         })
     }
 
+    /// All [`Event::AccessibilityActionRequest`]s for the given widget this frame.
+    pub fn accessibility_action_requests(
+        &self,
+        id: crate::Id,
+    ) -> impl Iterator<Item = &crate::accessibility::ActionRequest> + '_ {
+        self.events.iter().filter_map(move |event| match event {
+            Event::AccessibilityActionRequest(request) if request.target == id => Some(request),
+            _ => None,
+        })
+    }
+
+    /// Did assistive technology ask for this action on the given widget this frame?
+    pub fn has_accessibility_action(
+        &self,
+        id: crate::Id,
+        action: crate::accessibility::Action,
+    ) -> bool {
+        self.accessibility_action_requests(id)
+            .any(|request| request.action == action)
+    }
+
+    /// The latest [`ActionData`](crate::accessibility::ActionData) sent with
+    /// [`Action::SetValue`](crate::accessibility::Action::SetValue) to the given widget this frame.
+    pub fn accessibility_set_value(
+        &self,
+        id: crate::Id,
+    ) -> Option<&crate::accessibility::ActionData> {
+        self.accessibility_action_requests(id)
+            .filter(|request| request.action == crate::accessibility::Action::SetValue)
+            .filter_map(|request| request.data.as_ref())
+            .last()
+    }
+
     /// Also known as device pixel ratio, > 1 for high resolution screens.
     #[inline(always)]
     pub fn pixels_per_point(&self) -> f32 {
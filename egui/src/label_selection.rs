@@ -0,0 +1,478 @@
+use epaint::text::{cursor::CCursor, Galley};
+
+use crate::{
+    text_edit::{
+        paint_cursor_selection, select_line_at, select_word_at, CCursorRange, CursorRange,
+    },
+    Context, CursorIcon, Event, Id, PointerButton, Pos2, Rect, Response, Ui,
+};
+
+/// Text selection that can span several [`crate::Label`]s in the same [`Ui`].
+///
+/// Select by dragging, double-click to select a word, and triple-click to select a line.
+/// Shift-click extends the selection.
+/// The selected text is copied to [`crate::PlatformOutput::copied_text`] on [`Event::Copy`].
+///
+/// The state is stored in [`crate::Memory::data`].
+#[derive(Clone, Default)]
+pub(crate) struct LabelSelectionState {
+    selection: Option<Selection>,
+
+    /// The selectable labels in the order they were shown last frame.
+    ///
+    /// Used to tell which labels are between the two ends of the selection.
+    labels: Vec<Id>,
+
+    /// The selectable labels shown so far this frame.
+    labels_this_frame: Vec<Id>,
+
+    /// The selected text shown so far this frame.
+    text_this_frame: String,
+
+    /// Where the last label with selected text was, to tell if the next one is on a new line.
+    last_selected_rect: Option<Rect>,
+
+    /// Did the pointer press on a selectable label this frame?
+    pressed_label: bool,
+
+    /// Did a focused [`crate::TextEdit`] handle the keyboard events this frame?
+    /// Then it does its own copying.
+    text_edit_has_focus: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Selection {
+    /// The [`Ui`] the selected labels are in.
+    ui_id: Id,
+
+    /// Where the selection started: the label and the cursor in it.
+    secondary: (Id, CCursor),
+
+    /// Where the selection ends, which moves while dragging.
+    primary: (Id, CCursor),
+
+    /// Is the pointer still down, extending the selection?
+    dragging: bool,
+}
+
+/// The pointer input a selectable label cares about, read before locking the state.
+struct PointerInput {
+    pos: Option<Pos2>,
+
+    /// The primary button was pressed this frame.
+    pressed: bool,
+
+    /// The primary button is held down.
+    down: bool,
+
+    shift: bool,
+
+    /// Primary clicks on the label released this frame, with their click count.
+    clicks: Vec<(Pos2, u32)>,
+
+    /// Is the pointer over the label?
+    over_label: bool,
+}
+
+impl PointerInput {
+    fn read(ui: &Ui, response: &Response) -> Self {
+        // `response.hovered()` is false while the pointer is down, so check the rect directly:
+        let over_label = ui.rect_contains_pointer(response.rect);
+
+        let input = ui.input();
+        let pointer = &input.pointer;
+        let clicks = pointer
+            .pointer_events
+            .iter()
+            .filter_map(|event| match event {
+                crate::input_state::PointerEvent::Released(Some(click))
+                    if click.button == PointerButton::Primary
+                        && response.rect.contains(click.pos) =>
+                {
+                    Some((click.pos, click.count))
+                }
+                _ => None,
+            })
+            .collect();
+        Self {
+            pos: pointer.interact_pos(),
+            pressed: pointer.primary_clicked(),
+            down: pointer.button_down(PointerButton::Primary),
+            shift: input.modifiers.shift,
+            clicks,
+            over_label,
+        }
+    }
+}
+
+impl LabelSelectionState {
+    pub(crate) fn end_frame(ctx: &Context) {
+        let (pressed, released, copy) = {
+            let input = ctx.input();
+            (
+                input.pointer.any_pressed(),
+                input.pointer.any_released(),
+                input.events.iter().any(|event| event == &Event::Copy),
+            )
+        };
+
+        let copied_text = {
+            let mut memory = ctx.memory();
+            let state = memory.data.get_temp_mut_or_default::<Self>(Id::null());
+
+            if pressed && !state.pressed_label {
+                // Clicked somewhere else:
+                state.selection = None;
+            }
+            if released {
+                if let Some(selection) = &mut state.selection {
+                    selection.dragging = false;
+                }
+            }
+
+            let copied_text = (copy
+                && state.selection.is_some()
+                && !state.text_this_frame.is_empty()
+                && !state.text_edit_has_focus)
+                .then(|| std::mem::take(&mut state.text_this_frame));
+
+            std::mem::swap(&mut state.labels, &mut state.labels_this_frame);
+            state.labels_this_frame.clear();
+            state.text_this_frame.clear();
+            state.last_selected_rect = None;
+            state.pressed_label = false;
+            state.text_edit_has_focus = false;
+            copied_text
+        };
+
+        if let Some(copied_text) = copied_text {
+            ctx.output().copied_text = copied_text;
+        }
+    }
+
+    /// Called by a focused [`crate::TextEdit`] that handles the keyboard events this frame,
+    /// so that the selected label text is not copied over what it copies.
+    pub(crate) fn text_edit_has_focus(ctx: &Context) {
+        let mut memory = ctx.memory();
+        let state = memory.data.get_temp_mut_or_default::<Self>(Id::null());
+        state.text_edit_has_focus = true;
+    }
+
+    /// Handle the pointer for a selectable label, and paint its selected text (if any).
+    ///
+    /// `galley_pos` is where the galley is painted.
+    pub(crate) fn label_text_selection(
+        ui: &Ui,
+        response: &Response,
+        galley_pos: Pos2,
+        galley: &Galley,
+    ) {
+        let id = response.id;
+        let ui_id = ui.id();
+
+        if response.hovered() {
+            ui.output().cursor_icon = CursorIcon::Text;
+        }
+        let pointer = PointerInput::read(ui, response);
+
+        let ccursor_range = {
+            let mut memory = ui.memory();
+            let memory = &mut *memory;
+            let state = memory.data.get_temp_mut_or_default::<Self>(Id::null());
+            state.labels_this_frame.push(id);
+
+            // Windows have low priority on dragging, but don't interrupt resizing one:
+            let is_resizing_window = memory
+                .window_interaction
+                .map_or(false, |interaction| interaction.is_resize());
+            let can_take_drag = memory.interaction.drag_id.is_none()
+                || (memory.interaction.drag_is_window && !is_resizing_window);
+            let started_selection =
+                state.handle_pointer(&pointer, can_take_drag, ui_id, id, galley_pos, galley);
+            if started_selection && memory.interaction.drag_is_window {
+                // Select the text instead of moving the window:
+                memory.interaction.drag_id = Some(id);
+                memory.interaction.drag_is_window = false;
+            }
+
+            let ccursor_range = state
+                .selection
+                .filter(|selection| selection.ui_id == ui_id)
+                .and_then(|selection| selection.range_in_label(&state.labels, id, galley));
+            if let Some(ccursor_range) = ccursor_range {
+                let [min, max] = ccursor_range.sorted();
+                if min.index < max.index {
+                    state.add_selected_text(response.rect, galley.text(), min.index..max.index);
+                }
+            }
+            if started_selection {
+                memory.stop_text_input();
+            }
+            ccursor_range
+        };
+
+        if let Some(ccursor_range) = ccursor_range {
+            let cursor_range = CursorRange {
+                primary: galley.from_ccursor(ccursor_range.primary),
+                secondary: galley.from_ccursor(ccursor_range.secondary),
+            };
+            paint_cursor_selection(ui, ui.painter(), galley_pos, galley, &cursor_range);
+        }
+    }
+
+    /// Returns `true` if the pointer was pressed on the text of this label to start selecting it.
+    fn handle_pointer(
+        &mut self,
+        pointer: &PointerInput,
+        can_take_drag: bool,
+        ui_id: Id,
+        id: Id,
+        galley_pos: Pos2,
+        galley: &Galley,
+    ) -> bool {
+        let cursor_at = |pos: Pos2| galley.cursor_from_pos(pos - galley_pos).ccursor;
+        let mut started_selection = false;
+
+        if let Some(pointer_pos) = pointer.pos {
+            let on_text = galley
+                .rect
+                .translate(galley_pos.to_vec2())
+                .contains(pointer_pos);
+            if pointer.pressed && pointer.over_label && on_text && can_take_drag {
+                self.pressed_label = true;
+                started_selection = true;
+
+                let primary = (id, cursor_at(pointer_pos));
+                match &mut self.selection {
+                    Some(selection) if pointer.shift && selection.ui_id == ui_id => {
+                        selection.primary = primary;
+                        selection.dragging = true;
+                    }
+                    _ => {
+                        self.selection = Some(Selection {
+                            ui_id,
+                            secondary: primary,
+                            primary,
+                            dragging: true,
+                        });
+                    }
+                }
+            } else if pointer.down && pointer.over_label {
+                if let Some(selection) = &mut self.selection {
+                    if selection.dragging && selection.ui_id == ui_id {
+                        selection.primary = (id, cursor_at(pointer_pos));
+                    }
+                }
+            }
+        }
+
+        for &(pos, count) in &pointer.clicks {
+            if !(count == 2 || count == 3) {
+                continue;
+            }
+            let ccursor = cursor_at(pos);
+            let range = if count == 2 {
+                select_word_at(galley.text(), ccursor)
+            } else {
+                select_line_at(galley.text(), ccursor)
+            };
+            self.selection = Some(Selection {
+                ui_id,
+                secondary: (id, range.secondary),
+                primary: (id, range.primary),
+                dragging: false,
+            });
+        }
+
+        started_selection
+    }
+
+    fn add_selected_text(&mut self, rect: Rect, text: &str, char_range: std::ops::Range<usize>) {
+        if let Some(last_rect) = self.last_selected_rect {
+            if rect.top() >= last_rect.bottom() - 1.0 {
+                self.text_this_frame.push('\n');
+            } else {
+                self.text_this_frame.push(' ');
+            }
+        }
+        self.text_this_frame.extend(
+            text.chars()
+                .skip(char_range.start)
+                .take(char_range.end - char_range.start),
+        );
+        self.last_selected_rect = Some(rect);
+    }
+}
+
+impl Selection {
+    /// The selected part of the label with this id, if any.
+    ///
+    /// `labels` are the labels in the order they are shown.
+    fn range_in_label(&self, labels: &[Id], id: Id, galley: &Galley) -> Option<CCursorRange> {
+        let (secondary_id, secondary) = self.secondary;
+        let (primary_id, primary) = self.primary;
+        if secondary_id == id && primary_id == id {
+            return Some(CCursorRange { primary, secondary });
+        }
+
+        let index_of = |id: Id| labels.iter().position(|&label| label == id);
+        let label = index_of(id)?;
+        let secondary_index = index_of(secondary_id)?;
+        let primary_index = index_of(primary_id)?;
+
+        let start = CCursor::new(0);
+        let end = galley.end().ccursor;
+        let forward = secondary_index < primary_index;
+
+        if label == secondary_index {
+            Some(CCursorRange {
+                primary: if forward { end } else { start },
+                secondary,
+            })
+        } else if label == primary_index {
+            Some(CCursorRange {
+                primary,
+                secondary: if forward { start } else { end },
+            })
+        } else if secondary_index.min(primary_index) < label
+            && label < secondary_index.max(primary_index)
+        {
+            Some(CCursorRange::two(start, end))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::Harness, vec2, Event, PointerButton, Pos2};
+
+    fn labels_harness() -> Harness<'static, ()> {
+        Harness::new_ui(|ui| {
+            ui.label("Hello world");
+            ui.label("Second label");
+            ui.label("Third");
+        })
+    }
+
+    fn copy<State>(harness: &mut Harness<'_, State>) -> String {
+        harness.push_event(Event::Copy);
+        harness.run().platform_output.copied_text.clone()
+    }
+
+    fn press(harness: &mut Harness<'_, ()>, pos: Pos2, pressed: bool) {
+        harness.push_event(Event::PointerMoved(pos));
+        harness.push_event(Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        });
+        harness.run();
+    }
+
+    #[test]
+    fn drag_to_select_several_labels() {
+        let mut harness = labels_harness();
+        harness.run();
+
+        let first = harness.get_by_label("Hello world").rect;
+        let third = harness.get_by_label("Third").rect;
+        press(&mut harness, first.left_center() + vec2(0.5, 0.0), true);
+        harness.push_event(Event::PointerMoved(third.right_center() - vec2(0.5, 0.0)));
+        harness.run();
+        press(&mut harness, third.right_center() - vec2(0.5, 0.0), false);
+
+        assert_eq!(copy(&mut harness), "Hello world\nSecond label\nThird");
+
+        // Clicking somewhere else clears the selection:
+        harness.click_at(third.center() + vec2(0.0, 100.0), PointerButton::Primary);
+        harness.run();
+        assert_eq!(copy(&mut harness), "");
+    }
+
+    #[test]
+    fn select_in_window_without_moving_it() {
+        let mut harness = Harness::new(|ctx| {
+            crate::Window::new("Window")
+                .default_width(200.0)
+                .show(ctx, |ui| ui.label("Hi there"));
+        });
+        harness.run();
+        harness.run();
+
+        let drag = |harness: &mut Harness<'_, ()>, from: Pos2, to: Pos2| {
+            press(harness, from, true);
+            harness.push_event(Event::PointerMoved(to));
+            harness.run();
+            press(harness, to, false);
+        };
+
+        // Dragging over the label selects its text:
+        let label = harness.get_by_label("Hi there").rect;
+        drag(
+            &mut harness,
+            label.center(),
+            label.right_center() - vec2(0.5, 0.0),
+        );
+        assert_eq!(harness.get_by_label("Hi there").rect, label);
+        assert_eq!(copy(&mut harness), "here");
+
+        // Dragging next to it moves the window:
+        let from = label.right_center() + vec2(20.0, 0.0);
+        drag(&mut harness, from, from + vec2(30.0, 20.0));
+        assert_eq!(
+            harness.get_by_label("Hi there").rect,
+            label.translate(vec2(30.0, 20.0))
+        );
+    }
+
+    #[test]
+    fn copy_with_focused_widget() {
+        let mut harness = Harness::new_ui_state("text edit".to_owned(), |ui, text| {
+            ui.label("Hello world");
+            ui.button("Button");
+            ui.text_edit_singleline(text);
+        });
+        harness.run();
+
+        let label = harness.get_by_label("Hello world").rect;
+        let pos = label.left_center() + vec2(2.0, 0.0);
+        harness.click_at(pos, PointerButton::Primary);
+        harness.click_at(pos, PointerButton::Primary);
+        harness.run();
+
+        // Another focused widget does not stop the label text from being copied:
+        let button = harness.get_by_label("Button").id;
+        harness.ctx().memory().request_focus(button);
+        harness.run();
+        assert_eq!(copy(&mut harness), "Hello");
+
+        // A focused `TextEdit` copies its own text:
+        let text_edit = harness.get_by_type(crate::WidgetType::TextEdit).id;
+        harness.ctx().memory().request_focus(text_edit);
+        harness.run();
+        assert_eq!(copy(&mut harness), "text edit");
+    }
+
+    #[test]
+    fn double_click_selects_word() {
+        let mut harness = labels_harness();
+        harness.run();
+
+        let second = harness.get_by_label("Second label").rect;
+        let pos = second.left_center() + vec2(2.0, 0.0);
+        harness.click_at(pos, PointerButton::Primary);
+        harness.click_at(pos, PointerButton::Primary);
+        harness.run();
+        assert_eq!(copy(&mut harness), "Second");
+
+        // Triple-click selects the whole line:
+        harness.click_at(pos, PointerButton::Primary);
+        harness.click_at(pos, PointerButton::Primary);
+        harness.click_at(pos, PointerButton::Primary);
+        harness.run();
+        assert_eq!(copy(&mut harness), "Second label");
+    }
+}
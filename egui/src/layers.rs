@@ -18,7 +18,8 @@ pub enum Order {
     /// Foreground objects can also have tooltips
     Foreground,
     /// Things floating on top of everything else, like tooltips.
-    /// You cannot interact with these.
+    ///
+    /// Most tooltips can't be interacted with, but the ones from [`crate::Response::on_hover_ui`] can.
     Tooltip,
     /// Debug layer, always painted last / on top
     Debug,
@@ -35,6 +36,8 @@ impl Order {
         Self::Debug,
     ];
 
+    /// Returns what it did before all layers could be interacted with: `false` only for tooltips.
+    #[deprecated = "All layers can now be interacted with, including tooltips"] // Deprecated before egui 0.19
     #[inline(always)]
     pub fn allow_interaction(&self) -> bool {
         match self {
@@ -88,7 +91,10 @@ impl LayerId {
         }
     }
 
+    /// Returns what it did before all layers could be interacted with: `false` only for tooltips.
+    #[deprecated = "All layers can now be interacted with, including tooltips"] // Deprecated before egui 0.19
     #[inline(always)]
+    #[allow(deprecated)]
     pub fn allow_interaction(&self) -> bool {
         self.order.allow_interaction()
     }
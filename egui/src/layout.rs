@@ -1,4 +1,4 @@
-use crate::{egui_assert, emath::*, Align};
+use crate::{egui_assert, emath::*, Align, Style};
 use std::f32::INFINITY;
 
 // ----------------------------------------------------------------------------
@@ -100,6 +100,16 @@ impl Direction {
             Direction::TopDown | Direction::BottomUp => true,
         }
     }
+
+    /// Swap left and right.
+    #[inline(always)]
+    pub fn mirrored(self) -> Self {
+        match self {
+            Direction::LeftToRight => Direction::RightToLeft,
+            Direction::RightToLeft => Direction::LeftToRight,
+            Direction::TopDown | Direction::BottomUp => self,
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -257,6 +267,47 @@ impl Layout {
             ..self
         }
     }
+
+    /// Swap left and right, e.g. for right-to-left languages.
+    ///
+    /// ```
+    /// # use egui::{Align, Layout};
+    /// assert_eq!(Layout::left_to_right().mirrored(), Layout::right_to_left());
+    /// assert_eq!(Layout::top_down(Align::LEFT).mirrored(), Layout::top_down(Align::RIGHT));
+    /// ```
+    pub fn mirrored(self) -> Self {
+        if self.main_dir.is_horizontal() {
+            Self {
+                main_dir: self.main_dir.mirrored(),
+                main_align: flip_align(self.main_align),
+                ..self
+            }
+        } else {
+            Self {
+                cross_align: flip_align(self.cross_align),
+                ..self
+            }
+        }
+    }
+
+    /// Mirror the layout if the [`Style`] is right-to-left.
+    ///
+    /// Used for the layouts egui picks by default.
+    pub(crate) fn for_style(self, style: &Style) -> Self {
+        if style.right_to_left {
+            self.mirrored()
+        } else {
+            self
+        }
+    }
+}
+
+fn flip_align(align: Align) -> Align {
+    match align {
+        Align::Min => Align::Max,
+        Align::Center => Align::Center,
+        Align::Max => Align::Min,
+    }
 }
 
 /// ## Inspectors
@@ -299,22 +299,31 @@ mod animation_manager;
 pub mod containers;
 mod context;
 mod data;
+mod drag_and_drop;
 mod frame_state;
 pub(crate) mod grid;
+pub mod gui_zoom;
 mod id;
 mod input_state;
 pub mod introspection;
+mod label_selection;
 pub mod layers;
 mod layout;
 mod memory;
 pub mod menu;
 mod painter;
 pub(crate) mod placer;
+#[cfg(feature = "recording")]
+pub mod recording;
 mod response;
 mod sense;
+mod shortcut_registry;
 pub mod style;
+pub mod testing;
+mod toasts;
 mod ui;
 pub mod util;
+pub mod viewport;
 mod widget_text;
 pub mod widgets;
 
@@ -326,7 +335,9 @@ pub use emath::{lerp, pos2, remap, remap_clamp, vec2, Align, Align2, NumExt, Pos
 pub use epaint::hex_color;
 pub use epaint::{
     color, mutex,
-    text::{FontData, FontDefinitions, FontFamily, FontId, FontTweak},
+    text::{
+        FontData, FontDefinitions, FontFace, FontFamily, FontId, FontStyle, FontTweak, FontWeight,
+    },
     textures::{TextureFilter, TexturesDelta},
     ClippedPrimitive, Color32, ColorImage, FontImage, ImageData, Mesh, PaintCallback,
     PaintCallbackInfo, Rgba, Rounding, Shape, Stroke, TextureHandle, TextureId,
@@ -344,21 +355,26 @@ pub use {
     containers::*,
     context::Context,
     data::{
+        accessibility::{self, AccessibilityTree},
         input::*,
         output::{self, CursorIcon, FullOutput, PlatformOutput, WidgetInfo},
     },
+    drag_and_drop::DragAndDrop,
     grid::Grid,
     id::{Id, IdMap},
     input_state::{InputState, MultiTouchInfo, PointerState},
     layers::{LayerId, Order},
     layout::*,
-    memory::Memory,
+    memory::{FocusDirection, Memory},
     painter::Painter,
     response::{InnerResponse, Response},
     sense::Sense,
+    shortcut_registry::ShortcutRegistry,
     style::{FontSelection, Style, TextStyle, Visuals},
     text::{Galley, TextFormat},
+    toasts::{Toast, ToastId, ToastLevel, Toasts},
     ui::Ui,
+    viewport::{ViewportBuilder, ViewportId, ViewportIdMap, ViewportOutput},
     widget_text::{RichText, WidgetText},
     widgets::*,
 };
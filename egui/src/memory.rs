@@ -1,6 +1,6 @@
 use epaint::ahash::AHashSet;
 
-use crate::{area, window, Id, IdMap, InputState, LayerId, Pos2, Rect, Style};
+use crate::{area, window, Id, IdMap, InputState, Key, LayerId, Pos2, Rect, Style, Vec2};
 
 // ----------------------------------------------------------------------------
 
@@ -109,6 +109,12 @@ pub struct Options {
     /// Screen readers is an experimental feature of egui, and not supported on all platforms.
     pub screen_reader: bool,
 
+    /// If true, egui builds an [`crate::accessibility::AccessibilityTree`] each frame
+    /// and puts it in [`crate::PlatformOutput::accessibility_tree`].
+    ///
+    /// This is meant to be turned on by integrations that talk to a platform accessibility API.
+    pub accessibility_tree: bool,
+
     /// If true, the most common glyphs (ASCII) are pre-rendered to the texture atlas.
     ///
     /// Only the fonts in [`Style::text_styles`] will be pre-cached.
@@ -116,6 +122,26 @@ pub struct Options {
     /// This can lead to fewer texture operations, but may use up the texture atlas quicker
     /// if you are changing [`Style::text_styles`], of have a lot of text styles.
     pub preload_font_glyphs: bool,
+
+    /// The zoom factor chosen by the user, on top of the native scale of the screen.
+    ///
+    /// Private, like [`Self::style`], because changing it must also change the `pixels_per_point`.
+    /// Use [`crate::Context::zoom_factor`] and [`crate::Context::set_zoom_factor`] instead.
+    pub(crate) zoom_factor: f32,
+
+    /// If true, egui zooms the ui when the user presses
+    /// [`crate::gui_zoom::kb_shortcuts`] (`Ctrl +`, `Ctrl -` and `Ctrl 0`).
+    ///
+    /// Turn this off if you want to use those shortcuts for something else,
+    /// or call [`crate::gui_zoom::zoom_with_keyboard`] yourself.
+    pub zoom_with_keyboard: bool,
+
+    /// The key bindings of all [`crate::TextEdit`]s,
+    /// unless they have their own [`crate::TextEdit::keymap`].
+    ///
+    /// Defaults to the conventions of the platform we are compiled for.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub text_edit_keymap: crate::text_edit::TextEditKeymap,
 }
 
 impl Default for Options {
@@ -124,7 +150,11 @@ impl Default for Options {
             style: Default::default(),
             tessellation_options: Default::default(),
             screen_reader: false,
+            accessibility_tree: false,
             preload_font_glyphs: true,
+            zoom_factor: 1.0,
+            zoom_with_keyboard: true,
+            text_edit_keymap: Default::default(),
         }
     }
 }
@@ -176,21 +206,94 @@ pub(crate) struct Focus {
     /// Give focus to this widget next frame
     id_next_frame: Option<Id>,
 
-    /// If set, the next widget that is interested in focus will automatically get it.
-    /// Probably because the user pressed Tab.
-    give_to_next: bool,
-
-    /// The last widget interested in focus.
-    last_interested: Option<Id>,
-
     /// If `true`, pressing tab will NOT move focus away from the current widget.
     is_focus_locked: bool,
 
-    /// Set at the beginning of the frame, set to `false` when "used".
-    pressed_tab: bool,
+    /// The widgets interested in focus this frame, in the order they were created.
+    focus_widgets: Vec<FocusWidget>,
+
+    /// [`Self::focus_widgets`] of the previous frame.
+    /// Used to find where to move focus when the user presses tab or an arrow key.
+    focus_widgets_previous_frame: Vec<FocusWidget>,
+
+    /// Tab index overrides, set with [`Memory::set_tab_index`].
+    tab_indices: IdMap<i32>,
+
+    /// Layers that keep the focus within themselves. See [`Memory::trap_focus_in_layer`].
+    focus_scopes: AHashSet<LayerId>,
+    focus_scopes_previous_frame: AHashSet<LayerId>,
+
+    /// The top-most modal layer. See [`Memory::set_modal_layer`].
+    modal_layer: Option<LayerId>,
+    modal_layer_previous_frame: Option<LayerId>,
+
+    /// The layers that were drawn below [`Self::modal_layer_previous_frame`].
+    /// Popups and menus opened from the modal are drawn on top of it, so they are not in here.
+    layers_below_modal: AHashSet<LayerId>,
+
+    /// Did the focused widget use the arrow keys this frame? See [`Memory::claim_arrow_keys`].
+    arrow_keys_claimed: bool,
+
+    /// Was the focus moved with the keyboard at the start of this frame?
+    /// If so, we scroll to the newly focused widget.
+    pub(crate) moved_by_keyboard: bool,
+
+    /// The arrow key that moved the focus this frame, to be consumed from the input.
+    pub(crate) used_arrow_key: Option<Key>,
+}
+
+/// A widget that was interested in keyboard focus.
+#[derive(Clone, Copy, Debug)]
+struct FocusWidget {
+    id: Id,
+    layer_id: LayerId,
+    rect: Rect,
+    tab_index: i32,
+}
+
+/// In which direction to move the keyboard focus. See [`Memory::move_focus`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FocusDirection {
+    /// The closest widget above.
+    Up,
+
+    /// The closest widget to the right.
+    Right,
+
+    /// The closest widget below.
+    Down,
+
+    /// The closest widget to the left.
+    Left,
+
+    /// The next widget in tab order (what the tab key does).
+    Next,
+
+    /// The previous widget in tab order (what shift-tab does).
+    Previous,
+}
+
+impl FocusDirection {
+    fn from_arrow_key(key: Key) -> Option<Self> {
+        match key {
+            Key::ArrowUp => Some(Self::Up),
+            Key::ArrowRight => Some(Self::Right),
+            Key::ArrowDown => Some(Self::Down),
+            Key::ArrowLeft => Some(Self::Left),
+            _ => None,
+        }
+    }
 
-    /// Set at the beginning of the frame, set to `false` when "used".
-    pressed_shift_tab: bool,
+    /// Unit vector in this direction, or `None` for [`Self::Next`] and [`Self::Previous`].
+    fn vec(self) -> Option<Vec2> {
+        match self {
+            Self::Up => Some(Vec2::UP),
+            Self::Right => Some(Vec2::RIGHT),
+            Self::Down => Some(Vec2::DOWN),
+            Self::Left => Some(Vec2::LEFT),
+            Self::Next | Self::Previous => None,
+        }
+    }
 }
 
 impl Interaction {
@@ -233,35 +336,79 @@ impl Focus {
             self.id = Some(id);
         }
 
-        self.pressed_tab = false;
-        self.pressed_shift_tab = false;
+        let mut focus_widgets = std::mem::take(&mut self.focus_widgets);
+        for widget in &mut focus_widgets {
+            if let Some(tab_index) = self.tab_indices.get(&widget.id) {
+                widget.tab_index = *tab_index;
+            }
+        }
+        self.focus_widgets_previous_frame = focus_widgets;
+        self.tab_indices.clear();
+        self.focus_scopes_previous_frame = std::mem::take(&mut self.focus_scopes);
+        self.modal_layer_previous_frame = self.modal_layer.take();
+        let arrow_keys_claimed = std::mem::take(&mut self.arrow_keys_claimed);
+
+        self.moved_by_keyboard = false;
+        self.used_arrow_key = None;
         for event in &new_input.events {
-            if matches!(
-                event,
+            match event {
                 crate::Event::Key {
-                    key: crate::Key::Escape,
+                    key: Key::Escape,
                     pressed: true,
                     modifiers: _,
+                } => {
+                    self.id = None;
+                    self.is_focus_locked = false;
+                    break;
                 }
-            ) {
-                self.id = None;
-                self.is_focus_locked = false;
-                break;
-            }
-
-            if let crate::Event::Key {
-                key: crate::Key::Tab,
-                pressed: true,
-                modifiers,
-            } = event
-            {
-                if !self.is_focus_locked {
-                    if modifiers.shift {
-                        self.pressed_shift_tab = true;
+                crate::Event::Key {
+                    key: Key::Tab,
+                    pressed: true,
+                    modifiers,
+                } if !self.is_focus_locked => {
+                    let direction = if modifiers.shift {
+                        FocusDirection::Previous
                     } else {
-                        self.pressed_tab = true;
+                        FocusDirection::Next
+                    };
+                    if let Some(id) = self.find_focus_target(direction) {
+                        self.id = Some(id);
+                        self.moved_by_keyboard = true;
+                    }
+                }
+                crate::Event::Key {
+                    key,
+                    pressed: true,
+                    modifiers,
+                } if modifiers.is_none() => {
+                    // Arrow keys only move the focus once something has it,
+                    // so that apps can still use them for other things.
+                    if let Some(direction) = FocusDirection::from_arrow_key(*key) {
+                        if self.id.is_some() && !self.is_focus_locked && !arrow_keys_claimed {
+                            if let Some(id) = self.find_focus_target(direction) {
+                                self.id = Some(id);
+                                self.moved_by_keyboard = true;
+                                self.used_arrow_key = Some(*key);
+                            }
+                        }
                     }
                 }
+                crate::Event::AccessibilityActionRequest(crate::accessibility::ActionRequest {
+                    target,
+                    action: crate::accessibility::Action::Focus,
+                    ..
+                }) => {
+                    // Only focus enabled widgets that can have focus:
+                    let is_focusable = self
+                        .focus_widgets_previous_frame
+                        .iter()
+                        .any(|widget| widget.id == *target);
+                    if is_focusable {
+                        self.id = Some(*target);
+                        self.is_focus_locked = false;
+                    }
+                }
+                _ => {}
             }
         }
     }
@@ -278,30 +425,120 @@ impl Focus {
         }
     }
 
-    pub(crate) fn had_focus_last_frame(&self, id: Id) -> bool {
-        self.id_previous_frame == Some(id)
+    /// Is the layer below the top-most modal, so that its widgets can't have focus?
+    fn is_below_modal(&self, layer_id: LayerId) -> bool {
+        self.modal_layer_previous_frame
+            .map_or(false, |modal_layer| {
+                // A layer shown for the first time isn't in `layers_below_modal`, but goes to the top of its `Order`:
+                layer_id.order < modal_layer.order || self.layers_below_modal.contains(&layer_id)
+            })
     }
 
-    fn interested_in_focus(&mut self, id: Id) {
-        if self.give_to_next && !self.had_focus_last_frame(id) {
-            self.id = Some(id);
-            self.give_to_next = false;
-        } else if self.id == Some(id) {
-            if self.pressed_tab && !self.is_focus_locked {
-                self.id = None;
-                self.give_to_next = true;
-                self.pressed_tab = false;
-            } else if self.pressed_shift_tab && !self.is_focus_locked {
-                self.id_next_frame = self.last_interested; // frame-delay so gained_focus works
-                self.pressed_shift_tab = false;
-            }
-        } else if self.pressed_tab && self.id == None && !self.give_to_next {
-            // nothing has focus and the user pressed tab - give focus to the first widgets that wants it:
-            self.id = Some(id);
+    fn interested_in_focus(&mut self, id: Id, layer_id: LayerId, rect: Rect) {
+        if self.id == Some(id) && self.is_below_modal(layer_id) {
+            // Widgets below a modal can't have focus:
+            self.id = None;
         }
 
-        self.last_interested = Some(id);
+        self.focus_widgets.push(FocusWidget {
+            id,
+            layer_id,
+            rect,
+            tab_index: 0,
+        });
     }
+
+    /// Find the widget to give focus to when moving it in the given direction,
+    /// based on the widgets of the previous frame.
+    fn find_focus_target(&self, direction: FocusDirection) -> Option<Id> {
+        let widgets = &self.focus_widgets_previous_frame;
+        let current = self
+            .id
+            .and_then(|id| widgets.iter().find(|widget| widget.id == id));
+
+        // Don't leave the modal (and its popups), or the focus scope of the focused widget:
+        let current = current.filter(|widget| !self.is_below_modal(widget.layer_id));
+        let scope = match current {
+            Some(widget) => Some(widget.layer_id)
+                .filter(|layer_id| self.focus_scopes_previous_frame.contains(layer_id)),
+            None => self.modal_layer_previous_frame,
+        };
+        let candidates = widgets.iter().filter(|widget| {
+            widget.tab_index >= 0
+                && !self.is_below_modal(widget.layer_id)
+                && scope.map_or(true, |layer_id| widget.layer_id == layer_id)
+        });
+
+        if let Some(dir) = direction.vec() {
+            let current = current?;
+            find_in_direction(
+                current.rect,
+                dir,
+                candidates.filter(|widget| widget.id != current.id),
+            )
+        } else {
+            // A widget can register more than once (e.g. a `DragValue` being edited as text):
+            let mut seen = AHashSet::default();
+            let mut tab_order: Vec<&FocusWidget> =
+                candidates.filter(|widget| seen.insert(widget.id)).collect();
+            // Positive tab indices go first. The sort is stable, so the rest stay in creation order.
+            tab_order.sort_by_key(|widget| {
+                if widget.tab_index > 0 {
+                    widget.tab_index
+                } else {
+                    i32::MAX
+                }
+            });
+
+            let n = tab_order.len();
+            if n == 0 {
+                return None;
+            }
+            let current_index =
+                current.and_then(|current| tab_order.iter().position(|w| w.id == current.id));
+            let index = match (direction, current_index) {
+                (FocusDirection::Previous, Some(i)) => (i + n - 1) % n,
+                (FocusDirection::Previous, None) => n - 1,
+                (_, Some(i)) => (i + 1) % n,
+                (_, None) => 0,
+            };
+            Some(tab_order[index].id)
+        }
+    }
+}
+
+/// The closest widget in the direction `dir` from `current`,
+/// preferring widgets that are lined up with it (e.g. in the same row when moving right).
+fn find_in_direction<'a>(
+    current: Rect,
+    dir: Vec2,
+    candidates: impl Iterator<Item = &'a FocusWidget>,
+) -> Option<Id> {
+    use epaint::util::FloatOrd as _;
+
+    let range = |rect: Rect, axis: Vec2| {
+        let a = rect.min.to_vec2().dot(axis);
+        let b = rect.max.to_vec2().dot(axis);
+        (a.min(b), a.max(b))
+    };
+    let across = dir.rot90();
+    let (current_min, current_max) = range(current, across);
+    let current_center = current.center().to_vec2().dot(dir);
+
+    candidates
+        .filter_map(|widget| {
+            let (start, _) = range(widget.rect, dir);
+            if start <= current_center {
+                return None; // not (entirely) in that direction
+            }
+            let distance = (widget.rect.center() - current.center()).dot(dir);
+            let (min, max) = range(widget.rect, across);
+            let gap_across = (min - current_max).max(current_min - max).max(0.0);
+            let is_lined_up = gap_across == 0.0;
+            Some((!is_lined_up, distance + 2.0 * gap_across, widget.id))
+        })
+        .min_by_key(|(not_lined_up, score, _)| (*not_lined_up, score.ord()))
+        .map(|(_, _, id)| id)
 }
 
 impl Memory {
@@ -312,6 +549,17 @@ impl Memory {
     ) {
         self.interaction.begin_frame(prev_input, new_input);
 
+        let focus = &mut self.interaction.focus;
+        focus.layers_below_modal.clear();
+        if let Some(modal_layer) = focus.modal_layer_previous_frame {
+            focus.layers_below_modal.extend(
+                self.areas
+                    .order()
+                    .iter()
+                    .take_while(|layer_id| **layer_id != modal_layer),
+            );
+        }
+
         if !prev_input.pointer.any_down() {
             self.window_interaction = None;
         }
@@ -319,6 +567,12 @@ impl Memory {
 
     pub(crate) fn end_frame(&mut self, input: &InputState, used_ids: &IdMap<Rect>) {
         self.caches.update();
+        self.end_viewport_frame(input, used_ids);
+    }
+
+    /// Like [`Self::end_frame`], but for the frame of a child viewport,
+    /// which runs in the middle of the frame of its parent.
+    pub(crate) fn end_viewport_frame(&mut self, input: &InputState, used_ids: &IdMap<Rect>) {
         self.areas.end_frame();
         self.interaction.focus.end_frame(used_ids);
         self.drag_value.end_frame(input);
@@ -359,7 +613,7 @@ impl Memory {
         self.interaction.focus.id
     }
 
-    /// Prevent keyboard focus from moving away from this widget even if users presses the tab key.
+    /// Prevent keyboard focus from moving away from this widget even if users presses the tab key or an arrow key.
     /// You must first give focus to the widget before calling this.
     pub fn lock_focus(&mut self, id: Id, lock_focus: bool) {
         if self.had_focus_last_frame(id) && self.has_focus(id) {
@@ -395,10 +649,73 @@ impl Memory {
     }
 
     /// Register this widget as being interested in getting keyboard focus.
-    /// This will allow the user to select it with tab and shift-tab.
+    /// This will allow the user to select it with tab and shift-tab, and with the arrow keys.
     #[inline(always)]
-    pub(crate) fn interested_in_focus(&mut self, id: Id) {
-        self.interaction.focus.interested_in_focus(id);
+    pub(crate) fn interested_in_focus(&mut self, id: Id, layer_id: LayerId, rect: Rect) {
+        self.interaction
+            .focus
+            .interested_in_focus(id, layer_id, rect);
+    }
+
+    /// Move the keyboard focus to another widget, like pressing tab or an arrow key would.
+    ///
+    /// The new widget is picked among the widgets of the previous frame,
+    /// and gets the focus next frame.
+    /// Moving in a direction ([`FocusDirection::Up`] etc) only works if some widget has focus.
+    pub fn move_focus(&mut self, direction: FocusDirection) {
+        let focus = &mut self.interaction.focus;
+        if let Some(id) = focus.find_focus_target(direction) {
+            focus.id_next_frame = Some(id);
+            focus.is_focus_locked = false;
+        }
+    }
+
+    /// Call this each frame a focused widget uses the arrow keys (like a slider or a text edit does),
+    /// so that the arrow keys won't move the focus away from it.
+    ///
+    /// Does nothing if the widget doesn't have focus.
+    pub fn claim_arrow_keys(&mut self, id: Id) {
+        if self.has_focus(id) {
+            self.interaction.focus.arrow_keys_claimed = true;
+        }
+    }
+
+    /// Change where the widget is in the tab order. Call this every frame.
+    ///
+    /// This works like `tabindex` in HTML:
+    /// * Widgets with a positive tab index come first, in increasing order.
+    /// * Then come the widgets with tab index `0` (the default), in the order they were created.
+    /// * Widgets with a negative tab index are skipped when pressing tab or an arrow key,
+    ///   but can still get focus by clicking them or with [`Self::request_focus`].
+    ///
+    /// See also [`crate::Response::tab_index`].
+    pub fn set_tab_index(&mut self, id: Id, tab_index: i32) {
+        self.interaction.focus.tab_indices.insert(id, tab_index);
+    }
+
+    /// Keep the keyboard focus within this layer:
+    /// while one of its widgets has focus, tab and the arrow keys only move it between the widgets of the layer.
+    ///
+    /// Call this every frame. Usually you would instead use [`crate::Area::trap_focus`] or [`crate::Window::trap_focus`].
+    pub fn trap_focus_in_layer(&mut self, layer_id: LayerId) {
+        self.interaction.focus.focus_scopes.insert(layer_id);
+    }
+
+    /// Make this layer modal: widgets in the layers below it can't have keyboard focus,
+    /// and tab and the arrow keys only move the focus between the widgets of this layer
+    /// (and of the layers above it, like the popups and menus opened from it).
+    ///
+    /// Call this every frame. If called for several layers, the last one wins.
+    /// Usually you would use a [`crate::Modal`] instead.
+    pub fn set_modal_layer(&mut self, layer_id: LayerId) {
+        self.interaction.focus.modal_layer = Some(layer_id);
+    }
+
+    /// The layer of the top-most [`crate::Modal`] (see [`Self::set_modal_layer`]) of the previous frame, if any.
+    ///
+    /// You can check this to ignore your own keyboard shortcuts while a modal is open.
+    pub fn top_modal_layer(&self) -> Option<LayerId> {
+        self.interaction.focus.modal_layer_previous_frame
     }
 
     /// Stop editing of active [`TextEdit`](crate::TextEdit) (if any).
@@ -593,3 +910,129 @@ fn memory_impl_send_sync() {
     fn assert_send_sync<T: Send + Sync>() {}
     assert_send_sync::<Memory>();
 }
+
+#[cfg(test)]
+mod focus_tests {
+    use crate::*;
+
+    fn frame(ctx: &Context, key: Option<(Key, Modifiers)>, run_ui: impl FnOnce(&Context)) {
+        let raw_input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0))),
+            events: key
+                .map(|(key, modifiers)| Event::Key {
+                    key,
+                    pressed: true,
+                    modifiers,
+                })
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        let _ = ctx.run(raw_input, run_ui);
+    }
+
+    /// Run a frame where `key` is pressed, and return the index of the focused widget.
+    ///
+    /// There are three buttons in a row, then a row with a button and a drag value.
+    fn press(ctx: &Context, key: Option<(Key, Modifiers)>, tab_indices: [i32; 5]) -> Option<usize> {
+        let mut focused = None;
+        frame(ctx, key, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let mut responses = vec![];
+                ui.horizontal(|ui| {
+                    for i in 0..3 {
+                        responses.push(ui.button(format!("Button {}", i)));
+                    }
+                });
+                ui.horizontal(|ui| {
+                    responses.push(ui.button("Button 3"));
+                    responses.push(ui.add(DragValue::new(&mut 0.5).clamp_range(0.0..=1.0)));
+                });
+                for (i, (response, tab_index)) in responses.into_iter().zip(tab_indices).enumerate()
+                {
+                    if response.tab_index(tab_index).has_focus() {
+                        focused = Some(i);
+                    }
+                }
+            });
+        });
+        focused
+    }
+
+    const TAB: Option<(Key, Modifiers)> = Some((Key::Tab, Modifiers::NONE));
+
+    #[test]
+    fn tab_order() {
+        let tab_indices = [0, 0, 2, -1, 1];
+        let ctx = Context::default();
+        press(&ctx, None, tab_indices);
+
+        let order: Vec<_> = (0..5)
+            .map(|_| press(&ctx, TAB, tab_indices).unwrap())
+            .collect();
+        assert_eq!(
+            order,
+            [4, 2, 0, 1, 4],
+            "positive first, skip negative, wrap"
+        );
+
+        let shift_tab = Some((Key::Tab, Modifiers::SHIFT));
+        assert_eq!(press(&ctx, shift_tab, tab_indices), Some(1));
+    }
+
+    #[test]
+    fn arrow_keys() {
+        let ctx = Context::default();
+        let press = |key| press(&ctx, Some((key, Modifiers::NONE)), [0; 5]);
+
+        assert_eq!(press(Key::ArrowRight), None, "nothing has focus yet");
+        assert_eq!(press(Key::Tab), Some(0));
+        assert_eq!(press(Key::ArrowRight), Some(1));
+        assert_eq!(press(Key::ArrowRight), Some(2));
+        assert_eq!(press(Key::ArrowRight), Some(2), "nothing further right");
+        assert_eq!(press(Key::ArrowLeft), Some(1));
+        assert_eq!(press(Key::ArrowDown), Some(4), "the drag value is below");
+        assert_eq!(
+            press(Key::ArrowLeft),
+            Some(4),
+            "it uses the arrow keys itself"
+        );
+        assert_eq!(press(Key::Tab), Some(0));
+        assert_eq!(press(Key::ArrowDown), Some(3));
+        assert_eq!(press(Key::ArrowUp), Some(0));
+    }
+
+    #[test]
+    fn trap_focus() {
+        let ctx = Context::default();
+        let press = |key| {
+            let mut focused = vec![];
+            frame(&ctx, key, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    if ui.button("outside").has_focus() {
+                        focused.push("outside");
+                    }
+                });
+                Area::new("dialog")
+                    .movable(false)
+                    .trap_focus(true)
+                    .show(ctx, |ui| {
+                        for label in ["first", "second"] {
+                            if ui.button(label).has_focus() {
+                                focused.push(label);
+                            }
+                        }
+                    });
+            });
+            focused
+        };
+
+        press(None);
+        assert_eq!(press(TAB), ["outside"]);
+        assert_eq!(press(TAB), ["first"], "can tab into the area");
+        assert_eq!(press(TAB), ["second"]);
+        assert_eq!(press(TAB), ["first"], "but not out of it");
+        let up = Some((Key::ArrowUp, Modifiers::NONE));
+        assert_eq!(press(up), ["first"]);
+    }
+}
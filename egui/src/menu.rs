@@ -118,10 +118,16 @@ pub(crate) fn menu_ui<'c, R>(
     menu_state_arc: &Arc<RwLock<MenuState>>,
     add_contents: impl FnOnce(&mut Ui) -> R + 'c,
 ) -> InnerResponse<R> {
+    let right_to_left = ctx.style().right_to_left;
     let pos = {
         let mut menu_state = menu_state_arc.write();
         menu_state.entry_count = 0;
-        menu_state.rect.min
+        if right_to_left {
+            // Open to the left of the position:
+            menu_state.position - vec2(menu_state.rect.width(), 0.0)
+        } else {
+            menu_state.position
+        }
     };
 
     let area = Area::new(menu_id)
@@ -130,7 +136,10 @@ pub(crate) fn menu_ui<'c, R>(
         .interactable(true)
         .drag_bounds(Rect::EVERYTHING);
     let inner_response = area.show(ctx, |ui| {
-        ui.scope(|ui| {
+        let id = ui.layer_id().id;
+        ui.ctx()
+            .accessibility_begin_container(id, crate::accessibility::Role::Menu, None, true);
+        let inner_response = ui.scope(|ui| {
             let style = ui.style_mut();
             style.spacing.item_spacing = Vec2::ZERO;
             style.spacing.button_padding = crate::vec2(2.0, 0.0);
@@ -145,12 +154,14 @@ pub(crate) fn menu_ui<'c, R>(
                     const DEFAULT_MENU_WIDTH: f32 = 150.0; // TODO(emilk): add to ui.spacing
                     ui.set_max_width(DEFAULT_MENU_WIDTH);
                     ui.set_menu_state(Some(menu_state_arc.clone()));
-                    ui.with_layout(Layout::top_down_justified(Align::LEFT), add_contents)
-                        .inner
+                    let layout = Layout::top_down_justified(Align::LEFT).for_style(ui.style());
+                    ui.with_layout(layout, add_contents).inner
                 })
                 .inner
-        })
-        .inner
+        });
+        ui.ctx()
+            .accessibility_end_container(id, inner_response.response.rect, true);
+        inner_response.inner
     });
     menu_state_arc.write().rect = inner_response.response.rect;
     inner_response
@@ -278,6 +289,8 @@ impl MenuRoot {
         root: &mut MenuRootManager,
         id: Id,
     ) -> MenuResponse {
+        let right_to_left = response.ctx.style().right_to_left;
+
         // Lock the input once for the whole function call (see https://github.com/emilk/egui/pull/1380).
         let input = response.ctx.input();
 
@@ -289,7 +302,11 @@ impl MenuRoot {
         {
             // menu not open and button clicked
             // or button hovered while other menu is open
-            let pos = response.rect.left_bottom();
+            let pos = if right_to_left {
+                response.rect.right_bottom()
+            } else {
+                response.rect.left_bottom()
+            };
             return MenuResponse::Create(pos, id);
         } else if input.pointer.any_pressed() && input.pointer.primary_down() {
             if let Some(pos) = input.pointer.interact_pos() {
@@ -405,6 +422,9 @@ impl SubMenuButton {
 
     pub(crate) fn show(self, ui: &mut Ui, menu_state: &MenuState, sub_id: Id) -> Response {
         let SubMenuButton { text, icon, .. } = self;
+        let right_to_left = ui.style().right_to_left;
+        // Sub-menus open to the left in a right-to-left ui:
+        let icon = if right_to_left { "⏴".into() } else { icon };
 
         let text_style = TextStyle::Button;
         let sense = Sense::click();
@@ -430,10 +450,15 @@ impl SubMenuButton {
 
         if ui.is_rect_visible(rect) {
             let visuals = Self::visuals(ui, &response, menu_state, sub_id);
-            let text_pos = Align2::LEFT_CENTER
+            let (text_align, icon_align) = if right_to_left {
+                (Align2::RIGHT_CENTER, Align2::LEFT_CENTER)
+            } else {
+                (Align2::LEFT_CENTER, Align2::RIGHT_CENTER)
+            };
+            let text_pos = text_align
                 .align_size_within_rect(text_galley.size(), rect.shrink2(button_padding))
                 .min;
-            let icon_pos = Align2::RIGHT_CENTER
+            let icon_pos = icon_align
                 .align_size_within_rect(icon_galley.size(), rect.shrink2(button_padding))
                 .min;
 
@@ -483,6 +508,9 @@ impl SubMenu {
 pub(crate) struct MenuState {
     /// The opened sub-menu and its [`Id`]
     sub_menu: Option<(Id, Arc<RwLock<MenuState>>)>,
+    /// Where the menu was opened. The menu is to the right of it,
+    /// or to the left of it in a right-to-left ui.
+    position: Pos2,
     /// Bounding box of this menu (without the sub-menu)
     pub rect: Rect,
     /// Used to check if any menu in the tree wants to close
@@ -493,6 +521,7 @@ pub(crate) struct MenuState {
 impl MenuState {
     pub fn new(position: Pos2) -> Self {
         Self {
+            position,
             rect: Rect::from_min_size(position, Vec2::ZERO),
             sub_menu: None,
             response: MenuResponse::Stay,
@@ -540,31 +569,49 @@ impl MenuState {
     fn submenu_button_interaction(&mut self, ui: &mut Ui, sub_id: Id, button: &Response) {
         let pointer = &ui.input().pointer.clone();
         let open = self.is_open(sub_id);
-        if self.moving_towards_current_submenu(pointer) {
+        let right_to_left = ui.style().right_to_left;
+        if self.moving_towards_current_submenu(pointer, right_to_left) {
             // ensure to repaint once even when pointer is not moving
             ui.ctx().request_repaint();
         } else if !open && button.hovered() {
-            let pos = button.rect.right_top();
+            let pos = if right_to_left {
+                button.rect.left_top()
+            } else {
+                button.rect.right_top()
+            };
             self.open_submenu(sub_id, pos);
         } else if open && !button.hovered() && !self.hovering_current_submenu(pointer) {
             self.close_submenu();
         }
     }
-    /// Check if `dir` points from `pos` towards left side of `rect`.
-    fn points_at_left_of_rect(pos: Pos2, dir: Vec2, rect: Rect) -> bool {
-        let vel_a = dir.angle();
-        let top_a = (rect.left_top() - pos).angle();
-        let bottom_a = (rect.left_bottom() - pos).angle();
+    /// Check if `dir` points from `pos` towards the near side of `rect`:
+    /// the left side, or the right side if `right_to_left`.
+    fn points_at_near_side_of_rect(pos: Pos2, dir: Vec2, rect: Rect, right_to_left: bool) -> bool {
+        // Mirror everything so that we can always check the left side:
+        let mirror = |v: Vec2| if right_to_left { vec2(-v.x, v.y) } else { v };
+        let (top, bottom) = if right_to_left {
+            (rect.right_top(), rect.right_bottom())
+        } else {
+            (rect.left_top(), rect.left_bottom())
+        };
+        let vel_a = mirror(dir).angle();
+        let top_a = mirror(top - pos).angle();
+        let bottom_a = mirror(bottom - pos).angle();
         bottom_a - vel_a >= 0.0 && top_a - vel_a <= 0.0
     }
     /// Check if pointer is moving towards current submenu.
-    fn moving_towards_current_submenu(&self, pointer: &PointerState) -> bool {
+    fn moving_towards_current_submenu(&self, pointer: &PointerState, right_to_left: bool) -> bool {
         if pointer.is_still() {
             return false;
         }
         if let Some(sub_menu) = self.get_current_submenu() {
             if let Some(pos) = pointer.hover_pos() {
-                return Self::points_at_left_of_rect(pos, pointer.velocity(), sub_menu.read().rect);
+                return Self::points_at_near_side_of_rect(
+                    pos,
+                    pointer.velocity(),
+                    sub_menu.read().rect,
+                    right_to_left,
+                );
             }
         }
         false
@@ -0,0 +1,214 @@
+//! Record the [`RawInput`] of a session and replay it later.
+//!
+//! This lets you turn a bug report into a reproducible regression test,
+//! or benchmark a real session of your app.
+//!
+//! Record by wrapping [`Context::run`] with [`InputRecorder::run`]
+//! (or call [`InputRecorder::record`] yourself), then save [`InputRecording::to_ron`] to a file.
+//! `eframe` can do this for you (see its `recording` feature).
+//!
+//! Replay by loading the file with [`InputRecording::from_ron`] and calling [`InputRecording::replay`]
+//! on a new [`Context`]. Each frame gets the same [`RawInput`] (including [`RawInput::time`]) as when recorded,
+//! so as long as your ui code and the initial [`crate::Memory`] are the same, so is the output.
+//!
+//! ```
+//! use egui::recording::{InputRecorder, InputRecording};
+//!
+//! fn my_ui(ctx: &egui::Context) {
+//!     egui::CentralPanel::default().show(ctx, |ui| {
+//!         ui.label("Hello");
+//!     });
+//! }
+//!
+//! let ctx = egui::Context::default();
+//! let mut recorder = InputRecorder::default();
+//! for _ in 0..3 {
+//!     let raw_input = egui::RawInput::default(); // from your integration
+//!     let full_output = recorder.run(&ctx, raw_input, my_ui);
+//!     // handle full_output
+//! }
+//!
+//! let file_contents = recorder.finish().to_ron().unwrap();
+//!
+//! // Later, e.g. in a test:
+//! let recording = InputRecording::from_ron(&file_contents).unwrap();
+//! assert_eq!(recording.len(), 3);
+//! let last_output = recording.replay(&egui::Context::default(), my_ui);
+//! ```
+
+use crate::{Context, FullOutput, RawInput};
+
+/// The [`RawInput`] of every frame of a session, in order.
+///
+/// Create it with an [`InputRecorder`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputRecording {
+    pub frames: Vec<RawInput>,
+}
+
+impl InputRecording {
+    /// Number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Serialize to compact [RON](https://github.com/ron-rs/ron), with one line per frame.
+    ///
+    /// Because each frame is on its own line, you can append to a recording,
+    /// or cut it short, with any text editor.
+    ///
+    /// # Errors
+    /// If [`ron`] fails to serialize a frame.
+    pub fn to_ron(&self) -> ron::Result<String> {
+        let mut out = String::new();
+        for frame in &self.frames {
+            out += &ron::to_string(frame)?;
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    /// Parse what was written by [`Self::to_ron`]. Empty lines are ignored.
+    ///
+    /// # Errors
+    /// If any line is not a valid [`RawInput`].
+    pub fn from_ron(ron: &str) -> ron::Result<Self> {
+        let frames = ron
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(ron::from_str)
+            .collect::<ron::Result<_>>()?;
+        Ok(Self { frames })
+    }
+
+    /// Run `run_ui` once for each recorded frame, feeding it the recorded input.
+    ///
+    /// Use a new [`Context`] (or one in the same state as when the recording started)
+    /// for the output to be the same as when recording.
+    ///
+    /// Returns the output of the last frame, or `None` if the recording is empty.
+    pub fn replay(&self, ctx: &Context, mut run_ui: impl FnMut(&Context)) -> Option<FullOutput> {
+        let mut last_output = None;
+        for raw_input in &self.frames {
+            last_output = Some(ctx.run(raw_input.clone(), &mut run_ui));
+        }
+        last_output
+    }
+}
+
+/// Records the [`RawInput`] of each frame into an [`InputRecording`].
+#[derive(Clone, Debug, Default)]
+pub struct InputRecorder {
+    recording: InputRecording,
+}
+
+impl InputRecorder {
+    /// Record the input of one frame. Call this with the input you pass to [`Context::run`].
+    pub fn record(&mut self, raw_input: &RawInput) {
+        self.recording.frames.push(raw_input.clone());
+    }
+
+    /// Record the input and pass it on to [`Context::run`].
+    pub fn run(
+        &mut self,
+        ctx: &Context,
+        raw_input: RawInput,
+        run_ui: impl FnOnce(&Context),
+    ) -> FullOutput {
+        self.record(&raw_input);
+        ctx.run(raw_input, run_ui)
+    }
+
+    /// What has been recorded so far.
+    pub fn recording(&self) -> &InputRecording {
+        &self.recording
+    }
+
+    /// Stop recording.
+    pub fn finish(self) -> InputRecording {
+        self.recording
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn test_ui(ctx: &Context, clicks: &mut usize) {
+        CentralPanel::default().show(ctx, |ui| {
+            if ui.button("Click me").clicked() {
+                *clicks += 1;
+            }
+            ui.text_edit_singleline(&mut "text".to_owned());
+        });
+    }
+
+    fn session() -> Vec<RawInput> {
+        let button = |pos, pressed| Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        let pos = pos2(20.0, 15.0);
+        let events = vec![
+            vec![],
+            vec![Event::PointerMoved(pos)],
+            vec![button(pos, true)],
+            vec![button(pos, false)],
+            vec![Event::Text("ö".into()), Event::Zoom(1.5)],
+            vec![Event::PointerGone],
+        ];
+        events
+            .into_iter()
+            .enumerate()
+            .map(|(i, events)| RawInput {
+                screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0))),
+                time: Some(i as f64 / 60.0),
+                events,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ron_round_trip() {
+        let ctx = Context::default();
+        let mut recorder = recording::InputRecorder::default();
+        for raw_input in session() {
+            let _ = recorder.run(&ctx, raw_input, |ctx| test_ui(ctx, &mut 0));
+        }
+        let recording = recorder.finish();
+        assert_eq!(recording.frames, session());
+
+        let ron = recording.to_ron().unwrap();
+        assert_eq!(ron.lines().count(), recording.len());
+        assert_eq!(
+            recording::InputRecording::from_ron(&ron).unwrap(),
+            recording
+        );
+    }
+
+    #[test]
+    fn replay_is_deterministic() {
+        let recording = recording::InputRecording { frames: session() };
+
+        let mut clicks = 0;
+        let first = recording
+            .replay(&Context::default(), |ctx| test_ui(ctx, &mut clicks))
+            .unwrap();
+        let second = recording
+            .replay(&Context::default(), |ctx| test_ui(ctx, &mut clicks))
+            .unwrap();
+
+        assert_eq!(clicks, 2);
+        assert_eq!(
+            format!("{:?}", first.shapes),
+            format!("{:?}", second.shapes)
+        );
+    }
+}
@@ -1,6 +1,8 @@
+use std::{any::Any, sync::Arc};
+
 use crate::{
     emath::{Align, Pos2, Rect, Vec2},
-    menu, Context, CursorIcon, Id, LayerId, PointerButton, Sense, Ui, WidgetText,
+    menu, Context, CursorIcon, DragAndDrop, Id, LayerId, PointerButton, Sense, Ui, WidgetText,
     NUM_POINTER_BUTTONS,
 };
 
@@ -249,6 +251,24 @@ impl Response {
         self.ctx.memory().surrender_focus(self.id);
     }
 
+    /// Change where the widget is in the tab order.
+    ///
+    /// Widgets with a positive tab index come first, and with a negative one are skipped
+    /// when navigating with the keyboard.
+    /// See [`crate::Memory::set_tab_index`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// ui.text_edit_singleline(&mut String::new()).tab_index(2);
+    /// ui.text_edit_singleline(&mut String::new()).tab_index(1); // tab goes here first
+    /// ui.button("Not reachable with tab").tab_index(-1);
+    /// # });
+    /// ```
+    pub fn tab_index(self, tab_index: i32) -> Self {
+        self.ctx.memory().set_tab_index(self.id, tab_index);
+        self
+    }
+
     /// The widgets is being dragged.
     ///
     /// To find out which button(s), query [`crate::PointerState::button_down`]
@@ -286,6 +306,54 @@ impl Response {
         }
     }
 
+    /// If the user started dragging this widget this frame, store the payload for drag-and-drop.
+    ///
+    /// The widget needs to sense drags.
+    /// The payload can be read by drop zones with [`Self::dnd_hover_payload`] and [`Self::dnd_release_payload`].
+    ///
+    /// This does not paint a preview of what is being dragged. For that, use [`Ui::dnd_drag_source`].
+    #[doc(alias = "drag and drop")]
+    pub fn dnd_set_drag_payload<Payload: Any + Send + Sync>(&self, payload: Payload) {
+        if self.drag_started() {
+            DragAndDrop::set_payload(&self.ctx, payload);
+        }
+
+        if self.hovered() && !self.sense.click {
+            // Only set this if there is no click-sense (in which case the click will change the cursor)
+            self.ctx.output().cursor_icon = CursorIcon::Grab;
+        }
+    }
+
+    /// Drag-and-Drop: Return what is being held over this widget, if any.
+    ///
+    /// Only returns something if the payload is of the right type.
+    #[doc(alias = "drag and drop")]
+    pub fn dnd_hover_payload<Payload: Any + Send + Sync>(&self) -> Option<Arc<Payload>> {
+        if self.contains_pointer() {
+            DragAndDrop::payload::<Payload>(&self.ctx)
+        } else {
+            None
+        }
+    }
+
+    /// Drag-and-Drop: Return what is being dropped onto this widget, if any.
+    ///
+    /// Only returns something if the payload is of the right type,
+    /// and only on the frame the pointer is released.
+    #[doc(alias = "drag and drop")]
+    pub fn dnd_release_payload<Payload: Any + Send + Sync>(&self) -> Option<Arc<Payload>> {
+        if self.contains_pointer() && self.ctx.input().pointer.any_released() {
+            DragAndDrop::take_payload::<Payload>(&self.ctx)
+        } else {
+            None
+        }
+    }
+
+    /// Is the pointer above this widget, even if some other widget is being dragged?
+    fn contains_pointer(&self) -> bool {
+        self.ctx.rect_contains_pointer(self.layer_id, self.rect)
+    }
+
     /// Where the pointer (mouse/touch) were when when this widget was clicked or dragged.
     /// `None` if the widget is not being interacted with.
     pub fn interact_pointer_pos(&self) -> Option<Pos2> {
@@ -344,7 +412,7 @@ impl Response {
     /// If you call this multiple times the tooltips will stack underneath the previous ones.
     #[doc(alias = "tooltip")]
     pub fn on_hover_ui(self, add_contents: impl FnOnce(&mut Ui)) -> Self {
-        if self.should_show_hover_ui() {
+        if self.should_show_hover_ui(self.hovered) {
             crate::containers::show_tooltip_for(
                 &self.ctx,
                 self.id.with("__tooltip"),
@@ -357,7 +425,9 @@ impl Response {
 
     /// Show this UI when hovering if the widget is disabled.
     pub fn on_disabled_hover_ui(self, add_contents: impl FnOnce(&mut Ui)) -> Self {
-        if !self.enabled && self.ctx.rect_contains_pointer(self.layer_id, self.rect) {
+        if !self.enabled
+            && self.should_show_hover_ui(self.ctx.rect_contains_pointer(self.layer_id, self.rect))
+        {
             crate::containers::show_tooltip_for(
                 &self.ctx,
                 self.id.with("__tooltip"),
@@ -370,7 +440,7 @@ impl Response {
 
     /// Like `on_hover_ui`, but show the ui next to cursor.
     pub fn on_hover_ui_at_pointer(self, add_contents: impl FnOnce(&mut Ui)) -> Self {
-        if self.should_show_hover_ui() {
+        if self.should_show_hover_ui(self.hovered) {
             crate::containers::show_tooltip_at_pointer(
                 &self.ctx,
                 self.id.with("__tooltip"),
@@ -380,12 +450,18 @@ impl Response {
         self
     }
 
-    fn should_show_hover_ui(&self) -> bool {
+    fn should_show_hover_ui(&self, hovered: bool) -> bool {
         if self.ctx.memory().everything_is_visible() {
             return true;
         }
 
-        if !self.hovered || !self.ctx.input().pointer.has_pointer() {
+        let tooltip_id = self.id.with("__tooltip");
+        if crate::containers::popup::is_pointer_over_tooltip(&self.ctx, tooltip_id) {
+            // Keep the tooltip open while the pointer moves into it:
+            return true;
+        }
+
+        if !hovered || !self.ctx.input().pointer.has_pointer() {
             return false;
         }
 
@@ -405,7 +481,7 @@ impl Response {
             return false;
         }
 
-        true
+        crate::containers::popup::tooltip_delay_passed(&self.ctx, tooltip_id)
     }
 
     /// Like `on_hover_text`, but show the text next to cursor.
@@ -496,6 +572,10 @@ impl Response {
     /// Call after interacting and potential calls to [`Self::mark_changed`].
     pub fn widget_info(&self, make_info: impl Fn() -> crate::WidgetInfo) {
         use crate::output::OutputEvent;
+        let make_info = || crate::WidgetInfo {
+            enabled: self.enabled,
+            ..make_info()
+        };
         let event = if self.clicked() {
             Some(OutputEvent::Clicked(make_info()))
         } else if self.double_clicked() {
@@ -512,6 +592,40 @@ impl Response {
         if let Some(event) = event {
             self.ctx.output().events.push(event);
         }
+
+        if self.ctx.frame_state().accessibility_tree.is_some() {
+            use crate::accessibility::Action;
+            let mut actions = vec![Action::ScrollIntoView];
+            if self.enabled {
+                if self.sense.focusable {
+                    actions.push(Action::Focus);
+                }
+                if self.sense.click {
+                    actions.push(Action::Click);
+                }
+            }
+            let info = make_info();
+            if self.enabled
+                && (info.value.is_some() || info.current_text_value.is_some())
+                && info.typ != crate::WidgetType::Label
+            {
+                actions.push(Action::SetValue);
+            }
+            if let Some(builder) = &mut self.ctx.frame_state().accessibility_tree {
+                builder.widget(self.id, self.rect, info, actions);
+            }
+        }
+
+        if self.ctx.is_collecting_widget_infos() {
+            let snapshot = crate::testing::WidgetSnapshot {
+                id: self.id,
+                layer_id: self.layer_id,
+                rect: self.rect,
+                sense: self.sense,
+                info: make_info(),
+            };
+            self.ctx.frame_state().widget_infos.push(snapshot);
+        }
     }
 
     /// Response to secondary clicks (right-clicks) by showing the given menu.
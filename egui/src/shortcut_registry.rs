@@ -0,0 +1,179 @@
+use crate::{Context, InputState, KeyboardShortcut};
+
+/// The keyboard shortcuts of all the commands of an app.
+///
+/// Keeping all shortcuts in one place lets you list them (e.g. in a help window or a settings panel),
+/// show them next to their menu buttons, and find shortcuts that conflict with each other.
+///
+/// `C` is your command type, usually an `enum`.
+///
+/// ```
+/// use egui::{Key, KeyboardShortcut, Modifiers, ShortcutRegistry};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Command {
+///     Save,
+///     Quit,
+/// }
+///
+/// let mut shortcuts = ShortcutRegistry::default();
+/// shortcuts.add(Command::Save, KeyboardShortcut::new(Modifiers::COMMAND, Key::S));
+/// shortcuts.add(Command::Quit, KeyboardShortcut::new(Modifiers::COMMAND, Key::Q));
+/// assert!(shortcuts.conflicts().is_empty());
+///
+/// # egui::__run_test_ui(|ui| {
+/// while let Some(command) = shortcuts.consume(&mut ui.input_mut()) {
+///     // run the command
+/// }
+///
+/// ui.menu_button("File", |ui| {
+///     let save = egui::Button::new("Save").shortcut_text(shortcuts.format(ui.ctx(), &Command::Save));
+///     if ui.add(save).clicked() {
+///         ui.close_menu();
+///     }
+/// });
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+pub struct ShortcutRegistry<C> {
+    bindings: Vec<(C, KeyboardShortcut)>,
+}
+
+impl<C> Default for ShortcutRegistry<C> {
+    fn default() -> Self {
+        Self {
+            bindings: Default::default(),
+        }
+    }
+}
+
+impl<C: PartialEq> ShortcutRegistry<C> {
+    /// Bind a shortcut to a command.
+    ///
+    /// A command can have more than one shortcut.
+    pub fn add(&mut self, command: C, shortcut: KeyboardShortcut) {
+        self.bindings.push((command, shortcut));
+    }
+
+    /// Replace all shortcuts of the command with this one, e.g. when the user rebinds it.
+    pub fn set(&mut self, command: C, shortcut: KeyboardShortcut) {
+        self.remove(&command);
+        self.add(command, shortcut);
+    }
+
+    /// Remove all shortcuts of the command.
+    pub fn remove(&mut self, command: &C) {
+        self.bindings.retain(|(c, _)| c != command);
+    }
+
+    /// The first shortcut of the command, if any.
+    pub fn shortcut(&self, command: &C) -> Option<&KeyboardShortcut> {
+        self.bindings
+            .iter()
+            .find(|(c, _)| c == command)
+            .map(|(_, shortcut)| shortcut)
+    }
+
+    /// All commands and their shortcuts, in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (&C, &KeyboardShortcut)> {
+        self.bindings
+            .iter()
+            .map(|(command, shortcut)| (command, shortcut))
+    }
+
+    /// All pairs of different commands with conflicting shortcuts,
+    /// i.e. that would be triggered by the same key press (see [`KeyboardShortcut::conflicts_with`]).
+    pub fn conflicts(&self) -> Vec<(&C, &C)> {
+        let mut conflicts = vec![];
+        for (i, (a, a_shortcut)) in self.bindings.iter().enumerate() {
+            for (b, b_shortcut) in &self.bindings[i + 1..] {
+                if a != b && a_shortcut.conflicts_with(b_shortcut) {
+                    conflicts.push((a, b));
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Does any shortcut of this command conflict with that of another command?
+    ///
+    /// Use this to flag the command when listing the shortcuts.
+    pub fn has_conflict(&self, command: &C) -> bool {
+        self.conflicts()
+            .iter()
+            .any(|(a, b)| *a == command || *b == command)
+    }
+
+    /// Consume the first pressed shortcut and return its command.
+    ///
+    /// Call this in a loop to get all the commands triggered this frame.
+    pub fn consume(&self, input: &mut InputState) -> Option<&C> {
+        self.bindings
+            .iter()
+            .find(|(_, shortcut)| input.consume_shortcut(shortcut))
+            .map(|(command, _)| command)
+    }
+
+    /// The first shortcut of the command formatted with [`Context::format_shortcut`],
+    /// or an empty string if the command has no shortcut.
+    ///
+    /// Use this for [`crate::Button::shortcut_text`].
+    pub fn format(&self, ctx: &Context, command: &C) -> String {
+        self.shortcut(command)
+            .map(|shortcut| ctx.format_shortcut(shortcut))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn conflicting_shortcuts() {
+        let mut shortcuts = ShortcutRegistry::default();
+        shortcuts.add("save", KeyboardShortcut::new(Modifiers::COMMAND, Key::S));
+        shortcuts.add("save", KeyboardShortcut::new(Modifiers::CTRL, Key::S));
+        shortcuts.add("quit", KeyboardShortcut::new(Modifiers::COMMAND, Key::Q));
+        shortcuts.add("search", KeyboardShortcut::new(Modifiers::MAC_CMD, Key::S));
+        shortcuts.add("next", KeyboardShortcut::new(Modifiers::NONE, Key::S));
+
+        // A command may have several shortcuts that conflict with each other:
+        assert_eq!(shortcuts.conflicts(), vec![(&"save", &"search")]);
+        assert!(shortcuts.has_conflict(&"search"));
+        assert!(!shortcuts.has_conflict(&"quit"));
+
+        shortcuts.set(
+            "search",
+            KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::S),
+        );
+        assert!(shortcuts.conflicts().is_empty());
+    }
+
+    #[test]
+    fn consume_shortcuts() {
+        let mut shortcuts = ShortcutRegistry::default();
+        shortcuts.add("save", KeyboardShortcut::new(Modifiers::COMMAND, Key::S));
+        shortcuts.add("quit", KeyboardShortcut::new(Modifiers::COMMAND, Key::Q));
+
+        let key = |key| Event::Key {
+            key,
+            pressed: true,
+            modifiers: Modifiers::CTRL | Modifiers::COMMAND,
+        };
+        let mut input = InputState::default().begin_frame(
+            RawInput {
+                events: vec![key(Key::Q), key(Key::S), key(Key::A)],
+                ..Default::default()
+            },
+            false,
+        );
+
+        let mut commands = vec![];
+        while let Some(command) = shortcuts.consume(&mut input) {
+            commands.push(*command);
+        }
+        assert_eq!(commands, vec!["save", "quit"]);
+        assert_eq!(input.events, vec![key(Key::A)]);
+    }
+}
@@ -201,6 +201,15 @@ pub struct Style {
     ///
     /// This only affects a few egui widgets.
     pub explanation_tooltips: bool,
+
+    /// Mirror the ui for right-to-left languages, such as Arabic and Hebrew.
+    ///
+    /// Widgets are then right-aligned and horizontal layouts go from right to left.
+    /// [`crate::SidePanel::left`] is shown on the right, sub-menus open to the left,
+    /// and horizontal sliders increase to the left.
+    ///
+    /// The text itself is always laid out with the Unicode bidirectional algorithm.
+    pub right_to_left: bool,
 }
 
 impl Style {
@@ -392,6 +401,20 @@ pub struct Interaction {
 
     /// If `false`, tooltips will show up anytime you hover anything, even is mouse is still moving
     pub show_tooltips_only_when_still: bool,
+
+    /// How many seconds the mouse must hover a widget before its tooltip is shown.
+    pub tooltip_delay: f32,
+
+    /// If a tooltip was shown less than this many seconds ago,
+    /// the next one is shown right away, without waiting for [`Self::tooltip_delay`].
+    ///
+    /// This makes it quick to go through the tooltips of e.g. a row of buttons.
+    pub tooltip_grace_time: f32,
+
+    /// Can the text of [`crate::Label`]s be selected and copied?
+    ///
+    /// This can be overridden per label with [`crate::Label::selectable`].
+    pub selectable_labels: bool,
 }
 
 /// Controls the visual style (colors etc) of egui.
@@ -622,6 +645,7 @@ impl Default for Style {
             animation_time: 1.0 / 12.0,
             debug: Default::default(),
             explanation_tooltips: false,
+            right_to_left: false,
         }
     }
 }
@@ -653,6 +677,9 @@ impl Default for Interaction {
             resize_grab_radius_side: 5.0,
             resize_grab_radius_corner: 10.0,
             show_tooltips_only_when_still: false,
+            tooltip_delay: 0.5,
+            tooltip_grace_time: 0.2,
+            selectable_labels: true,
         }
     }
 }
@@ -830,6 +857,7 @@ impl Style {
             animation_time,
             debug,
             explanation_tooltips,
+            right_to_left,
         } = self;
 
         visuals.light_dark_radio_buttons(ui);
@@ -884,6 +912,9 @@ impl Style {
                 "Show explanatory text when hovering DragValue:s and other egui widgets",
             );
 
+        ui.checkbox(right_to_left, "Right-to-left")
+            .on_hover_text("Mirror the ui for right-to-left languages, such as Arabic and Hebrew");
+
         ui.vertical_centered(|ui| reset_button(ui, self));
     }
 }
@@ -1017,6 +1048,9 @@ impl Interaction {
             resize_grab_radius_side,
             resize_grab_radius_corner,
             show_tooltips_only_when_still,
+            tooltip_delay,
+            tooltip_grace_time,
+            selectable_labels,
         } = self;
         ui.add(Slider::new(resize_grab_radius_side, 0.0..=20.0).text("resize_grab_radius_side"));
         ui.add(
@@ -1026,6 +1060,17 @@ impl Interaction {
             show_tooltips_only_when_still,
             "Only show tooltips if mouse is still",
         );
+        ui.add(
+            Slider::new(tooltip_delay, 0.0..=2.0)
+                .suffix(" s")
+                .text("tooltip_delay"),
+        );
+        ui.add(
+            Slider::new(tooltip_grace_time, 0.0..=2.0)
+                .suffix(" s")
+                .text("tooltip_grace_time"),
+        );
+        ui.checkbox(selectable_labels, "Selectable text in labels");
 
         ui.vertical_centered(|ui| reset_button(ui, self));
     }
@@ -0,0 +1,457 @@
+//! Tools for testing egui code without a window, a GPU or an integration.
+//!
+//! The [`Harness`] runs your ui code frame by frame, records every widget that reports itself
+//! through [`Response::widget_info`](crate::Response::widget_info), and lets you
+//! inject clicks, drags, key presses and text at the position of those widgets.
+//!
+//! ```
+//! use egui::testing::Harness;
+//!
+//! let mut harness = Harness::new_ui_state(false, |ui, checked: &mut bool| {
+//!     ui.checkbox(checked, "Check me");
+//! });
+//! harness.run();
+//!
+//! let checkbox = harness.get_by_label("Check me").id;
+//! harness.click(checkbox);
+//! harness.run();
+//!
+//! assert!(*harness.state());
+//! assert_eq!(harness.get_by_label("Check me").info.selected, Some(true));
+//! ```
+
+use crate::{
+    vec2, CentralPanel, Context, Event, FullOutput, Id, Key, LayerId, Modifiers, PointerButton,
+    Pos2, RawInput, Rect, Sense, Ui, Vec2, WidgetInfo, WidgetType,
+};
+
+/// The time step between two frames run by a [`Harness`].
+const FRAME_DT: f32 = 1.0 / 60.0;
+
+type AppFn<'a, State> = Box<dyn FnMut(&Context, &mut State) + 'a>;
+
+/// A widget that reported itself with [`Response::widget_info`](crate::Response::widget_info)
+/// during a frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WidgetSnapshot {
+    /// The [`Id`] of the widget.
+    pub id: Id,
+
+    /// Which layer the widget is part of.
+    pub layer_id: LayerId,
+
+    /// Where the widget was on screen.
+    pub rect: Rect,
+
+    /// The senses (click and/or drag) that the widget was interested in.
+    pub sense: Sense,
+
+    /// The description the widget gave of itself.
+    pub info: WidgetInfo,
+}
+
+impl WidgetSnapshot {
+    /// The type of widget this is.
+    #[inline]
+    pub fn typ(&self) -> WidgetType {
+        self.info.typ
+    }
+
+    /// The text on labels, buttons, checkboxes etc.
+    #[inline]
+    pub fn label(&self) -> Option<&str> {
+        self.info.label.as_deref()
+    }
+
+    /// Was the widget enabled?
+    #[inline]
+    pub fn enabled(&self) -> bool {
+        self.info.enabled
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Runs ui code headlessly and lets you interact with the widgets it shows.
+///
+/// The harness owns a `State` that is handed to the ui code each frame,
+/// so that the test can inspect it between frames with [`Self::state`].
+///
+/// Interactions such as [`Self::click`] and [`Self::type_text`] are queued up
+/// and take effect the next time you call [`Self::run`].
+///
+/// Widgets can only be found after a frame has been run,
+/// and the positions used for interactions are those of the latest frame.
+pub struct Harness<'a, State = ()> {
+    ctx: Context,
+    app: AppFn<'a, State>,
+    state: State,
+
+    screen_rect: Rect,
+    pixels_per_point: f32,
+    time: f64,
+    modifiers: Modifiers,
+    events: Vec<Event>,
+
+    widgets: Vec<WidgetSnapshot>,
+    output: FullOutput,
+}
+
+impl<'a> Harness<'a> {
+    /// Test the given app code.
+    pub fn new(mut app: impl FnMut(&Context) + 'a) -> Self {
+        Self::new_state((), move |ctx, _| app(ctx))
+    }
+
+    /// Test the given ui code, which will be shown in a [`CentralPanel`].
+    pub fn new_ui(mut add_contents: impl FnMut(&mut Ui) + 'a) -> Self {
+        Self::new_ui_state((), move |ui, _| add_contents(ui))
+    }
+}
+
+impl<'a, State> Harness<'a, State> {
+    /// Test the given app code, which operates on some `State` owned by the harness.
+    pub fn new_state(state: State, app: impl FnMut(&Context, &mut State) + 'a) -> Self {
+        let ctx = Context::default();
+        ctx.set_collect_widget_infos(true);
+
+        Self {
+            ctx,
+            app: Box::new(app),
+            state,
+            screen_rect: Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0)),
+            pixels_per_point: 1.0,
+            time: 0.0,
+            modifiers: Modifiers::default(),
+            events: Default::default(),
+            widgets: Default::default(),
+            output: Default::default(),
+        }
+    }
+
+    /// Test the given ui code, which will be shown in a [`CentralPanel`]
+    /// and operates on some `State` owned by the harness.
+    pub fn new_ui_state(
+        state: State,
+        mut add_contents: impl FnMut(&mut Ui, &mut State) + 'a,
+    ) -> Self {
+        Self::new_state(state, move |ctx, state| {
+            CentralPanel::default().show(ctx, |ui| add_contents(ui, state));
+        })
+    }
+
+    /// Size of the screen in points. Default: 800x600.
+    pub fn with_size(mut self, size: Vec2) -> Self {
+        self.screen_rect = Rect::from_min_size(Pos2::ZERO, size);
+        self
+    }
+
+    /// Default: 1.0.
+    pub fn with_pixels_per_point(mut self, pixels_per_point: f32) -> Self {
+        self.pixels_per_point = pixels_per_point;
+        self
+    }
+
+    /// The [`Context`] used to run the ui code.
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// The state handed to the ui code each frame.
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// The state handed to the ui code each frame.
+    pub fn state_mut(&mut self) -> &mut State {
+        &mut self.state
+    }
+
+    /// Run one frame, feeding it all queued up input.
+    pub fn run(&mut self) -> &FullOutput {
+        let raw_input = RawInput {
+            screen_rect: Some(self.screen_rect),
+            pixels_per_point: Some(self.pixels_per_point),
+            time: Some(self.time),
+            predicted_dt: FRAME_DT,
+            modifiers: self.modifiers,
+            events: std::mem::take(&mut self.events),
+            ..Default::default()
+        };
+        self.time += FRAME_DT as f64;
+
+        let Self {
+            ctx, app, state, ..
+        } = self;
+        self.output = ctx.run(raw_input, |ctx| app(ctx, state));
+        self.widgets = std::mem::take(&mut self.ctx.frame_state().widget_infos);
+        &self.output
+    }
+
+    /// Run frames until egui no longer asks for a repaint (e.g. when all animations are done),
+    /// but at most `max_frames` of them.
+    ///
+    /// Returns the number of frames that were run.
+    pub fn run_until_idle(&mut self, max_frames: usize) -> usize {
+        for frame_nr in 1..=max_frames {
+            if !self.run().needs_repaint {
+                return frame_nr;
+            }
+        }
+        max_frames
+    }
+
+    /// What egui output the latest frame.
+    pub fn output(&self) -> &FullOutput {
+        &self.output
+    }
+
+    // ------------------------------------------------------------------------
+    // Queries:
+
+    /// All widgets shown in the latest frame, in the order they were added.
+    pub fn widgets(&self) -> &[WidgetSnapshot] {
+        &self.widgets
+    }
+
+    /// The first widget in the latest frame matching the predicate.
+    pub fn find(&self, predicate: impl Fn(&WidgetSnapshot) -> bool) -> Option<&WidgetSnapshot> {
+        self.widgets.iter().find(|widget| predicate(widget))
+    }
+
+    /// All widgets of the given type in the latest frame.
+    pub fn get_all_by_type(&self, typ: WidgetType) -> impl Iterator<Item = &WidgetSnapshot> + '_ {
+        self.widgets
+            .iter()
+            .filter(move |widget| widget.typ() == typ)
+    }
+
+    /// The first widget with the given label in the latest frame.
+    ///
+    /// Panics if there is none.
+    pub fn get_by_label(&self, label: &str) -> &WidgetSnapshot {
+        self.find(|widget| widget.label() == Some(label))
+            .unwrap_or_else(|| {
+                let labels: Vec<&str> = self.widgets.iter().filter_map(|w| w.label()).collect();
+                panic!("No widget with label {:?}. Labels: {:?}", label, labels)
+            })
+    }
+
+    /// The first widget of the given type in the latest frame.
+    ///
+    /// Panics if there is none.
+    pub fn get_by_type(&self, typ: WidgetType) -> &WidgetSnapshot {
+        self.find(|widget| widget.typ() == typ)
+            .unwrap_or_else(|| panic!("No widget of type {:?}", typ))
+    }
+
+    /// The widget with the given [`Id`] in the latest frame.
+    ///
+    /// Panics if there is none.
+    pub fn get_by_id(&self, id: Id) -> &WidgetSnapshot {
+        self.find(|widget| widget.id == id)
+            .unwrap_or_else(|| panic!("No widget with id {:?}", id))
+    }
+
+    /// Does the given widget have keyboard focus?
+    pub fn has_focus(&self, id: Id) -> bool {
+        self.ctx.memory().has_focus(id)
+    }
+
+    /// The widget with keyboard focus, if it was shown in the latest frame.
+    pub fn focused(&self) -> Option<&WidgetSnapshot> {
+        let focused = self.ctx.memory().focus()?;
+        self.find(|widget| widget.id == focused)
+    }
+
+    // ------------------------------------------------------------------------
+    // Input:
+
+    /// Queue up a raw input event for the next frame.
+    pub fn push_event(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    /// Which modifier keys are held down from the next frame and onwards.
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+    }
+
+    /// Move the pointer over the center of the given widget.
+    pub fn hover(&mut self, id: Id) {
+        let pos = self.get_by_id(id).rect.center();
+        self.events.push(Event::PointerMoved(pos));
+    }
+
+    /// Click the center of the given widget with the primary button.
+    pub fn click(&mut self, id: Id) {
+        let pos = self.get_by_id(id).rect.center();
+        self.click_at(pos, PointerButton::Primary);
+    }
+
+    /// Press and release the given pointer button at the given position.
+    pub fn click_at(&mut self, pos: Pos2, button: PointerButton) {
+        self.events.push(Event::PointerMoved(pos));
+        for pressed in [true, false] {
+            self.events.push(Event::PointerButton {
+                pos,
+                button,
+                pressed,
+                modifiers: self.modifiers,
+            });
+        }
+    }
+
+    /// Drag the given widget by `delta` points with the primary button.
+    ///
+    /// This runs three frames: one for the press, one for the move, and one for the release.
+    pub fn drag(&mut self, id: Id, delta: Vec2) {
+        let start = self.get_by_id(id).rect.center();
+        let end = start + delta;
+
+        self.events.push(Event::PointerMoved(start));
+        self.push_primary_button(start, true);
+        self.run();
+
+        self.events.push(Event::PointerMoved(end));
+        self.run();
+
+        self.push_primary_button(end, false);
+        self.run();
+    }
+
+    fn push_primary_button(&mut self, pos: Pos2, pressed: bool) {
+        self.events.push(Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: self.modifiers,
+        });
+    }
+
+    /// Type some text, e.g. into the focused [`crate::TextEdit`].
+    pub fn type_text(&mut self, text: &str) {
+        self.events.push(Event::Text(text.to_owned()));
+    }
+
+    /// Press and release a key with the current modifiers.
+    pub fn key_press(&mut self, key: Key) {
+        self.key_press_with_modifiers(self.modifiers, key);
+    }
+
+    /// Press and release a key with the given modifiers.
+    pub fn key_press_with_modifiers(&mut self, modifiers: Modifiers, key: Key) {
+        for pressed in [true, false] {
+            self.events.push(Event::Key {
+                key,
+                pressed,
+                modifiers,
+            });
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn click_button() {
+        let mut harness = Harness::new_ui_state(0, |ui, clicks: &mut i32| {
+            if ui.button("Click me").clicked() {
+                *clicks += 1;
+            }
+        });
+        harness.run();
+
+        let button = harness.get_by_label("Click me");
+        assert_eq!(button.typ(), WidgetType::Button);
+        assert!(button.enabled());
+
+        harness.click(button.id);
+        harness.run();
+        assert_eq!(*harness.state(), 1);
+    }
+
+    #[test]
+    fn disabled_button_is_not_clickable() {
+        let mut harness = Harness::new_ui_state(0, |ui, clicks: &mut i32| {
+            if ui
+                .add_enabled(false, crate::Button::new("Disabled"))
+                .clicked()
+            {
+                *clicks += 1;
+            }
+        });
+        harness.run();
+
+        let button = harness.get_by_label("Disabled");
+        assert!(!button.enabled());
+
+        harness.click(button.id);
+        harness.run();
+        assert_eq!(*harness.state(), 0);
+    }
+
+    #[test]
+    fn type_into_text_edit() {
+        let mut harness = Harness::new_ui_state(String::new(), |ui, text: &mut String| {
+            ui.text_edit_singleline(text);
+        });
+        harness.run();
+
+        let text_edit = harness.get_by_type(WidgetType::TextEdit).id;
+        harness.click(text_edit);
+        harness.run();
+        assert!(harness.has_focus(text_edit));
+
+        harness.type_text("Hello");
+        harness.run();
+        assert_eq!(harness.state(), "Hello");
+        assert_eq!(
+            harness
+                .get_by_id(text_edit)
+                .info
+                .current_text_value
+                .as_deref(),
+            Some("Hello")
+        );
+
+        harness.key_press(Key::Escape);
+        harness.run();
+        assert!(harness.focused().is_none());
+    }
+
+    #[test]
+    fn tab_moves_focus() {
+        let mut harness = Harness::new_ui(|ui| {
+            let _ = ui.button("First");
+            let _ = ui.button("Second");
+        });
+        harness.run();
+
+        harness.key_press(Key::Tab);
+        harness.run();
+        assert_eq!(harness.focused().and_then(|w| w.label()), Some("First"));
+
+        harness.key_press(Key::Tab);
+        harness.run();
+        assert_eq!(harness.focused().and_then(|w| w.label()), Some("Second"));
+    }
+
+    #[test]
+    fn drag_slider() {
+        let mut harness = Harness::new_ui_state(0.0, |ui, value: &mut f64| {
+            ui.add(crate::Slider::new(value, 0.0..=100.0));
+        });
+        harness.run();
+
+        let slider = harness.get_by_type(WidgetType::Slider);
+        let (id, width) = (slider.id, slider.rect.width());
+        harness.drag(id, vec2(width, 0.0));
+        assert_eq!(*harness.state(), 100.0);
+        assert_eq!(harness.get_by_id(id).info.value, Some(100.0));
+    }
+}
@@ -0,0 +1,371 @@
+//! Short-lived notifications ("toasts"), e.g. "Saved" or "Export failed". See [`Toasts`].
+
+use crate::*;
+
+/// How important a [`Toast`] is. Decides its icon and color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    fn icon(self) -> &'static str {
+        match self {
+            Self::Info => "ℹ",
+            Self::Success => "✔",
+            Self::Warning => "⚠",
+            Self::Error => "❗",
+        }
+    }
+
+    fn color(self, visuals: &Visuals) -> Color32 {
+        match self {
+            Self::Info => visuals.hyperlink_color,
+            Self::Success => Color32::from_rgb(0, 190, 90),
+            Self::Warning => Color32::GOLD,
+            Self::Error => Color32::from_rgb(255, 90, 90),
+        }
+    }
+}
+
+/// A notification to show with [`Context::add_toast`].
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// ctx.add_toast(egui::Toast::success("Saved"));
+///
+/// let undo_toast = ctx.add_toast(egui::Toast::info("Deleted 3 files").action("Undo"));
+/// // In a later frame:
+/// if egui::Toasts::action_clicked(ctx, undo_toast) {
+///     // undo
+/// }
+/// # });
+/// ```
+#[derive(Clone)]
+#[must_use = "You should call ctx.add_toast(toast)"]
+pub struct Toast {
+    level: ToastLevel,
+    text: WidgetText,
+    action: Option<WidgetText>,
+    duration: Option<f32>,
+}
+
+impl Toast {
+    pub fn new(level: ToastLevel, text: impl Into<WidgetText>) -> Self {
+        Self {
+            level,
+            text: text.into(),
+            action: None,
+            duration: Some(4.0),
+        }
+    }
+
+    pub fn info(text: impl Into<WidgetText>) -> Self {
+        Self::new(ToastLevel::Info, text)
+    }
+
+    pub fn success(text: impl Into<WidgetText>) -> Self {
+        Self::new(ToastLevel::Success, text)
+    }
+
+    pub fn warning(text: impl Into<WidgetText>) -> Self {
+        Self::new(ToastLevel::Warning, text)
+    }
+
+    pub fn error(text: impl Into<WidgetText>) -> Self {
+        Self::new(ToastLevel::Error, text)
+    }
+
+    /// Add a button to the toast. Check if it was clicked with [`Toasts::action_clicked`].
+    ///
+    /// Clicking the button dismisses the toast.
+    pub fn action(mut self, label: impl Into<WidgetText>) -> Self {
+        self.action = Some(label.into());
+        self
+    }
+
+    /// For how many seconds to show the toast, or `None` to show it until the user closes it.
+    ///
+    /// The countdown is paused while the mouse is over the toast. Default: 4 seconds.
+    pub fn duration(mut self, seconds: Option<f32>) -> Self {
+        self.duration = seconds;
+        self
+    }
+}
+
+/// Identifies a toast added with [`Context::add_toast`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ToastId(u64);
+
+#[derive(Clone)]
+struct ToastState {
+    id: ToastId,
+    toast: Toast,
+
+    /// Seconds left until the toast closes.
+    remaining: Option<f32>,
+
+    /// Animating out, then removed.
+    closing: bool,
+
+    /// Width last frame, for sliding in and out.
+    width: f32,
+}
+
+/// The toasts of a [`Context`], shown stacked in a corner of the screen.
+///
+/// Add toasts with [`Context::add_toast`].
+/// They are shown at the end of each frame, on top of the rest of the ui.
+///
+/// The state is stored in [`crate::Memory::data`].
+#[derive(Clone)]
+pub struct Toasts {
+    toasts: Vec<ToastState>,
+    next_id: u64,
+    anchor: Align2,
+
+    /// The toasts whose action buttons were clicked last frame.
+    clicked_actions: Vec<ToastId>,
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self {
+            toasts: Default::default(),
+            next_id: 0,
+            anchor: Align2::RIGHT_BOTTOM,
+            clicked_actions: Default::default(),
+        }
+    }
+}
+
+impl Toasts {
+    fn anim_id(id: ToastId) -> Id {
+        Id::new(("toast", id.0))
+    }
+
+    /// Show a new toast below (or above, if the toasts are at the bottom of the screen) the existing ones.
+    pub fn add(ctx: &Context, toast: Toast) -> ToastId {
+        let mut data = ctx.data();
+        let toasts = data.get_temp_mut_or_default::<Self>(Id::null());
+        let id = ToastId(toasts.next_id);
+        toasts.next_id += 1;
+        toasts.toasts.push(ToastState {
+            id,
+            remaining: toast.duration,
+            toast,
+            closing: false,
+            width: 0.0,
+        });
+        drop(data);
+
+        ctx.animate_bool(Self::anim_id(id), false); // so that it animates in
+        ctx.request_repaint();
+        id
+    }
+
+    /// Close a toast (with an animation).
+    pub fn dismiss(ctx: &Context, id: ToastId) {
+        let mut data = ctx.data();
+        let toasts = data.get_temp_mut_or_default::<Self>(Id::null());
+        for state in &mut toasts.toasts {
+            if state.id == id {
+                state.closing = true;
+            }
+        }
+        drop(data);
+        ctx.request_repaint();
+    }
+
+    /// Was the action button (see [`Toast::action`]) of this toast clicked last frame?
+    pub fn action_clicked(ctx: &Context, id: ToastId) -> bool {
+        ctx.data()
+            .get_temp::<Self>(Id::null())
+            .map_or(false, |toasts| toasts.clicked_actions.contains(&id))
+    }
+
+    /// Is the toast still being shown?
+    pub fn is_open(ctx: &Context, id: ToastId) -> bool {
+        ctx.data()
+            .get_temp::<Self>(Id::null())
+            .map_or(false, |toasts| {
+                toasts.toasts.iter().any(|state| state.id == id)
+            })
+    }
+
+    /// In which corner (or at which edge) of the screen to show the toasts. Default: [`Align2::RIGHT_BOTTOM`].
+    pub fn set_anchor(ctx: &Context, anchor: Align2) {
+        ctx.data()
+            .get_temp_mut_or_default::<Self>(Id::null())
+            .anchor = anchor;
+    }
+
+    pub(crate) fn end_frame(ctx: &Context) {
+        if ctx.viewport_id() != ViewportId::ROOT {
+            return; // toasts are only shown in the main window
+        }
+        let mut toasts = match ctx.data().get_temp::<Self>(Id::null()) {
+            Some(toasts) => toasts,
+            None => return,
+        };
+        toasts.clicked_actions.clear();
+        toasts.show(ctx);
+        ctx.data().insert_temp(Id::null(), toasts);
+    }
+
+    fn show(&mut self, ctx: &Context) {
+        let Self {
+            toasts,
+            anchor,
+            clicked_actions,
+            ..
+        } = self;
+
+        let dt = ctx.input().stable_dt.min(0.1);
+        let margin = ctx.style().spacing.window_margin.left_top();
+        let spacing = ctx.style().spacing.item_spacing.y;
+
+        // Toasts are stacked away from the edge of the screen they are anchored to,
+        // and slide in and out from the side:
+        let away_from_edge = -anchor.to_sign();
+        let slide_dir = if anchor.x() == Align::Min { -1.0 } else { 1.0 };
+        let stack_dir = if anchor.y() == Align::Max { -1.0 } else { 1.0 };
+
+        let mut stack_offset = 0.0;
+        let mut is_counting_down = false;
+
+        for state in toasts.iter_mut() {
+            let openness = ctx.animate_bool(Self::anim_id(state.id), !state.closing);
+
+            let slide = (1.0 - openness) * (state.width + margin.x);
+            let offset =
+                away_from_edge * margin + vec2(slide_dir * slide, stack_dir * stack_offset);
+
+            let response = Area::new(Self::anim_id(state.id))
+                .order(Order::Foreground)
+                .anchor(*anchor, offset)
+                .show(ctx, |ui| show_toast(ui, state, clicked_actions))
+                .response;
+
+            state.width = response.rect.width();
+            stack_offset += openness * (response.rect.height() + spacing);
+
+            if response.hovered() || state.closing {
+                continue; // pause the countdown
+            }
+            if let Some(remaining) = &mut state.remaining {
+                *remaining -= dt;
+                if *remaining <= 0.0 {
+                    state.closing = true;
+                    ctx.request_repaint(); // start animating out
+                } else {
+                    is_counting_down = true;
+                }
+            }
+        }
+
+        toasts.retain(|state| {
+            !state.closing || ctx.animate_bool(Self::anim_id(state.id), false) > 0.0
+        });
+
+        if is_counting_down {
+            ctx.request_repaint();
+        }
+    }
+}
+
+fn show_toast(ui: &mut Ui, state: &mut ToastState, clicked_actions: &mut Vec<ToastId>) {
+    let level = state.toast.level;
+    let color = level.color(ui.visuals());
+    Frame::popup(ui.style())
+        .stroke(Stroke::new(1.0, color))
+        .show(ui, |ui| {
+            ui.set_max_width(ui.spacing().tooltip_width);
+            ui.horizontal(|ui| {
+                ui.colored_label(color, level.icon());
+                ui.add(Label::new(state.toast.text.clone()).wrap(true));
+                if let Some(action) = &state.toast.action {
+                    if ui.button(action.clone()).clicked() {
+                        clicked_actions.push(state.id);
+                        state.closing = true;
+                    }
+                }
+                if ui.small_button("✖").on_hover_text("Close").clicked() {
+                    state.closing = true;
+                }
+            });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::Harness;
+    use crate::*;
+
+    fn harness_with_toast(toast: Toast) -> Harness<'static, (Option<ToastId>, bool)> {
+        let mut harness = Harness::new_state((None, false), move |ctx, (id, action_clicked)| {
+            let id = *id.get_or_insert_with(|| ctx.add_toast(toast.clone()));
+            *action_clicked |= Toasts::action_clicked(ctx, id);
+        });
+        for _ in 0..10 {
+            harness.run(); // let it slide in
+        }
+        harness
+    }
+
+    #[test]
+    fn closes_after_timeout() {
+        let mut harness = harness_with_toast(Toast::info("Saved").duration(Some(1.0)));
+        let id = harness.state().0.unwrap();
+        assert!(Toasts::is_open(harness.ctx(), id));
+
+        let frames = harness.run_until_idle(1000);
+        assert!(
+            frames >= 45,
+            "the toast is shown for a second ({} frames)",
+            frames
+        );
+        assert!(frames < 1000, "and then egui stops repainting");
+        assert!(!Toasts::is_open(harness.ctx(), id));
+    }
+
+    #[test]
+    fn hovering_pauses_the_countdown() {
+        let mut harness = harness_with_toast(Toast::info("Saved").duration(Some(1.0)));
+        let id = harness.state().0.unwrap();
+        harness.hover(harness.get_by_label("✖").id);
+        for _ in 0..120 {
+            harness.run();
+        }
+        assert!(Toasts::is_open(harness.ctx(), id));
+    }
+
+    #[test]
+    fn action_button() {
+        let mut harness = harness_with_toast(Toast::warning("Deleted").action("Undo"));
+        let id = harness.state().0.unwrap();
+        harness.click(harness.get_by_label("Undo").id);
+        harness.run();
+        harness.run();
+        assert!(harness.state().1, "the click is reported");
+        harness.run_until_idle(100);
+        assert!(!Toasts::is_open(harness.ctx(), id), "and closes the toast");
+    }
+
+    #[test]
+    fn no_timeout() {
+        let mut harness = harness_with_toast(Toast::error("Failed").duration(None));
+        let id = harness.state().0.unwrap();
+        let frames = harness.run_until_idle(1000);
+        assert!(frames < 1000, "no repaints without a countdown");
+        assert!(Toasts::is_open(harness.ctx(), id));
+
+        Toasts::dismiss(harness.ctx(), id);
+        harness.run_until_idle(100);
+        assert!(!Toasts::is_open(harness.ctx(), id));
+    }
+}
@@ -77,8 +77,8 @@ impl Ui {
             id,
             next_auto_id_source: id.with("auto").value(),
             painter: Painter::new(ctx, layer_id, clip_rect),
+            placer: Placer::new(max_rect, Layout::default().for_style(&style)),
             style,
-            placer: Placer::new(max_rect, Layout::default()),
             enabled: true,
             menu_state: None,
         }
@@ -1713,6 +1713,124 @@ impl Ui {
         })
     }
 
+    /// Create something that can be drag-and-dropped.
+    ///
+    /// The `id` needs to be globally unique.
+    /// The payload is what will be dropped if the user starts dragging.
+    ///
+    /// While dragged, the contents are painted on top of everything else, following the pointer.
+    /// Press Escape to cancel the drag.
+    ///
+    /// See also [`Self::dnd_drop_zone`] and [`Response::dnd_set_drag_payload`].
+    #[doc(alias = "drag and drop")]
+    pub fn dnd_drag_source<Payload, R>(
+        &mut self,
+        id: Id,
+        payload: Payload,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> InnerResponse<R>
+    where
+        Payload: std::any::Any + Send + Sync,
+    {
+        let is_being_dragged = self.memory().is_being_dragged(id);
+
+        if is_being_dragged {
+            self.output().cursor_icon = CursorIcon::Grabbing;
+
+            // Paint the body to a new layer:
+            let layer_id = LayerId::new(Order::Tooltip, id);
+            let InnerResponse { inner, response } = self.with_layer_id(layer_id, add_contents);
+
+            // Now we move the visuals of the body to where the mouse is.
+            // Normally you need to decide a location for a widget first,
+            // because otherwise that widget cannot interact with the mouse.
+            // However, the body is only a preview of what is being dragged,
+            // and the pointer button is held down for the whole drag, so nothing in it can be clicked.
+            // So this is fine!
+            if let Some(pointer_pos) = self.ctx().pointer_interact_pos() {
+                let delta = pointer_pos - response.rect.center();
+                self.ctx().translate_layer(layer_id, delta);
+            }
+
+            InnerResponse::new(inner, response)
+        } else {
+            let InnerResponse { inner, response } = self.scope(add_contents);
+
+            // Check for drags:
+            let dnd_response = self.interact(response.rect, id, Sense::drag());
+            dnd_response.dnd_set_drag_payload(payload);
+
+            InnerResponse::new(inner, dnd_response | response)
+        }
+    }
+
+    /// Surround the given ui with a frame which
+    /// changes colors when you can drop something onto it.
+    ///
+    /// Returns the dropped item, if it was released this frame.
+    ///
+    /// The given frame is used for its margins, but it color is ignored.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let mut items = vec![0, 1, 2];
+    /// for (i, item) in items.iter().enumerate() {
+    ///     ui.dnd_drag_source(egui::Id::new(("item", i)), i, |ui| {
+    ///         ui.label(format!("Item {}", item));
+    ///     });
+    /// }
+    /// let (_, dropped) = ui.dnd_drop_zone::<usize, _>(egui::Frame::group(ui.style()), |ui| {
+    ///     ui.label("Drop here to remove");
+    /// });
+    /// if let Some(i) = dropped {
+    ///     items.remove(*i);
+    /// }
+    /// # });
+    /// ```
+    #[doc(alias = "drag and drop")]
+    pub fn dnd_drop_zone<Payload, R>(
+        &mut self,
+        frame: Frame,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> (InnerResponse<R>, Option<Arc<Payload>>)
+    where
+        Payload: std::any::Any + Send + Sync,
+    {
+        let is_anything_being_dragged = DragAndDrop::has_any_payload(self.ctx());
+        let can_accept_what_is_being_dragged =
+            DragAndDrop::has_payload_of_type::<Payload>(self.ctx());
+
+        let mut frame = frame.begin(self);
+        let inner = add_contents(&mut frame.content_ui);
+
+        // NOTE: we check if the rect contains the pointer, since `hovered`
+        // is always false when another widget is being dragged.
+        let is_hovered = self.rect_contains_pointer(frame.paint_rect());
+
+        let style = if is_anything_being_dragged && can_accept_what_is_being_dragged && is_hovered {
+            self.visuals().widgets.active
+        } else {
+            self.visuals().widgets.inactive
+        };
+
+        let mut fill = style.bg_fill;
+        let mut stroke = style.bg_stroke;
+        if is_anything_being_dragged && !can_accept_what_is_being_dragged {
+            // gray out:
+            fill = tint_color_towards(fill, self.visuals().window_fill());
+            stroke.color = tint_color_towards(stroke.color, self.visuals().window_fill());
+        }
+
+        frame.frame.fill = fill;
+        frame.frame.stroke = stroke;
+        frame.frame.rounding = style.rounding;
+
+        let response = frame.end(self);
+        let payload = response.dnd_release_payload::<Payload>();
+
+        (InnerResponse::new(inner, response), payload)
+    }
+
     /// A [`CollapsingHeader`] that starts out collapsed.
     pub fn collapsing<R>(
         &mut self,
@@ -1897,7 +2015,8 @@ impl Ui {
     /// See also [`Self::with_layout`] for more options.
     #[inline]
     pub fn vertical<R>(&mut self, add_contents: impl FnOnce(&mut Ui) -> R) -> InnerResponse<R> {
-        self.with_layout_dyn(Layout::top_down(Align::Min), Box::new(add_contents))
+        let layout = Layout::top_down(Align::Min).for_style(self.style());
+        self.with_layout_dyn(layout, Box::new(add_contents))
     }
 
     /// Start a ui with vertical layout.
@@ -2047,15 +2166,23 @@ impl Ui {
         let column_width = (self.available_width() - total_spacing) / (num_columns as f32);
         let top_left = self.cursor().min;
 
+        let right_to_left = self.style().right_to_left;
+        let column_layout = Layout::top_down_justified(Align::LEFT).for_style(self.style());
+
         let mut columns: Vec<Self> = (0..num_columns)
             .map(|col_idx| {
-                let pos = top_left + vec2((col_idx as f32) * (column_width + spacing), 0.0);
+                // The first column is on the right in a right-to-left ui:
+                let visual_idx = if right_to_left {
+                    num_columns - 1 - col_idx
+                } else {
+                    col_idx
+                };
+                let pos = top_left + vec2((visual_idx as f32) * (column_width + spacing), 0.0);
                 let child_rect = Rect::from_min_max(
                     pos,
                     pos2(pos.x + column_width, self.max_rect().right_bottom().y),
                 );
-                let mut column_ui =
-                    self.child_ui(child_rect, Layout::top_down_justified(Align::LEFT));
+                let mut column_ui = self.child_ui(child_rect, column_layout);
                 column_ui.set_width(column_width);
                 column_ui
             })
@@ -57,15 +57,21 @@ pub struct Undoer<State> {
     /// The latest undo point may (often) be the current state.
     undos: VecDeque<State>,
 
+    /// The states we have undone, latest last.
+    /// Cleared when the state changes in any other way.
+    #[cfg_attr(feature = "serde", serde(default))]
+    redos: Vec<State>,
+
     #[cfg_attr(feature = "serde", serde(skip))]
     flux: Option<Flux<State>>,
 }
 
 impl<State> std::fmt::Debug for Undoer<State> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let Self { undos, .. } = self;
+        let Self { undos, redos, .. } = self;
         f.debug_struct("Undoer")
             .field("undo count", &undos.len())
+            .field("redo count", &redos.len())
             .finish()
     }
 }
@@ -91,6 +97,11 @@ where
         }
     }
 
+    /// Do we have a state to redo to, i.e. has the state not changed since the latest [`Self::undo`]?
+    pub fn has_redo(&self, current_state: &State) -> bool {
+        !self.redos.is_empty() && self.undos.back() == Some(current_state)
+    }
+
     /// Return true if the state is currently changing
     pub fn is_in_flux(&self) -> bool {
         self.flux.is_some()
@@ -101,7 +112,10 @@ where
             self.flux = None;
 
             if self.undos.back() == Some(current_state) {
-                self.undos.pop_back();
+                self.redos.push(self.undos.pop_back().unwrap());
+            } else {
+                self.redos.clear();
+                self.redos.push(current_state.clone());
             }
 
             // Note: we keep the undo point intact.
@@ -111,12 +125,24 @@ where
         }
     }
 
+    /// Go back to the state before the latest [`Self::undo`].
+    pub fn redo(&mut self, current_state: &State) -> Option<&State> {
+        if !self.has_redo(current_state) {
+            self.redos.clear();
+            return None;
+        }
+        self.flux = None;
+        self.undos.push_back(self.redos.pop()?);
+        self.undos.back()
+    }
+
     /// Add an undo point if, and only if, there has been a change since the latest undo point.
     ///
     /// * `time`: current time in seconds.
     pub fn add_undo(&mut self, current_state: &State) {
         if self.undos.back() != Some(current_state) {
             self.undos.push_back(current_state.clone());
+            self.redos.clear();
         }
         while self.undos.len() > self.settings.max_undos {
             self.undos.pop_front();
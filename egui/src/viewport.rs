@@ -0,0 +1,254 @@
+//! Showing egui in more than one native window.
+//!
+//! The main window of the integration is the _root viewport_.
+//! From inside it you can show any number of _child viewports_ with [`crate::Context::show_viewport`],
+//! each of which the integration shows in its own native window.
+//!
+//! All viewports share the same [`crate::Context`], so they share [`crate::Memory`], fonts, textures etc,
+//! but each viewport has its own [`crate::InputState`], layers, keyboard focus and painted output.
+//!
+//! For the integration, the flow each frame is:
+//! * Gather the input for each open child viewport into [`crate::RawInput::viewports`].
+//! * Run the root viewport with [`crate::Context::run`].
+//! * Create a native window for each new viewport in [`crate::FullOutput::viewports`],
+//!   and close the windows of any viewport that is no longer there.
+//! * Paint each [`ViewportOutput`] into its window.
+
+use crate::{FullOutput, Id, Vec2};
+
+/// Identifies a viewport (a native window).
+///
+/// This is the same from one frame to the next.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ViewportId(pub Id);
+
+impl ViewportId {
+    /// The main window of the integration.
+    pub const ROOT: Self = Self(Id::null());
+
+    /// Generate a new [`ViewportId`] by hashing some source (e.g. a string or integer).
+    pub fn from_hash_of(source: impl std::hash::Hash) -> Self {
+        Self(Id::new(source))
+    }
+}
+
+impl Default for ViewportId {
+    fn default() -> Self {
+        Self::ROOT
+    }
+}
+
+impl From<Id> for ViewportId {
+    fn from(id: Id) -> Self {
+        Self(id)
+    }
+}
+
+/// A `HashMap<ViewportId, T>`.
+pub type ViewportIdMap<T> = std::collections::HashMap<ViewportId, T, crate::id::BuilIdHasher>;
+
+// ----------------------------------------------------------------------------
+
+/// Describes the native window of a viewport.
+///
+/// The integration uses this when creating the window,
+/// and to update it when it changes.
+///
+/// ```
+/// let builder = egui::ViewportBuilder::new("Tools")
+///     .inner_size(egui::vec2(320.0, 240.0))
+///     .resizable(false);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ViewportBuilder {
+    /// The title of the window.
+    pub title: String,
+
+    /// The size of the inside of the window, in points.
+    ///
+    /// `None` means the integration picks a size.
+    pub inner_size: Option<Vec2>,
+
+    /// Can the user resize the window?
+    pub resizable: bool,
+
+    /// Show the title bar and borders of the window?
+    pub decorations: bool,
+
+    /// Keep the window above all other windows?
+    pub always_on_top: bool,
+}
+
+impl Default for ViewportBuilder {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            inner_size: None,
+            resizable: true,
+            decorations: true,
+            always_on_top: false,
+        }
+    }
+}
+
+impl ViewportBuilder {
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn new(title: impl ToString) -> Self {
+        Self {
+            title: title.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// The size of the inside of the window, in points.
+    pub fn inner_size(mut self, inner_size: Vec2) -> Self {
+        self.inner_size = Some(inner_size);
+        self
+    }
+
+    /// Can the user resize the window? Default: `true`.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Show the title bar and borders of the window? Default: `true`.
+    pub fn decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    /// Keep the window above all other windows? Default: `false`.
+    pub fn always_on_top(mut self, always_on_top: bool) -> Self {
+        self.always_on_top = always_on_top;
+        self
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// What a child viewport emitted during one frame.
+///
+/// See [`crate::FullOutput::viewports`].
+#[derive(Clone, PartialEq)]
+pub struct ViewportOutput {
+    /// The viewport that showed this one.
+    pub parent: ViewportId,
+
+    /// What the native window should look like.
+    pub builder: ViewportBuilder,
+
+    /// The scale factor this viewport was laid out for.
+    ///
+    /// Use this (and not [`crate::Context::pixels_per_point`]) when
+    /// tessellating and painting the viewport.
+    pub pixels_per_point: f32,
+
+    /// The output of the viewport.
+    ///
+    /// Note that all texture changes are in the [`crate::FullOutput::textures_delta`] of the root viewport,
+    /// so [`FullOutput::textures_delta`] is always empty here.
+    /// Likewise, the viewports shown by this viewport are in the [`crate::FullOutput::viewports`] of the root viewport,
+    /// so [`FullOutput::viewports`] is always empty here.
+    pub output: FullOutput,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn child_viewport_has_its_own_input_and_output() {
+        let child = ViewportId::from_hash_of("child");
+        let ctx = Context::default();
+        ctx.set_supports_viewports(true);
+
+        let clicks = std::cell::Cell::new(0);
+        let run = |events: Vec<Event>, viewport_input: Option<RawInput>| {
+            let mut raw_input = RawInput {
+                screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0))),
+                events,
+                ..Default::default()
+            };
+            if let Some(viewport_input) = viewport_input {
+                raw_input.viewports.insert(child, viewport_input);
+            }
+            ctx.run(raw_input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    ui.label("Root");
+                });
+                ctx.show_viewport(child, ViewportBuilder::new("Child"), |ctx| {
+                    assert_eq!(ctx.viewport_id(), child);
+                    CentralPanel::default().show(ctx, |ui| {
+                        if ui.button("Click me").clicked() {
+                            clicks.set(clicks.get() + 1);
+                        }
+                    });
+                });
+                assert_eq!(ctx.viewport_id(), ViewportId::ROOT);
+            })
+        };
+
+        let child_input = |events| RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(200.0, 100.0))),
+            pixels_per_point: Some(2.0),
+            events,
+            ..Default::default()
+        };
+
+        // The button is at the top left of the child viewport:
+        let pos = pos2(20.0, 15.0);
+        let click = || {
+            let button = |pressed| Event::PointerButton {
+                pos,
+                button: PointerButton::Primary,
+                pressed,
+                modifiers: Default::default(),
+            };
+            vec![Event::PointerMoved(pos), button(true), button(false)]
+        };
+
+        let output = run(vec![], None);
+        let viewport = &output.viewports[&child];
+        assert_eq!(viewport.parent, ViewportId::ROOT);
+        assert_eq!(viewport.builder.title, "Child");
+        assert!(!viewport.output.shapes.is_empty());
+
+        // Clicking in the root viewport should not click the button in the child viewport:
+        run(click(), Some(child_input(vec![])));
+        assert_eq!(clicks.get(), 0);
+
+        let output = run(vec![], Some(child_input(click())));
+        assert_eq!(clicks.get(), 1);
+        assert_eq!(output.viewports[&child].pixels_per_point, 2.0);
+
+        // Not showing the viewport closes it:
+        let output = ctx.run(RawInput::default(), |_ctx| {});
+        assert!(output.viewports.is_empty());
+    }
+
+    #[test]
+    fn nested_viewports_are_flattened() {
+        let child = ViewportId::from_hash_of("child");
+        let grandchild = ViewportId::from_hash_of("grandchild");
+        let ctx = Context::default();
+        ctx.set_supports_viewports(true);
+
+        let output = ctx.run(RawInput::default(), |ctx| {
+            ctx.show_viewport(child, ViewportBuilder::new("Child"), |ctx| {
+                ctx.show_viewport(grandchild, ViewportBuilder::new("Grandchild"), |ctx| {
+                    CentralPanel::default().show(ctx, |ui| {
+                        ui.label("Grandchild");
+                    });
+                });
+            });
+        });
+
+        assert_eq!(output.viewports.len(), 2);
+        assert_eq!(output.viewports[&child].parent, ViewportId::ROOT);
+        assert_eq!(output.viewports[&grandchild].parent, child);
+        assert!(output.viewports[&child].output.viewports.is_empty());
+    }
+}
@@ -1,8 +1,8 @@
 use std::sync::Arc;
 
 use crate::{
-    style::WidgetVisuals, text::LayoutJob, Align, Color32, FontFamily, FontSelection, Galley, Pos2,
-    Style, TextStyle, Ui, Visuals,
+    style::WidgetVisuals, text::LayoutJob, Align, Color32, FontFamily, FontSelection, FontStyle,
+    FontWeight, Galley, Pos2, Style, TextStyle, Ui, Visuals,
 };
 
 /// Text and optional style choices for it.
@@ -25,6 +25,7 @@ pub struct RichText {
     text: String,
     size: Option<f32>,
     family: Option<FontFamily>,
+    weight: Option<FontWeight>,
     text_style: Option<TextStyle>,
     background_color: Color32,
     text_color: Option<Color32>,
@@ -103,13 +104,29 @@ impl RichText {
         self
     }
 
+    /// Select how bold the text is, e.g. [`FontWeight::LIGHT`].
+    ///
+    /// The closest weight in [`crate::FontDefinitions::faces`] is used.
+    #[inline]
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
     /// Select the font and size.
     /// This overrides the value from [`Self::text_style`].
     #[inline]
     pub fn font(mut self, font_id: crate::FontId) -> Self {
-        let crate::FontId { size, family } = font_id;
+        let crate::FontId {
+            size,
+            family,
+            weight,
+            style,
+        } = font_id;
         self.size = Some(size);
         self.family = Some(family);
+        self.weight = Some(weight);
+        self.italics |= style == FontStyle::Italic;
         self
     }
 
@@ -146,7 +163,7 @@ impl RichText {
         self.text_style(TextStyle::Monospace)
     }
 
-    /// Extra strong text (stronger color).
+    /// Extra strong text (stronger color, and bold if the font has a bold face).
     #[inline]
     pub fn strong(mut self) -> Self {
         self.strong = true;
@@ -231,6 +248,9 @@ impl RichText {
         if let Some(family) = &self.family {
             font_id.family = family.clone();
         }
+        if let Some(weight) = self.font_weight() {
+            font_id.weight = weight;
+        }
         fonts.row_height(&font_id)
     }
 
@@ -241,11 +261,13 @@ impl RichText {
         default_valign: Align,
     ) -> WidgetTextJob {
         let text_color = self.get_text_color(&style.visuals);
+        let font_weight = self.font_weight();
 
         let Self {
             text,
             size,
             family,
+            weight: _, // already used by `font_weight`
             text_style,
             background_color,
             text_color: _, // already used by `get_text_color`
@@ -275,6 +297,9 @@ impl RichText {
             if let Some(family) = family {
                 font_id.family = family;
             }
+            if let Some(weight) = font_weight {
+                font_id.weight = weight;
+            }
             font_id
         };
 
@@ -313,6 +338,16 @@ impl RichText {
         WidgetTextJob { job, job_has_color }
     }
 
+    fn font_weight(&self) -> Option<FontWeight> {
+        if self.weight.is_some() {
+            self.weight
+        } else if self.strong {
+            Some(FontWeight::BOLD)
+        } else {
+            None
+        }
+    }
+
     fn get_text_color(&self, visuals: &Visuals) -> Option<Color32> {
         if let Some(text_color) = self.text_color {
             Some(text_color)
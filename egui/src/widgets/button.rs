@@ -21,6 +21,7 @@ use crate::*;
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
 pub struct Button {
     text: WidgetText,
+    shortcut_text: WidgetText,
     wrap: Option<bool>,
     /// None means default for interact
     fill: Option<Color32>,
@@ -36,6 +37,7 @@ impl Button {
     pub fn new(text: impl Into<WidgetText>) -> Self {
         Self {
             text: text.into(),
+            shortcut_text: Default::default(),
             wrap: None,
             fill: None,
             stroke: None,
@@ -56,6 +58,7 @@ impl Button {
     ) -> Self {
         Self {
             text: text.into(),
+            shortcut_text: Default::default(),
             fill: None,
             stroke: None,
             sense: Sense::click(),
@@ -116,6 +119,29 @@ impl Button {
         self
     }
 
+    /// Show some text on the right side of the button, in weak color.
+    ///
+    /// Designed for menu buttons, for setting a keyboard shortcut text (e.g. `Ctrl+S`).
+    ///
+    /// The text can be created with [`Context::format_shortcut`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// const SAVE: egui::KeyboardShortcut =
+    ///     egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::S);
+    /// ui.menu_button("File", |ui| {
+    ///     let button = egui::Button::new("Save").shortcut_text(ui.ctx().format_shortcut(&SAVE));
+    ///     if ui.add(button).clicked() {
+    ///         ui.close_menu();
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    pub fn shortcut_text(mut self, shortcut_text: impl Into<WidgetText>) -> Self {
+        self.shortcut_text = shortcut_text.into();
+        self
+    }
+
     pub(crate) fn min_size(mut self, min_size: Vec2) -> Self {
         self.min_size = min_size;
         self
@@ -126,6 +152,7 @@ impl Widget for Button {
     fn ui(self, ui: &mut Ui) -> Response {
         let Button {
             text,
+            shortcut_text,
             wrap,
             fill,
             stroke,
@@ -144,10 +171,17 @@ impl Widget for Button {
         }
         let total_extra = button_padding + button_padding;
 
-        let wrap_width = ui.available_width() - total_extra.x;
+        let shortcut_text = (!shortcut_text.is_empty())
+            .then(|| shortcut_text.into_galley(ui, Some(false), f32::INFINITY, TextStyle::Button));
+        let shortcut_width = shortcut_text.as_ref().map_or(0.0, |shortcut_text| {
+            ui.spacing().item_spacing.x + shortcut_text.size().x
+        });
+
+        let wrap_width = ui.available_width() - total_extra.x - shortcut_width;
         let text = text.into_galley(ui, wrap, wrap_width, TextStyle::Button);
 
         let mut desired_size = text.size() + 2.0 * button_padding;
+        desired_size.x += shortcut_width;
         if !small {
             desired_size.y = desired_size.y.at_least(ui.spacing().interact_size.y);
         }
@@ -161,17 +195,28 @@ impl Widget for Button {
         let (rect, response) = ui.allocate_at_least(desired_size, sense);
         response.widget_info(|| WidgetInfo::labeled(WidgetType::Button, text.text()));
 
+        // In a right-to-left ui the image is on the right and the shortcut text on the left:
+        let right_to_left = ui.style().right_to_left;
+
         if ui.is_rect_visible(rect) {
             let visuals = ui.style().interact(&response);
             let text_pos = if let Some(image) = image {
                 let icon_spacing = ui.spacing().icon_spacing;
-                pos2(
-                    rect.min.x + button_padding.x + image.size().x + icon_spacing,
-                    rect.center().y - 0.5 * text.size().y,
-                )
+                let x = if right_to_left {
+                    rect.max.x - button_padding.x - image.size().x - icon_spacing - text.size().x
+                } else {
+                    rect.min.x + button_padding.x + image.size().x + icon_spacing
+                };
+                pos2(x, rect.center().y - 0.5 * text.size().y)
             } else {
+                let mut text_rect = rect.shrink2(button_padding);
+                if right_to_left {
+                    text_rect.min.x += shortcut_width;
+                } else {
+                    text_rect.max.x -= shortcut_width;
+                }
                 ui.layout()
-                    .align_size_within_rect(text.size(), rect.shrink2(button_padding))
+                    .align_size_within_rect(text.size(), text_rect)
                     .min
             };
 
@@ -187,11 +232,30 @@ impl Widget for Button {
             }
 
             text.paint_with_visuals(ui.painter(), text_pos, visuals);
+
+            if let Some(shortcut_text) = shortcut_text {
+                let x = if right_to_left {
+                    rect.min.x + button_padding.x
+                } else {
+                    rect.max.x - button_padding.x - shortcut_text.size().x
+                };
+                let shortcut_text_pos = pos2(x, rect.center().y - 0.5 * shortcut_text.size().y);
+                shortcut_text.paint_with_fallback_color(
+                    ui.painter(),
+                    shortcut_text_pos,
+                    ui.visuals().weak_text_color(),
+                );
+            }
         }
 
         if let Some(image) = image {
+            let x = if right_to_left {
+                rect.max.x - image.size().x
+            } else {
+                rect.min.x
+            };
             let image_rect = Rect::from_min_size(
-                pos2(rect.min.x, rect.center().y - 0.5 - (image.size().y / 2.0)),
+                pos2(x, rect.center().y - 0.5 - (image.size().y / 2.0)),
                 image.size(),
             );
             image.paint_at(ui, image_rect);
@@ -228,6 +228,19 @@ impl<'a> Widget for DragValue<'a> {
                 ));
             }
 
+            if response.enabled {
+                let set_value = match ui.input().accessibility_set_value(response.id) {
+                    Some(accessibility::ActionData::NumericValue(new_value)) => Some(*new_value),
+                    _ => None,
+                };
+                if let Some(new_value) = set_value {
+                    set(
+                        &mut get_set_value,
+                        clamp_to_range(new_value, clamp_range.clone()),
+                    );
+                }
+            }
+
             if response.clicked() {
                 ui.memory().request_focus(kb_edit_id);
                 ui.memory().drag_value.edit_string = None; // Filled in next frame
@@ -266,6 +279,7 @@ impl<'a> Widget for DragValue<'a> {
                     ui.memory().drag_value = drag_state;
                 }
             } else if response.has_focus() {
+                ui.memory().claim_arrow_keys(response.id);
                 let change = ui.input().num_presses(Key::ArrowUp) as f64
                     + ui.input().num_presses(Key::ArrowRight) as f64
                     - ui.input().num_presses(Key::ArrowDown) as f64
@@ -17,6 +17,7 @@ pub struct Label {
     text: WidgetText,
     wrap: Option<bool>,
     sense: Sense,
+    selectable: Option<bool>,
 }
 
 impl Label {
@@ -25,6 +26,7 @@ impl Label {
             text: text.into(),
             wrap: None,
             sense: Sense::focusable_noninteractive(),
+            selectable: None,
         }
     }
 
@@ -63,6 +65,19 @@ impl Label {
         self.sense = sense;
         self
     }
+
+    /// Can the user select the text with the mouse, and copy it?
+    ///
+    /// The selection can continue into other selectable labels in the same [`Ui`].
+    ///
+    /// Labels that [`Self::sense`] clicks or drags are never selectable.
+    ///
+    /// By default this is [`crate::style::Interaction::selectable_labels`].
+    #[inline]
+    pub fn selectable(mut self, selectable: bool) -> Self {
+        self.selectable = Some(selectable);
+        self
+    }
 }
 
 impl Label {
@@ -157,6 +172,12 @@ impl Label {
 
 impl Widget for Label {
     fn ui(self, ui: &mut Ui) -> Response {
+        let selectable = self
+            .selectable
+            .unwrap_or_else(|| ui.style().interaction.selectable_labels)
+            && !self.sense.click
+            && !self.sense.drag;
+
         let (pos, text_galley, response) = self.layout_in_ui(ui);
         response.widget_info(|| WidgetInfo::labeled(WidgetType::Label, text_galley.text()));
 
@@ -177,13 +198,22 @@ impl Widget for Label {
 
             ui.painter().add(epaint::TextShape {
                 pos,
-                galley: text_galley.galley,
+                galley: text_galley.galley.clone(),
                 override_text_color,
                 underline,
                 angle: 0.0,
             });
         }
 
+        if selectable {
+            crate::label_selection::LabelSelectionState::label_text_selection(
+                ui,
+                &response,
+                pos,
+                &text_galley.galley,
+            );
+        }
+
         response
     }
 }
@@ -311,7 +311,8 @@ impl<'a> Slider<'a> {
     /// Just the slider, no text
     fn slider_ui(&mut self, ui: &mut Ui, response: &Response) {
         let rect = &response.rect;
-        let position_range = self.position_range(rect);
+        let mirrored = ui.style().right_to_left;
+        let position_range = self.position_range(rect, mirrored);
 
         if let Some(pointer_position_2d) = response.interact_pointer_pos() {
             let position = self.pointer_position(pointer_position_2d);
@@ -327,10 +328,21 @@ impl<'a> Slider<'a> {
             self.set_value(new_value);
         }
 
+        if response.enabled {
+            let set_value = match ui.input().accessibility_set_value(response.id) {
+                Some(accessibility::ActionData::NumericValue(new_value)) => Some(*new_value),
+                _ => None,
+            };
+            if let Some(new_value) = set_value {
+                self.set_value(new_value);
+            }
+        }
+
         let value = self.get_value();
         response.widget_info(|| WidgetInfo::slider(value, &self.text));
 
         if response.has_focus() {
+            ui.memory().claim_arrow_keys(response.id);
             let (dec_key, inc_key) = match self.orientation {
                 SliderOrientation::Horizontal => (Key::ArrowLeft, Key::ArrowRight),
                 // Note that this is for moving the slider position,
@@ -347,6 +359,13 @@ impl<'a> Slider<'a> {
                 let prev_position = self.position_from_value(prev_value, position_range.clone());
                 let new_position = prev_position + kb_step;
                 let new_value = match self.step {
+                    // The right arrow key decreases the value of a right-to-left slider:
+                    Some(step)
+                        if mirrored
+                            && matches!(self.orientation, SliderOrientation::Horizontal) =>
+                    {
+                        prev_value - (kb_step as f64 * step)
+                    }
                     Some(step) => prev_value + (kb_step as f64 * step),
                     None if self.smart_aim => {
                         let aim_radius = ui.input().aim_radius();
@@ -413,9 +432,13 @@ impl<'a> Slider<'a> {
         }
     }
 
-    fn position_range(&self, rect: &Rect) -> RangeInclusive<f32> {
+    /// `mirrored`: for right-to-left uis, where horizontal sliders increase to the left.
+    fn position_range(&self, rect: &Rect, mirrored: bool) -> RangeInclusive<f32> {
         let handle_radius = self.handle_radius(rect);
         match self.orientation {
+            SliderOrientation::Horizontal if mirrored => {
+                (rect.right() - handle_radius)..=(rect.left() + handle_radius)
+            }
             SliderOrientation::Horizontal => {
                 (rect.left() + handle_radius)..=(rect.right() - handle_radius)
             }
@@ -503,7 +526,8 @@ impl<'a> Slider<'a> {
         self.slider_ui(ui, &response);
 
         if self.show_value {
-            let position_range = self.position_range(&response.rect);
+            // Not mirrored, so that dragging the value to the right increases it:
+            let position_range = self.position_range(&response.rect, false);
             let value_response = self.value_ui(ui, position_range);
             if value_response.gained_focus()
                 || value_response.has_focus()
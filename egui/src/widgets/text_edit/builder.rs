@@ -1,10 +1,16 @@
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 use epaint::text::{cursor::*, Galley, LayoutJob};
 
 use crate::{output::OutputEvent, *};
 
-use super::{CCursorRange, CursorRange, TextEditOutput, TextEditState};
+use super::{
+    code_editor, find_replace::find_replace_overlay, input_filter::InsertionFilter,
+    keymap::KeymapSource, merge_overlapping, CCursorRange, CursorRange, InputFilter,
+    TextEditAction, TextEditKeymap, TextEditOutput, TextEditState,
+};
+
+type Validator<'a> = Box<dyn 'a + Fn(&str) -> Result<(), String>>;
 
 /// A text region that the user can edit the contents of.
 ///
@@ -44,6 +50,15 @@ use super::{CCursorRange, CursorRange, TextEditOutput, TextEditState};
 /// }
 /// ```
 ///
+/// ## Multiple cursors
+/// Alt-click to add another cursor, and press ctrl-D (cmd-D on Mac) to select the word at the cursor,
+/// and then the next occurrence of it. Typing, deleting, pasting and moving applies to all cursors.
+/// See [`TextEditState::cursor_ranges`].
+///
+/// ## Key bindings
+/// The shortcuts for moving the cursor, deleting, undo, redo etc are in a [`super::TextEditKeymap`].
+/// Change them for all [`TextEdit`]s in the `text_edit_keymap` of [`Context::options`], or for one with [`TextEdit::keymap`].
+///
 /// ## Advanced usage
 /// See [`TextEdit::show`].
 ///
@@ -67,6 +82,16 @@ pub struct TextEdit<'t> {
     desired_height_rows: usize,
     lock_focus: bool,
     cursor_at_end: bool,
+    incremental_layout: bool,
+    find_replace: bool,
+    line_numbers: bool,
+    highlight_current_line: bool,
+    match_brackets: bool,
+    auto_indent: bool,
+    char_limit: usize,
+    input_filter: Option<Box<dyn InputFilter + 't>>,
+    validator: Option<Validator<'t>>,
+    keymap: Option<&'t TextEditKeymap>,
 }
 
 impl<'t> WidgetWithState for TextEdit<'t> {
@@ -112,6 +137,16 @@ impl<'t> TextEdit<'t> {
             desired_height_rows: 4,
             lock_focus: false,
             cursor_at_end: true,
+            incremental_layout: false,
+            find_replace: false,
+            line_numbers: false,
+            highlight_current_line: false,
+            match_brackets: false,
+            auto_indent: false,
+            char_limit: usize::MAX,
+            input_filter: None,
+            validator: None,
+            keymap: None,
         }
     }
 
@@ -119,6 +154,9 @@ impl<'t> TextEdit<'t> {
     /// By default it comes with:
     /// - monospaced font
     /// - focus lock
+    ///
+    /// You can opt in to [`Self::line_numbers`], [`Self::highlight_current_line`],
+    /// [`Self::match_brackets`] and [`Self::auto_indent`].
     pub fn code_editor(self) -> Self {
         self.font(TextStyle::Monospace).lock_focus(true)
     }
@@ -249,6 +287,131 @@ impl<'t> TextEdit<'t> {
         self.cursor_at_end = b;
         self
     }
+
+    /// When `true`, only lay out the paragraphs that are visible
+    /// (e.g. in the enclosing [`ScrollArea`]), and only lay out again the paragraphs that changed.
+    ///
+    /// Use this for editing huge texts, e.g. together with a `RopeBuffer` (with the `rope` feature).
+    /// The height of the paragraphs that are not visible is estimated.
+    /// See [`epaint::text::LayoutJob::visible_y_range`].
+    ///
+    /// The whole text is still hashed and copied each frame, so this is not free for huge texts
+    /// (see the limitations of `RopeBuffer`).
+    ///
+    /// This only affects the default layouter (see [`Self::layouter`]).
+    /// Default: `false`.
+    pub fn incremental_layout(mut self, incremental_layout: bool) -> Self {
+        self.incremental_layout = incremental_layout;
+        self
+    }
+
+    /// When `true`, pressing ctrl-F (cmd-F on Mac) opens a find/replace overlay
+    /// in the top right corner of the [`TextEdit`].
+    ///
+    /// All matches are highlighted, using the [`LayoutJob`] of the layouter (see [`Self::layouter`]).
+    /// Regular expressions require the `regex` feature.
+    /// The state of the overlay is in [`TextEditState::find_replace_mut`].
+    ///
+    /// Not available for [`Self::password`] fields.
+    /// Default: `false`.
+    pub fn find_replace(mut self, find_replace: bool) -> Self {
+        self.find_replace = find_replace;
+        self
+    }
+
+    /// Show line numbers in a gutter to the left of the text.
+    ///
+    /// Only the first row of a wrapped line gets a number.
+    /// Only for multiline text. Default: `false`.
+    pub fn line_numbers(mut self, line_numbers: bool) -> Self {
+        self.line_numbers = line_numbers;
+        self
+    }
+
+    /// Highlight the background of the line with the cursor, when the [`TextEdit`] has focus.
+    ///
+    /// Default: `false`.
+    pub fn highlight_current_line(mut self, highlight_current_line: bool) -> Self {
+        self.highlight_current_line = highlight_current_line;
+        self
+    }
+
+    /// When the cursor is next to a bracket (`()`, `[]` or `{}`),
+    /// outline it and the bracket it matches.
+    ///
+    /// Default: `false`.
+    pub fn match_brackets(mut self, match_brackets: bool) -> Self {
+        self.match_brackets = match_brackets;
+        self
+    }
+
+    /// Indent like a code editor:
+    /// * Enter keeps the indentation of the current line, and indents one more level after an opening bracket.
+    /// * Tab and shift-Tab indent and outdent all the lines of a selection (with [`Self::lock_focus`]).
+    ///
+    /// Default: `false`.
+    pub fn auto_indent(mut self, auto_indent: bool) -> Self {
+        self.auto_indent = auto_indent;
+        self
+    }
+
+    /// The user can't type or paste more than this many chars.
+    ///
+    /// This includes newlines, tabs, automatic indentation and text from an IME.
+    /// This doesn't truncate a longer text that is already there.
+    /// Default: no limit.
+    pub fn char_limit(mut self, char_limit: usize) -> Self {
+        self.char_limit = char_limit;
+        self
+    }
+
+    /// Filter what the user types or pastes (or enters with an IME), before it is inserted.
+    ///
+    /// For instance `.input_filter(|c: char| c.is_ascii_digit())` for a field that only accepts digits,
+    /// or an [`super::InputMask`] for a fixed format like `__/__/____`.
+    /// See [`InputFilter`].
+    pub fn input_filter(mut self, input_filter: impl InputFilter + 't) -> Self {
+        self.input_filter = Some(Box::new(input_filter));
+        self
+    }
+
+    /// Check the text every frame. If the validator returns an error,
+    /// the frame is painted red, and the error is shown when hovering the [`TextEdit`].
+    ///
+    /// The error is also returned in [`TextEditOutput::validation_error`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut address = String::new();
+    /// ui.add(
+    ///     egui::TextEdit::singleline(&mut address)
+    ///         .input_filter(|c: char| c.is_ascii_hexdigit() || c == '.' || c == ':')
+    ///         .validator(|text| {
+    ///             text.parse::<std::net::IpAddr>()
+    ///                 .map(|_| ())
+    ///                 .map_err(|_| "Not an IP address".to_owned())
+    ///         }),
+    /// );
+    /// # });
+    /// ```
+    pub fn validator(mut self, validator: impl Fn(&str) -> Result<(), String> + 't) -> Self {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+
+    /// Use these key bindings instead of the `text_edit_keymap` of [`Context::options`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut text = String::new();
+    /// let keymap = egui::text_edit::TextEditKeymap::default().with_emacs_bindings();
+    /// ui.add(egui::TextEdit::multiline(&mut text).keymap(&keymap));
+    /// # });
+    /// ```
+    pub fn keymap(mut self, keymap: &'t TextEditKeymap) -> Self {
+        self.keymap = Some(keymap);
+        self
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -294,6 +457,9 @@ impl<'t> TextEdit<'t> {
         if output.response.clicked() && !output.response.lost_focus() {
             ui.memory().request_focus(output.response.id);
         }
+        if let Some(validation_error) = &output.validation_error {
+            output.response = output.response.clone().on_hover_text(validation_error);
+        }
 
         if frame {
             let visuals = ui.style().interact(&output.response);
@@ -327,6 +493,14 @@ impl<'t> TextEdit<'t> {
                 }
             };
 
+            let shape = if output.validation_error.is_some() {
+                epaint::RectShape {
+                    stroke: Stroke::new(shape.stroke.width.at_least(1.0), Color32::RED),
+                    ..shape
+                }
+            } else {
+                shape
+            };
             ui.painter().set(where_to_put_background, shape);
         }
 
@@ -351,6 +525,16 @@ impl<'t> TextEdit<'t> {
             desired_height_rows,
             lock_focus,
             cursor_at_end,
+            incremental_layout,
+            find_replace,
+            line_numbers,
+            highlight_current_line,
+            match_brackets,
+            auto_indent,
+            char_limit,
+            input_filter,
+            validator,
+            keymap,
         } = self;
 
         let text_color = text_color
@@ -365,17 +549,37 @@ impl<'t> TextEdit<'t> {
         const MIN_WIDTH: f32 = 24.0; // Never make a [`TextEdit`] more narrow than this.
         let available_width = ui.available_width().at_least(MIN_WIDTH);
         let desired_width = desired_width.unwrap_or_else(|| ui.spacing().text_edit_width);
-        let wrap_width = if ui.layout().horizontal_justify() {
+        let show_gutter = line_numbers && multiline;
+        // The gutter must fit the number of the last line, which we only know after layout,
+        // so we start with the number of digits it needed last frame:
+        let gutter_digits_id = ui.auto_id_with("gutter_digits");
+        let mut gutter_digits = if show_gutter {
+            ui.data().get_temp(gutter_digits_id).unwrap_or(1)
+        } else {
+            0
+        };
+        let full_wrap_width = if ui.layout().horizontal_justify() {
             available_width
         } else {
             desired_width.min(available_width)
         };
 
+        // Lay out what is visible, and one screen above and below it for smooth scrolling:
+        let visible_y_range = (incremental_layout && multiline).then(|| {
+            let clip_rect = ui.clip_rect();
+            let top = ui.available_rect_before_wrap().top();
+            let margin = clip_rect.height();
+            (clip_rect.top() - top - margin)..=(clip_rect.bottom() - top + margin)
+        });
+
         let font_id_clone = font_id.clone();
         let mut default_layouter = move |ui: &Ui, text: &str, wrap_width: f32| {
             let text = mask_if_password(password, text);
             ui.fonts().layout_job(if multiline {
-                LayoutJob::simple(text, font_id_clone.clone(), text_color, wrap_width)
+                LayoutJob {
+                    visible_y_range: visible_y_range.clone(),
+                    ..LayoutJob::simple(text, font_id_clone.clone(), text_color, wrap_width)
+                }
             } else {
                 LayoutJob::simple_singleline(text, font_id_clone.clone(), text_color)
             })
@@ -383,7 +587,19 @@ impl<'t> TextEdit<'t> {
 
         let layouter = layouter.unwrap_or(&mut default_layouter);
 
+        let mut gutter_width = code_editor::gutter_width(ui, &font_id, gutter_digits);
+        let mut wrap_width = full_wrap_width - gutter_width;
         let mut galley = layouter(ui, text.as_ref(), wrap_width);
+        if show_gutter {
+            let digits = code_editor::num_digits(code_editor::num_lines(&galley));
+            if digits != gutter_digits {
+                gutter_digits = digits;
+                gutter_width = code_editor::gutter_width(ui, &font_id, gutter_digits);
+                wrap_width = full_wrap_width - gutter_width;
+                galley = layouter(ui, text.as_ref(), wrap_width);
+                ui.data().insert_temp(gutter_digits_id, gutter_digits);
+            }
+        }
 
         let desired_width = if multiline {
             galley.size().x.max(wrap_width) // always show everything in multiline
@@ -391,9 +607,18 @@ impl<'t> TextEdit<'t> {
             wrap_width // visual clipping with scroll in singleline input. TODO(emilk): opt-in/out?
         };
         let desired_height = (desired_height_rows.at_least(1) as f32) * row_height;
-        let desired_size = vec2(desired_width, galley.size().y.max(desired_height));
+        let desired_size = vec2(
+            gutter_width + desired_width,
+            galley.size().y.max(desired_height),
+        );
 
-        let (auto_id, rect) = ui.allocate_space(desired_size);
+        let (auto_id, outer_rect) = ui.allocate_space(desired_size);
+        // The line numbers are to the left of the text:
+        let gutter_rect = Rect::from_min_max(
+            outer_rect.min,
+            pos2(outer_rect.left() + gutter_width, outer_rect.bottom()),
+        );
+        let rect = Rect::from_min_max(gutter_rect.right_top(), outer_rect.max);
 
         let id = id.unwrap_or_else(|| {
             if let Some(id_source) = id_source {
@@ -420,7 +645,7 @@ impl<'t> TextEdit<'t> {
         } else {
             Sense::hover()
         };
-        let mut response = ui.interact(rect, id, sense);
+        let mut response = ui.interact(outer_rect, id, sense);
         let text_clip_rect = rect;
         let painter = ui.painter_at(text_clip_rect.expand(1.0)); // expand to avoid clipping cursor
 
@@ -433,7 +658,7 @@ impl<'t> TextEdit<'t> {
                 // TODO(emilk): drag selected text to either move or clone (ctrl on windows, alt on mac)
                 let singleline_offset = vec2(state.singleline_offset, 0.0);
                 let cursor_at_pointer =
-                    galley.cursor_from_pos(pointer_pos - response.rect.min + singleline_offset);
+                    galley.cursor_from_pos(pointer_pos - rect.min + singleline_offset);
 
                 if ui.visuals().text_cursor_preview
                     && response.hovered()
@@ -444,7 +669,7 @@ impl<'t> TextEdit<'t> {
                         ui,
                         row_height,
                         &painter,
-                        response.rect.min,
+                        rect.min,
                         &galley,
                         &cursor_at_pointer,
                     );
@@ -468,8 +693,15 @@ impl<'t> TextEdit<'t> {
                     }));
                 } else if allow_drag_to_select {
                     if response.hovered() && ui.input().pointer.any_pressed() {
+                        let had_focus = ui.memory().has_focus(id);
                         ui.memory().request_focus(id);
-                        if ui.input().modifiers.shift {
+                        let modifiers = ui.input().modifiers;
+                        if modifiers.alt && had_focus {
+                            // Add another cursor:
+                            let mut cursor_ranges = state.cursor_ranges(&*galley);
+                            cursor_ranges.push(CursorRange::one(cursor_at_pointer));
+                            state.set_cursor_ranges(cursor_ranges);
+                        } else if modifiers.shift {
                             if let Some(mut cursor_range) = state.cursor_range(&*galley) {
                                 cursor_range.primary = cursor_at_pointer;
                                 state.set_cursor_range(Some(cursor_range));
@@ -481,10 +713,11 @@ impl<'t> TextEdit<'t> {
                         }
                     } else if ui.input().pointer.any_down() && response.is_pointer_button_down_on()
                     {
-                        // drag to select text:
-                        if let Some(mut cursor_range) = state.cursor_range(&*galley) {
+                        // drag to select text with the primary cursor:
+                        let mut cursor_ranges = state.cursor_ranges(&*galley);
+                        if let Some(cursor_range) = cursor_ranges.last_mut() {
                             cursor_range.primary = cursor_at_pointer;
-                            state.set_cursor_range(Some(cursor_range));
+                            state.set_cursor_ranges(cursor_ranges);
                         }
                     }
                 }
@@ -495,10 +728,66 @@ impl<'t> TextEdit<'t> {
             ui.output().cursor_icon = CursorIcon::Text;
         }
 
-        let mut cursor_range = None;
-        let prev_cursor_range = state.cursor_range(&*galley);
+        if interactive && text.is_mutable() {
+            let new_text = match ui.input().accessibility_set_value(id) {
+                Some(crate::accessibility::ActionData::Text(new_text)) => Some(new_text.clone()),
+                _ => None,
+            };
+            if let Some(new_text) = new_text {
+                text.replace(&new_text);
+                galley = layouter(ui, text.as_ref(), wrap_width);
+                state.set_cursor_range(Some(CursorRange::one(galley.end())));
+                response.mark_changed();
+            }
+        }
+
+        let ctx = ui.ctx().clone();
+        let keymap = match keymap {
+            Some(keymap) => KeymapSource::Own(keymap),
+            None => KeymapSource::Options(&ctx),
+        };
+
+        let mut reveal_selection = false;
+        let highlighter = if find_replace && !password {
+            let output = find_replace_overlay(ui, id, rect, &mut state, text, keymap);
+            if output.text_changed {
+                response.mark_changed();
+            }
+            reveal_selection = output.reveal_selection;
+            state.find_replace.highlighter(ui.visuals())
+        } else {
+            None
+        };
+
+        // Highlight the matches of the find/replace overlay:
+        let mut highlighting_layouter = |ui: &Ui, text: &str, wrap_width: f32| {
+            let galley = layouter(ui, text, wrap_width);
+            match &highlighter {
+                Some(highlighter) => highlighter.highlight(ui, galley),
+                None => galley,
+            }
+        };
+        let layouter: &mut dyn FnMut(&Ui, &str, f32) -> Arc<Galley> = &mut highlighting_layouter;
+        if response.changed || highlighter.is_some() {
+            galley = layouter(ui, text.as_ref(), wrap_width);
+        }
+
+        if reveal_selection {
+            if let Some(cursor_range) = state.cursor_range(&*galley) {
+                let [min, max] = cursor_range.sorted_cursors();
+                let selection_rect = galley
+                    .pos_from_cursor(&min)
+                    .union(galley.pos_from_cursor(&max));
+                ui.scroll_to_rect(selection_rect.translate(rect.min.to_vec2()), None);
+            }
+        }
+
+        let mut cursor_ranges = None;
+        let prev_cursor_ranges = state.cursor_ranges(&*galley);
         if ui.memory().has_focus(id) && interactive {
             ui.memory().lock_focus(id, lock_focus);
+            ui.memory().claim_arrow_keys(id); // for moving the cursor
+            crate::label_selection::LabelSelectionState::text_edit_has_focus(ui.ctx());
 
             let default_cursor_range = if cursor_at_end {
                 CursorRange::one(galley.end())
@@ -506,7 +795,7 @@ impl<'t> TextEdit<'t> {
                 CursorRange::default()
             };
 
-            let (changed, new_cursor_range) = events(
+            let (changed, new_cursor_ranges) = events(
                 ui,
                 &mut state,
                 text,
@@ -516,16 +805,26 @@ impl<'t> TextEdit<'t> {
                 wrap_width,
                 multiline,
                 password,
+                auto_indent,
+                keymap,
+                &InsertionFilter {
+                    char_limit,
+                    input_filter: input_filter.as_deref(),
+                },
                 default_cursor_range,
             );
 
             if changed {
                 response.mark_changed();
             }
-            cursor_range = Some(new_cursor_range);
+            cursor_ranges = Some(new_cursor_ranges);
         }
+        // The primary cursor:
+        let cursor_range = cursor_ranges
+            .as_ref()
+            .and_then(|cursor_ranges| cursor_ranges.last().copied());
 
-        let mut text_draw_pos = response.rect.min;
+        let mut text_draw_pos = rect.min;
 
         // Visual clipping for singleline text editor with text larger than width
         if !multiline {
@@ -553,17 +852,65 @@ impl<'t> TextEdit<'t> {
             text_draw_pos -= vec2(offset_x, 0.0);
         }
 
-        let selection_changed = if let (Some(cursor_range), Some(prev_cursor_range)) =
-            (cursor_range, prev_cursor_range)
-        {
-            prev_cursor_range.as_ccursor_range() != cursor_range.as_ccursor_range()
+        let selection_changed = if let Some(cursor_ranges) = &cursor_ranges {
+            !prev_cursor_ranges.is_empty()
+                && prev_cursor_ranges
+                    .iter()
+                    .map(CursorRange::as_ccursor_range)
+                    .ne(cursor_ranges.iter().map(CursorRange::as_ccursor_range))
         } else {
             false
         };
 
         if ui.is_rect_visible(rect) {
+            let focused_cursor_range = if ui.memory().has_focus(id) {
+                state.cursor_range(&*galley)
+            } else {
+                None
+            };
+
+            if highlight_current_line && multiline {
+                if let Some(cursor_range) = &focused_cursor_range {
+                    code_editor::paint_current_line(
+                        ui,
+                        &painter,
+                        rect,
+                        text_draw_pos,
+                        &galley,
+                        &cursor_range.primary,
+                    );
+                }
+            }
+
             painter.galley(text_draw_pos, galley.clone());
 
+            if match_brackets {
+                if let Some(cursor_range) = &focused_cursor_range {
+                    code_editor::paint_matching_brackets(
+                        ui,
+                        &painter,
+                        text_draw_pos,
+                        &galley,
+                        text,
+                        cursor_range.primary.ccursor,
+                    );
+                }
+            }
+
+            if gutter_width > 0.0 {
+                let current_line_row = focused_cursor_range.map(|cursor_range| {
+                    code_editor::line_rows(&galley, cursor_range.primary.rcursor.row).start
+                });
+                code_editor::paint_line_numbers(
+                    ui,
+                    gutter_rect,
+                    text_draw_pos,
+                    &galley,
+                    &font_id,
+                    current_line_row,
+                );
+            }
+
             if text.as_ref().is_empty() && !hint_text.is_empty() {
                 let hint_text_color = ui.visuals().weak_text_color();
                 let galley = if multiline {
@@ -571,23 +918,36 @@ impl<'t> TextEdit<'t> {
                 } else {
                     hint_text.into_galley(ui, Some(false), f32::INFINITY, font_id)
                 };
-                galley.paint_with_fallback_color(&painter, response.rect.min, hint_text_color);
+                galley.paint_with_fallback_color(&painter, rect.min, hint_text_color);
             }
 
             if ui.memory().has_focus(id) {
-                if let Some(cursor_range) = state.cursor_range(&*galley) {
+                let cursor_ranges = state.cursor_ranges(&*galley);
+                if let Some((primary, others)) = cursor_ranges.split_last() {
                     // We paint the cursor on top of the text, in case
                     // the text galley has backgrounds (as e.g. `code` snippets in markup do).
-                    paint_cursor_selection(ui, &painter, text_draw_pos, &galley, &cursor_range);
+                    for cursor_range in &cursor_ranges {
+                        paint_cursor_selection(ui, &painter, text_draw_pos, &galley, cursor_range);
+                    }
 
                     if text.is_mutable() {
+                        for cursor_range in others {
+                            paint_cursor_end(
+                                ui,
+                                row_height,
+                                &painter,
+                                text_draw_pos,
+                                &galley,
+                                &cursor_range.primary,
+                            );
+                        }
                         let cursor_pos = paint_cursor_end(
                             ui,
                             row_height,
                             &painter,
                             text_draw_pos,
                             &galley,
-                            &cursor_range.primary,
+                            &primary.primary,
                         );
 
                         if response.changed || selection_changed {
@@ -635,6 +995,8 @@ impl<'t> TextEdit<'t> {
             });
         }
 
+        let validation_error = validator.and_then(|validator| validator(text.as_str()).err());
+
         TextEditOutput {
             response,
             galley,
@@ -642,6 +1004,7 @@ impl<'t> TextEdit<'t> {
             text_clip_rect,
             state,
             cursor_range,
+            validation_error,
         }
     }
 }
@@ -662,7 +1025,7 @@ fn mask_if_password(is_password: bool, text: &str) -> String {
 
 // ----------------------------------------------------------------------------
 
-/// Check for (keyboard) events to edit the cursor and/or text.
+/// Check for (keyboard) events to edit the cursors and/or text.
 #[allow(clippy::too_many_arguments)]
 fn events(
     ui: &mut crate::Ui,
@@ -674,15 +1037,21 @@ fn events(
     wrap_width: f32,
     multiline: bool,
     password: bool,
+    auto_indent: bool,
+    keymap: KeymapSource<'_>,
+    insertion_filter: &InsertionFilter<'_>,
     default_cursor_range: CursorRange,
-) -> (bool, CursorRange) {
-    let mut cursor_range = state.cursor_range(&*galley).unwrap_or(default_cursor_range);
+) -> (bool, Vec<CursorRange>) {
+    let mut cursor_ranges = state.cursor_ranges(&*galley);
+    if cursor_ranges.is_empty() {
+        cursor_ranges.push(default_cursor_range);
+    }
 
     // We feed state to the undoer both before and after handling input
     // so that the undoer creates automatic saves even when there are no events for a while.
     state.undoer.lock().feed_state(
         ui.input().time,
-        &(cursor_range.as_ccursor_range(), text.as_ref().to_owned()),
+        &(as_ccursor_ranges(&cursor_ranges), text.as_ref().to_owned()),
     );
 
     let copy_if_not_password = |ui: &Ui, text: String| {
@@ -697,27 +1066,55 @@ fn events(
     for event in &events {
         let did_mutate_text = match event {
             Event::Copy => {
-                if cursor_range.is_empty() {
+                if cursor_ranges.iter().all(CursorRange::is_empty) {
                     copy_if_not_password(ui, text.as_ref().to_owned());
                 } else {
-                    copy_if_not_password(ui, selected_str(text, &cursor_range).to_owned());
+                    copy_if_not_password(ui, selected_text(text, &cursor_ranges));
                 }
                 None
             }
             Event::Cut => {
-                if cursor_range.is_empty() {
+                if cursor_ranges.iter().all(CursorRange::is_empty) {
                     copy_if_not_password(ui, text.take());
-                    Some(CCursorRange::default())
+                    Some(vec![CCursorRange::default()])
                 } else {
-                    copy_if_not_password(ui, selected_str(text, &cursor_range).to_owned());
-                    Some(CCursorRange::one(delete_selected(text, &cursor_range)))
+                    copy_if_not_password(ui, selected_text(text, &cursor_ranges));
+                    edit_each_cursor(
+                        ui,
+                        &mut cursor_ranges,
+                        text,
+                        galley,
+                        layouter,
+                        wrap_width,
+                        |_, cursor_range, text, _| {
+                            Some(CCursorRange::one(delete_selected(text, cursor_range)))
+                        },
+                    )
                 }
             }
             Event::Paste(text_to_insert) => {
                 if !text_to_insert.is_empty() {
-                    let mut ccursor = delete_selected(text, &cursor_range);
-                    insert_text(&mut ccursor, text, text_to_insert);
-                    Some(CCursorRange::one(ccursor))
+                    // If there is one line per cursor (e.g. when they were cut with the same cursors),
+                    // paste one line at each cursor:
+                    let lines: Vec<&str> = text_to_insert.split('\n').collect();
+                    let one_line_per_cursor =
+                        cursor_ranges.len() > 1 && lines.len() == cursor_ranges.len();
+                    edit_each_cursor(
+                        ui,
+                        &mut cursor_ranges,
+                        text,
+                        galley,
+                        layouter,
+                        wrap_width,
+                        |index, cursor_range, text, _| {
+                            let text_to_insert = if one_line_per_cursor {
+                                lines[index]
+                            } else {
+                                text_to_insert
+                            };
+                            insert_filtered(insertion_filter, cursor_range, text, text_to_insert)
+                        },
+                    )
                 } else {
                     None
                 }
@@ -725,9 +1122,17 @@ fn events(
             Event::Text(text_to_insert) => {
                 // Newlines are handled by `Key::Enter`.
                 if !text_to_insert.is_empty() && text_to_insert != "\n" && text_to_insert != "\r" {
-                    let mut ccursor = delete_selected(text, &cursor_range);
-                    insert_text(&mut ccursor, text, text_to_insert);
-                    Some(CCursorRange::one(ccursor))
+                    edit_each_cursor(
+                        ui,
+                        &mut cursor_ranges,
+                        text,
+                        galley,
+                        layouter,
+                        wrap_width,
+                        |_, cursor_range, text, _| {
+                            insert_filtered(insertion_filter, cursor_range, text, text_to_insert)
+                        },
+                    )
                 } else {
                     None
                 }
@@ -738,14 +1143,43 @@ fn events(
                 modifiers,
             } => {
                 if multiline && ui.memory().has_lock_focus(id) {
-                    let mut ccursor = delete_selected(text, &cursor_range);
-                    if modifiers.shift {
-                        // TODO(emilk): support removing indentation over a selection?
-                        decrease_identation(&mut ccursor, text);
-                    } else {
-                        insert_text(&mut ccursor, text, "\t");
-                    }
-                    Some(CCursorRange::one(ccursor))
+                    edit_each_cursor(
+                        ui,
+                        &mut cursor_ranges,
+                        text,
+                        galley,
+                        layouter,
+                        wrap_width,
+                        |_, cursor_range, text, _| {
+                            if auto_indent
+                                && (modifiers.shift
+                                    || selected_str(text, cursor_range).contains('\n'))
+                            {
+                                let room = insertion_filter.room(text.as_str(), 0..0);
+                                return code_editor::indent_lines(
+                                    text,
+                                    cursor_range,
+                                    modifiers.shift,
+                                    room,
+                                );
+                            }
+
+                            let selection = cursor_range.as_sorted_char_range();
+                            if !modifiers.shift
+                                && insertion_filter.room(text.as_str(), selection) == 0
+                            {
+                                return None;
+                            }
+                            let mut ccursor = delete_selected(text, cursor_range);
+                            if modifiers.shift {
+                                // TODO(emilk): support removing indentation over a selection?
+                                decrease_identation(&mut ccursor, text);
+                            } else {
+                                insert_text(&mut ccursor, text, "\t");
+                            }
+                            Some(CCursorRange::one(ccursor))
+                        },
+                    )
                 } else {
                     None
                 }
@@ -756,38 +1190,75 @@ fn events(
                 ..
             } => {
                 if multiline {
-                    let mut ccursor = delete_selected(text, &cursor_range);
-                    insert_text(&mut ccursor, text, "\n");
-                    // TODO(emilk): if code editor, auto-indent by same leading tabs, + one if the lines end on an opening bracket
-                    Some(CCursorRange::one(ccursor))
+                    edit_each_cursor(
+                        ui,
+                        &mut cursor_ranges,
+                        text,
+                        galley,
+                        layouter,
+                        wrap_width,
+                        |_, cursor_range, text, _| {
+                            let room = insertion_filter
+                                .room(text.as_str(), cursor_range.as_sorted_char_range());
+                            if room == 0 {
+                                return None;
+                            }
+                            let mut ccursor = delete_selected(text, cursor_range);
+                            if auto_indent {
+                                code_editor::insert_newline_with_indentation(
+                                    &mut ccursor,
+                                    text,
+                                    room,
+                                );
+                            } else {
+                                insert_text(&mut ccursor, text, "\n");
+                            }
+                            Some(CCursorRange::one(ccursor))
+                        },
+                    )
                 } else {
                     ui.memory().surrender_focus(id); // End input with enter
                     break;
                 }
             }
             Event::Key {
-                key: Key::Z,
+                key,
                 pressed: true,
                 modifiers,
-            } if modifiers.command && !modifiers.shift => {
-                // TODO(emilk): redo
-                if let Some((undo_ccursor_range, undo_txt)) = state
-                    .undoer
-                    .lock()
-                    .undo(&(cursor_range.as_ccursor_range(), text.as_ref().to_owned()))
-                {
-                    text.replace(undo_txt);
-                    Some(*undo_ccursor_range)
-                } else {
+            } => match keymap.action(*modifiers, *key) {
+                Some(action @ (TextEditAction::Undo | TextEditAction::Redo)) => {
+                    let current_state =
+                        (as_ccursor_ranges(&cursor_ranges), text.as_ref().to_owned());
+                    let mut undoer = state.undoer.lock();
+                    let new_state = if action == TextEditAction::Undo {
+                        undoer.undo(&current_state)
+                    } else {
+                        undoer.redo(&current_state)
+                    };
+                    if let Some((new_ccursor_ranges, new_text)) = new_state {
+                        text.replace(new_text);
+                        Some(new_ccursor_ranges.clone())
+                    } else {
+                        None
+                    }
+                }
+                Some(TextEditAction::SelectNextOccurrence) => {
+                    select_next_occurrence(&mut cursor_ranges, text, galley);
                     None
                 }
-            }
-
-            Event::Key {
-                key,
-                pressed: true,
-                modifiers,
-            } => on_key_press(&mut cursor_range, text, galley, *key, modifiers),
+                Some(action) => edit_each_cursor(
+                    ui,
+                    &mut cursor_ranges,
+                    text,
+                    galley,
+                    layouter,
+                    wrap_width,
+                    |_, cursor_range, text, galley| {
+                        on_action(cursor_range, text, galley, action, modifiers.shift)
+                    },
+                ),
+                None => None,
+            },
 
             Event::CompositionStart => {
                 state.has_ime = true;
@@ -797,10 +1268,27 @@ fn events(
             Event::CompositionUpdate(text_mark) => {
                 if !text_mark.is_empty() && text_mark != "\n" && text_mark != "\r" && state.has_ime
                 {
-                    let mut ccursor = delete_selected(text, &cursor_range);
-                    let start_cursor = ccursor;
-                    insert_text(&mut ccursor, text, text_mark);
-                    Some(CCursorRange::two(start_cursor, ccursor))
+                    edit_each_cursor(
+                        ui,
+                        &mut cursor_ranges,
+                        text,
+                        galley,
+                        layouter,
+                        wrap_width,
+                        |_, cursor_range, text, _| {
+                            // The preview is not filtered, as it is not the final text,
+                            // but it should not grow the text past the char limit:
+                            let text_mark = insertion_filter.limit(
+                                text.as_str(),
+                                cursor_range.as_sorted_char_range(),
+                                Cow::Borrowed(text_mark.as_str()),
+                            );
+                            let mut ccursor = delete_selected(text, cursor_range);
+                            let start_cursor = ccursor;
+                            insert_text(&mut ccursor, text, &text_mark);
+                            Some(CCursorRange::two(start_cursor, ccursor))
+                        },
+                    )
                 } else {
                     None
                 }
@@ -813,9 +1301,21 @@ fn events(
                     && state.has_ime
                 {
                     state.has_ime = false;
-                    let mut ccursor = delete_selected(text, &cursor_range);
-                    insert_text(&mut ccursor, text, prediction);
-                    Some(CCursorRange::one(ccursor))
+                    edit_each_cursor(
+                        ui,
+                        &mut cursor_ranges,
+                        text,
+                        galley,
+                        layouter,
+                        wrap_width,
+                        |_, cursor_range, text, _| {
+                            // Replaces the selected preview of the composition:
+                            insert_filtered(insertion_filter, cursor_range, text, prediction)
+                                .or_else(|| {
+                                    Some(CCursorRange::one(delete_selected(text, cursor_range)))
+                                })
+                        },
+                    )
                 } else {
                     None
                 }
@@ -824,34 +1324,150 @@ fn events(
             _ => None,
         };
 
-        if let Some(new_ccursor_range) = did_mutate_text {
+        if let Some(new_ccursor_ranges) = did_mutate_text {
             any_change = true;
 
             // Layout again to avoid frame delay, and to keep `text` and `galley` in sync.
             *galley = layouter(ui, text.as_ref(), wrap_width);
 
-            // Set cursor_range using new galley:
-            cursor_range = CursorRange {
-                primary: galley.from_ccursor(new_ccursor_range.primary),
-                secondary: galley.from_ccursor(new_ccursor_range.secondary),
-            };
+            // Set cursor_ranges using new galley:
+            cursor_ranges = new_ccursor_ranges
+                .iter()
+                .map(|ccursor_range| CursorRange {
+                    primary: galley.from_ccursor(ccursor_range.primary),
+                    secondary: galley.from_ccursor(ccursor_range.secondary),
+                })
+                .collect();
         }
+        cursor_ranges = merge_overlapping(cursor_ranges);
     }
 
-    state.set_cursor_range(Some(cursor_range));
+    state.set_cursor_ranges(cursor_ranges.clone());
 
     state.undoer.lock().feed_state(
         ui.input().time,
-        &(cursor_range.as_ccursor_range(), text.as_ref().to_owned()),
+        &(as_ccursor_ranges(&cursor_ranges), text.as_ref().to_owned()),
     );
 
-    (any_change, cursor_range)
+    (any_change, cursor_ranges)
+}
+
+fn as_ccursor_ranges(cursor_ranges: &[CursorRange]) -> Vec<CCursorRange> {
+    cursor_ranges
+        .iter()
+        .map(CursorRange::as_ccursor_range)
+        .collect()
+}
+
+/// Calls `edit` for each cursor, and returns the new cursors if it mutated the text.
+///
+/// `edit` is given the index of the cursor in text order, and returns `Some(new_cursor)`
+/// if it mutated the text (like [`on_action`]).
+///
+/// The cursors are edited from the last one in the text to the first one,
+/// so that editing the text doesn't move the cursors that are yet to be edited.
+fn edit_each_cursor(
+    ui: &Ui,
+    cursor_ranges: &mut [CursorRange],
+    text: &mut dyn TextBuffer,
+    galley: &mut Arc<Galley>,
+    layouter: &mut dyn FnMut(&Ui, &str, f32) -> Arc<Galley>,
+    wrap_width: f32,
+    mut edit: impl FnMut(usize, &mut CursorRange, &mut dyn TextBuffer, &Galley) -> Option<CCursorRange>,
+) -> Option<Vec<CCursorRange>> {
+    let mut order: Vec<usize> = (0..cursor_ranges.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(cursor_ranges[i].sorted_cursors()[0].ccursor.index));
+
+    let mut ccursor_ranges = as_ccursor_ranges(cursor_ranges);
+    let mut did_mutate_text = false;
+
+    for (num_edited, &i) in order.iter().enumerate() {
+        let index_in_text = order.len() - 1 - num_edited;
+        let num_chars_before = galley.end().ccursor.index;
+        if let Some(new_ccursor_range) = edit(index_in_text, &mut cursor_ranges[i], text, galley) {
+            did_mutate_text = true;
+
+            // Keep the galley in sync with the text for the next cursor:
+            *galley = layouter(ui, text.as_ref(), wrap_width);
+            let num_chars_after = galley.end().ccursor.index;
+
+            // Move the cursors we already edited along with the text after this cursor:
+            let [_, new_max] = new_ccursor_range.sorted();
+            for &j in &order[..num_edited] {
+                let ccursor_range = &mut ccursor_ranges[j];
+                for ccursor in [&mut ccursor_range.primary, &mut ccursor_range.secondary] {
+                    ccursor.index = (ccursor.index + num_chars_after)
+                        .saturating_sub(num_chars_before)
+                        .max(new_max.index);
+                }
+            }
+            ccursor_ranges[i] = new_ccursor_range;
+        } else {
+            ccursor_ranges[i] = cursor_ranges[i].as_ccursor_range();
+        }
+    }
+
+    did_mutate_text.then(|| ccursor_ranges)
+}
+
+/// Selects the word at the primary cursor if nothing is selected,
+/// else adds a cursor selecting the next occurrence of the selected text.
+fn select_next_occurrence(
+    cursor_ranges: &mut Vec<CursorRange>,
+    text: &dyn TextBuffer,
+    galley: &Galley,
+) {
+    let primary = match cursor_ranges.last_mut() {
+        Some(primary) => primary,
+        None => return,
+    };
+
+    if primary.is_empty() {
+        let ccursor_range = select_word_at(text.as_ref(), primary.primary.ccursor);
+        *primary = CursorRange {
+            primary: galley.from_ccursor(ccursor_range.primary),
+            secondary: galley.from_ccursor(ccursor_range.secondary),
+        };
+        return;
+    }
+
+    let needle = selected_str(text, primary);
+    let needle_len = needle.chars().count();
+    let haystack = text.as_ref();
+    let start = text.byte_index_from_char_index(primary.as_sorted_char_range().end);
+
+    // Search from the primary cursor, wrapping around to the start of the text:
+    let next_occurrence = haystack[start..]
+        .match_indices(needle)
+        .map(|(byte_index, _)| start + byte_index)
+        .chain(
+            haystack
+                .match_indices(needle)
+                .map(|(byte_index, _)| byte_index)
+                .take_while(|&byte_index| byte_index < start),
+        )
+        .map(|byte_index| {
+            let min = haystack[..byte_index].chars().count();
+            min..min + needle_len
+        })
+        .find(|char_range| {
+            !cursor_ranges
+                .iter()
+                .any(|cursor_range| cursor_range.as_sorted_char_range() == *char_range)
+        });
+
+    if let Some(char_range) = next_occurrence {
+        cursor_ranges.push(CursorRange::two(
+            galley.from_ccursor(CCursor::new(char_range.start)),
+            galley.from_ccursor(CCursor::new(char_range.end)),
+        ));
+    }
 }
 
 // ----------------------------------------------------------------------------
 
-fn paint_cursor_selection(
-    ui: &mut Ui,
+pub(crate) fn paint_cursor_selection(
+    ui: &Ui,
     painter: &Painter,
     pos: Pos2,
     galley: &Galley,
@@ -869,26 +1485,28 @@ fn paint_cursor_selection(
 
     for ri in min.row..=max.row {
         let row = &galley.rows[ri];
-        let left = if ri == min.row {
-            row.x_offset(min.column)
+        let first_column = if ri == min.row { min.column } else { 0 };
+        let last_column = if ri == max.row {
+            max.column
         } else {
-            row.rect.left()
+            row.char_count_excluding_newline()
         };
-        let right = if ri == max.row {
-            row.x_offset(max.column)
-        } else {
-            let newline_size = if row.ends_with_newline {
-                row.height() / 2.0 // visualize that we select the newline
-            } else {
-                0.0
-            };
-            row.rect.right() + newline_size
-        };
-        let rect = Rect::from_min_max(
-            pos + vec2(left, row.min_y()),
-            pos + vec2(right, row.max_y()),
-        );
-        painter.rect_filled(rect, 0.0, color);
+
+        // Bidirectional text can make the selection non-contiguous:
+        let mut x_ranges = row.x_ranges(first_column..last_column);
+        if ri != max.row && row.ends_with_newline {
+            // visualize that we select the newline
+            let newline_size = row.height() / 2.0;
+            x_ranges.push(row.rect.right()..=row.rect.right() + newline_size);
+        }
+
+        for x_range in x_ranges {
+            let rect = Rect::from_min_max(
+                pos + vec2(*x_range.start(), row.min_y()),
+                pos + vec2(*x_range.end(), row.max_y()),
+            );
+            painter.rect_filled(rect, 0.0, color);
+        }
     }
 }
 
@@ -938,10 +1556,46 @@ fn selected_str<'s>(text: &'s dyn TextBuffer, cursor_range: &CursorRange) -> &'s
     text.char_range(min.ccursor.index..max.ccursor.index)
 }
 
+/// The selected text of all cursors, in text order, one selection per line.
+fn selected_text(text: &dyn TextBuffer, cursor_ranges: &[CursorRange]) -> String {
+    let mut cursor_ranges: Vec<&CursorRange> = cursor_ranges
+        .iter()
+        .filter(|cursor_range| !cursor_range.is_empty())
+        .collect();
+    cursor_ranges.sort_by_key(|cursor_range| cursor_range.as_sorted_char_range().start);
+    cursor_ranges
+        .into_iter()
+        .map(|cursor_range| selected_str(text, cursor_range))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 fn insert_text(ccursor: &mut CCursor, text: &mut dyn TextBuffer, text_to_insert: &str) {
     ccursor.index += text.insert_text(text_to_insert, ccursor.index);
 }
 
+/// Replaces the selection with what the filter lets through of `text_to_insert`.
+///
+/// Does nothing if the filter lets nothing through.
+fn insert_filtered(
+    insertion_filter: &InsertionFilter<'_>,
+    cursor_range: &CursorRange,
+    text: &mut dyn TextBuffer,
+    text_to_insert: &str,
+) -> Option<CCursorRange> {
+    let text_to_insert = insertion_filter.filter(
+        text.as_str(),
+        cursor_range.as_sorted_char_range(),
+        text_to_insert,
+    );
+    if text_to_insert.is_empty() {
+        return None;
+    }
+    let mut ccursor = delete_selected(text, cursor_range);
+    insert_text(&mut ccursor, text, &text_to_insert);
+    Some(CCursorRange::one(ccursor))
+}
+
 // ----------------------------------------------------------------------------
 
 fn delete_selected(text: &mut dyn TextBuffer, cursor_range: &CursorRange) -> CCursor {
@@ -957,18 +1611,21 @@ fn delete_selected_ccursor_range(text: &mut dyn TextBuffer, [min, max]: [CCursor
     }
 }
 
-fn delete_previous_char(text: &mut dyn TextBuffer, ccursor: CCursor) -> CCursor {
+/// Deletes the grapheme cluster (e.g. a letter with its accents) before the cursor.
+fn delete_previous_char(text: &mut dyn TextBuffer, galley: &Galley, ccursor: CCursor) -> CCursor {
     if ccursor.index > 0 {
         let max_ccursor = ccursor;
-        let min_ccursor = max_ccursor - 1;
+        let min_ccursor = galley.previous_grapheme_boundary(max_ccursor);
         delete_selected_ccursor_range(text, [min_ccursor, max_ccursor])
     } else {
         ccursor
     }
 }
 
-fn delete_next_char(text: &mut dyn TextBuffer, ccursor: CCursor) -> CCursor {
-    delete_selected_ccursor_range(text, [ccursor, ccursor + 1])
+/// Deletes the grapheme cluster (e.g. a letter with its accents) after the cursor.
+fn delete_next_char(text: &mut dyn TextBuffer, galley: &Galley, ccursor: CCursor) -> CCursor {
+    let max_ccursor = galley.next_grapheme_boundary(ccursor);
+    delete_selected_ccursor_range(text, [ccursor, max_ccursor])
 }
 
 fn delete_previous_word(text: &mut dyn TextBuffer, max_ccursor: CCursor) -> CCursor {
@@ -993,7 +1650,7 @@ fn delete_paragraph_before_cursor(
         prefer_next_row: true,
     });
     if min.ccursor == max.ccursor {
-        delete_previous_char(text, min.ccursor)
+        delete_previous_char(text, galley, min.ccursor)
     } else {
         delete_selected(text, &CursorRange::two(min, max))
     }
@@ -1011,7 +1668,7 @@ fn delete_paragraph_after_cursor(
         prefer_next_row: false,
     });
     if min.ccursor == max.ccursor {
-        delete_next_char(text, min.ccursor)
+        delete_next_char(text, galley, min.ccursor)
     } else {
         delete_selected(text, &CursorRange::two(min, max))
     }
@@ -1019,179 +1676,152 @@ fn delete_paragraph_after_cursor(
 
 // ----------------------------------------------------------------------------
 
+/// Swaps the chars before and after the cursor (or the two chars before it at the end of a line),
+/// and returns the cursor after them, like ctrl-T in Emacs.
+fn transpose_chars(
+    text: &mut dyn TextBuffer,
+    galley: &Galley,
+    ccursor: CCursor,
+) -> Option<CCursor> {
+    let index = ccursor.index;
+    let at_line_end =
+        index == galley.end().ccursor.index || text.char_range(index..index + 1) == "\n";
+    let end = if at_line_end { index } else { index + 1 };
+    if end < 2 {
+        return None;
+    }
+    let swapped: String = text.char_range(end - 2..end).chars().rev().collect();
+    if swapped.contains('\n') {
+        return None;
+    }
+    text.delete_char_range(end - 2..end);
+    text.insert_text(&swapped, end - 2);
+    Some(CCursor::new(end))
+}
+
+// ----------------------------------------------------------------------------
+
+/// Performs an action of the [`TextEditKeymap`] on one cursor,
+/// except the ones handled by [`events`] for all cursors at once (undo etc).
+///
+/// `select` is true when shift is held down, so that moving the cursor extends the selection.
+///
 /// Returns `Some(new_cursor)` if we did mutate `text`.
-fn on_key_press(
+fn on_action(
     cursor_range: &mut CursorRange,
     text: &mut dyn TextBuffer,
     galley: &Galley,
-    key: Key,
-    modifiers: &Modifiers,
+    action: TextEditAction,
+    select: bool,
 ) -> Option<CCursorRange> {
-    match key {
-        Key::Backspace => {
-            let ccursor = if modifiers.mac_cmd {
-                delete_paragraph_before_cursor(text, galley, cursor_range)
-            } else if let Some(cursor) = cursor_range.single() {
-                if modifiers.alt || modifiers.ctrl {
-                    // alt on mac, ctrl on windows
-                    delete_previous_word(text, cursor.ccursor)
-                } else {
-                    delete_previous_char(text, cursor.ccursor)
-                }
-            } else {
-                delete_selected(text, cursor_range)
-            };
-            Some(CCursorRange::one(ccursor))
-        }
-        Key::Delete if !modifiers.shift || !cfg!(target_os = "windows") => {
-            let ccursor = if modifiers.mac_cmd {
-                delete_paragraph_after_cursor(text, galley, cursor_range)
-            } else if let Some(cursor) = cursor_range.single() {
-                if modifiers.alt || modifiers.ctrl {
-                    // alt on mac, ctrl on windows
-                    delete_next_word(text, cursor.ccursor)
-                } else {
-                    delete_next_char(text, cursor.ccursor)
-                }
+    let ccursor = match action {
+        TextEditAction::MoveLeft | TextEditAction::MoveRight
+            if !select && !cursor_range.is_empty() =>
+        {
+            // Collapse the selection:
+            let [min, max] = cursor_range.sorted_cursors();
+            *cursor_range = CursorRange::one(if action == TextEditAction::MoveLeft {
+                min
             } else {
-                delete_selected(text, cursor_range)
-            };
-            let ccursor = CCursor {
-                prefer_next_row: true,
-                ..ccursor
-            };
-            Some(CCursorRange::one(ccursor))
-        }
-
-        Key::A if modifiers.command => {
-            // select all
-            *cursor_range = CursorRange::two(Cursor::default(), galley.end());
-            None
+                max
+            });
+            return None;
         }
 
-        Key::K if modifiers.ctrl => {
-            let ccursor = delete_paragraph_after_cursor(text, galley, cursor_range);
-            Some(CCursorRange::one(ccursor))
+        TextEditAction::MoveLeft
+        | TextEditAction::MoveRight
+        | TextEditAction::MoveUp
+        | TextEditAction::MoveDown
+        | TextEditAction::MoveWordLeft
+        | TextEditAction::MoveWordRight
+        | TextEditAction::MoveToLineStart
+        | TextEditAction::MoveToLineEnd
+        | TextEditAction::MoveToTextStart
+        | TextEditAction::MoveToTextEnd => {
+            move_single_cursor(&mut cursor_range.primary, galley, action);
+            if !select {
+                cursor_range.secondary = cursor_range.primary;
+            }
+            return None;
         }
 
-        Key::U if modifiers.ctrl => {
-            let ccursor = delete_paragraph_before_cursor(text, galley, cursor_range);
-            Some(CCursorRange::one(ccursor))
+        TextEditAction::SelectAll => {
+            *cursor_range = CursorRange::two(Cursor::default(), galley.end());
+            return None;
         }
 
-        Key::W if modifiers.ctrl => {
-            let ccursor = if let Some(cursor) = cursor_range.single() {
-                delete_previous_word(text, cursor.ccursor)
-            } else {
-                delete_selected(text, cursor_range)
+        TextEditAction::DeletePreviousChar
+        | TextEditAction::DeletePreviousWord
+        | TextEditAction::DeleteNextChar
+        | TextEditAction::DeleteNextWord => {
+            let ccursor = match cursor_range.single() {
+                Some(cursor) => match action {
+                    TextEditAction::DeletePreviousChar => {
+                        delete_previous_char(text, galley, cursor.ccursor)
+                    }
+                    TextEditAction::DeletePreviousWord => {
+                        delete_previous_word(text, cursor.ccursor)
+                    }
+                    TextEditAction::DeleteNextChar => {
+                        delete_next_char(text, galley, cursor.ccursor)
+                    }
+                    _ => delete_next_word(text, cursor.ccursor),
+                },
+                None => delete_selected(text, cursor_range),
             };
-            Some(CCursorRange::one(ccursor))
-        }
-
-        Key::ArrowLeft | Key::ArrowRight if modifiers.is_none() && !cursor_range.is_empty() => {
-            if key == Key::ArrowLeft {
-                *cursor_range = CursorRange::one(cursor_range.sorted_cursors()[0]);
+            if matches!(
+                action,
+                TextEditAction::DeleteNextChar | TextEditAction::DeleteNextWord
+            ) {
+                CCursor {
+                    prefer_next_row: true,
+                    ..ccursor
+                }
             } else {
-                *cursor_range = CursorRange::one(cursor_range.sorted_cursors()[1]);
+                ccursor
             }
-            None
         }
 
-        Key::ArrowLeft | Key::ArrowRight | Key::ArrowUp | Key::ArrowDown | Key::Home | Key::End => {
-            move_single_cursor(&mut cursor_range.primary, galley, key, modifiers);
-            if !modifiers.shift {
-                cursor_range.secondary = cursor_range.primary;
-            }
-            None
+        TextEditAction::DeleteToLineStart => {
+            delete_paragraph_before_cursor(text, galley, cursor_range)
         }
-
-        Key::P | Key::N | Key::B | Key::F | Key::A | Key::E
-            if cfg!(target_os = "macos") && modifiers.ctrl && !modifiers.shift =>
-        {
-            move_single_cursor(&mut cursor_range.primary, galley, key, modifiers);
-            cursor_range.secondary = cursor_range.primary;
-            None
+        TextEditAction::DeleteToLineEnd => {
+            delete_paragraph_after_cursor(text, galley, cursor_range)
         }
 
-        _ => None,
-    }
+        TextEditAction::Transpose => transpose_chars(text, galley, cursor_range.single()?.ccursor)?,
+
+        TextEditAction::Undo
+        | TextEditAction::Redo
+        | TextEditAction::SelectNextOccurrence
+        | TextEditAction::Find => return None,
+    };
+    Some(CCursorRange::one(ccursor))
 }
 
-fn move_single_cursor(cursor: &mut Cursor, galley: &Galley, key: Key, modifiers: &Modifiers) {
-    if cfg!(target_os = "macos") && modifiers.ctrl && !modifiers.shift {
-        match key {
-            Key::A => *cursor = galley.cursor_begin_of_row(cursor),
-            Key::E => *cursor = galley.cursor_end_of_row(cursor),
-            Key::P => *cursor = galley.cursor_up_one_row(cursor),
-            Key::N => *cursor = galley.cursor_down_one_row(cursor),
-            Key::B => *cursor = galley.cursor_left_one_character(cursor),
-            Key::F => *cursor = galley.cursor_right_one_character(cursor),
-            _ => (),
-        }
-        return;
-    }
-    match key {
-        Key::ArrowLeft => {
-            if modifiers.alt || modifiers.ctrl {
-                // alt on mac, ctrl on windows
-                *cursor = galley.from_ccursor(ccursor_previous_word(galley.text(), cursor.ccursor));
-            } else if modifiers.mac_cmd {
-                *cursor = galley.cursor_begin_of_row(cursor);
-            } else {
-                *cursor = galley.cursor_left_one_character(cursor);
-            }
-        }
-        Key::ArrowRight => {
-            if modifiers.alt || modifiers.ctrl {
-                // alt on mac, ctrl on windows
-                *cursor = galley.from_ccursor(ccursor_next_word(galley.text(), cursor.ccursor));
-            } else if modifiers.mac_cmd {
-                *cursor = galley.cursor_end_of_row(cursor);
-            } else {
-                *cursor = galley.cursor_right_one_character(cursor);
-            }
+fn move_single_cursor(cursor: &mut Cursor, galley: &Galley, action: TextEditAction) {
+    *cursor = match action {
+        TextEditAction::MoveLeft => galley.cursor_left_one_character(cursor),
+        TextEditAction::MoveRight => galley.cursor_right_one_character(cursor),
+        TextEditAction::MoveUp => galley.cursor_up_one_row(cursor),
+        TextEditAction::MoveDown => galley.cursor_down_one_row(cursor),
+        TextEditAction::MoveWordLeft => {
+            galley.from_ccursor(ccursor_previous_word(galley.text(), cursor.ccursor))
         }
-        Key::ArrowUp => {
-            if modifiers.command {
-                // mac and windows behavior
-                *cursor = Cursor::default();
-            } else {
-                *cursor = galley.cursor_up_one_row(cursor);
-            }
-        }
-        Key::ArrowDown => {
-            if modifiers.command {
-                // mac and windows behavior
-                *cursor = galley.end();
-            } else {
-                *cursor = galley.cursor_down_one_row(cursor);
-            }
-        }
-
-        Key::Home => {
-            if modifiers.ctrl {
-                // windows behavior
-                *cursor = Cursor::default();
-            } else {
-                *cursor = galley.cursor_begin_of_row(cursor);
-            }
+        TextEditAction::MoveWordRight => {
+            galley.from_ccursor(ccursor_next_word(galley.text(), cursor.ccursor))
         }
-        Key::End => {
-            if modifiers.ctrl {
-                // windows behavior
-                *cursor = galley.end();
-            } else {
-                *cursor = galley.cursor_end_of_row(cursor);
-            }
-        }
-
-        _ => unreachable!(),
-    }
+        TextEditAction::MoveToLineStart => galley.cursor_begin_of_row(cursor),
+        TextEditAction::MoveToLineEnd => galley.cursor_end_of_row(cursor),
+        TextEditAction::MoveToTextStart => Cursor::default(),
+        TextEditAction::MoveToTextEnd => galley.end(),
+        _ => return,
+    };
 }
 
 // ----------------------------------------------------------------------------
 
-fn select_word_at(text: &str, ccursor: CCursor) -> CCursorRange {
+pub(crate) fn select_word_at(text: &str, ccursor: CCursor) -> CCursorRange {
     if ccursor.index == 0 {
         CCursorRange::two(ccursor, ccursor_next_word(text, ccursor))
     } else {
@@ -1226,7 +1856,7 @@ fn select_word_at(text: &str, ccursor: CCursor) -> CCursorRange {
     }
 }
 
-fn select_line_at(text: &str, ccursor: CCursor) -> CCursorRange {
+pub(crate) fn select_line_at(text: &str, ccursor: CCursor) -> CCursorRange {
     if ccursor.index == 0 {
         CCursorRange::two(ccursor, ccursor_next_line(text, ccursor))
     } else {
@@ -1380,3 +2010,250 @@ fn decrease_identation(ccursor: &mut CCursor, text: &mut dyn TextBuffer) {
         }
     }
 }
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::Harness, *};
+
+    #[test]
+    fn incremental_layout_of_huge_text() {
+        let text: String = (0..10_000).map(|i| format!("Line {}\n", i)).collect();
+        let mut harness = Harness::new_ui_state((text, 0), |ui, (text, num_laid_out_rows)| {
+            ScrollArea::vertical().show(ui, |ui| {
+                let output = TextEdit::multiline(text)
+                    .id_source("text")
+                    .incremental_layout(true)
+                    .show(ui);
+                *num_laid_out_rows = output
+                    .galley
+                    .rows
+                    .iter()
+                    .filter(|row| row.elided.is_none())
+                    .count();
+            });
+        });
+        harness.run();
+        let num_laid_out_rows = harness.state().1;
+        assert!(0 < num_laid_out_rows && num_laid_out_rows < 200);
+
+        // Edit at the top:
+        let rect = harness.get_by_type(WidgetType::TextEdit).rect;
+        harness.click_at(rect.left_top() + vec2(1.0, 8.0), PointerButton::Primary);
+        harness.run();
+        harness.type_text("X");
+        harness.run();
+        assert!(harness.state().0.starts_with("XLine 0\nLine 1\n"));
+        assert!(harness.state().0.ends_with("Line 9999\n"));
+    }
+
+    /// Run frames until the next click won't count as a double-click.
+    fn wait_for_double_click_timeout<State>(harness: &mut Harness<'_, State>) {
+        for _ in 0..30 {
+            harness.run();
+        }
+    }
+
+    #[test]
+    fn alt_click_adds_cursor() {
+        let mut harness = Harness::new_ui_state("abc def".to_owned(), |ui, text| {
+            ui.text_edit_singleline(text);
+        });
+        harness.run();
+        let rect = harness.get_by_type(WidgetType::TextEdit).rect;
+
+        harness.click_at(rect.left_center() + vec2(1.0, 0.0), PointerButton::Primary);
+        wait_for_double_click_timeout(&mut harness);
+        harness.set_modifiers(Modifiers::ALT);
+        harness.click_at(rect.right_center() - vec2(1.0, 0.0), PointerButton::Primary);
+        harness.run();
+        harness.set_modifiers(Modifiers::NONE);
+
+        harness.type_text("X");
+        harness.run();
+        assert_eq!(harness.state(), "Xabc defX");
+
+        harness.key_press(Key::Backspace);
+        harness.run();
+        assert_eq!(harness.state(), "abc def");
+
+        harness.key_press(Key::ArrowLeft);
+        harness.type_text("-");
+        harness.run();
+        assert_eq!(harness.state(), "-abc de-f");
+
+        // A click without alt goes back to a single cursor:
+        wait_for_double_click_timeout(&mut harness);
+        harness.click_at(rect.left_center() + vec2(1.0, 0.0), PointerButton::Primary);
+        harness.run();
+        harness.type_text("Y");
+        harness.run();
+        assert_eq!(harness.state(), "Y-abc de-f");
+    }
+
+    #[test]
+    fn select_next_occurrence_and_edit() {
+        let text = "foo bar\nfoo baz\nfoo".to_owned();
+        let mut harness = Harness::new_ui_state(text, |ui, text| {
+            ui.text_edit_multiline(text);
+        });
+        harness.run();
+        let rect = harness.get_by_type(WidgetType::TextEdit).rect;
+        harness.click_at(rect.left_top() + vec2(1.0, 8.0), PointerButton::Primary);
+        harness.run();
+
+        // The first one selects the word at the cursor, the others add the next occurrences:
+        for _ in 0..3 {
+            harness.key_press_with_modifiers(Modifiers::COMMAND, Key::D);
+            harness.run();
+        }
+        harness.push_event(Event::Copy);
+        assert_eq!(harness.run().platform_output.copied_text, "foo\nfoo\nfoo");
+
+        harness.type_text("qux");
+        harness.run();
+        assert_eq!(harness.state(), "qux bar\nqux baz\nqux");
+
+        // Cutting and pasting keeps one line per cursor:
+        harness.key_press_with_modifiers(Modifiers::SHIFT, Key::ArrowLeft);
+        harness.push_event(Event::Cut);
+        harness.run();
+        assert_eq!(harness.state(), "qu bar\nqu baz\nqu");
+        harness.key_press(Key::Home);
+        harness.push_event(Event::Paste("1\n2\n3".to_owned()));
+        harness.run();
+        assert_eq!(harness.state(), "1qu bar\n2qu baz\n3qu");
+    }
+
+    #[test]
+    fn custom_keymap() {
+        let text = "ab".to_owned();
+        let mut harness = Harness::new_ui_state(text, |ui, text| {
+            let keymap = text_edit::TextEditKeymap::pc().with_emacs_bindings();
+            ui.add(TextEdit::singleline(text).keymap(&keymap));
+        });
+        harness.run();
+        let rect = harness.get_by_type(WidgetType::TextEdit).rect;
+        harness.click_at(rect.left_top() + vec2(1.0, 8.0), PointerButton::Primary);
+        harness.run();
+
+        // ctrl-E moves to the end of the line, where ctrl-T swaps the two chars before the cursor:
+        harness.key_press_with_modifiers(Modifiers::CTRL, Key::E);
+        harness.key_press_with_modifiers(Modifiers::CTRL, Key::T);
+        harness.run();
+        assert_eq!(harness.state(), "ba");
+
+        harness.key_press_with_modifiers(Modifiers::COMMAND, Key::Z);
+        harness.run();
+        assert_eq!(harness.state(), "ab");
+        harness.key_press_with_modifiers(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z);
+        harness.run();
+        assert_eq!(harness.state(), "ba");
+
+        // ctrl-A moves to the start of the line instead of selecting all:
+        harness.key_press_with_modifiers(Modifiers::CTRL, Key::A);
+        harness.type_text("x");
+        harness.run();
+        assert_eq!(harness.state(), "xba");
+    }
+
+    #[test]
+    fn code_editor_with_line_numbers() {
+        let text = "fn f() {}".to_owned();
+        let mut harness = Harness::new_ui_state((text, 0.0), |ui, (text, text_offset)| {
+            let output = TextEdit::multiline(text)
+                .code_editor()
+                .line_numbers(true)
+                .auto_indent(true)
+                .show(ui);
+            *text_offset = output.text_draw_pos.x - output.response.rect.left();
+        });
+        harness.run();
+        assert!(
+            harness.state().1 > 10.0,
+            "the text should be to the right of the line numbers"
+        );
+
+        let rect = harness.get_by_type(WidgetType::TextEdit).rect;
+        harness.click_at(rect.right_top() + vec2(-2.0, 8.0), PointerButton::Primary);
+        harness.run();
+        harness.key_press(Key::ArrowLeft);
+        harness.key_press(Key::Enter);
+        harness.type_text("x");
+        harness.run();
+        assert_eq!(harness.state().0, "fn f() {\n\tx\n}");
+
+        // Indent and outdent the selected lines:
+        harness.key_press_with_modifiers(Modifiers::SHIFT, Key::ArrowUp);
+        harness.key_press(Key::Tab);
+        harness.run();
+        assert_eq!(harness.state().0, "\tfn f() {\n\t\tx\n}");
+        harness.key_press_with_modifiers(Modifiers::SHIFT, Key::Tab);
+        harness.key_press_with_modifiers(Modifiers::SHIFT, Key::Tab);
+        harness.run();
+        assert_eq!(harness.state().0, "fn f() {\nx\n}");
+    }
+
+    #[test]
+    fn input_filter_and_validator() {
+        let state = (String::new(), None);
+        let mut harness = Harness::new_ui_state(state, |ui, (text, validation_error)| {
+            let output = TextEdit::singleline(text)
+                .input_filter(|c: char| c.is_ascii_digit())
+                .char_limit(4)
+                .validator(|text| match text.parse::<u32>() {
+                    Ok(year) if year >= 1900 => Ok(()),
+                    _ => Err("Not a year".to_owned()),
+                })
+                .show(ui);
+            *validation_error = output.validation_error;
+        });
+        harness.run();
+        assert_eq!(harness.state().1.as_deref(), Some("Not a year"));
+
+        harness.click(harness.get_by_type(WidgetType::TextEdit).id);
+        harness.run();
+        harness.type_text("2a0");
+        harness.push_event(Event::Paste("2-25".to_owned()));
+        harness.run();
+        assert_eq!(harness.state().0, "2022");
+        assert_eq!(harness.state().1, None);
+
+        // The cursor is after what was inserted:
+        harness.key_press(Key::Backspace);
+        harness.type_text("3");
+        harness.run();
+        assert_eq!(harness.state().0, "2023");
+    }
+
+    #[test]
+    fn ime_and_newlines_respect_char_limit() {
+        let mut harness = Harness::new_ui_state(String::new(), |ui, text| {
+            TextEdit::multiline(text)
+                .input_filter(|c: char| c != 'x')
+                .char_limit(3)
+                .lock_focus(true)
+                .show(ui);
+        });
+        harness.run();
+        harness.click(harness.get_by_type(WidgetType::TextEdit).id);
+        harness.run();
+        harness.type_text("1");
+
+        // The preview is cut off at the limit, and the committed text is filtered:
+        harness.push_event(Event::CompositionStart);
+        harness.push_event(Event::CompositionUpdate("abcd".to_owned()));
+        harness.run();
+        assert_eq!(harness.state(), "1ab");
+        harness.push_event(Event::CompositionEnd("x2".to_owned()));
+        harness.run();
+        assert_eq!(harness.state(), "12");
+
+        harness.key_press(Key::Enter);
+        harness.key_press(Key::Enter);
+        harness.key_press(Key::Tab);
+        harness.run();
+        assert_eq!(harness.state(), "12\n");
+    }
+}
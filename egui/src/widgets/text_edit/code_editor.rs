@@ -0,0 +1,428 @@
+//! The code editing features of [`TextEdit`]: line numbers, current line highlighting,
+//! bracket matching and indentation.
+
+use std::ops::Range;
+
+use epaint::text::{cursor::*, Galley, TAB_SIZE};
+
+use crate::*;
+
+use super::{CCursorRange, CursorRange, TextBuffer};
+
+const BRACKETS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+
+/// How many chars to look through for a matching bracket.
+/// We do this every frame, so we must not scan all of a huge text.
+const MAX_BRACKET_SCAN: usize = 10_000;
+
+/// The width of a line number gutter that fits `num_digits` digits,
+/// including the space between the numbers and the text. Zero for no digits.
+pub(crate) fn gutter_width(ui: &Ui, font_id: &FontId, num_digits: usize) -> f32 {
+    if num_digits == 0 {
+        return 0.0;
+    }
+    num_digits as f32 * ui.fonts().glyph_width(font_id, '0') + ui.spacing().item_spacing.x
+}
+
+/// The number of lines (paragraphs) in the galley, including the ones that were not laid out.
+pub(crate) fn num_lines(galley: &Galley) -> usize {
+    galley.end().pcursor.paragraph + 1
+}
+
+/// The number of decimal digits of `n`.
+pub(crate) fn num_digits(n: usize) -> usize {
+    n.to_string().len()
+}
+
+/// The rows of the line (paragraph) that the given row is part of.
+pub(crate) fn line_rows(galley: &Galley, row: usize) -> Range<usize> {
+    let rows = &galley.rows;
+    if rows.is_empty() {
+        return 0..0;
+    }
+    let mut start = row.min(rows.len() - 1);
+    while start > 0 && !rows[start - 1].ends_with_newline {
+        start -= 1;
+    }
+    let mut end = start;
+    while end + 1 < rows.len() && !rows[end].ends_with_newline {
+        end += 1;
+    }
+    start..end + 1
+}
+
+/// Paints the line numbers right-aligned in `gutter_rect`, next to the `galley` painted at `galley_pos`.
+///
+/// Only the first row of a wrapped line gets a number.
+/// The number of the line that starts at `current_line_row` is painted stronger.
+pub(crate) fn paint_line_numbers(
+    ui: &Ui,
+    gutter_rect: Rect,
+    galley_pos: Pos2,
+    galley: &Galley,
+    font_id: &FontId,
+    current_line_row: Option<usize>,
+) {
+    let painter = ui.painter_at(gutter_rect);
+    let clip_rect = painter.clip_rect();
+    let x = gutter_rect.right() - ui.spacing().item_spacing.x;
+
+    let mut line_number = 1;
+    let mut starts_line = true;
+    for (row_index, row) in galley.rows.iter().enumerate() {
+        if let Some(elided) = &row.elided {
+            line_number += elided.num_paragraphs;
+            starts_line = true;
+            continue;
+        }
+
+        let y = galley_pos.y + row.rect.top();
+        if y > clip_rect.bottom() {
+            break;
+        }
+        if starts_line && clip_rect.top() <= galley_pos.y + row.rect.bottom() {
+            let color = if current_line_row == Some(row_index) {
+                ui.visuals().text_color()
+            } else {
+                ui.visuals().weak_text_color()
+            };
+            painter.text(
+                pos2(x, y),
+                Align2::RIGHT_TOP,
+                line_number,
+                font_id.clone(),
+                color,
+            );
+        }
+
+        starts_line = row.ends_with_newline;
+        if starts_line {
+            line_number += 1;
+        }
+    }
+}
+
+/// Highlights the background of the line with the cursor, across the whole width of `rect`.
+pub(crate) fn paint_current_line(
+    ui: &Ui,
+    painter: &Painter,
+    rect: Rect,
+    galley_pos: Pos2,
+    galley: &Galley,
+    cursor: &Cursor,
+) {
+    let rows = line_rows(galley, cursor.rcursor.row);
+    if rows.is_empty() {
+        return;
+    }
+    let top = galley_pos.y + galley.rows[rows.start].rect.top();
+    let bottom = galley_pos.y + galley.rows[rows.end - 1].rect.bottom();
+    painter.rect_filled(
+        Rect::from_x_y_ranges(rect.x_range(), top..=bottom),
+        0.0,
+        ui.visuals().faint_bg_color,
+    );
+}
+
+/// Outlines the bracket next to the cursor, and the bracket it matches.
+pub(crate) fn paint_matching_brackets(
+    ui: &Ui,
+    painter: &Painter,
+    galley_pos: Pos2,
+    galley: &Galley,
+    text: &dyn TextBuffer,
+    ccursor: CCursor,
+) {
+    if let Some(brackets) = matching_brackets(text, ccursor) {
+        for index in brackets {
+            let min = galley.pos_from_cursor(&galley.from_ccursor(CCursor::new(index)));
+            let max = galley.pos_from_cursor(&galley.from_ccursor(CCursor::new(index + 1)));
+            if min.top() == max.top() {
+                let bracket_rect = Rect::from_min_max(min.min, max.max);
+                painter.rect_stroke(
+                    bracket_rect.translate(galley_pos.to_vec2()),
+                    1.0,
+                    ui.visuals().selection.stroke,
+                );
+            }
+        }
+    }
+}
+
+/// If there is a bracket right after (or else right before) the cursor,
+/// returns the char indices of it and of its matching bracket.
+///
+/// Brackets further apart than [`MAX_BRACKET_SCAN`] chars are not matched.
+pub(crate) fn matching_brackets(text: &dyn TextBuffer, ccursor: CCursor) -> Option<[usize; 2]> {
+    let s = text.as_str();
+    let byte_index = text.byte_index_from_char_index(ccursor.index);
+
+    let after = s[byte_index..]
+        .chars()
+        .next()
+        .map(|c| (ccursor.index, byte_index, c));
+    let before = s[..byte_index]
+        .char_indices()
+        .next_back()
+        .map(|(byte_index, c)| (ccursor.index - 1, byte_index, c));
+
+    after
+        .into_iter()
+        .chain(before)
+        .find_map(|(index, byte_index, c)| {
+            let other = if let Some((open, close)) = BRACKETS.iter().find(|(open, _)| c == *open) {
+                let after_bracket = &s[byte_index + c.len_utf8()..];
+                index + 1 + closing_offset(after_bracket.chars(), *open, *close)?
+            } else if let Some((open, close)) = BRACKETS.iter().find(|(_, close)| c == *close) {
+                let before_bracket = &s[..byte_index];
+                index - 1 - closing_offset(before_bracket.chars().rev(), *close, *open)?
+            } else {
+                return None;
+            };
+            Some([index, other])
+        })
+}
+
+/// The number of chars before the `close` bracket that closes the `open` bracket we are after,
+/// taking nested brackets into account.
+fn closing_offset(chars: impl Iterator<Item = char>, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    for (offset, c) in chars.take(MAX_BRACKET_SCAN).enumerate() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            if depth == 0 {
+                return Some(offset);
+            }
+            depth -= 1;
+        }
+    }
+    None
+}
+
+// ----------------------------------------------------------------------------
+
+/// The indentation to add for one more level, in the style of the given indentation.
+fn indent_unit(indentation: &str) -> String {
+    if indentation.starts_with(' ') {
+        " ".repeat(TAB_SIZE)
+    } else {
+        "\t".to_owned()
+    }
+}
+
+/// Inserts a newline at the cursor, followed by the indentation of the current line.
+///
+/// After an opening bracket the new line is indented one more level,
+/// and a closing bracket right after the cursor is moved to a line of its own.
+///
+/// At most `max_chars` chars are inserted, which cuts the indentation short.
+pub(crate) fn insert_newline_with_indentation(
+    ccursor: &mut CCursor,
+    text: &mut dyn TextBuffer,
+    max_chars: usize,
+) {
+    let s = text.as_str();
+    let byte_index = text.byte_index_from_char_index(ccursor.index);
+    let line_start = s[..byte_index].rfind('\n').map_or(0, |i| i + 1);
+    let line = &s[line_start..byte_index];
+    let indentation: String = line
+        .chars()
+        .take_while(|&c| c == ' ' || c == '\t')
+        .collect();
+    let opens_block = line
+        .trim_end()
+        .ends_with(|c| BRACKETS.iter().any(|(open, _)| c == *open));
+    let closes_block = opens_block
+        && s[byte_index..].starts_with(|c| BRACKETS.iter().any(|(_, close)| c == *close));
+
+    let mut new_line = format!("\n{}", indentation);
+    if opens_block {
+        new_line += &indent_unit(&indentation);
+    }
+    let closing_line = format!("\n{}", indentation);
+    let num_chars = new_line.chars().count();
+    if closes_block && num_chars + closing_line.chars().count() <= max_chars {
+        text.insert_text(&closing_line, ccursor.index);
+    }
+    if num_chars > max_chars {
+        new_line = new_line.chars().take(max_chars).collect();
+    }
+    ccursor.index += text.insert_text(&new_line, ccursor.index);
+}
+
+/// Indents (or outdents) all the lines that the selection touches by one level,
+/// and returns the new selection.
+///
+/// A line is left alone if the selection ends at its very start.
+/// Does nothing if indenting would insert more than `max_chars` chars.
+pub(crate) fn indent_lines(
+    text: &mut dyn TextBuffer,
+    cursor_range: &CursorRange,
+    outdent: bool,
+    max_chars: usize,
+) -> Option<CCursorRange> {
+    let [min, max] = cursor_range.sorted_cursors();
+    let (min, max) = (min.ccursor.index, max.ccursor.index);
+
+    let s = text.as_str();
+    let min_byte = text.byte_index_from_char_index(min);
+    let max_byte = text.byte_index_from_char_index(max);
+    let first_line_byte = s[..min_byte].rfind('\n').map_or(0, |i| i + 1);
+    let first_line = min - s[first_line_byte..min_byte].chars().count();
+    let unit = indent_unit(&s[first_line_byte..]);
+
+    let mut line_starts = vec![first_line];
+    for (offset, c) in s[first_line_byte..max_byte].chars().enumerate() {
+        if c == '\n' && first_line + offset + 1 < max {
+            line_starts.push(first_line + offset + 1);
+        }
+    }
+
+    if !outdent && line_starts.len() * unit.chars().count() > max_chars {
+        return None;
+    }
+
+    let mut primary = cursor_range.primary.ccursor.index;
+    let mut secondary = cursor_range.secondary.ccursor.index;
+
+    // From the last line to the first, so the line starts stay valid:
+    for &line_start in line_starts.iter().rev() {
+        if outdent {
+            let line = &text.as_str()[text.byte_index_from_char_index(line_start)..];
+            let len = if line.starts_with('\t') {
+                1
+            } else {
+                line.chars()
+                    .take(TAB_SIZE)
+                    .take_while(|&c| c == ' ')
+                    .count()
+            };
+            text.delete_char_range(line_start..line_start + len);
+            for index in [&mut primary, &mut secondary] {
+                if *index > line_start {
+                    *index -= len.min(*index - line_start);
+                }
+            }
+        } else {
+            let len = text.insert_text(&unit, line_start);
+            for index in [&mut primary, &mut secondary] {
+                if *index > line_start {
+                    *index += len;
+                }
+            }
+        }
+    }
+
+    Some(CCursorRange {
+        primary: CCursor::new(primary),
+        secondary: CCursor::new(secondary),
+    })
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ccursor_range(primary: usize, secondary: usize) -> CCursorRange {
+        CCursorRange {
+            primary: CCursor::new(primary),
+            secondary: CCursor::new(secondary),
+        }
+    }
+
+    /// A [`CursorRange`] with only the [`CCursor`]s set, which is all [`indent_lines`] uses.
+    fn cursor_range(primary: usize, secondary: usize) -> CursorRange {
+        let cursor = |index| Cursor {
+            ccursor: CCursor::new(index),
+            ..Default::default()
+        };
+        CursorRange {
+            primary: cursor(primary),
+            secondary: cursor(secondary),
+        }
+    }
+
+    #[test]
+    fn test_matching_brackets() {
+        let text = "fn f(a: [u8; 2]) { g(a) }";
+        let brackets = |index| matching_brackets(&text, CCursor::new(index));
+        assert_eq!(brackets(4), Some([4, 15])); // before `(`
+        assert_eq!(brackets(5), Some([4, 15])); // after `(`
+        assert_eq!(brackets(16), Some([15, 4])); // after `)`
+        assert_eq!(brackets(17), Some([17, 24])); // before `{`
+        assert_eq!(brackets(24), Some([24, 17])); // before `}`
+        assert_eq!(brackets(2), None);
+        assert_eq!(matching_brackets(&"(()", CCursor::new(0)), None);
+        assert_eq!(matching_brackets(&"", CCursor::new(0)), None);
+
+        let far_apart = format!("({})", " ".repeat(MAX_BRACKET_SCAN));
+        assert_eq!(
+            matching_brackets(&far_apart.as_str(), CCursor::new(0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_newline_indentation() {
+        let newline_limited = |text: &str, index: usize, max_chars: usize| {
+            let mut text = text.to_owned();
+            let mut ccursor = CCursor::new(index);
+            insert_newline_with_indentation(&mut ccursor, &mut text, max_chars);
+            (text, ccursor.index)
+        };
+        let newline = |text: &str, index: usize| newline_limited(text, index, usize::MAX);
+        assert_eq!(newline("\tfoo", 4), ("\tfoo\n\t".to_owned(), 6));
+        assert_eq!(newline("\tif x {", 7), ("\tif x {\n\t\t".to_owned(), 10));
+        assert_eq!(newline("  f(", 4), ("  f(\n      ".to_owned(), 11));
+        assert_eq!(newline("{}", 1), ("{\n\t\n}".to_owned(), 3));
+        assert_eq!(newline("ab", 1), ("a\nb".to_owned(), 2));
+
+        // Limited to a number of chars:
+        assert_eq!(newline_limited("\tfoo", 4, 1), ("\tfoo\n".to_owned(), 5));
+        assert_eq!(newline_limited("{}", 1, 2), ("{\n\t}".to_owned(), 3));
+    }
+
+    #[test]
+    fn test_indent_lines() {
+        let indent = |text: &str, primary: usize, secondary: usize, outdent: bool| {
+            let mut text = text.to_owned();
+            let new_range = indent_lines(
+                &mut text,
+                &cursor_range(primary, secondary),
+                outdent,
+                usize::MAX,
+            );
+            (text, new_range.unwrap())
+        };
+
+        // The line where the selection ends at the start of is left alone:
+        assert_eq!(
+            indent("a\nb\nc", 0, 4, false),
+            ("\ta\n\tb\nc".to_owned(), ccursor_range(0, 6))
+        );
+        assert_eq!(
+            indent("\ta\n\tb\nc", 6, 0, true),
+            ("a\nb\nc".to_owned(), ccursor_range(4, 0))
+        );
+
+        // Spaces, and lines that are not indented:
+        assert_eq!(
+            indent("    ab\n  cd\nef", 5, 12, true),
+            ("ab\ncd\nef".to_owned(), ccursor_range(1, 6))
+        );
+
+        // Just a cursor:
+        assert_eq!(
+            indent("\tab", 2, 2, true),
+            ("ab".to_owned(), ccursor_range(1, 1))
+        );
+
+        // Not enough room for a tab on every line:
+        let mut text = "a\nb".to_owned();
+        assert_eq!(indent_lines(&mut text, &cursor_range(0, 3), false, 1), None);
+        assert_eq!(text, "a\nb");
+    }
+}
@@ -88,6 +88,65 @@ impl CursorRange {
     }
 }
 
+/// Sorts the cursor ranges and merges the ones that overlap.
+///
+/// The last range is the primary one, and the range it ends up in is kept last.
+pub(crate) fn merge_overlapping(cursor_ranges: Vec<CursorRange>) -> Vec<CursorRange> {
+    let num_ranges = cursor_ranges.len();
+    let mut sorted: Vec<(CursorRange, bool)> = cursor_ranges
+        .into_iter()
+        .enumerate()
+        .map(|(i, cursor_range)| (cursor_range, i + 1 == num_ranges))
+        .collect();
+    sorted.sort_by_key(|(cursor_range, _)| cursor_range.sorted_cursors()[0].ccursor.index);
+
+    let mut merged: Vec<(CursorRange, bool)> = Vec::with_capacity(num_ranges);
+    for (cursor_range, is_primary) in sorted {
+        if let Some((last, last_is_primary)) = merged.last_mut() {
+            let [min, max] = last.sorted_cursors();
+            let [next_min, next_max] = cursor_range.sorted_cursors();
+            let overlaps = next_min.ccursor.index < max.ccursor.index
+                || (next_min.ccursor.index == max.ccursor.index
+                    && (last.is_empty() || cursor_range.is_empty()));
+            if overlaps {
+                let max = if next_max.ccursor.index > max.ccursor.index {
+                    next_max
+                } else {
+                    max
+                };
+                // Keep the direction of the primary range (unless it is empty):
+                let (first, second) = if is_primary {
+                    (&cursor_range, &*last)
+                } else {
+                    (&*last, &cursor_range)
+                };
+                let direction = if first.is_empty() { second } else { first };
+                let primary_first = !direction.is_empty() && direction.is_sorted();
+                *last = if primary_first {
+                    CursorRange {
+                        primary: min,
+                        secondary: max,
+                    }
+                } else {
+                    CursorRange::two(min, max)
+                };
+                *last_is_primary |= is_primary;
+                continue;
+            }
+        }
+        merged.push((cursor_range, is_primary));
+    }
+
+    if let Some(index) = merged.iter().position(|(_, is_primary)| *is_primary) {
+        let primary = merged.remove(index);
+        merged.push(primary);
+    }
+    merged
+        .into_iter()
+        .map(|(cursor_range, _)| cursor_range)
+        .collect()
+}
+
 /// A selected text range (could be a range of length zero).
 ///
 /// The selection is based on character count (NOT byte count!).
@@ -148,3 +207,50 @@ pub struct PCursorRange {
     /// This part of the cursor does not move when shift is down.
     pub secondary: PCursor,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(secondary: usize, primary: usize) -> CursorRange {
+        let cursor = |index| Cursor {
+            ccursor: CCursor::new(index),
+            ..Default::default()
+        };
+        CursorRange {
+            primary: cursor(primary),
+            secondary: cursor(secondary),
+        }
+    }
+
+    fn char_ranges(cursor_ranges: &[CursorRange]) -> Vec<(usize, usize)> {
+        cursor_ranges
+            .iter()
+            .map(|cursor_range| {
+                (
+                    cursor_range.secondary.ccursor.index,
+                    cursor_range.primary.ccursor.index,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merge_overlapping_cursor_ranges() {
+        // Separate ranges are sorted, with the primary one last:
+        let merged = merge_overlapping(vec![range(8, 8), range(0, 2), range(4, 6)]);
+        assert_eq!(char_ranges(&merged), vec![(0, 2), (8, 8), (4, 6)]);
+
+        // Equal cursors are merged:
+        let merged = merge_overlapping(vec![range(3, 3), range(3, 3)]);
+        assert_eq!(char_ranges(&merged), vec![(3, 3)]);
+
+        // Touching selections are not merged, but a cursor at the end of a selection is:
+        let merged = merge_overlapping(vec![range(0, 2), range(2, 4), range(4, 4)]);
+        assert_eq!(char_ranges(&merged), vec![(0, 2), (2, 4)]);
+
+        // Overlapping selections are merged, keeping the direction of the primary one:
+        let merged = merge_overlapping(vec![range(0, 3), range(5, 2)]);
+        assert_eq!(char_ranges(&merged), vec![(5, 0)]);
+    }
+}
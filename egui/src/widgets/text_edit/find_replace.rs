@@ -0,0 +1,866 @@
+use std::{ops::Range, sync::Arc};
+
+use epaint::text::{cursor::CCursor, Galley, LayoutJob, LayoutSection};
+
+use crate::*;
+
+use super::{keymap::KeymapSource, CCursorRange, TextBuffer, TextEditAction, TextEditState};
+
+/// The state of the find/replace overlay of a [`TextEdit`].
+///
+/// The overlay is enabled with [`TextEdit::find_replace`], and opened with ctrl-F (cmd-F on Mac),
+/// or whatever [`super::TextEditAction::Find`] is bound to in the [`super::TextEditKeymap`].
+/// It is stored in the [`TextEditState`], see [`TextEditState::find_replace_mut`].
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FindReplaceState {
+    /// Is the overlay shown?
+    pub open: bool,
+
+    /// What to search for.
+    pub query: String,
+
+    /// What to replace the matches with.
+    ///
+    /// With [`Self::regex`] you can refer to capture groups with `$1`, `$name` etc.
+    pub replacement: String,
+
+    /// Only find text with the same case as the query.
+    pub match_case: bool,
+
+    /// Only find whole words.
+    pub whole_word: bool,
+
+    /// Search with a regular expression. Requires the `regex` feature.
+    pub regex: bool,
+
+    /// The byte range of the match we navigated to.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    current_match: Option<Range<usize>>,
+
+    /// Give the focus back to the [`TextEdit`] next frame.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    refocus: bool,
+
+    /// The matches in the text, see [`Self::search`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    search: Option<Arc<Search>>,
+}
+
+impl FindReplaceState {
+    /// Returns `None` if there is nothing to search for,
+    /// and an error if [`Self::query`] is not a valid regular expression.
+    fn matcher(&self) -> Option<Result<Matcher, String>> {
+        if self.query.is_empty() {
+            return None;
+        }
+
+        #[cfg(feature = "regex")]
+        {
+            if self.regex {
+                let pattern = if self.whole_word {
+                    format!(r"\b(?:{})\b", self.query)
+                } else {
+                    self.query.clone()
+                };
+                return Some(
+                    regex::RegexBuilder::new(&pattern)
+                        .case_insensitive(!self.match_case)
+                        .build()
+                        .map(Matcher::Regex)
+                        .map_err(|err| err.to_string()),
+                );
+            }
+        }
+
+        Some(Ok(Matcher::Plain {
+            query: self.query.clone(),
+            match_case: self.match_case,
+            whole_word: self.whole_word,
+        }))
+    }
+
+    /// Search `text` for the query.
+    ///
+    /// The result is cached, so the query is only compiled again when it or its options change,
+    /// and the text is only searched again when it changes.
+    fn search(&mut self, text: &str) -> Arc<Search> {
+        let query_hash =
+            crate::util::hash((&self.query, self.match_case, self.whole_word, self.regex));
+        let text_hash = crate::util::hash(text);
+
+        if let Some(search) = &self.search {
+            if search.query_hash == query_hash && search.text_hash == text_hash {
+                return search.clone();
+            }
+        }
+
+        let matcher = match &self.search {
+            Some(search) if search.query_hash == query_hash => search.matcher.clone(),
+            _ => self.matcher(),
+        };
+        let matches = match &matcher {
+            Some(Ok(matcher)) => matcher.find_all(text),
+            _ => vec![],
+        };
+        let search = Arc::new(Search {
+            query_hash,
+            text_hash,
+            matcher,
+            matches,
+        });
+        self.search = Some(search.clone());
+        search
+    }
+
+    /// Highlights the matches in the galleys of the [`TextEdit`], if the overlay is open.
+    ///
+    /// Uses the matches of the last [`Self::search`].
+    pub(crate) fn highlighter(&self, visuals: &Visuals) -> Option<Highlighter> {
+        if !self.open {
+            return None;
+        }
+        let search = self.search.clone()?;
+        if !matches!(search.matcher, Some(Ok(_))) {
+            return None;
+        }
+        Some(Highlighter {
+            search,
+            current_match: self.current_match.clone(),
+            match_color: visuals.selection.bg_fill.linear_multiply(0.3),
+            current_match_color: visuals.selection.bg_fill,
+        })
+    }
+
+    /// Navigate to the next (or previous) match after `from` (a byte offset),
+    /// wrapping around at the end (or start) of the text.
+    fn select_match(
+        &mut self,
+        matches: &[Range<usize>],
+        from: usize,
+        forward: bool,
+    ) -> Option<Range<usize>> {
+        let has_current = self.current_match.is_some();
+        let index = if forward {
+            matches
+                .iter()
+                .position(|m| m.start > from || (!has_current && m.start == from))
+                .or(if matches.is_empty() { None } else { Some(0) })
+        } else {
+            matches
+                .iter()
+                .rposition(|m| m.start < from)
+                .or_else(|| matches.len().checked_sub(1))
+        };
+        self.current_match = index.map(|index| matches[index].clone());
+        self.current_match.clone()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// The result of [`FindReplaceState::search`].
+#[derive(Debug)]
+struct Search {
+    /// Hash of the query and its options.
+    query_hash: u64,
+
+    /// Hash of the searched text.
+    text_hash: u64,
+
+    /// `None` if there is nothing to search for,
+    /// and an error if the query is not a valid regular expression.
+    matcher: Option<Result<Matcher, String>>,
+
+    /// The byte ranges of the matches, in order.
+    matches: Vec<Range<usize>>,
+}
+
+/// Finds the matches of the query of a [`FindReplaceState`].
+#[derive(Clone, Debug)]
+enum Matcher {
+    Plain {
+        query: String,
+        match_case: bool,
+        whole_word: bool,
+    },
+
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+impl Matcher {
+    /// The byte ranges of all non-empty matches, in order.
+    fn find_all(&self, text: &str) -> Vec<Range<usize>> {
+        match self {
+            Self::Plain {
+                query,
+                match_case,
+                whole_word,
+            } => {
+                let mut matches = vec![];
+                let mut end_of_last_match = 0;
+                for (start, _) in text.char_indices() {
+                    if start < end_of_last_match {
+                        continue;
+                    }
+                    if let Some(len) = match_len(&text[start..], query, *match_case) {
+                        let range = start..start + len;
+                        if !*whole_word || is_whole_word(text, &range) {
+                            end_of_last_match = range.end;
+                            matches.push(range);
+                        }
+                    }
+                }
+                matches
+            }
+
+            #[cfg(feature = "regex")]
+            Self::Regex(regex) => regex
+                .find_iter(text)
+                .map(|m| m.start()..m.end())
+                .filter(|range| !range.is_empty())
+                .collect(),
+        }
+    }
+
+    /// What to replace the given match with.
+    #[cfg_attr(not(feature = "regex"), allow(unused_variables))]
+    fn replacement(&self, text: &str, range: &Range<usize>, replacement: &str) -> String {
+        match self {
+            Self::Plain { .. } => replacement.to_owned(),
+
+            #[cfg(feature = "regex")]
+            Self::Regex(regex) => {
+                let mut expanded = String::new();
+                if let Some(captures) = regex
+                    .captures_iter(text)
+                    .find(|captures| captures.get(0).map_or(false, |m| m.start() == range.start))
+                {
+                    captures.expand(replacement, &mut expanded);
+                }
+                expanded
+            }
+        }
+    }
+
+    /// Replaces all matches, returning the new text.
+    fn replace_all(&self, text: &str, matches: &[Range<usize>], replacement: &str) -> String {
+        let mut new_text = String::with_capacity(text.len());
+        let mut end_of_last_match = 0;
+        let mut push_replacement =
+            |new_text: &mut String, range: Range<usize>, replacement: &str| {
+                new_text.push_str(&text[end_of_last_match..range.start]);
+                new_text.push_str(replacement);
+                end_of_last_match = range.end;
+            };
+
+        match self {
+            Self::Plain { .. } => {
+                for range in matches {
+                    push_replacement(&mut new_text, range.clone(), replacement);
+                }
+            }
+
+            #[cfg(feature = "regex")]
+            Self::Regex(regex) => {
+                let mut expanded = String::new();
+                for captures in regex.captures_iter(text) {
+                    let m = captures.get(0).unwrap();
+                    if m.start() < m.end() {
+                        expanded.clear();
+                        captures.expand(replacement, &mut expanded);
+                        push_replacement(&mut new_text, m.start()..m.end(), &expanded);
+                    }
+                }
+            }
+        }
+
+        new_text.push_str(&text[end_of_last_match..]);
+        new_text
+    }
+}
+
+/// If `text` starts with `query`, return the length in bytes of that part of `text`.
+fn match_len(text: &str, query: &str, match_case: bool) -> Option<usize> {
+    if match_case {
+        return text.starts_with(query).then(|| query.len());
+    }
+    let mut text_chars = text.char_indices();
+    for query_char in query.chars() {
+        let (_, text_char) = text_chars.next()?;
+        if !text_char.to_lowercase().eq(query_char.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(text_chars.next().map_or(text.len(), |(index, _)| index))
+}
+
+fn is_whole_word(text: &str, range: &Range<usize>) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    !text[..range.start]
+        .chars()
+        .next_back()
+        .map_or(false, is_word_char)
+        && !text[range.end..].chars().next().map_or(false, is_word_char)
+}
+
+fn char_index_from_byte_index(text: &str, byte_index: usize) -> usize {
+    text[..byte_index].chars().count()
+}
+
+// ----------------------------------------------------------------------------
+
+/// Highlights the matches of the find/replace overlay by laying out the galley again,
+/// with a background color on the matched parts of its [`LayoutJob`] sections.
+pub(crate) struct Highlighter {
+    search: Arc<Search>,
+    current_match: Option<Range<usize>>,
+    match_color: Color32,
+    current_match_color: Color32,
+}
+
+impl Highlighter {
+    pub(crate) fn highlight(&self, ui: &Ui, galley: Arc<Galley>) -> Arc<Galley> {
+        let search = &self.search;
+        let searched_again;
+        let matches = if search.text_hash == crate::util::hash(galley.text()) {
+            &search.matches
+        } else if let Some(Ok(matcher)) = &search.matcher {
+            // The text was edited since the search:
+            searched_again = matcher.find_all(galley.text());
+            &searched_again
+        } else {
+            return galley;
+        };
+        if matches.is_empty() {
+            return galley;
+        }
+
+        let job = &galley.job;
+        let mut sections = Vec::with_capacity(job.sections.len() + 2 * matches.len());
+        let mut next_match = 0;
+        for section in &job.sections {
+            let mut push = |byte_range: Range<usize>, background: Option<Color32>| {
+                let mut format = section.format.clone();
+                if let Some(background) = background {
+                    format.background = background;
+                }
+                sections.push(LayoutSection {
+                    // Only the first part of the section is indented:
+                    leading_space: if byte_range.start == section.byte_range.start {
+                        section.leading_space
+                    } else {
+                        0.0
+                    },
+                    byte_range,
+                    format,
+                });
+            };
+
+            let mut start = section.byte_range.start;
+            while next_match < matches.len() && matches[next_match].start < section.byte_range.end {
+                let m = &matches[next_match];
+                let highlighted = m.start.max(start)..m.end.min(section.byte_range.end);
+                if start < highlighted.start {
+                    push(start..highlighted.start, None);
+                }
+                if !highlighted.is_empty() {
+                    let color = if self.current_match.as_ref() == Some(m) {
+                        self.current_match_color
+                    } else {
+                        self.match_color
+                    };
+                    push(highlighted.clone(), Some(color));
+                    start = highlighted.end;
+                }
+                if m.end <= section.byte_range.end {
+                    next_match += 1;
+                } else {
+                    break; // the match continues in the next section
+                }
+            }
+            if start < section.byte_range.end {
+                push(start..section.byte_range.end, None);
+            }
+        }
+
+        ui.fonts().layout_job(LayoutJob {
+            sections,
+            ..(**job).clone()
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// What the find/replace overlay did this frame.
+#[derive(Default)]
+pub(crate) struct FindReplaceOutput {
+    /// The text was replaced.
+    pub text_changed: bool,
+
+    /// The selection was moved to a match, which should be scrolled into view.
+    pub reveal_selection: bool,
+}
+
+/// Shows the find/replace overlay in the top right corner of the visible part of `rect`,
+/// if it is open. Ctrl-F (cmd-F on Mac) opens it when the [`TextEdit`] with the given `id` has focus.
+pub(crate) fn find_replace_overlay(
+    ui: &Ui,
+    id: Id,
+    rect: Rect,
+    state: &mut TextEditState,
+    text: &mut dyn TextBuffer,
+    keymap: KeymapSource<'_>,
+) -> FindReplaceOutput {
+    let mut output = FindReplaceOutput::default();
+    let query_id = id.with("find_query");
+    let replacement_id = id.with("find_replacement");
+
+    if std::mem::take(&mut state.find_replace.refocus) {
+        ui.memory().request_focus(id);
+    }
+
+    let has_focus = ui.memory().has_focus(id);
+    let find_pressed = has_focus && {
+        let find_shortcuts: Vec<KeyboardShortcut> = keymap.read(|keymap| {
+            keymap
+                .shortcuts
+                .iter()
+                .filter(|(action, _)| **action == TextEditAction::Find)
+                .map(|(_, shortcut)| *shortcut)
+                .collect()
+        });
+        find_shortcuts
+            .iter()
+            .any(|shortcut| ui.ctx().input_mut().consume_shortcut(shortcut))
+    };
+    if find_pressed {
+        // Search for the selected text:
+        if let Some(ccursor_range) = state.ccursor_range() {
+            let [min, max] = ccursor_range.sorted();
+            let selected = text.char_range(min.index..max.index);
+            if !selected.is_empty() && !selected.contains('\n') {
+                state.find_replace.query = selected.to_owned();
+            }
+        }
+        state.find_replace.open = true;
+        ui.memory().request_focus(query_id);
+    }
+
+    if !state.find_replace.open {
+        return output;
+    }
+
+    let search = state.find_replace.search(text.as_ref());
+    let matches = &search.matches;
+
+    enum Action {
+        Next { forward: bool },
+        Replace,
+        ReplaceAll,
+        Close,
+    }
+    let mut action = None;
+
+    let area_id = id.with("find_replace_overlay");
+    let overlay_width = ui
+        .memory()
+        .areas
+        .get(area_id)
+        .map_or(0.0, |area| area.size.x);
+    let visible_rect = rect.intersect(ui.clip_rect());
+    if !visible_rect.is_positive() {
+        return output; // scrolled out of view
+    }
+
+    // Put the overlay on the layer of the `TextEdit`, so other windows can cover it.
+    // Panels are painted above the areas in the background, so they need a window:
+    let owner = ui.layer_id();
+    let order = owner.order.max(Order::Middle);
+    let overlay_layer = LayerId::new(order, area_id);
+    if order == owner.order {
+        // Keep the overlay above the window of the `TextEdit`:
+        let (owner_index, overlay_index) = {
+            let memory = ui.memory();
+            let layers = memory.areas.order();
+            let index = |layer: LayerId| layers.iter().position(|&l| l == layer);
+            (index(owner), index(overlay_layer))
+        };
+        if let (Some(owner_index), Some(overlay_index)) = (owner_index, overlay_index) {
+            if overlay_index < owner_index {
+                ui.ctx().move_to_top(overlay_layer);
+                ui.ctx().request_repaint();
+            }
+        }
+    }
+
+    Area::new(area_id)
+        .order(order)
+        .fixed_pos(visible_rect.right_top() - vec2(overlay_width, 0.0))
+        .show(ui.ctx(), |ui| {
+            Frame::popup(ui.style()).show(ui, |ui| {
+                let find = &mut state.find_replace;
+                let key_pressed = |ui: &Ui, key: Key| ui.input().key_pressed(key);
+
+                ui.horizontal(|ui| {
+                    let response = ui.add(
+                        TextEdit::singleline(&mut find.query)
+                            .id(query_id)
+                            .hint_text("Find")
+                            .desired_width(160.0),
+                    );
+                    if response.changed() {
+                        find.current_match = None;
+                    }
+                    if response.lost_focus() && key_pressed(ui, Key::Enter) {
+                        let forward = !ui.input().modifiers.shift;
+                        action = Some(Action::Next { forward });
+                        response.request_focus();
+                    } else if response.lost_focus() && key_pressed(ui, Key::Escape) {
+                        action = Some(Action::Close);
+                    }
+
+                    let toggle = |ui: &mut Ui, value: &mut bool, text: &str, hover: &str| {
+                        let clicked = ui
+                            .selectable_label(*value, text)
+                            .on_hover_text(hover)
+                            .clicked();
+                        if clicked {
+                            *value = !*value;
+                        }
+                        clicked
+                    };
+                    let mut options_changed = false;
+                    options_changed |= toggle(ui, &mut find.match_case, "Aa", "Match case");
+                    options_changed |= toggle(ui, &mut find.whole_word, "W", "Match whole word");
+                    if cfg!(feature = "regex") {
+                        options_changed |=
+                            toggle(ui, &mut find.regex, ".*", "Use regular expression");
+                    }
+                    if options_changed {
+                        find.current_match = None;
+                    }
+
+                    match &search.matcher {
+                        Some(Err(err)) => {
+                            ui.colored_label(Color32::RED, "Invalid")
+                                .on_hover_text(err.as_str());
+                        }
+                        Some(Ok(_)) => {
+                            let current = find
+                                .current_match
+                                .as_ref()
+                                .and_then(|current| matches.iter().position(|m| m == current));
+                            if matches.is_empty() {
+                                ui.label("No results");
+                            } else if let Some(current) = current {
+                                ui.label(format!("{} of {}", current + 1, matches.len()));
+                            } else {
+                                ui.label(format!("{} found", matches.len()));
+                            }
+                        }
+                        None => {}
+                    }
+
+                    if ui
+                        .small_button("⏶")
+                        .on_hover_text("Previous match")
+                        .clicked()
+                    {
+                        action = Some(Action::Next { forward: false });
+                    }
+                    if ui.small_button("⏷").on_hover_text("Next match").clicked() {
+                        action = Some(Action::Next { forward: true });
+                    }
+                    if ui.small_button("✖").on_hover_text("Close").clicked() {
+                        action = Some(Action::Close);
+                    }
+                });
+
+                if text.is_mutable() {
+                    ui.horizontal(|ui| {
+                        let response = ui.add(
+                            TextEdit::singleline(&mut find.replacement)
+                                .id(replacement_id)
+                                .hint_text("Replace")
+                                .desired_width(160.0),
+                        );
+                        if response.lost_focus() && key_pressed(ui, Key::Enter) {
+                            action = Some(Action::Replace);
+                            response.request_focus();
+                        } else if response.lost_focus() && key_pressed(ui, Key::Escape) {
+                            action = Some(Action::Close);
+                        }
+
+                        if ui.button("Replace").clicked() {
+                            action = Some(Action::Replace);
+                        }
+                        if ui.button("Replace all").clicked() {
+                            action = Some(Action::ReplaceAll);
+                        }
+                    });
+                }
+            });
+        });
+
+    let matcher = match &search.matcher {
+        Some(Ok(matcher)) => Some(matcher),
+        _ => None,
+    };
+
+    match action {
+        Some(Action::Next { forward }) => {
+            let from = search_from(state, text);
+            if let Some(range) = state.find_replace.select_match(matches, from, forward) {
+                select_byte_range(state, text.as_ref(), &range);
+                output.reveal_selection = true;
+            }
+        }
+
+        Some(Action::Replace) => {
+            let current_match = state
+                .find_replace
+                .current_match
+                .clone()
+                .filter(|current| matches.contains(current));
+            let mut from = search_from(state, text);
+            if let (Some(matcher), Some(range)) = (matcher, current_match) {
+                let replacement =
+                    matcher.replacement(text.as_ref(), &range, &state.find_replace.replacement);
+                let before = (state.ccursor_ranges(), text.as_ref().to_owned());
+
+                let start = char_index_from_byte_index(text.as_ref(), range.start);
+                let end = start + text.as_ref()[range.clone()].chars().count();
+                text.delete_char_range(start..end);
+                let num_inserted = text.insert_text(&replacement, start);
+                state
+                    .set_ccursor_range(Some(CCursorRange::one(CCursor::new(start + num_inserted))));
+                add_undo_point(state, &before, text);
+                output.text_changed = true;
+
+                // Continue with the match after the replacement:
+                from = range.start + replacement.len();
+                state.find_replace.current_match = None;
+            }
+
+            // Go to the next match:
+            if matcher.is_some() {
+                let search = state.find_replace.search(text.as_ref());
+                if let Some(range) = state.find_replace.select_match(&search.matches, from, true) {
+                    select_byte_range(state, text.as_ref(), &range);
+                    output.reveal_selection = true;
+                }
+            }
+        }
+
+        Some(Action::ReplaceAll) => {
+            if let Some(matcher) = matcher {
+                if !matches.is_empty() {
+                    let before = (state.ccursor_ranges(), text.as_ref().to_owned());
+                    let new_text = matcher.replace_all(
+                        text.as_ref(),
+                        matches,
+                        &state.find_replace.replacement,
+                    );
+                    text.replace(&new_text);
+                    state.set_ccursor_range(Some(CCursorRange::one(CCursor::new(0))));
+                    state.find_replace.current_match = None;
+                    add_undo_point(state, &before, text);
+                    output.text_changed = true;
+                }
+            }
+        }
+
+        Some(Action::Close) => {
+            state.find_replace.open = false;
+            state.find_replace.current_match = None;
+            // Clicking the close button counts as clicking outside the `TextEdit`,
+            // which would make it surrender the focus again this frame:
+            state.find_replace.refocus = true;
+        }
+
+        None => {}
+    }
+
+    output
+}
+
+/// Where to search for the next or previous match from: the current match, or else the cursor.
+fn search_from(state: &TextEditState, text: &dyn TextBuffer) -> usize {
+    if let Some(current_match) = &state.find_replace.current_match {
+        current_match.start
+    } else {
+        let cursor = state.ccursor_range().map_or(0, |range| range.primary.index);
+        text.byte_index_from_char_index(cursor)
+    }
+}
+
+/// Selects the given byte range in the [`TextEdit`].
+fn select_byte_range(state: &mut TextEditState, text: &str, range: &Range<usize>) {
+    let min = char_index_from_byte_index(text, range.start);
+    let max = min + text[range.clone()].chars().count();
+    state.set_ccursor_range(Some(CCursorRange::two(
+        CCursor::new(min),
+        CCursor::new(max),
+    )));
+}
+
+/// Record a replacement as one undoable edit.
+fn add_undo_point(
+    state: &mut TextEditState,
+    before: &(Vec<CCursorRange>, String),
+    text: &dyn TextBuffer,
+) {
+    let after = (state.ccursor_ranges(), text.as_ref().to_owned());
+    let mut undoer = state.undoer.lock();
+    undoer.add_undo(before);
+    undoer.add_undo(&after);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Harness;
+
+    fn find_all<'t>(
+        text: &'t str,
+        query: &str,
+        match_case: bool,
+        whole_word: bool,
+    ) -> Vec<&'t str> {
+        let find = FindReplaceState {
+            query: query.to_owned(),
+            match_case,
+            whole_word,
+            ..Default::default()
+        };
+        let matcher = find.matcher().unwrap().unwrap();
+        matcher
+            .find_all(text)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_all() {
+        let text = "Straße strasse STRASSE Strassen";
+        assert_eq!(find_all(text, "strasse", true, false), vec!["strasse"]);
+        assert_eq!(
+            find_all(text, "strasse", false, false),
+            vec!["strasse", "STRASSE", "Strasse"]
+        );
+        assert_eq!(
+            find_all(text, "strasse", false, true),
+            vec!["strasse", "STRASSE"]
+        );
+        assert_eq!(find_all(text, "STRAßE", false, false), vec!["Straße"]);
+        assert_eq!(find_all("aaaa", "aa", true, false), vec!["aa", "aa"]);
+    }
+
+    #[test]
+    fn test_search_is_cached() {
+        let mut find = FindReplaceState {
+            query: "foo".to_owned(),
+            ..Default::default()
+        };
+        let search = find.search("foo bar foo");
+        assert_eq!(search.matches, vec![0..3, 8..11]);
+        assert!(Arc::ptr_eq(&search, &find.search("foo bar foo")));
+
+        let search = find.search("bar foo");
+        assert_eq!(search.matches, vec![4..7]);
+        find.match_case = true;
+        assert!(!Arc::ptr_eq(&search, &find.search("bar foo")));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_regex_replace_all() {
+        let find = FindReplaceState {
+            query: r"(\w+)@(\w+)".to_owned(),
+            regex: true,
+            ..Default::default()
+        };
+        let matcher = find.matcher().unwrap().unwrap();
+        let text = "alice@home, bob@work";
+        let matches = matcher.find_all(text);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(
+            matcher.replace_all(text, &matches, "$2:$1"),
+            "home:alice, work:bob"
+        );
+
+        let find = FindReplaceState {
+            query: "(".to_owned(),
+            regex: true,
+            ..Default::default()
+        };
+        assert!(find.matcher().unwrap().is_err());
+    }
+
+    #[test]
+    fn find_and_replace_in_text_edit() {
+        let id = Id::new("code");
+        let text = "foo bar foo\nFoo".to_owned();
+        let mut harness = Harness::new_ui_state((text, None), |ui, (text, galley)| {
+            let output = TextEdit::multiline(text).id(id).find_replace(true).show(ui);
+            *galley = Some(output.galley);
+        });
+        harness.run();
+        harness.click(id);
+        harness.run();
+
+        harness.key_press_with_modifiers(Modifiers::COMMAND, Key::F);
+        harness.run();
+        assert!(harness.has_focus(id.with("find_query")));
+        harness.type_text("foo");
+        harness.run();
+        harness.run();
+
+        let num_highlighted = |harness: &Harness<'_, (String, Option<Arc<Galley>>)>| {
+            let galley = harness.state().1.as_ref().unwrap();
+            galley
+                .job
+                .sections
+                .iter()
+                .filter(|section| section.format.background != Color32::TRANSPARENT)
+                .count()
+        };
+        assert_eq!(num_highlighted(&harness), 3);
+
+        // Match case:
+        harness.click(harness.get_by_label("Aa").id);
+        harness.run();
+        harness.run();
+        assert_eq!(num_highlighted(&harness), 2);
+
+        // Next match, and replace it:
+        harness.click(id.with("find_replacement"));
+        harness.run();
+        harness.type_text("baz");
+        harness.run();
+        harness.click(harness.get_by_label("Replace").id);
+        harness.run();
+        harness.click(harness.get_by_label("Replace").id);
+        harness.run();
+        assert_eq!(harness.state().0, "baz bar foo\nFoo");
+
+        harness.click(harness.get_by_label("Replace all").id);
+        harness.run();
+        assert_eq!(harness.state().0, "baz bar baz\nFoo");
+
+        // Close, and undo the replace all:
+        harness.run(); // the overlay is aligned to the right, so it moves when its width changes
+        harness.click(harness.get_by_label("✖").id);
+        harness.run();
+        harness.run();
+        assert!(harness.has_focus(id));
+        harness.key_press_with_modifiers(Modifiers::COMMAND, Key::Z);
+        harness.run();
+        assert_eq!(harness.state().0, "baz bar foo\nFoo");
+    }
+}
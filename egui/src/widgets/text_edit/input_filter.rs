@@ -0,0 +1,211 @@
+use std::{borrow::Cow, ops::Range};
+
+/// Decides what the user can type or paste into a [`crate::TextEdit`], see [`crate::TextEdit::input_filter`].
+///
+/// The filter runs before the text is inserted, so the cursor ends up after what was actually inserted.
+///
+/// Any `Fn(char) -> bool` is an [`InputFilter`] that only lets through the chars for which it returns `true`:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut hex = String::new();
+/// ui.add(egui::TextEdit::singleline(&mut hex).input_filter(|c: char| c.is_ascii_hexdigit()));
+/// # });
+/// ```
+///
+/// See also [`InputMask`].
+pub trait InputFilter {
+    /// `insertion` is about to replace the chars in `selection` of `text`
+    /// (an empty range if nothing is selected).
+    ///
+    /// Returns what to insert instead. Return an empty string to ignore the input.
+    fn filter(&self, text: &str, selection: Range<usize>, insertion: &str) -> String;
+}
+
+impl<F: Fn(char) -> bool> InputFilter for F {
+    fn filter(&self, _text: &str, _selection: Range<usize>, insertion: &str) -> String {
+        insertion.chars().filter(|&c| self(c)).collect()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// An [`InputFilter`] for a fixed format, like `__/__/____` for a date.
+///
+/// The user types into the placeholders (`_` by default),
+/// and the other chars of the mask are inserted automatically.
+/// Nothing can be typed beyond the end of the mask.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut date = String::new();
+/// let mask = egui::text_edit::InputMask::new("__/__/____").accept(|c| c.is_ascii_digit());
+/// let complete = mask.is_complete(&date);
+/// ui.add(egui::TextEdit::singleline(&mut date).input_filter(mask));
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+pub struct InputMask {
+    mask: Vec<char>,
+    placeholder: char,
+    accept: fn(char) -> bool,
+}
+
+impl InputMask {
+    /// By default the placeholders accept alphanumeric chars.
+    pub fn new(mask: &str) -> Self {
+        Self {
+            mask: mask.chars().collect(),
+            placeholder: '_',
+            accept: char::is_alphanumeric,
+        }
+    }
+
+    /// The char in the mask that the user types into. Default: `_`.
+    pub fn placeholder(mut self, placeholder: char) -> Self {
+        self.placeholder = placeholder;
+        self
+    }
+
+    /// Which chars can be typed into the placeholders. Default: [`char::is_alphanumeric`].
+    pub fn accept(mut self, accept: fn(char) -> bool) -> Self {
+        self.accept = accept;
+        self
+    }
+
+    /// Does the text fill out the whole mask?
+    pub fn is_complete(&self, text: &str) -> bool {
+        text.chars().count() == self.mask.len()
+            && text.chars().zip(&self.mask).all(|(c, &m)| {
+                if m == self.placeholder {
+                    (self.accept)(c)
+                } else {
+                    c == m
+                }
+            })
+    }
+}
+
+impl InputFilter for InputMask {
+    fn filter(&self, text: &str, selection: Range<usize>, insertion: &str) -> String {
+        let num_chars_left = text.chars().count() - selection.len();
+        let max_len = self.mask.len().saturating_sub(num_chars_left);
+
+        let mut position = selection.start;
+        let mut result = String::new();
+        for c in insertion.chars() {
+            // Insert the literal chars of the mask up to the next placeholder,
+            // unless the user typed the literal char:
+            let mut literals = String::new();
+            for (i, &m) in self.mask.iter().enumerate().skip(position) {
+                if (m == self.placeholder && (self.accept)(c)) || (m != self.placeholder && c == m)
+                {
+                    result += &literals;
+                    result.push(c);
+                    position = i + 1;
+                    break;
+                } else if m == self.placeholder {
+                    break; // ignore the char
+                }
+                literals.push(m);
+            }
+        }
+
+        result.chars().take(max_len).collect()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// The [`crate::TextEdit::char_limit`] and [`crate::TextEdit::input_filter`] of a [`crate::TextEdit`].
+pub(crate) struct InsertionFilter<'a> {
+    pub char_limit: usize,
+    pub input_filter: Option<&'a dyn InputFilter>,
+}
+
+impl<'a> InsertionFilter<'a> {
+    /// What to insert instead of `insertion`, which is about to replace `selection` in `text`.
+    pub fn filter<'i>(
+        &self,
+        text: &str,
+        selection: Range<usize>,
+        insertion: &'i str,
+    ) -> Cow<'i, str> {
+        let mut insertion = Cow::Borrowed(insertion);
+        if let Some(input_filter) = self.input_filter {
+            insertion = Cow::Owned(input_filter.filter(text, selection.clone(), &insertion));
+        }
+        self.limit(text, selection, insertion)
+    }
+
+    /// Truncates `insertion` to the [`Self::char_limit`], without the [`Self::input_filter`].
+    pub fn limit<'i>(
+        &self,
+        text: &str,
+        selection: Range<usize>,
+        insertion: Cow<'i, str>,
+    ) -> Cow<'i, str> {
+        let max_len = self.room(text, selection);
+        if max_len < usize::MAX && insertion.chars().count() > max_len {
+            return Cow::Owned(insertion.chars().take(max_len).collect());
+        }
+        insertion
+    }
+
+    /// How many chars can replace `selection` in `text` without going over the [`Self::char_limit`].
+    pub fn room(&self, text: &str, selection: Range<usize>) -> usize {
+        if self.char_limit == usize::MAX {
+            return usize::MAX;
+        }
+        let num_chars_left = text.chars().count() - selection.len();
+        self.char_limit.saturating_sub(num_chars_left)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_mask() {
+        let mask = InputMask::new("__/__/____").accept(|c| c.is_ascii_digit());
+        let filter = |text: &str, selection: Range<usize>, insertion: &str| {
+            mask.filter(text, selection, insertion)
+        };
+
+        assert_eq!(filter("", 0..0, "1"), "1");
+        assert_eq!(filter("12", 2..2, "0"), "/0");
+        assert_eq!(filter("12", 2..2, "/"), "/");
+        assert_eq!(filter("12", 2..2, "x"), "");
+        assert_eq!(filter("", 0..0, "12a05-2024 and more"), "12/05/2024");
+        assert_eq!(filter("12/05/2024", 6..10, "1999"), "1999");
+        assert_eq!(filter("12/05/2024", 10..10, "1"), "");
+
+        assert!(mask.is_complete("12/05/2024"));
+        assert!(!mask.is_complete("12/05/202"));
+        assert!(!mask.is_complete("12-05-2024"));
+    }
+
+    #[test]
+    fn test_insertion_filter() {
+        let digits = |c: char| c.is_ascii_digit();
+        let filter = InsertionFilter {
+            char_limit: 4,
+            input_filter: Some(&digits),
+        };
+        assert_eq!(filter.filter("12", 2..2, "3a4b5"), "34");
+        assert_eq!(filter.filter("1234", 1..3, "56789"), "56");
+        assert_eq!(filter.filter("1234", 4..4, "5"), "");
+
+        let no_filter = InsertionFilter {
+            char_limit: usize::MAX,
+            input_filter: None,
+        };
+        assert!(matches!(
+            no_filter.filter("", 0..0, "abc"),
+            Cow::Borrowed("abc")
+        ));
+    }
+}
@@ -0,0 +1,399 @@
+use crate::{Context, Key, KeyboardShortcut, Modifiers, ShortcutRegistry};
+
+/// Something a [`crate::TextEdit`] can do when a key is pressed, see [`TextEditKeymap`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextEditAction {
+    /// Move the cursor one character to the left,
+    /// or to the start of the selection if there is one.
+    MoveLeft,
+
+    /// Move the cursor one character to the right,
+    /// or to the end of the selection if there is one.
+    MoveRight,
+
+    /// Move the cursor one row up.
+    MoveUp,
+
+    /// Move the cursor one row down.
+    MoveDown,
+
+    /// Move the cursor to the start of the word.
+    MoveWordLeft,
+
+    /// Move the cursor to the end of the word.
+    MoveWordRight,
+
+    /// Move the cursor to the start of the row (of a wrapped line).
+    MoveToLineStart,
+
+    /// Move the cursor to the end of the row (of a wrapped line).
+    MoveToLineEnd,
+
+    /// Move the cursor to the start of the text.
+    MoveToTextStart,
+
+    /// Move the cursor to the end of the text.
+    MoveToTextEnd,
+
+    SelectAll,
+
+    /// Delete the character before the cursor, or the selection.
+    DeletePreviousChar,
+
+    /// Delete the character after the cursor, or the selection.
+    DeleteNextChar,
+
+    /// Delete the word before the cursor, or the selection.
+    DeletePreviousWord,
+
+    /// Delete the word after the cursor, or the selection.
+    DeleteNextWord,
+
+    /// Delete from the start of the line to the cursor.
+    DeleteToLineStart,
+
+    /// Delete from the cursor to the end of the line.
+    DeleteToLineEnd,
+
+    /// Swap the characters before and after the cursor,
+    /// or the two characters before the cursor at the end of a line.
+    Transpose,
+
+    Undo,
+
+    Redo,
+
+    /// Select the word at the cursor, or add a cursor at the next occurrence of the selected text.
+    SelectNextOccurrence,
+
+    /// Open the find/replace overlay, see [`crate::TextEdit::find_replace`].
+    Find,
+}
+
+impl TextEditAction {
+    /// Does this action move the cursor? Then holding down shift extends the selection.
+    pub fn moves_cursor(self) -> bool {
+        matches!(
+            self,
+            Self::MoveLeft
+                | Self::MoveRight
+                | Self::MoveUp
+                | Self::MoveDown
+                | Self::MoveWordLeft
+                | Self::MoveWordRight
+                | Self::MoveToLineStart
+                | Self::MoveToLineEnd
+                | Self::MoveToTextStart
+                | Self::MoveToTextEnd
+        )
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// The key bindings of [`crate::TextEdit`].
+///
+/// The keymap used by all [`crate::TextEdit`]s is the `text_edit_keymap` of [`Context::options`],
+/// and you can give a single [`crate::TextEdit`] its own with [`crate::TextEdit::keymap`].
+///
+/// The default is [`Self::mac`] on Mac and [`Self::pc`] elsewhere.
+/// On the web, where we don't know the platform,
+/// it is [`Self::pc`] with the bindings of [`Self::mac`] that use alt or ⌘ added.
+///
+/// ```
+/// use egui::{text_edit::{TextEditAction, TextEditKeymap}, Key, KeyboardShortcut, Modifiers};
+///
+/// let mut keymap = TextEditKeymap::pc().with_emacs_bindings();
+/// keymap.shortcuts.add(
+///     TextEditAction::MoveWordRight,
+///     KeyboardShortcut::new(Modifiers::ALT, Key::F),
+/// );
+/// assert_eq!(keymap.action(Modifiers::CTRL, Key::E), Some(TextEditAction::MoveToLineEnd));
+/// # let ctx = egui::Context::default();
+/// ctx.options().text_edit_keymap = keymap;
+/// ```
+#[derive(Clone, Debug)]
+pub struct TextEditKeymap {
+    /// The bindings, in priority order.
+    ///
+    /// Actions that move the cursor should be bound without shift:
+    /// they are also triggered with shift held down, which extends the selection.
+    pub shortcuts: ShortcutRegistry<TextEditAction>,
+}
+
+impl Default for TextEditKeymap {
+    fn default() -> Self {
+        if cfg!(target_os = "macos") {
+            Self::mac()
+        } else if cfg!(target_arch = "wasm32") {
+            let mut keymap = Self::pc();
+            for (action, shortcut) in Self::mac().shortcuts.iter() {
+                let modifiers = shortcut.modifiers;
+                if (modifiers.alt || modifiers.mac_cmd) && !keymap.conflicts_with(shortcut) {
+                    keymap.shortcuts.add(*action, *shortcut);
+                }
+            }
+            keymap
+        } else {
+            Self::pc()
+        }
+    }
+}
+
+impl TextEditKeymap {
+    /// No key bindings at all.
+    pub fn empty() -> Self {
+        Self {
+            shortcuts: Default::default(),
+        }
+    }
+
+    /// The conventions of Windows and Linux.
+    pub fn pc() -> Self {
+        let (none, shift, alt, ctrl, command) = (
+            Modifiers::NONE,
+            Modifiers::SHIFT,
+            Modifiers::ALT,
+            Modifiers::CTRL,
+            Modifiers::COMMAND,
+        );
+
+        let mut keymap = Self::from_bindings(&[
+            (TextEditAction::MoveLeft, none, Key::ArrowLeft),
+            (TextEditAction::MoveRight, none, Key::ArrowRight),
+            (TextEditAction::MoveUp, none, Key::ArrowUp),
+            (TextEditAction::MoveDown, none, Key::ArrowDown),
+            (TextEditAction::MoveWordLeft, ctrl, Key::ArrowLeft),
+            (TextEditAction::MoveWordRight, ctrl, Key::ArrowRight),
+            (TextEditAction::MoveWordLeft, alt, Key::ArrowLeft),
+            (TextEditAction::MoveWordRight, alt, Key::ArrowRight),
+            (TextEditAction::MoveToLineStart, none, Key::Home),
+            (TextEditAction::MoveToLineEnd, none, Key::End),
+            (TextEditAction::MoveToTextStart, ctrl, Key::Home),
+            (TextEditAction::MoveToTextEnd, ctrl, Key::End),
+            (TextEditAction::MoveToTextStart, command, Key::ArrowUp),
+            (TextEditAction::MoveToTextEnd, command, Key::ArrowDown),
+            (TextEditAction::SelectAll, command, Key::A),
+            (TextEditAction::DeletePreviousChar, none, Key::Backspace),
+            (TextEditAction::DeletePreviousChar, shift, Key::Backspace),
+            (TextEditAction::DeleteNextChar, none, Key::Delete),
+            (TextEditAction::DeletePreviousWord, ctrl, Key::Backspace),
+            (TextEditAction::DeleteNextWord, ctrl, Key::Delete),
+            (TextEditAction::DeletePreviousWord, ctrl, Key::W),
+            (TextEditAction::DeleteToLineStart, ctrl, Key::U),
+            (TextEditAction::DeleteToLineEnd, ctrl, Key::K),
+            (TextEditAction::Undo, command, Key::Z),
+            (TextEditAction::Redo, command, Key::Y),
+            (TextEditAction::Redo, command | shift, Key::Z),
+            (TextEditAction::SelectNextOccurrence, command, Key::D),
+            (TextEditAction::Find, command, Key::F),
+        ]);
+        if !cfg!(target_os = "windows") {
+            // On Windows shift-delete is cut.
+            keymap.shortcuts.add(
+                TextEditAction::DeleteNextChar,
+                KeyboardShortcut::new(shift, Key::Delete),
+            );
+        }
+        keymap
+    }
+
+    /// The conventions of Mac, including the Emacs-like bindings with ctrl.
+    pub fn mac() -> Self {
+        let (none, shift, alt, cmd) = (
+            Modifiers::NONE,
+            Modifiers::SHIFT,
+            Modifiers::ALT,
+            Modifiers::MAC_CMD,
+        );
+
+        let keymap = Self::from_bindings(&[
+            (TextEditAction::MoveLeft, none, Key::ArrowLeft),
+            (TextEditAction::MoveRight, none, Key::ArrowRight),
+            (TextEditAction::MoveUp, none, Key::ArrowUp),
+            (TextEditAction::MoveDown, none, Key::ArrowDown),
+            (TextEditAction::MoveWordLeft, alt, Key::ArrowLeft),
+            (TextEditAction::MoveWordRight, alt, Key::ArrowRight),
+            (TextEditAction::MoveToLineStart, cmd, Key::ArrowLeft),
+            (TextEditAction::MoveToLineEnd, cmd, Key::ArrowRight),
+            (TextEditAction::MoveToLineStart, none, Key::Home),
+            (TextEditAction::MoveToLineEnd, none, Key::End),
+            (TextEditAction::MoveToTextStart, cmd, Key::ArrowUp),
+            (TextEditAction::MoveToTextEnd, cmd, Key::ArrowDown),
+            (TextEditAction::SelectAll, cmd, Key::A),
+            (TextEditAction::DeletePreviousChar, none, Key::Backspace),
+            (TextEditAction::DeletePreviousChar, shift, Key::Backspace),
+            (TextEditAction::DeleteNextChar, none, Key::Delete),
+            (TextEditAction::DeleteNextChar, shift, Key::Delete),
+            (TextEditAction::DeletePreviousWord, alt, Key::Backspace),
+            (TextEditAction::DeleteNextWord, alt, Key::Delete),
+            (TextEditAction::DeleteToLineStart, cmd, Key::Backspace),
+            (TextEditAction::DeleteToLineEnd, cmd, Key::Delete),
+            (TextEditAction::Undo, cmd, Key::Z),
+            (TextEditAction::Redo, cmd | shift, Key::Z),
+            (TextEditAction::SelectNextOccurrence, cmd, Key::D),
+            (TextEditAction::Find, cmd, Key::F),
+        ]);
+        keymap.with_emacs_bindings()
+    }
+
+    /// Adds the Emacs bindings with ctrl (`ctrl-A` to go to the start of the line etc),
+    /// replacing the bindings they conflict with (like `ctrl-A` to select all on Windows and Linux).
+    pub fn with_emacs_bindings(self) -> Self {
+        let ctrl = Modifiers::CTRL;
+
+        let emacs = Self::from_bindings(&[
+            (TextEditAction::MoveLeft, ctrl, Key::B),
+            (TextEditAction::MoveRight, ctrl, Key::F),
+            (TextEditAction::MoveUp, ctrl, Key::P),
+            (TextEditAction::MoveDown, ctrl, Key::N),
+            (TextEditAction::MoveToLineStart, ctrl, Key::A),
+            (TextEditAction::MoveToLineEnd, ctrl, Key::E),
+            (TextEditAction::DeletePreviousChar, ctrl, Key::H),
+            (TextEditAction::DeleteNextChar, ctrl, Key::D),
+            (TextEditAction::DeletePreviousWord, ctrl, Key::W),
+            (TextEditAction::DeleteToLineStart, ctrl, Key::U),
+            (TextEditAction::DeleteToLineEnd, ctrl, Key::K),
+            (TextEditAction::Transpose, ctrl, Key::T),
+        ]);
+
+        let mut keymap = Self::empty();
+        for (action, shortcut) in self.shortcuts.iter() {
+            if !emacs.conflicts_with(shortcut) {
+                keymap.shortcuts.add(*action, *shortcut);
+            }
+        }
+        for (action, shortcut) in emacs.shortcuts.iter() {
+            keymap.shortcuts.add(*action, *shortcut);
+        }
+        keymap
+    }
+
+    fn from_bindings(bindings: &[(TextEditAction, Modifiers, Key)]) -> Self {
+        let mut keymap = Self::empty();
+        for &(action, modifiers, key) in bindings {
+            keymap
+                .shortcuts
+                .add(action, KeyboardShortcut::new(modifiers, key));
+        }
+        keymap
+    }
+
+    /// Would the shortcut trigger any of our bindings (see [`KeyboardShortcut::conflicts_with`])?
+    fn conflicts_with(&self, shortcut: &KeyboardShortcut) -> bool {
+        self.shortcuts
+            .iter()
+            .any(|(_, other)| other.conflicts_with(shortcut))
+    }
+
+    /// The action bound to pressing `key` with `modifiers`, if any.
+    ///
+    /// If there is no binding with shift, then the actions that move the cursor
+    /// are also triggered with shift held down (and should extend the selection).
+    pub fn action(&self, modifiers: Modifiers, key: Key) -> Option<TextEditAction> {
+        let without_shift = Modifiers {
+            shift: false,
+            ..modifiers
+        };
+        let find = |modifiers: Modifiers, only_moves: bool| {
+            self.shortcuts
+                .iter()
+                .find(|(action, shortcut)| {
+                    shortcut.key == key
+                        && modifiers.matches(shortcut.modifiers)
+                        && (!only_moves || action.moves_cursor())
+                })
+                .map(|(action, _)| *action)
+        };
+        find(modifiers, false).or_else(|| {
+            if modifiers.shift {
+                find(without_shift, true)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// The keymap of a [`crate::TextEdit`]: its own, or the one in the options of the [`Context`].
+///
+/// This avoids cloning the keymap out of the options every frame.
+#[derive(Clone, Copy)]
+pub(crate) enum KeymapSource<'a> {
+    Own(&'a TextEditKeymap),
+    Options(&'a Context),
+}
+
+impl<'a> KeymapSource<'a> {
+    /// Don't lock the [`Context`] in `reader`, it is already locked.
+    pub fn read<R>(self, reader: impl FnOnce(&TextEditKeymap) -> R) -> R {
+        match self {
+            Self::Own(keymap) => reader(keymap),
+            Self::Options(ctx) => reader(&ctx.options().text_edit_keymap),
+        }
+    }
+
+    /// The action bound to pressing `key` with `modifiers`, see [`TextEditKeymap::action`].
+    pub fn action(self, modifiers: Modifiers, key: Key) -> Option<TextEditAction> {
+        self.read(|keymap| keymap.action(modifiers, key))
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keymap_actions() {
+        let pc = TextEditKeymap::pc();
+        let ctrl = Modifiers::CTRL | Modifiers::COMMAND; // as pressed on Windows and Linux
+        assert_eq!(pc.action(ctrl, Key::Z), Some(TextEditAction::Undo));
+        assert_eq!(
+            pc.action(ctrl | Modifiers::SHIFT, Key::Z),
+            Some(TextEditAction::Redo)
+        );
+        assert_eq!(
+            pc.action(ctrl | Modifiers::SHIFT, Key::ArrowLeft),
+            Some(TextEditAction::MoveWordLeft)
+        );
+        assert_eq!(
+            pc.action(Modifiers::SHIFT, Key::Delete),
+            if cfg!(target_os = "windows") {
+                None
+            } else {
+                Some(TextEditAction::DeleteNextChar)
+            }
+        );
+        assert_eq!(pc.action(ctrl, Key::A), Some(TextEditAction::SelectAll));
+        assert_eq!(
+            pc.action(Modifiers::ALT, Key::ArrowLeft),
+            Some(TextEditAction::MoveWordLeft)
+        );
+
+        let emacs = pc.with_emacs_bindings();
+        assert_eq!(
+            emacs.action(ctrl, Key::A),
+            Some(TextEditAction::MoveToLineStart)
+        );
+        assert_eq!(emacs.action(ctrl, Key::T), Some(TextEditAction::Transpose));
+        assert_eq!(emacs.action(ctrl, Key::Z), Some(TextEditAction::Undo));
+
+        let mac = TextEditKeymap::mac();
+        let cmd = Modifiers::MAC_CMD | Modifiers::COMMAND; // as pressed on Mac
+        assert_eq!(mac.action(cmd, Key::A), Some(TextEditAction::SelectAll));
+        assert_eq!(
+            mac.action(Modifiers::CTRL, Key::A),
+            Some(TextEditAction::MoveToLineStart)
+        );
+        assert_eq!(
+            mac.action(Modifiers::ALT, Key::ArrowRight),
+            Some(TextEditAction::MoveWordRight)
+        );
+        assert!(mac.shortcuts.conflicts().is_empty());
+        assert!(TextEditKeymap::pc().shortcuts.conflicts().is_empty());
+    }
+}
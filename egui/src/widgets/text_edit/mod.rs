@@ -1,10 +1,27 @@
 mod builder;
+mod code_editor;
 mod cursor_range;
+mod find_replace;
+mod input_filter;
+mod keymap;
 mod output;
+#[cfg(feature = "rope")]
+mod rope_buffer;
 mod state;
 mod text_buffer;
 
 pub use {
-    builder::TextEdit, cursor_range::*, output::TextEditOutput, state::TextEditState,
+    builder::TextEdit,
+    cursor_range::*,
+    find_replace::FindReplaceState,
+    input_filter::{InputFilter, InputMask},
+    keymap::{TextEditAction, TextEditKeymap},
+    output::TextEditOutput,
+    state::TextEditState,
     text_buffer::TextBuffer,
 };
+
+#[cfg(feature = "rope")]
+pub use rope_buffer::RopeBuffer;
+
+pub(crate) use builder::{paint_cursor_selection, select_line_at, select_word_at};
@@ -19,6 +19,9 @@ pub struct TextEditOutput {
 
     /// Where the text cursor is.
     pub cursor_range: Option<super::CursorRange>,
+
+    /// The error returned by the [`crate::TextEdit::validator`], if any.
+    pub validation_error: Option<String>,
 }
 
 // TODO(emilk): add `output.paint` and `output.store` and split out that code from `TextEdit::show`.
@@ -0,0 +1,198 @@
+use std::ops::Range;
+
+use once_cell::sync::OnceCell;
+use ropey::Rope;
+
+use super::TextBuffer;
+
+/// A [`TextBuffer`] backed by a rope ([`ropey::Rope`]), for editing huge texts.
+///
+/// Inserting and deleting text in the rope is fast no matter how long the text is,
+/// and so is going from character indices to byte indices.
+///
+/// [`crate::TextEdit`] still needs the whole text as one `&str` every frame (to lay it out).
+/// That string is put together the first time it is asked for,
+/// and after that each change is applied to it too, instead of putting it together again.
+/// This is only a `memmove` of the text after the change, but it is not free for huge texts.
+///
+/// ## Limitations
+/// A rope does not make [`crate::TextEdit`] itself scale with the size of the text.
+/// Every frame it still does work proportional to the whole text:
+/// * the layout is looked up in the galley cache by a hash of the whole text,
+/// * the text is copied for the [`crate::WidgetInfo`] and for the undo history
+///   (each undo point is a full copy of the text).
+///
+/// When the text changes, every paragraph is hashed again to find the ones that changed.
+/// In a release build, a 10 MB text takes around 10 ms per frame, and around 40 ms for a frame with an edit.
+///
+/// Use together with [`crate::TextEdit::incremental_layout`]:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let huge_text = "Hello\nworld";
+/// let mut text = egui::text_edit::RopeBuffer::new(huge_text);
+/// egui::ScrollArea::vertical().show(ui, |ui| {
+///     ui.add(egui::TextEdit::multiline(&mut text).incremental_layout(true));
+/// });
+/// # });
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RopeBuffer {
+    rope: Rope,
+
+    /// [`Self::rope`] as one string, put together when first needed and then kept in sync.
+    text: OnceCell<String>,
+}
+
+impl RopeBuffer {
+    pub fn new(text: &str) -> Self {
+        Self::from_rope(Rope::from_str(text))
+    }
+
+    pub fn from_rope(rope: Rope) -> Self {
+        Self {
+            rope,
+            text: Default::default(),
+        }
+    }
+
+    pub fn rope(&self) -> &Rope {
+        &self.rope
+    }
+
+    /// Change the rope directly.
+    pub fn rope_mut(&mut self) -> &mut Rope {
+        self.text = Default::default();
+        &mut self.rope
+    }
+
+    pub fn into_rope(self) -> Rope {
+        self.rope
+    }
+}
+
+impl From<&str> for RopeBuffer {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<Rope> for RopeBuffer {
+    fn from(rope: Rope) -> Self {
+        Self::from_rope(rope)
+    }
+}
+
+impl PartialEq for RopeBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        self.rope == other.rope
+    }
+}
+
+impl AsRef<str> for RopeBuffer {
+    fn as_ref(&self) -> &str {
+        self.text.get_or_init(|| self.rope.to_string())
+    }
+}
+
+impl TextBuffer for RopeBuffer {
+    fn is_mutable(&self) -> bool {
+        true
+    }
+
+    fn byte_index_from_char_index(&self, char_index: usize) -> usize {
+        self.rope
+            .char_to_byte(char_index.min(self.rope.len_chars()))
+    }
+
+    fn insert_text(&mut self, text: &str, char_index: usize) -> usize {
+        let char_index = char_index.min(self.rope.len_chars());
+        if let Some(string) = self.text.get_mut() {
+            string.insert_str(self.rope.char_to_byte(char_index), text);
+        }
+        self.rope.insert(char_index, text);
+        text.chars().count()
+    }
+
+    fn delete_char_range(&mut self, char_range: Range<usize>) {
+        assert!(char_range.start <= char_range.end);
+        let num_chars = self.rope.len_chars();
+        let char_range = char_range.start.min(num_chars)..char_range.end.min(num_chars);
+        if let Some(string) = self.text.get_mut() {
+            let byte_range =
+                self.rope.char_to_byte(char_range.start)..self.rope.char_to_byte(char_range.end);
+            string.replace_range(byte_range, "");
+        }
+        self.rope.remove(char_range);
+    }
+
+    fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn replace(&mut self, text: &str) {
+        *self = Self::new(text);
+    }
+
+    fn take(&mut self) -> String {
+        let text = self.as_ref().to_owned();
+        self.clear();
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RopeBuffer, TextBuffer as _};
+
+    #[test]
+    fn edit_rope_buffer() {
+        let mut text = RopeBuffer::new("Hello wörld");
+        assert_eq!(text.as_str(), "Hello wörld");
+        assert_eq!(text.char_range(6..9), "wör");
+
+        assert_eq!(text.insert_text(", big", 5), 5);
+        assert_eq!(text.as_str(), "Hello, big wörld");
+        text.delete_char_range(0..7);
+        assert_eq!(text.as_str(), "big wörld");
+        assert_eq!(text.rope().len_chars(), 9);
+
+        assert_eq!(text.insert_text("ö", 100), 1);
+        assert_eq!(text.as_str(), "big wörldö");
+        text.delete_char_range(9..100);
+        assert_eq!(text.as_str(), "big wörld");
+        assert_eq!(text.rope().to_string(), "big wörld");
+
+        assert_eq!(text.take(), "big wörld");
+        assert_eq!(text.as_str(), "");
+    }
+
+    #[test]
+    fn edit_multi_megabyte_text() {
+        use crate::{testing::Harness, ScrollArea, TextEdit, WidgetType};
+
+        // 10 MB in 250 000 lines:
+        let line = format!("{}\n", "x".repeat(39));
+        let text = RopeBuffer::new(&line.repeat(250_000));
+        let mut harness = Harness::new_ui_state((text, 0), |ui, (text, num_rows)| {
+            ScrollArea::vertical().show(ui, |ui| {
+                let output = TextEdit::multiline(text).incremental_layout(true).show(ui);
+                *num_rows = output.galley.rows.len();
+            });
+        });
+        harness.run();
+
+        // Only the visible lines are laid out, the rest are placeholder rows:
+        assert!(harness.state().1 < 1_000, "{} rows", harness.state().1);
+
+        let id = harness.get_by_type(WidgetType::TextEdit).id;
+        harness.ctx().memory().request_focus(id);
+        harness.run();
+        harness.type_text("y"); // at the end
+        harness.run();
+        let rope = harness.state().0.rope();
+        assert_eq!(rope.len_chars(), 10_000_001);
+        assert_eq!(rope.char(10_000_000), 'y');
+        assert_eq!(harness.state().0.as_str().len(), 10_000_001);
+    }
+}
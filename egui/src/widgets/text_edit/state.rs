@@ -4,20 +4,24 @@ use crate::mutex::Mutex;
 
 use crate::*;
 
-use super::{CCursorRange, CursorRange};
+use super::{merge_overlapping, CCursorRange, CursorRange, FindReplaceState};
 
-type Undoer = crate::util::undoer::Undoer<(CCursorRange, String)>;
+type Undoer = crate::util::undoer::Undoer<(Vec<CCursorRange>, String)>;
 
 /// The text edit state stored between frames.
+///
+/// A [`TextEdit`] can have several cursors (added with alt-click, or with ctrl/cmd-D
+/// to select the next occurrence of the selected text).
+/// The last one is the _primary_ cursor: the one that is kept in view, and used for IME.
 #[derive(Clone, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(default))]
 pub struct TextEditState {
-    cursor_range: Option<CursorRange>,
+    cursor_ranges: Vec<CursorRange>,
 
     /// This is what is easiest to work with when editing text,
     /// so users are more likely to read/write this.
-    ccursor_range: Option<CCursorRange>,
+    ccursor_ranges: Vec<CCursorRange>,
 
     /// Wrapped in Arc for cheaper clones.
     #[cfg_attr(feature = "serde", serde(skip))]
@@ -30,6 +34,9 @@ pub struct TextEditState {
     // Visual offset when editing singleline text bigger than the width.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) singleline_offset: f32,
+
+    /// The find/replace overlay, see [`TextEdit::find_replace`].
+    pub(crate) find_replace: FindReplaceState,
 }
 
 impl TextEditState {
@@ -41,45 +48,100 @@ impl TextEditState {
         ctx.data().insert_persisted(id, self);
     }
 
-    /// The the currently selected range of characters.
+    /// The the currently selected range of characters of the primary cursor.
     pub fn ccursor_range(&self) -> Option<CCursorRange> {
-        self.ccursor_range.or_else(|| {
-            self.cursor_range
+        self.ccursor_ranges().pop()
+    }
+
+    /// The currently selected ranges of characters, one per cursor.
+    ///
+    /// The last one is the primary cursor.
+    pub fn ccursor_ranges(&self) -> Vec<CCursorRange> {
+        if self.ccursor_ranges.is_empty() {
+            self.cursor_ranges
+                .iter()
                 .map(|cursor_range| cursor_range.as_ccursor_range())
-        })
+                .collect()
+        } else {
+            self.ccursor_ranges.clone()
+        }
     }
 
-    /// Sets the currently selected range of characters.
+    /// Sets the currently selected range of characters, removing any other cursors.
     pub fn set_ccursor_range(&mut self, ccursor_range: Option<CCursorRange>) {
-        self.cursor_range = None;
-        self.ccursor_range = ccursor_range;
+        self.set_ccursor_ranges(ccursor_range.into_iter().collect());
     }
 
+    /// Sets the cursors, one per selected range of characters.
+    ///
+    /// The last one is the primary cursor.
+    /// Overlapping ranges are kept as they are until [`Self::cursor_ranges`] merges them,
+    /// which the [`TextEdit`] does the next time it is shown.
+    pub fn set_ccursor_ranges(&mut self, ccursor_ranges: Vec<CCursorRange>) {
+        self.cursor_ranges.clear();
+        self.ccursor_ranges = ccursor_ranges;
+    }
+
+    /// Sets the cursor, removing any other cursors.
     pub fn set_cursor_range(&mut self, cursor_range: Option<CursorRange>) {
-        self.cursor_range = cursor_range;
-        self.ccursor_range = None;
+        self.set_cursor_ranges(cursor_range.into_iter().collect());
+    }
+
+    /// Sets the cursors.
+    ///
+    /// The last one is the primary cursor.
+    /// Overlapping ranges are kept as they are until [`Self::cursor_ranges`] merges them,
+    /// which the [`TextEdit`] does the next time it is shown.
+    pub fn set_cursor_ranges(&mut self, cursor_ranges: Vec<CursorRange>) {
+        self.cursor_ranges = cursor_ranges;
+        self.ccursor_ranges.clear();
     }
 
+    /// The state of the find/replace overlay, see [`TextEdit::find_replace`].
+    pub fn find_replace(&self) -> &FindReplaceState {
+        &self.find_replace
+    }
+
+    /// Change the find/replace overlay, e.g. to open it with a query.
+    pub fn find_replace_mut(&mut self) -> &mut FindReplaceState {
+        &mut self.find_replace
+    }
+
+    /// The primary cursor.
     pub fn cursor_range(&mut self, galley: &Galley) -> Option<CursorRange> {
-        self.cursor_range
-            .map(|cursor_range| {
-                // We only use the PCursor (paragraph number, and character offset within that paragraph).
-                // This is so that if we resize the [`TextEdit`] region, and text wrapping changes,
-                // we keep the same byte character offset from the beginning of the text,
-                // even though the number of rows changes
-                // (each paragraph can be several rows, due to word wrapping).
-                // The column (character offset) should be able to extend beyond the last word so that we can
-                // go down and still end up on the same column when we return.
-                CursorRange {
-                    primary: galley.from_pcursor(cursor_range.primary.pcursor),
-                    secondary: galley.from_pcursor(cursor_range.secondary.pcursor),
-                }
-            })
-            .or_else(|| {
-                self.ccursor_range.map(|ccursor_range| CursorRange {
+        self.cursor_ranges(galley).pop()
+    }
+
+    /// All the cursors, with overlapping ranges merged.
+    ///
+    /// The last one is the primary cursor.
+    pub fn cursor_ranges(&mut self, galley: &Galley) -> Vec<CursorRange> {
+        let cursor_ranges = if self.ccursor_ranges.is_empty() {
+            self.cursor_ranges
+                .iter()
+                .map(|cursor_range| {
+                    // We only use the PCursor (paragraph number, and character offset within that paragraph).
+                    // This is so that if we resize the [`TextEdit`] region, and text wrapping changes,
+                    // we keep the same byte character offset from the beginning of the text,
+                    // even though the number of rows changes
+                    // (each paragraph can be several rows, due to word wrapping).
+                    // The column (character offset) should be able to extend beyond the last word so that we can
+                    // go down and still end up on the same column when we return.
+                    CursorRange {
+                        primary: galley.from_pcursor(cursor_range.primary.pcursor),
+                        secondary: galley.from_pcursor(cursor_range.secondary.pcursor),
+                    }
+                })
+                .collect()
+        } else {
+            self.ccursor_ranges
+                .iter()
+                .map(|ccursor_range| CursorRange {
                     primary: galley.from_ccursor(ccursor_range.primary),
                     secondary: galley.from_ccursor(ccursor_range.secondary),
                 })
-            })
+                .collect()
+        };
+        merge_overlapping(cursor_ranges)
     }
 }
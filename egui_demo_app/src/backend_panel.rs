@@ -149,6 +149,11 @@ impl BackendPanel {
             }
         }
 
+        ui.menu_button(
+            format!("🔍 Zoom: {:.0}%", 100.0 * ui.ctx().zoom_factor()),
+            egui::gui_zoom::zoom_menu_buttons,
+        );
+
         if !frame.is_web()
             && ui
                 .button("📱 Phone Size")
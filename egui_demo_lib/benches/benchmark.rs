@@ -36,6 +36,21 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         });
     }
 
+    // Replay a real session of an app showing `DemoWindows`, recorded with `egui::recording`
+    // (e.g. using `eframe::NativeOptions::input_recording_path`):
+    if let Ok(path) = std::env::var("EGUI_BENCH_RECORDING") {
+        let ron = std::fs::read_to_string(&path).expect("Failed to read recording");
+        let recording =
+            egui::recording::InputRecording::from_ron(&ron).expect("Failed to parse recording");
+        c.bench_function("demo_replay", |b| {
+            b.iter(|| {
+                let ctx = egui::Context::default();
+                let mut demo_windows = egui_demo_lib::DemoWindows::default();
+                recording.replay(&ctx, |ctx| demo_windows.ui(ctx))
+            });
+        });
+    }
+
     if false {
         let ctx = egui::Context::default();
         ctx.memory().set_everything_is_visible(true); // give us everything
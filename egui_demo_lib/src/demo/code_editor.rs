@@ -87,6 +87,10 @@ impl super::View for CodeEditor {
                 egui::TextEdit::multiline(code)
                     .font(egui::TextStyle::Monospace) // for cursor height
                     .code_editor()
+                    .line_numbers(true)
+                    .highlight_current_line(true)
+                    .match_brackets(true)
+                    .auto_indent(true)
                     .desired_rows(10)
                     .lock_focus(true)
                     .desired_width(f32::INFINITY)
@@ -28,6 +28,7 @@ impl Default for Demos {
             Box::new(super::drag_and_drop::DragAndDropDemo::default()),
             Box::new(super::font_book::FontBook::default()),
             Box::new(super::MiscDemoWindow::default()),
+            Box::new(super::modals::Modals::default()),
             Box::new(super::multi_touch::MultiTouch::default()),
             Box::new(super::painting::Painting::default()),
             Box::new(super::plot_demo::PlotDemo::default()),
@@ -36,6 +37,7 @@ impl Default for Demos {
             Box::new(super::strip_demo::StripDemo::default()),
             Box::new(super::table_demo::TableDemo::default()),
             Box::new(super::text_edit::TextEdit::default()),
+            Box::new(super::toasts::ToastsDemo::default()),
             Box::new(super::widget_gallery::WidgetGallery::default()),
             Box::new(super::window_options::WindowOptions::default()),
             Box::new(super::tests::WindowResizeTest::default()),
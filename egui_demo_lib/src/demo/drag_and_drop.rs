@@ -1,80 +1,5 @@
 use egui::*;
 
-pub fn drag_source(ui: &mut Ui, id: Id, body: impl FnOnce(&mut Ui)) {
-    let is_being_dragged = ui.memory().is_being_dragged(id);
-
-    if !is_being_dragged {
-        let response = ui.scope(body).response;
-
-        // Check for drags:
-        let response = ui.interact(response.rect, id, Sense::drag());
-        if response.hovered() {
-            ui.output().cursor_icon = CursorIcon::Grab;
-        }
-    } else {
-        ui.output().cursor_icon = CursorIcon::Grabbing;
-
-        // Paint the body to a new layer:
-        let layer_id = LayerId::new(Order::Tooltip, id);
-        let response = ui.with_layer_id(layer_id, body).response;
-
-        // Now we move the visuals of the body to where the mouse is.
-        // Normally you need to decide a location for a widget first,
-        // because otherwise that widget cannot interact with the mouse.
-        // However, a dragged component cannot be interacted with anyway
-        // (anything with `Order::Tooltip` always gets an empty [`Response`])
-        // So this is fine!
-
-        if let Some(pointer_pos) = ui.ctx().pointer_interact_pos() {
-            let delta = pointer_pos - response.rect.center();
-            ui.ctx().translate_layer(layer_id, delta);
-        }
-    }
-}
-
-pub fn drop_target<R>(
-    ui: &mut Ui,
-    can_accept_what_is_being_dragged: bool,
-    body: impl FnOnce(&mut Ui) -> R,
-) -> InnerResponse<R> {
-    let is_being_dragged = ui.memory().is_anything_being_dragged();
-
-    let margin = Vec2::splat(4.0);
-
-    let outer_rect_bounds = ui.available_rect_before_wrap();
-    let inner_rect = outer_rect_bounds.shrink2(margin);
-    let where_to_put_background = ui.painter().add(Shape::Noop);
-    let mut content_ui = ui.child_ui(inner_rect, *ui.layout());
-    let ret = body(&mut content_ui);
-    let outer_rect = Rect::from_min_max(outer_rect_bounds.min, content_ui.min_rect().max + margin);
-    let (rect, response) = ui.allocate_at_least(outer_rect.size(), Sense::hover());
-
-    let style = if is_being_dragged && can_accept_what_is_being_dragged && response.hovered() {
-        ui.visuals().widgets.active
-    } else {
-        ui.visuals().widgets.inactive
-    };
-
-    let mut fill = style.bg_fill;
-    let mut stroke = style.bg_stroke;
-    if is_being_dragged && !can_accept_what_is_being_dragged {
-        // gray out:
-        fill = color::tint_color_towards(fill, ui.visuals().window_fill());
-        stroke.color = color::tint_color_towards(stroke.color, ui.visuals().window_fill());
-    }
-
-    ui.painter().set(
-        where_to_put_background,
-        epaint::RectShape {
-            rounding: style.rounding,
-            fill,
-            stroke,
-            rect,
-        },
-    );
-
-    InnerResponse::new(ret, response)
-}
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct DragAndDropDemo {
@@ -114,21 +39,28 @@ impl super::Demo for DragAndDropDemo {
 
 impl super::View for DragAndDropDemo {
     fn ui(&mut self, ui: &mut Ui) {
-        ui.label("This is a proof-of-concept of drag-and-drop in egui.");
-        ui.label("Drag items between columns.");
+        ui.label("This is a simple example of drag-and-drop in egui.");
+        ui.label("Drag items between columns. Press Escape to cancel a drag.");
+
+        // If there is a drop, store the location of the item being dragged, and the destination for the drop.
+        let mut from: Option<std::sync::Arc<Location>> = None;
+        let mut to = None;
 
-        let id_source = "my_drag_and_drop_demo";
-        let mut source_col_row = None;
-        let mut drop_col = None;
         ui.columns(self.columns.len(), |uis| {
             for (col_idx, column) in self.columns.clone().into_iter().enumerate() {
                 let ui = &mut uis[col_idx];
-                let can_accept_what_is_being_dragged = true; // We accept anything being dragged (for now) ¯\_(ツ)_/¯
-                let response = drop_target(ui, can_accept_what_is_being_dragged, |ui| {
+
+                let frame = Frame::default().inner_margin(4.0);
+
+                let (response, dropped_payload) = ui.dnd_drop_zone::<Location, _>(frame, |ui| {
                     ui.set_min_size(vec2(64.0, 100.0));
                     for (row_idx, item) in column.iter().enumerate() {
-                        let item_id = Id::new(id_source).with(col_idx).with(row_idx);
-                        drag_source(ui, item_id, |ui| {
+                        let item_id = Id::new(("my_drag_and_drop_demo", col_idx, row_idx));
+                        let item_location = Location {
+                            col: col_idx,
+                            row: row_idx,
+                        };
+                        ui.dnd_drag_source(item_id, item_location, |ui| {
                             let response = ui.add(Label::new(item).sense(Sense::click()));
                             response.context_menu(|ui| {
                                 if ui.button("Remove").clicked() {
@@ -137,36 +69,27 @@ impl super::View for DragAndDropDemo {
                                 }
                             });
                         });
-
-                        if ui.memory().is_being_dragged(item_id) {
-                            source_col_row = Some((col_idx, row_idx));
-                        }
                     }
-                })
-                .response;
+                });
 
-                let response = response.context_menu(|ui| {
+                response.response.context_menu(|ui| {
                     if ui.button("New Item").clicked() {
-                        self.columns[col_idx].push("New Item".to_string());
+                        self.columns[col_idx].push("New Item".to_owned());
                         ui.close_menu();
                     }
                 });
 
-                let is_being_dragged = ui.memory().is_anything_being_dragged();
-                if is_being_dragged && can_accept_what_is_being_dragged && response.hovered() {
-                    drop_col = Some(col_idx);
+                if let Some(dragged_payload) = dropped_payload {
+                    from = Some(dragged_payload);
+                    to = Some(col_idx);
                 }
             }
         });
 
-        if let Some((source_col, source_row)) = source_col_row {
-            if let Some(drop_col) = drop_col {
-                if ui.input().pointer.any_released() {
-                    // do the drop:
-                    let item = self.columns[source_col].remove(source_row);
-                    self.columns[drop_col].push(item);
-                }
-            }
+        if let (Some(from), Some(to)) = (from, to) {
+            // do the drop:
+            let item = self.columns[from.col].remove(from.row);
+            self.columns[to].push(item);
         }
 
         ui.vertical_centered(|ui| {
@@ -174,3 +97,10 @@ impl super::View for DragAndDropDemo {
         });
     }
 }
+
+/// What is being dragged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Location {
+    col: usize,
+    row: usize,
+}
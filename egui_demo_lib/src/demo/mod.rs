@@ -14,6 +14,7 @@ pub mod drag_and_drop;
 pub mod font_book;
 pub mod layout_test;
 pub mod misc_demo_window;
+pub mod modals;
 pub mod multi_touch;
 pub mod paint_bezier;
 pub mod painting;
@@ -25,6 +26,7 @@ pub mod strip_demo;
 pub mod table_demo;
 pub mod tests;
 pub mod text_edit;
+pub mod toasts;
 pub mod toggle_switch;
 pub mod widget_gallery;
 pub mod window_options;
@@ -0,0 +1,101 @@
+use egui::*;
+
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Modals {
+    name: String,
+    saved_name: String,
+    close_on_backdrop_click: bool,
+    edit_open: bool,
+    confirm_open: bool,
+}
+
+impl Default for Modals {
+    fn default() -> Self {
+        Self {
+            name: "Ferris".to_owned(),
+            saved_name: "Ferris".to_owned(),
+            close_on_backdrop_click: true,
+            edit_open: false,
+            confirm_open: false,
+        }
+    }
+}
+
+impl super::Demo for Modals {
+    fn name(&self) -> &'static str {
+        "🗖 Modals"
+    }
+
+    fn show(&mut self, ctx: &Context, open: &mut bool) {
+        use super::View as _;
+        Window::new(self.name())
+            .open(open)
+            .resizable(false)
+            .show(ctx, |ui| self.ui(ui));
+    }
+}
+
+impl super::View for Modals {
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.label("A modal blocks the rest of the ui until it is closed.");
+        ui.label("Press escape to close the top-most modal.");
+        ui.checkbox(
+            &mut self.close_on_backdrop_click,
+            "Close when clicking outside the modal",
+        );
+        ui.horizontal(|ui| {
+            ui.label(format!("Name: {}", self.saved_name));
+            if ui.button("Edit…").clicked() {
+                self.name = self.saved_name.clone();
+                self.edit_open = true;
+            }
+        });
+
+        if self.edit_open {
+            let response = Modal::new("edit_name_modal")
+                .close_on_backdrop_click(self.close_on_backdrop_click)
+                .show(ui.ctx(), |ui| {
+                    ui.heading("Edit name");
+                    ui.text_edit_singleline(&mut self.name);
+                    ui.horizontal(|ui| {
+                        if ui.button("Save").clicked() {
+                            self.confirm_open = true;
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.edit_open = false;
+                        }
+                    });
+                });
+            if response.should_close {
+                self.edit_open = false;
+            }
+        }
+
+        if self.confirm_open {
+            let response = Modal::new("confirm_name_modal")
+                .close_on_backdrop_click(self.close_on_backdrop_click)
+                .show(ui.ctx(), |ui| {
+                    ui.label(format!("Change the name to {:?}?", self.name));
+                    ui.horizontal(|ui| {
+                        if ui.button("Yes").clicked() {
+                            self.saved_name = self.name.clone();
+                            self.confirm_open = false;
+                            self.edit_open = false;
+                        }
+                        if ui.button("No").clicked() {
+                            self.confirm_open = false;
+                        }
+                    });
+                });
+            if response.should_close {
+                self.confirm_open = false;
+            }
+        }
+
+        ui.vertical_centered(|ui| {
+            ui.add(crate::egui_github_link_file!());
+        });
+    }
+}
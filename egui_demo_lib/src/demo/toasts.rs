@@ -0,0 +1,114 @@
+use egui::*;
+
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ToastsDemo {
+    level: ToastLevel,
+    text: String,
+    auto_close: bool,
+    duration: f32,
+    with_undo: bool,
+    anchor: Align2,
+    undo_count: usize,
+
+    /// Toasts with an undo button that are still shown.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    undoable: Vec<ToastId>,
+}
+
+impl Default for ToastsDemo {
+    fn default() -> Self {
+        Self {
+            level: ToastLevel::Info,
+            text: "Deleted 3 files".to_owned(),
+            auto_close: true,
+            duration: 4.0,
+            with_undo: true,
+            anchor: Align2::RIGHT_BOTTOM,
+            undo_count: 0,
+            undoable: Default::default(),
+        }
+    }
+}
+
+impl super::Demo for ToastsDemo {
+    fn name(&self) -> &'static str {
+        "🍞 Toasts"
+    }
+
+    fn show(&mut self, ctx: &Context, open: &mut bool) {
+        use super::View as _;
+        Window::new(self.name())
+            .open(open)
+            .resizable(false)
+            .show(ctx, |ui| self.ui(ui));
+    }
+}
+
+impl super::View for ToastsDemo {
+    fn ui(&mut self, ui: &mut Ui) {
+        let ctx = ui.ctx().clone();
+
+        ui.label("Toasts are short notifications shown in a corner of the screen.");
+        ui.label("Hover a toast to keep it open.");
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.level, ToastLevel::Info, "Info");
+            ui.selectable_value(&mut self.level, ToastLevel::Success, "Success");
+            ui.selectable_value(&mut self.level, ToastLevel::Warning, "Warning");
+            ui.selectable_value(&mut self.level, ToastLevel::Error, "Error");
+        });
+        ui.text_edit_singleline(&mut self.text);
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.auto_close, "Close after");
+            ui.add_enabled(
+                self.auto_close,
+                Slider::new(&mut self.duration, 1.0..=10.0).suffix(" s"),
+            );
+        });
+        ui.checkbox(&mut self.with_undo, "With an undo button");
+
+        ui.horizontal(|ui| {
+            ui.label("Corner:");
+            for (anchor, name) in [
+                (Align2::LEFT_TOP, "↖"),
+                (Align2::RIGHT_TOP, "↗"),
+                (Align2::LEFT_BOTTOM, "↙"),
+                (Align2::RIGHT_BOTTOM, "↘"),
+            ] {
+                if ui
+                    .selectable_value(&mut self.anchor, anchor, name)
+                    .changed()
+                {
+                    Toasts::set_anchor(&ctx, anchor);
+                }
+            }
+        });
+
+        if ui.button("Show toast").clicked() {
+            let mut toast = Toast::new(self.level, self.text.clone())
+                .duration(self.auto_close.then(|| self.duration));
+            if self.with_undo {
+                toast = toast.action("Undo");
+            }
+            let id = ctx.add_toast(toast);
+            if self.with_undo {
+                self.undoable.push(id);
+            }
+        }
+
+        for &id in &self.undoable {
+            if Toasts::action_clicked(&ctx, id) {
+                self.undo_count += 1;
+            }
+        }
+        self.undoable.retain(|&id| Toasts::is_open(&ctx, id));
+        ui.label(format!("Undo clicked {} times", self.undo_count));
+
+        ui.vertical_centered(|ui| {
+            ui.add(crate::egui_github_link_file!());
+        });
+    }
+}
@@ -120,7 +120,10 @@ fn shortcuts(ui: &Ui, code: &mut dyn TextBuffer, ccursor_range: &mut CCursorRang
         (Key::S, "~"), // ~strikethrough~
         (Key::U, "_"), // _underline_
     ] {
-        if ui.input_mut().consume_key(egui::Modifiers::COMMAND, key) {
+        if ui
+            .input_mut()
+            .consume_shortcut(&egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, key))
+        {
             toggle_surrounding(code, ccursor_range, surrounding);
             any_change = true;
         };
@@ -247,6 +247,18 @@ impl Painter {
         self.max_texture_side
     }
 
+    /// Set up the vertex attributes each time we paint, instead of storing them in a vertex array object (VAO).
+    ///
+    /// VAOs can not be shared between OpenGL contexts, so call this if you want to use the same [`Painter`]
+    /// with several contexts that share their other objects
+    /// (e.g. one per window, created with `glutin::ContextBuilder::with_shared_lists`).
+    ///
+    /// The context the [`Painter`] was created with must be current.
+    pub fn disable_vertex_array_object(&mut self) {
+        self.assert_not_destroyed();
+        unsafe { self.vao.disable_vao(&self.gl) };
+    }
+
     unsafe fn prepare_painting(
         &mut self,
         [width_in_pixels, height_in_pixels]: [u32; 2],
@@ -70,6 +70,14 @@ impl VertexArrayObject {
         }
     }
 
+    /// Stop using a VAO, and emulate one instead.
+    pub(crate) unsafe fn disable_vao(&mut self, gl: &glow::Context) {
+        if let Some(vao) = self.vao.take() {
+            gl.delete_vertex_array(vao);
+            check_for_gl_error!(gl, "delete_vertex_array");
+        }
+    }
+
     pub(crate) unsafe fn bind(&self, gl: &glow::Context) {
         if let Some(vao) = self.vao {
             gl.bind_vertex_array(Some(vao));
@@ -55,6 +55,7 @@ impl EguiGlow {
             needs_repaint,
             textures_delta,
             shapes,
+            viewports: _, // child viewports are not supported here
         } = self.egui_ctx.run(raw_input, run_ui);
 
         self.egui_winit
@@ -0,0 +1,18 @@
+//! Render [`egui`] on the CPU, without a GPU or a window.
+//!
+//! This is useful for screenshot tests in CI, and for rendering thumbnails on a server.
+//!
+//! The main type is the [`Painter`], which rasterizes the output of [`egui::Context::tessellate`]
+//! into an [`egui::ColorImage`], matching what [`egui_glow`](https://docs.rs/egui_glow) paints on native.
+//!
+//! With the `snapshot` feature (on by default) you can compare the images to
+//! reference PNG files with [`snapshot::assert_snapshot`].
+
+#![allow(clippy::float_cmp)]
+#![allow(clippy::manual_range_contains)]
+
+mod painter;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+
+pub use painter::Painter;
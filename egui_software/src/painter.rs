@@ -0,0 +1,527 @@
+use std::collections::HashMap;
+
+use egui::{
+    epaint::{ClippedPrimitive, ImageDelta, Mesh, Primitive, Vertex},
+    ColorImage, ImageData, Rect, Rgba, TextureFilter, TextureId, TexturesDelta,
+};
+
+/// Number of sub-pixel bits used when snapping vertex positions, same as most GPUs.
+const SUBPIXEL_BITS: u32 = 8;
+const SUBPIXEL_SCALE: f32 = (1 << SUBPIXEL_BITS) as f32;
+
+/// A texture, decoded to linear space (like an `SRGB8_ALPHA8` texture on the GPU).
+struct Texture {
+    size: [usize; 2],
+    pixels: Vec<Rgba>,
+    filter: TextureFilter,
+}
+
+impl Texture {
+    /// Sample at the given uv coordinate, clamping to the edge.
+    fn sample(&self, u: f32, v: f32) -> Rgba {
+        let [w, h] = self.size;
+        match self.filter {
+            TextureFilter::Nearest => {
+                let x = ((u * w as f32).floor() as isize).clamp(0, w as isize - 1) as usize;
+                let y = ((v * h as f32).floor() as isize).clamp(0, h as isize - 1) as usize;
+                self.pixels[y * w + x]
+            }
+            TextureFilter::Linear => {
+                let x = u * w as f32 - 0.5;
+                let y = v * h as f32 - 0.5;
+                let (x0, y0) = (x.floor(), y.floor());
+                let (tx, ty) = (x - x0, y - y0);
+                let texel = |x: f32, y: f32| {
+                    let x = (x as isize).clamp(0, w as isize - 1) as usize;
+                    let y = (y as isize).clamp(0, h as isize - 1) as usize;
+                    self.pixels[y * w + x]
+                };
+                let top = lerp_rgba(texel(x0, y0), texel(x0 + 1.0, y0), tx);
+                let bottom = lerp_rgba(texel(x0, y0 + 1.0), texel(x0 + 1.0, y0 + 1.0), tx);
+                lerp_rgba(top, bottom, ty)
+            }
+        }
+    }
+}
+
+/// Paints egui output into a [`ColorImage`] on the CPU.
+///
+/// The result matches what `egui_glow` paints on native:
+/// vertex colors and textures are converted to linear space,
+/// and blended into the framebuffer with premultiplied alpha.
+///
+/// [`egui::PaintCallback`]s are not supported, and will be ignored.
+///
+/// ```
+/// let ctx = egui::Context::default();
+/// let mut painter = egui_software::Painter::default();
+///
+/// let raw_input = egui::RawInput {
+///     screen_rect: Some(egui::Rect::from_min_size(Default::default(), egui::vec2(200.0, 100.0))),
+///     pixels_per_point: Some(2.0),
+///     ..Default::default()
+/// };
+/// let full_output = ctx.run(raw_input, |ctx| {
+///     egui::CentralPanel::default().show(ctx, |ui| {
+///         ui.label("Hello world!");
+///     });
+/// });
+/// let image = painter.paint_output(&ctx, full_output, egui::Rgba::BLACK);
+/// assert_eq!(image.size, [400, 200]);
+/// ```
+#[derive(Default)]
+pub struct Painter {
+    textures: HashMap<TextureId, Texture>,
+}
+
+impl Painter {
+    /// Paint everything in a [`egui::FullOutput`] at the [`egui::Context::pixels_per_point`]
+    /// and screen size of the last frame.
+    ///
+    /// This is the simplest way to get a screenshot of a frame.
+    pub fn paint_output(
+        &mut self,
+        ctx: &egui::Context,
+        full_output: egui::FullOutput,
+        clear_color: Rgba,
+    ) -> ColorImage {
+        let pixels_per_point = ctx.pixels_per_point();
+        let screen_size = ctx.input().screen_rect().size() * pixels_per_point;
+        let size_in_pixels = [
+            screen_size.x.round() as usize,
+            screen_size.y.round() as usize,
+        ];
+        let clipped_primitives = ctx.tessellate(full_output.shapes);
+        self.paint_and_update_textures(
+            size_in_pixels,
+            pixels_per_point,
+            clear_color,
+            &clipped_primitives,
+            &full_output.textures_delta,
+        )
+    }
+
+    /// Set the new textures, paint, and then free the textures that should be freed.
+    pub fn paint_and_update_textures(
+        &mut self,
+        size_in_pixels: [usize; 2],
+        pixels_per_point: f32,
+        clear_color: Rgba,
+        clipped_primitives: &[ClippedPrimitive],
+        textures_delta: &TexturesDelta,
+    ) -> ColorImage {
+        for (id, image_delta) in &textures_delta.set {
+            self.set_texture(*id, image_delta);
+        }
+
+        let image = self.paint_primitives(
+            size_in_pixels,
+            pixels_per_point,
+            clear_color,
+            clipped_primitives,
+        );
+
+        for &id in &textures_delta.free {
+            self.free_texture(id);
+        }
+
+        image
+    }
+
+    /// Paint the primitives into a new image of the given size.
+    ///
+    /// Meshes using textures that have not been set are skipped.
+    pub fn paint_primitives(
+        &self,
+        size_in_pixels: [usize; 2],
+        pixels_per_point: f32,
+        clear_color: Rgba,
+        clipped_primitives: &[ClippedPrimitive],
+    ) -> ColorImage {
+        let mut target = Target {
+            size: size_in_pixels,
+            pixels: vec![clear_color; size_in_pixels[0] * size_in_pixels[1]],
+        };
+
+        for ClippedPrimitive {
+            clip_rect,
+            primitive,
+        } in clipped_primitives
+        {
+            match primitive {
+                Primitive::Mesh(mesh) => {
+                    if let Some(texture) = self.textures.get(&mesh.texture_id) {
+                        let clip = target.clip_rect_in_pixels(pixels_per_point, *clip_rect);
+                        paint_mesh(&mut target, clip, pixels_per_point, mesh, texture);
+                    }
+                }
+                Primitive::Callback(_) => {} // not supported
+            }
+        }
+
+        ColorImage {
+            size: size_in_pixels,
+            pixels: target.pixels.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Set (or update part of) a texture.
+    pub fn set_texture(&mut self, tex_id: TextureId, delta: &ImageDelta) {
+        let (size, pixels): ([usize; 2], Vec<Rgba>) = match &delta.image {
+            ImageData::Color(image) => (
+                image.size,
+                image.pixels.iter().copied().map(Rgba::from).collect(),
+            ),
+            ImageData::Font(image) => (
+                image.size,
+                image.srgba_pixels(1.0).map(Rgba::from).collect(),
+            ),
+        };
+        assert_eq!(
+            size[0] * size[1],
+            pixels.len(),
+            "Mismatch between texture size and texel count"
+        );
+
+        if let Some([x, y]) = delta.pos {
+            let texture = self
+                .textures
+                .get_mut(&tex_id)
+                .expect("Tried to update a texture that has not been allocated yet.");
+            for row in 0..size[1] {
+                let dst = (y + row) * texture.size[0] + x;
+                texture.pixels[dst..dst + size[0]]
+                    .copy_from_slice(&pixels[row * size[0]..(row + 1) * size[0]]);
+            }
+            texture.filter = delta.filter;
+        } else {
+            self.textures.insert(
+                tex_id,
+                Texture {
+                    size,
+                    pixels,
+                    filter: delta.filter,
+                },
+            );
+        }
+    }
+
+    pub fn free_texture(&mut self, tex_id: TextureId) {
+        self.textures.remove(&tex_id);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// The framebuffer, in linear space with premultiplied alpha.
+struct Target {
+    size: [usize; 2],
+    pixels: Vec<Rgba>,
+}
+
+/// A rectangle of whole pixels: `min` is inclusive, `max` exclusive.
+#[derive(Clone, Copy)]
+struct PixelRect {
+    min: [i64; 2],
+    max: [i64; 2],
+}
+
+impl Target {
+    /// Same rounding as the scissor rectangle in `egui_glow`.
+    fn clip_rect_in_pixels(&self, pixels_per_point: f32, clip_rect: Rect) -> PixelRect {
+        let [width, height] = [self.size[0] as f32, self.size[1] as f32];
+        let min_x = (pixels_per_point * clip_rect.min.x).clamp(0.0, width);
+        let min_y = (pixels_per_point * clip_rect.min.y).clamp(0.0, height);
+        let max_x = (pixels_per_point * clip_rect.max.x).clamp(min_x, width);
+        let max_y = (pixels_per_point * clip_rect.max.y).clamp(min_y, height);
+        PixelRect {
+            min: [min_x.round() as i64, min_y.round() as i64],
+            max: [max_x.round() as i64, max_y.round() as i64],
+        }
+    }
+
+    /// Premultiplied alpha blending, like `egui_glow`:
+    /// `ONE, ONE_MINUS_SRC_ALPHA` for color and `ONE_MINUS_DST_ALPHA, ONE` for alpha.
+    fn blend(&mut self, x: usize, y: usize, src: Rgba) {
+        let dst = &mut self.pixels[y * self.size[0] + x];
+        let [r, g, b, a] = dst.to_array();
+        *dst = Rgba::from_rgba_premultiplied(
+            src.r() + r * (1.0 - src.a()),
+            src.g() + g * (1.0 - src.a()),
+            src.b() + b * (1.0 - src.a()),
+            src.a() * (1.0 - a) + a,
+        );
+    }
+}
+
+/// A vertex snapped to sub-pixel precision, with its color in linear space.
+struct RasterVertex {
+    pos: [i64; 2],
+    uv: egui::Pos2,
+    color: Rgba,
+}
+
+impl RasterVertex {
+    fn new(vertex: &Vertex, pixels_per_point: f32) -> Self {
+        let snap = |coord: f32| (coord * pixels_per_point * SUBPIXEL_SCALE).round() as i64;
+        Self {
+            pos: [snap(vertex.pos.x), snap(vertex.pos.y)],
+            uv: vertex.uv,
+            color: vertex.color.into(),
+        }
+    }
+}
+
+fn paint_mesh(
+    target: &mut Target,
+    clip: PixelRect,
+    pixels_per_point: f32,
+    mesh: &Mesh,
+    texture: &Texture,
+) {
+    let vertices: Vec<RasterVertex> = mesh
+        .vertices
+        .iter()
+        .map(|vertex| RasterVertex::new(vertex, pixels_per_point))
+        .collect();
+
+    for triangle in mesh.indices.chunks_exact(3) {
+        paint_triangle(
+            target,
+            clip,
+            texture,
+            [
+                &vertices[triangle[0] as usize],
+                &vertices[triangle[1] as usize],
+                &vertices[triangle[2] as usize],
+            ],
+        );
+    }
+}
+
+/// Twice the signed area of the triangle `a, b, p`, in sub-pixel units.
+///
+/// Positive if `p` is to the right of `a → b` (with y pointing down).
+fn edge_function(a: [i64; 2], b: [i64; 2], p: [i64; 2]) -> i64 {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+}
+
+/// Pixels exactly on an edge are only painted if the edge is a top or left edge,
+/// so that pixels on an edge shared by two triangles are painted exactly once (like on a GPU).
+fn is_top_left(a: [i64; 2], b: [i64; 2]) -> bool {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    (dy == 0 && dx > 0) || dy < 0
+}
+
+fn paint_triangle(
+    target: &mut Target,
+    clip: PixelRect,
+    texture: &Texture,
+    tri: [&RasterVertex; 3],
+) {
+    let [mut v0, mut v1, v2] = tri;
+    let mut area = edge_function(v0.pos, v1.pos, v2.pos);
+    if area == 0 {
+        return;
+    }
+    if area < 0 {
+        // egui outputs triangles in both winding orders.
+        std::mem::swap(&mut v0, &mut v1);
+        area = -area;
+    }
+
+    let sub = 1 << SUBPIXEL_BITS;
+    let min_x = v0.pos[0].min(v1.pos[0]).min(v2.pos[0]).div_euclid(sub);
+    let min_y = v0.pos[1].min(v1.pos[1]).min(v2.pos[1]).div_euclid(sub);
+    let max_x = v0.pos[0].max(v1.pos[0]).max(v2.pos[0]).div_euclid(sub) + 1;
+    let max_y = v0.pos[1].max(v1.pos[1]).max(v2.pos[1]).div_euclid(sub) + 1;
+    let min_x = min_x.max(clip.min[0]);
+    let min_y = min_y.max(clip.min[1]);
+    let max_x = max_x.min(clip.max[0]);
+    let max_y = max_y.min(clip.max[1]);
+
+    let edges = [(v1, v2), (v2, v0), (v0, v1)];
+    let top_left = edges.map(|(a, b)| is_top_left(a.pos, b.pos));
+
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            // Sample at the pixel center:
+            let p = [x * sub + sub / 2, y * sub + sub / 2];
+            let w = edges.map(|(a, b)| edge_function(a.pos, b.pos, p));
+            let inside = (0..3).all(|i| w[i] > 0 || (w[i] == 0 && top_left[i]));
+            if !inside {
+                continue;
+            }
+
+            let [w0, w1, w2] = w.map(|w| w as f32 / area as f32);
+            let color = v0.color * w0 + v1.color * w1 + v2.color * w2;
+            let u = v0.uv.x * w0 + v1.uv.x * w1 + v2.uv.x * w2;
+            let v = v0.uv.y * w0 + v1.uv.y * w1 + v2.uv.y * w2;
+            let src = color * texture.sample(u, v);
+            target.blend(x as usize, y as usize, src);
+        }
+    }
+}
+
+fn lerp_rgba(a: Rgba, b: Rgba, t: f32) -> Rgba {
+    a * (1.0 - t) + b * t
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::{epaint, pos2, vec2, Color32, Rect, Rgba, TextureId, TexturesDelta};
+
+    use super::*;
+
+    const SIZE: [usize; 2] = [16, 16];
+
+    fn paint(
+        pixels_per_point: f32,
+        clip_rect: Rect,
+        mesh: epaint::Mesh,
+        textures_delta: &TexturesDelta,
+    ) -> ColorImage {
+        let mut painter = Painter::default();
+        let primitives = [ClippedPrimitive {
+            clip_rect,
+            primitive: Primitive::Mesh(mesh),
+        }];
+        painter.paint_and_update_textures(
+            SIZE,
+            pixels_per_point,
+            Rgba::WHITE,
+            &primitives,
+            textures_delta,
+        )
+    }
+
+    fn white_texture() -> TexturesDelta {
+        let mut delta = TexturesDelta::default();
+        delta.set.insert(
+            TextureId::default(),
+            ImageDelta::full(
+                ColorImage::new([1, 1], Color32::WHITE),
+                TextureFilter::Nearest,
+            ),
+        );
+        delta
+    }
+
+    fn rect_mesh(rect: Rect, color: Color32) -> epaint::Mesh {
+        let mut mesh = epaint::Mesh::default();
+        mesh.add_colored_rect(rect, color);
+        mesh
+    }
+
+    #[test]
+    fn fill_and_clip() {
+        let everything = Rect::EVERYTHING;
+        let mesh = rect_mesh(
+            Rect::from_min_size(pos2(2.0, 2.0), vec2(4.0, 4.0)),
+            Color32::RED,
+        );
+
+        let image = paint(1.0, everything, mesh.clone(), &white_texture());
+        let num_red = image.pixels.iter().filter(|c| **c == Color32::RED).count();
+        assert_eq!(
+            num_red, 16,
+            "pixel centers inside the rect, and nothing more"
+        );
+        assert_eq!(image[(2, 2)], Color32::RED);
+        assert_eq!(image[(5, 5)], Color32::RED);
+        assert_eq!(image[(6, 5)], Color32::WHITE);
+
+        let image = paint(2.0, everything, mesh.clone(), &white_texture());
+        let num_red = image.pixels.iter().filter(|c| **c == Color32::RED).count();
+        assert_eq!(num_red, 64, "scaled by pixels_per_point");
+
+        let clip_rect = Rect::from_min_size(pos2(4.0, 0.0), vec2(100.0, 100.0));
+        let image = paint(1.0, clip_rect, mesh, &white_texture());
+        let num_red = image.pixels.iter().filter(|c| **c == Color32::RED).count();
+        assert_eq!(num_red, 8);
+        assert_eq!(image[(3, 3)], Color32::WHITE);
+    }
+
+    #[test]
+    fn premultiplied_blending_on_shared_edges() {
+        // A quad is two triangles sharing a diagonal edge.
+        // The pixels on the diagonal must only be blended once.
+        let half_black = Color32::from_black_alpha(128);
+        let mesh = rect_mesh(
+            Rect::from_min_size(pos2(0.0, 0.0), vec2(8.0, 8.0)),
+            half_black,
+        );
+        let image = paint(1.0, Rect::EVERYTHING, mesh, &white_texture());
+
+        // Blended in linear space:
+        let expected: Color32 =
+            (Rgba::WHITE * (1.0 - half_black.a() as f32 / 255.0) + Rgba::from(half_black)).into();
+        for y in 0..8 {
+            for x in 0..8 {
+                assert_eq!(image[(x, y)], expected, "at {} {}", x, y);
+            }
+        }
+        assert_eq!(image[(8, 8)], Color32::WHITE);
+    }
+
+    #[test]
+    fn texture_filtering() {
+        let texture = ColorImage {
+            size: [2, 1],
+            pixels: vec![Color32::BLACK, Color32::WHITE],
+        };
+        let mut mesh = epaint::Mesh::with_texture(TextureId::default());
+        mesh.add_rect_with_uv(
+            Rect::from_min_size(pos2(0.0, 0.0), vec2(16.0, 1.0)),
+            Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+            Color32::WHITE,
+        );
+
+        let mut delta = TexturesDelta::default();
+        delta.set.insert(
+            TextureId::default(),
+            ImageDelta::full(texture.clone(), TextureFilter::Nearest),
+        );
+        let nearest = paint(1.0, Rect::EVERYTHING, mesh.clone(), &delta);
+        assert_eq!(nearest[(7, 0)], Color32::BLACK);
+        assert_eq!(nearest[(8, 0)], Color32::WHITE);
+
+        let mut delta = TexturesDelta::default();
+        delta.set.insert(
+            TextureId::default(),
+            ImageDelta::full(texture, TextureFilter::Linear),
+        );
+        let linear = paint(1.0, Rect::EVERYTHING, mesh, &delta);
+        assert_eq!(linear[(0, 0)], Color32::BLACK, "clamped to edge");
+        assert_eq!(linear[(15, 0)], Color32::WHITE, "clamped to edge");
+        let middle = linear[(8, 0)];
+        assert!(Color32::BLACK.r() < middle.r() && middle.r() < Color32::WHITE.r());
+    }
+
+    #[test]
+    fn partial_texture_update() {
+        let mut painter = Painter::default();
+        let id = TextureId::default();
+        painter.set_texture(
+            id,
+            &ImageDelta::full(
+                ColorImage::new([4, 4], Color32::RED),
+                TextureFilter::Nearest,
+            ),
+        );
+        painter.set_texture(
+            id,
+            &ImageDelta::partial(
+                [1, 2],
+                ColorImage::new([2, 1], Color32::GREEN),
+                TextureFilter::Nearest,
+            ),
+        );
+        let texture = &painter.textures[&id];
+        assert_eq!(texture.pixels[2 * 4 + 1], Rgba::from(Color32::GREEN));
+        assert_eq!(texture.pixels[2 * 4 + 3], Rgba::from(Color32::RED));
+
+        painter.free_texture(id);
+        assert!(painter.textures.is_empty());
+    }
+}
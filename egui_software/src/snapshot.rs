@@ -0,0 +1,338 @@
+//! Compare rendered images to reference images ("snapshots") stored as PNG files.
+//!
+//! Use this to regression-test the visuals of your widgets:
+//!
+//! ```no_run
+//! let ctx = egui::Context::default();
+//! let raw_input = egui::RawInput {
+//!     screen_rect: Some(egui::Rect::from_min_size(Default::default(), egui::vec2(200.0, 100.0))),
+//!     ..Default::default()
+//! };
+//! let full_output = ctx.run(raw_input, |ctx| {
+//!     egui::CentralPanel::default().show(ctx, |ui| {
+//!         ui.button("Click me");
+//!     });
+//! });
+//! let image = egui_software::Painter::default().paint_output(&ctx, full_output, egui::Rgba::BLACK);
+//!
+//! egui_software::snapshot::assert_snapshot("tests/snapshots/button.png", &image);
+//! ```
+//!
+//! If there is no snapshot yet, or if it differs from the image,
+//! the image is written next to it (as `button.new.png`) together with an image highlighting
+//! the differing pixels (`button.diff.png`), and the test fails.
+//!
+//! Set the environment variable `UPDATE_SNAPSHOTS` to overwrite the snapshots with the new images instead,
+//! e.g. `UPDATE_SNAPSHOTS=1 cargo test`.
+
+use std::path::{Path, PathBuf};
+
+use egui::{Color32, ColorImage};
+
+/// How close an image must be to the snapshot to pass.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SnapshotOptions {
+    /// The largest difference of any color channel (0-255) for two pixels to be considered equal.
+    ///
+    /// A small value allows for tiny rounding differences between platforms.
+    pub tolerance: u8,
+
+    /// How many pixels may differ before the snapshot fails.
+    pub max_differing_pixels: usize,
+}
+
+impl Default for SnapshotOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: 2,
+            max_differing_pixels: 0,
+        }
+    }
+}
+
+/// The result of comparing two images of the same size with [`image_diff`].
+#[derive(Clone, PartialEq)]
+pub struct ImageDiff {
+    /// Number of pixels that differ by more than the tolerance.
+    pub num_differing_pixels: usize,
+
+    /// The largest difference of any color channel of any pixel.
+    pub max_difference: u8,
+
+    /// The differing pixels in red, on top of a faded copy of the expected image.
+    pub diff_image: ColorImage,
+}
+
+/// Compare two images of the same size, pixel by pixel.
+///
+/// Returns `None` if the images are of different sizes.
+pub fn image_diff(expected: &ColorImage, actual: &ColorImage, tolerance: u8) -> Option<ImageDiff> {
+    if expected.size != actual.size {
+        return None;
+    }
+
+    let mut num_differing_pixels = 0;
+    let mut max_difference = 0;
+    let diff_pixels = expected
+        .pixels
+        .iter()
+        .zip(&actual.pixels)
+        .map(|(e, a)| {
+            let difference = (0..4)
+                .map(|i| e[i].abs_diff(a[i]))
+                .max()
+                .unwrap_or_default();
+            max_difference = max_difference.max(difference);
+            if difference > tolerance {
+                num_differing_pixels += 1;
+                Color32::RED
+            } else {
+                let [r, g, b, _] = e.to_array();
+                let gray = ((r as u16 + g as u16 + b as u16) / 3) as u8;
+                Color32::from_gray(gray / 4 + 191) // faded, so the differences stand out
+            }
+        })
+        .collect();
+
+    Some(ImageDiff {
+        num_differing_pixels,
+        max_difference,
+        diff_image: ColorImage {
+            size: expected.size,
+            pixels: diff_pixels,
+        },
+    })
+}
+
+/// Why [`check_snapshot`] failed.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// There was no snapshot. The image was saved to `new_path`.
+    Missing { path: PathBuf, new_path: PathBuf },
+
+    /// The snapshot has a different size than the image. The image was saved to `new_path`.
+    SizeMismatch {
+        path: PathBuf,
+        new_path: PathBuf,
+        expected: [usize; 2],
+        actual: [usize; 2],
+    },
+
+    /// Too many pixels differ. The image was saved to `new_path`, and the differences to `diff_path`.
+    Mismatch {
+        path: PathBuf,
+        new_path: PathBuf,
+        diff_path: PathBuf,
+        num_differing_pixels: usize,
+    },
+
+    /// Failed to read or write an image.
+    Image(image::ImageError),
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const HINT: &str = "Run with UPDATE_SNAPSHOTS=1 to accept the new image.";
+        match self {
+            Self::Missing { path, new_path } => write!(
+                f,
+                "Missing snapshot {:?}. The new image was saved to {:?}. {}",
+                path, new_path, HINT
+            ),
+            Self::SizeMismatch {
+                path,
+                new_path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Snapshot {:?} is {}x{}, but the image is {}x{}. The new image was saved to {:?}. {}",
+                path, expected[0], expected[1], actual[0], actual[1], new_path, HINT
+            ),
+            Self::Mismatch {
+                path,
+                new_path,
+                diff_path,
+                num_differing_pixels,
+            } => write!(
+                f,
+                "{} pixels differ from snapshot {:?}. The new image was saved to {:?}, and the differences to {:?}. {}",
+                num_differing_pixels, path, new_path, diff_path, HINT
+            ),
+            Self::Image(err) => write!(f, "Snapshot image error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<image::ImageError> for SnapshotError {
+    fn from(err: image::ImageError) -> Self {
+        Self::Image(err)
+    }
+}
+
+/// Compare the image to the PNG snapshot at `path` with [`SnapshotOptions::default`].
+///
+/// See [`check_snapshot_with_options`].
+///
+/// # Errors
+/// If the snapshot is missing or differs from the image, or the images could not be read or written.
+pub fn check_snapshot(path: impl AsRef<Path>, image: &ColorImage) -> Result<(), SnapshotError> {
+    check_snapshot_with_options(path, image, &SnapshotOptions::default())
+}
+
+/// Compare the image to the PNG snapshot at `path`.
+///
+/// On failure, the image is saved next to the snapshot with the extension `.new.png`,
+/// and the differing pixels with the extension `.diff.png`.
+/// If the environment variable `UPDATE_SNAPSHOTS` is set, the snapshot is overwritten instead.
+///
+/// # Errors
+/// If the snapshot is missing or differs from the image, or the images could not be read or written.
+pub fn check_snapshot_with_options(
+    path: impl AsRef<Path>,
+    image: &ColorImage,
+    options: &SnapshotOptions,
+) -> Result<(), SnapshotError> {
+    let path = path.as_ref();
+    let new_path = path.with_extension("new.png");
+    let diff_path = path.with_extension("diff.png");
+
+    // Remove the output of earlier failures:
+    std::fs::remove_file(&new_path).ok();
+    std::fs::remove_file(&diff_path).ok();
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        return save_png(path, image);
+    }
+
+    if !path.exists() {
+        save_png(&new_path, image)?;
+        return Err(SnapshotError::Missing {
+            path: path.to_owned(),
+            new_path,
+        });
+    }
+
+    // Compare what would be saved, so that a snapshot always matches the image it was saved from:
+    let expected = load_png(path)?;
+    let actual = to_png_roundtrip(image);
+
+    match image_diff(&expected, &actual, options.tolerance) {
+        None => {
+            save_png(&new_path, image)?;
+            Err(SnapshotError::SizeMismatch {
+                path: path.to_owned(),
+                new_path,
+                expected: expected.size,
+                actual: actual.size,
+            })
+        }
+        Some(diff) if diff.num_differing_pixels > options.max_differing_pixels => {
+            save_png(&new_path, image)?;
+            save_png(&diff_path, &diff.diff_image)?;
+            Err(SnapshotError::Mismatch {
+                path: path.to_owned(),
+                new_path,
+                diff_path,
+                num_differing_pixels: diff.num_differing_pixels,
+            })
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+/// Like [`check_snapshot`], but panics on failure. Use this in tests.
+#[track_caller]
+pub fn assert_snapshot(path: impl AsRef<Path>, image: &ColorImage) {
+    if let Err(err) = check_snapshot(path, image) {
+        panic!("{}", err);
+    }
+}
+
+/// Save the image as a PNG (with unmultiplied alpha).
+///
+/// # Errors
+/// If the file could not be written.
+pub fn save_png(path: impl AsRef<Path>, image: &ColorImage) -> Result<(), SnapshotError> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(image::ImageError::IoError)?;
+    }
+    let [w, h] = image.size;
+    let bytes: Vec<u8> = image
+        .pixels
+        .iter()
+        .flat_map(|color| color.to_srgba_unmultiplied())
+        .collect();
+    image::save_buffer(path, &bytes, w as u32, h as u32, image::ColorType::Rgba8)?;
+    Ok(())
+}
+
+/// Load a PNG saved with [`save_png`].
+///
+/// # Errors
+/// If the file could not be read or decoded.
+pub fn load_png(path: impl AsRef<Path>) -> Result<ColorImage, SnapshotError> {
+    let image = image::open(path)?.to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(ColorImage::from_rgba_unmultiplied(size, image.as_raw()))
+}
+
+/// What [`load_png`] returns for an image saved with [`save_png`].
+fn to_png_roundtrip(image: &ColorImage) -> ColorImage {
+    let bytes: Vec<u8> = image
+        .pixels
+        .iter()
+        .flat_map(|color| color.to_srgba_unmultiplied())
+        .collect();
+    ColorImage::from_rgba_unmultiplied(image.size, &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff() {
+        let a = ColorImage::new([4, 4], Color32::WHITE);
+        let mut b = a.clone();
+        b[(1, 2)] = Color32::from_gray(254);
+        b[(3, 3)] = Color32::BLACK;
+
+        let diff = image_diff(&a, &b, 1).unwrap();
+        assert_eq!(diff.num_differing_pixels, 1);
+        assert_eq!(diff.max_difference, 255);
+        assert_eq!(diff.diff_image[(3, 3)], Color32::RED);
+        assert_ne!(diff.diff_image[(1, 2)], Color32::RED);
+
+        assert!(image_diff(&a, &ColorImage::new([4, 3], Color32::WHITE), 0).is_none());
+    }
+
+    #[test]
+    fn snapshot_round_trip() {
+        let dir = std::env::temp_dir().join(format!("egui_software_test_{}", std::process::id()));
+        let path = dir.join("snapshot.png");
+        let mut image = ColorImage::new([8, 8], Color32::from_rgba_unmultiplied(10, 20, 30, 128));
+
+        assert!(matches!(
+            check_snapshot(&path, &image),
+            Err(SnapshotError::Missing { .. })
+        ));
+        std::fs::rename(path.with_extension("new.png"), &path).unwrap();
+        check_snapshot(&path, &image).unwrap();
+
+        image[(4, 4)] = Color32::GREEN;
+        assert!(matches!(
+            check_snapshot(&path, &image),
+            Err(SnapshotError::Mismatch {
+                num_differing_pixels: 1,
+                ..
+            })
+        ));
+        assert!(path.with_extension("diff.png").exists());
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
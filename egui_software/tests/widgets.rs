@@ -0,0 +1,41 @@
+//! Snapshot tests of a few widgets.
+//!
+//! If they fail, look at the `.diff.png` files in `tests/snapshots`.
+//! Run with `UPDATE_SNAPSHOTS=1` to accept the new images.
+
+#![cfg(feature = "snapshot")]
+
+use egui::{vec2, Context, RawInput, Rect, Rgba};
+
+fn render(run_ui: impl Fn(&mut egui::Ui)) -> egui::ColorImage {
+    let ctx = Context::default();
+    let raw_input = || RawInput {
+        screen_rect: Some(Rect::from_min_size(Default::default(), vec2(240.0, 140.0))),
+        pixels_per_point: Some(1.0),
+        ..Default::default()
+    };
+
+    // Run twice, so that everything has settled.
+    // Paint both frames, since the font texture is only sent in the first one.
+    let mut painter = egui_software::Painter::default();
+    let mut image = None;
+    for _ in 0..2 {
+        let full_output = ctx.run(raw_input(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| run_ui(ui));
+        });
+        image = Some(painter.paint_output(&ctx, full_output, Rgba::BLACK));
+    }
+    image.unwrap()
+}
+
+#[test]
+fn widgets() {
+    let image = render(|ui| {
+        ui.heading("Widgets");
+        ui.label("Hello world!");
+        let _ = ui.button("Button");
+        ui.checkbox(&mut true, "Checkbox");
+        ui.add(egui::Slider::new(&mut 0.5, 0.0..=1.0));
+    });
+    egui_software::snapshot::assert_snapshot("tests/snapshots/widgets.png", &image);
+}
@@ -161,6 +161,8 @@ impl From<ColorImage> for ImageData {
 /// Each value represents "coverage", i.e. how much a texel is covered by a character.
 ///
 /// This is roughly interpreted as the opacity of a white image.
+///
+/// Color glyphs (e.g. emojis) are stored in a separate RGBA layer, see [`Self::colors`].
 #[derive(Clone, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct FontImage {
@@ -171,6 +173,12 @@ pub struct FontImage {
     ///
     /// Often you want to use [`Self::srgba_pixels`] instead.
     pub pixels: Vec<f32>,
+
+    /// Premultiplied colors of the color glyphs, used instead of the coverage where not transparent.
+    ///
+    /// Empty until the first color glyph is added, otherwise the same size as [`Self::pixels`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub colors: Vec<Color32>,
 }
 
 impl FontImage {
@@ -178,9 +186,20 @@ impl FontImage {
         Self {
             size,
             pixels: vec![0.0; size[0] * size[1]],
+            colors: vec![],
         }
     }
 
+    /// The color of a texel in the color layer, which is added if needed.
+    pub fn color_mut(&mut self, (x, y): (usize, usize)) -> &mut Color32 {
+        let [w, h] = self.size;
+        assert!(x < w && y < h);
+        if self.colors.is_empty() {
+            self.colors = vec![Color32::TRANSPARENT; w * h];
+        }
+        &mut self.colors[y * w + x]
+    }
+
     #[inline]
     pub fn width(&self) -> usize {
         self.size[0]
@@ -197,7 +216,13 @@ impl FontImage {
     /// If you are having problems with text looking skinny and pixelated, try
     /// setting a lower gamma, e.g. `0.5`.
     pub fn srgba_pixels(&'_ self, gamma: f32) -> impl ExactSizeIterator<Item = Color32> + '_ {
-        self.pixels.iter().map(move |coverage| {
+        self.pixels.iter().enumerate().map(move |(i, coverage)| {
+            if let Some(&color) = self.colors.get(i) {
+                if color != Color32::TRANSPARENT {
+                    return color;
+                }
+            }
+
             // This is arbitrarily chosen to make text look as good as possible.
             // In particular, it looks good with gamma=1 and the default eframe backend,
             // which uses linear blending.
@@ -213,14 +238,19 @@ impl FontImage {
         assert!(y + h <= self.height());
 
         let mut pixels = Vec::with_capacity(w * h);
+        let mut colors = Vec::with_capacity(if self.colors.is_empty() { 0 } else { w * h });
         for y in y..y + h {
             let offset = y * self.width() + x;
             pixels.extend(&self.pixels[offset..(offset + w)]);
+            if !self.colors.is_empty() {
+                colors.extend(&self.colors[offset..(offset + w)]);
+            }
         }
         assert_eq!(pixels.len(), w * h);
         FontImage {
             size: [w, h],
             pixels,
+            colors,
         }
     }
 }
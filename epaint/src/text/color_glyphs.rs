@@ -0,0 +1,384 @@
+//! Glyphs with their own colors, such as emojis.
+//!
+//! Supports fonts with colored layers (`COLR` and `CPAL` tables, version 0)
+//! and fonts with bitmap images (`CBDT` and `sbix` tables).
+
+use crate::{Color32, ColorImage};
+
+/// The colored layers of the glyphs in a font with `COLR` and `CPAL` tables.
+///
+/// Each color glyph is drawn as a stack of ordinary glyphs, each in its own color.
+pub(crate) struct ColorLayers {
+    /// Sorted by glyph id.
+    base_glyphs: Vec<BaseGlyph>,
+    layers: Vec<Layer>,
+    /// The first palette of the font.
+    palette: Vec<Color32>,
+}
+
+#[derive(Clone, Copy)]
+struct BaseGlyph {
+    glyph_id: u16,
+    first_layer: u16,
+    num_layers: u16,
+}
+
+#[derive(Clone, Copy)]
+struct Layer {
+    glyph_id: u16,
+    palette_index: u16,
+}
+
+/// Palette index meaning "use the text color".
+const FOREGROUND_PALETTE_INDEX: u16 = 0xFFFF;
+
+impl ColorLayers {
+    /// Returns `None` if the font has no color layers.
+    pub fn from_font(font: &[u8], index: u32) -> Option<Self> {
+        let colr = find_table(font, index, b"COLR")?;
+        let cpal = find_table(font, index, b"CPAL")?;
+
+        let num_base_glyphs = read_u16(colr, 2)? as usize;
+        let base_glyphs_offset = read_u32(colr, 4)? as usize;
+        let layers_offset = read_u32(colr, 8)? as usize;
+        let num_layers = read_u16(colr, 12)? as usize;
+
+        let base_glyphs = (0..num_base_glyphs)
+            .map(|i| {
+                let offset = base_glyphs_offset + 6 * i;
+                Some(BaseGlyph {
+                    glyph_id: read_u16(colr, offset)?,
+                    first_layer: read_u16(colr, offset + 2)?,
+                    num_layers: read_u16(colr, offset + 4)?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let layers = (0..num_layers)
+            .map(|i| {
+                let offset = layers_offset + 4 * i;
+                Some(Layer {
+                    glyph_id: read_u16(colr, offset)?,
+                    palette_index: read_u16(colr, offset + 2)?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let num_palette_entries = read_u16(cpal, 2)? as usize;
+        let color_records_offset = read_u32(cpal, 8)? as usize;
+        let first_color_index = read_u16(cpal, 12)? as usize;
+        let palette = (0..num_palette_entries)
+            .map(|i| {
+                let offset = color_records_offset + 4 * (first_color_index + i);
+                let bgra = cpal.get(offset..offset + 4)?;
+                Some(Color32::from_rgba_unmultiplied(
+                    bgra[2], bgra[1], bgra[0], bgra[3],
+                ))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        if base_glyphs.is_empty() {
+            None
+        } else {
+            Some(Self {
+                base_glyphs,
+                layers,
+                palette,
+            })
+        }
+    }
+
+    /// The layers of this glyph from the bottom up, with their colors,
+    /// or `None` if it is not a color glyph.
+    ///
+    /// A `None` color means the layer should use the text color.
+    pub fn layers(
+        &self,
+        glyph_id: ab_glyph::GlyphId,
+    ) -> Option<impl Iterator<Item = (ab_glyph::GlyphId, Option<Color32>)> + '_> {
+        let index = self
+            .base_glyphs
+            .binary_search_by_key(&glyph_id.0, |base| base.glyph_id)
+            .ok()?;
+        let base = self.base_glyphs[index];
+        let first = base.first_layer as usize;
+        let layers = self.layers.get(first..first + base.num_layers as usize)?;
+        Some(layers.iter().map(move |layer| {
+            let color = if layer.palette_index == FOREGROUND_PALETTE_INDEX {
+                None
+            } else {
+                self.palette.get(layer.palette_index as usize).copied()
+            };
+            (ab_glyph::GlyphId(layer.glyph_id), color)
+        }))
+    }
+}
+
+/// Decode the bitmap image of a glyph, if it is in color.
+///
+/// PNG images (used by most emoji fonts) are only supported with the `color_bitmap_fonts` feature.
+pub(crate) fn decode_raster_image(image: &ab_glyph::v2::GlyphImage<'_>) -> Option<ColorImage> {
+    match image.format {
+        ab_glyph::GlyphImageFormat::BitmapPremulBgra32 => {
+            let size = [image.width as usize, image.height as usize];
+            let pixels: Vec<Color32> = image
+                .data
+                .chunks_exact(4)
+                .map(|bgra| Color32::from_rgba_premultiplied(bgra[2], bgra[1], bgra[0], bgra[3]))
+                .collect();
+            (pixels.len() == size[0] * size[1]).then(|| ColorImage { size, pixels })
+        }
+        #[cfg(feature = "color_bitmap_fonts")]
+        ab_glyph::GlyphImageFormat::Png => decode_png(image.data),
+        _ => None, // Monochrome bitmaps are drawn from the outlines instead
+    }
+}
+
+#[cfg(feature = "color_bitmap_fonts")]
+fn decode_png(png_bytes: &[u8]) -> Option<ColorImage> {
+    let mut decoder = png::Decoder::new(png_bytes);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().ok()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).ok()?;
+    let size = [info.width as usize, info.height as usize];
+    let buffer = &buffer[..info.buffer_size()];
+
+    let pixels = match info.color_type {
+        png::ColorType::Rgba => buffer
+            .chunks_exact(4)
+            .map(|p| Color32::from_rgba_unmultiplied(p[0], p[1], p[2], p[3]))
+            .collect(),
+        png::ColorType::Rgb => buffer
+            .chunks_exact(3)
+            .map(|p| Color32::from_rgb(p[0], p[1], p[2]))
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buffer
+            .chunks_exact(2)
+            .map(|p| Color32::from_rgba_unmultiplied(p[0], p[0], p[0], p[1]))
+            .collect(),
+        png::ColorType::Grayscale => buffer.iter().map(|&l| Color32::from_gray(l)).collect(),
+        png::ColorType::Indexed => return None, // expanded to rgb(a) by the transformations
+    };
+    Some(ColorImage { size, pixels })
+}
+
+/// Resize an image, averaging the source pixels that cover each new pixel.
+pub(crate) fn resize_image(image: &ColorImage, new_size: [usize; 2]) -> ColorImage {
+    let [src_w, src_h] = image.size;
+    let [dst_w, dst_h] = new_size;
+
+    // The source pixels covering a destination pixel, at least one:
+    let src_range = |dst: usize, dst_len: usize, src_len: usize| {
+        let start = (dst * src_len / dst_len).min(src_len - 1);
+        let end = ((dst + 1) * src_len + dst_len - 1) / dst_len;
+        start..end.clamp(start + 1, src_len)
+    };
+
+    let mut pixels = Vec::with_capacity(dst_w * dst_h);
+    for dst_y in 0..dst_h {
+        let ys = src_range(dst_y, dst_h, src_h);
+        for dst_x in 0..dst_w {
+            let xs = src_range(dst_x, dst_w, src_w);
+            let mut sum = [0_u32; 4];
+            for y in ys.clone() {
+                for x in xs.clone() {
+                    let color = image.pixels[y * src_w + x];
+                    for (sum, value) in sum.iter_mut().zip(color.to_array()) {
+                        *sum += value as u32;
+                    }
+                }
+            }
+            let n = (ys.len() * xs.len()) as u32;
+            let [r, g, b, a] = sum.map(|sum| ((sum + n / 2) / n) as u8);
+            pixels.push(Color32::from_rgba_premultiplied(r, g, b, a));
+        }
+    }
+
+    ColorImage {
+        size: new_size,
+        pixels,
+    }
+}
+
+/// Paint `src` over `dst` (both premultiplied).
+#[inline]
+pub(crate) fn blend_over(dst: Color32, src: Color32) -> Color32 {
+    let keep = 255 - src.a() as u32;
+    let [dr, dg, db, da] = dst.to_array();
+    let [sr, sg, sb, sa] = src.to_array();
+    let mix = |d: u8, s: u8| (s as u32 + (d as u32 * keep + 127) / 255).min(255) as u8;
+    Color32::from_rgba_premultiplied(mix(dr, sr), mix(dg, sg), mix(db, sb), mix(da, sa))
+}
+
+/// Paint a layer in the text color with coverage `alpha` on top of a color glyph.
+///
+/// The glyph is painted as its premultiplied `color` over the text color with `coverage`,
+/// since we don't know the text color until we tessellate.
+/// This changes both so that the result is the same as painting the new layer on top.
+pub(crate) fn paint_foreground(color: &mut Color32, coverage: &mut f32, alpha: f32) {
+    let color_alpha = color.a() as f32 / 255.0;
+    let visible = 1.0 - (1.0 - alpha) * color_alpha;
+    if visible > 0.0 {
+        *coverage = ((alpha + (1.0 - alpha) * (1.0 - color_alpha) * *coverage) / visible).min(1.0);
+    }
+    *color = color.linear_multiply(1.0 - alpha);
+}
+
+// ----------------------------------------------------------------------------
+
+/// Find a table in a `.ttf`/`.otf` file, or in font `index` of a `.ttc` collection.
+fn find_table<'a>(font: &'a [u8], index: u32, tag: &[u8; 4]) -> Option<&'a [u8]> {
+    let face_offset = if font.get(0..4)? == b"ttcf" {
+        let num_fonts = read_u32(font, 8)?;
+        if index >= num_fonts {
+            return None;
+        }
+        read_u32(font, 12 + 4 * index as usize)? as usize
+    } else if index == 0 {
+        0
+    } else {
+        return None;
+    };
+
+    let num_tables = read_u16(font, face_offset + 4)? as usize;
+    (0..num_tables).find_map(|i| {
+        let record = face_offset + 12 + 16 * i;
+        if font.get(record..record + 4)? == tag {
+            let offset = read_u32(font, record + 8)? as usize;
+            let length = read_u32(font, record + 12)? as usize;
+            font.get(offset..offset + length)
+        } else {
+            None
+        }
+    })
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A font file with just a `COLR` table and a `CPAL` table,
+    /// where glyph 5 is drawn as glyph 6 in red and then glyph 7 in the text color.
+    pub(crate) fn test_font() -> Vec<u8> {
+        let mut colr = vec![];
+        colr.extend(0_u16.to_be_bytes()); // version
+        colr.extend(1_u16.to_be_bytes()); // number of base glyphs
+        colr.extend(14_u32.to_be_bytes()); // base glyphs offset
+        colr.extend(20_u32.to_be_bytes()); // layers offset
+        colr.extend(2_u16.to_be_bytes()); // number of layers
+        for value in [5_u16, 0, 2] {
+            colr.extend(value.to_be_bytes()); // glyph id, first layer, number of layers
+        }
+        for value in [6_u16, 0, 7, FOREGROUND_PALETTE_INDEX] {
+            colr.extend(value.to_be_bytes()); // glyph id, palette index
+        }
+
+        let mut cpal = vec![];
+        for value in [0_u16, 1, 1, 1] {
+            cpal.extend(value.to_be_bytes()); // version, entries, palettes, colors
+        }
+        cpal.extend(14_u32.to_be_bytes()); // color records offset
+        cpal.extend(0_u16.to_be_bytes()); // first color of the first palette
+        cpal.extend([0, 0, 255, 255]); // red, as BGRA
+
+        let tables_start = 12 + 2 * 16;
+        let mut font = vec![];
+        font.extend(0x0001_0000_u32.to_be_bytes()); // sfnt version
+        font.extend(2_u16.to_be_bytes()); // number of tables
+        font.extend([0; 6]); // search range etc
+        for (tag, offset, table) in [
+            (b"COLR", tables_start, &colr),
+            (b"CPAL", tables_start + colr.len(), &cpal),
+        ] {
+            font.extend(tag);
+            font.extend(0_u32.to_be_bytes()); // checksum
+            font.extend((offset as u32).to_be_bytes());
+            font.extend((table.len() as u32).to_be_bytes());
+        }
+        font.extend(&colr);
+        font.extend(&cpal);
+        font
+    }
+
+    #[test]
+    fn test_color_layers() {
+        let font = test_font();
+        let color_layers = ColorLayers::from_font(&font, 0).unwrap();
+
+        let layers: Vec<_> = color_layers.layers(ab_glyph::GlyphId(5)).unwrap().collect();
+        assert_eq!(
+            layers,
+            vec![
+                (ab_glyph::GlyphId(6), Some(Color32::RED)),
+                (ab_glyph::GlyphId(7), None)
+            ]
+        );
+        assert!(color_layers.layers(ab_glyph::GlyphId(6)).is_none());
+
+        assert!(ColorLayers::from_font(&font, 1).is_none());
+        let monochrome_font = include_bytes!("../../fonts/Hack-Regular.ttf");
+        assert!(ColorLayers::from_font(monochrome_font, 0).is_none());
+    }
+
+    #[test]
+    fn test_resize_image() {
+        let image = ColorImage {
+            size: [2, 1],
+            pixels: vec![Color32::from_gray(100), Color32::from_gray(200)],
+        };
+        assert_eq!(
+            resize_image(&image, [1, 1]).pixels,
+            vec![Color32::from_gray(150)]
+        );
+        assert_eq!(
+            resize_image(&image, [4, 2]).pixels[..4],
+            [
+                Color32::from_gray(100),
+                Color32::from_gray(100),
+                Color32::from_gray(200),
+                Color32::from_gray(200)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blend_over() {
+        assert_eq!(blend_over(Color32::RED, Color32::BLUE), Color32::BLUE);
+        assert_eq!(blend_over(Color32::RED, Color32::TRANSPARENT), Color32::RED);
+        let half_blue = Color32::from_rgba_premultiplied(0, 0, 128, 128);
+        assert_eq!(
+            blend_over(Color32::RED, half_blue),
+            Color32::from_rgba_premultiplied(127, 0, 128, 255)
+        );
+    }
+
+    #[test]
+    fn test_paint_foreground() {
+        // Covering the colors completely:
+        let (mut color, mut coverage) = (Color32::RED, 0.0);
+        paint_foreground(&mut color, &mut coverage, 1.0);
+        assert_eq!((color, coverage), (Color32::TRANSPARENT, 1.0));
+
+        // Half over the colors, so half of each shows:
+        let (mut color, mut coverage) = (Color32::RED, 0.0);
+        paint_foreground(&mut color, &mut coverage, 0.5);
+        assert_eq!(color, Color32::RED.linear_multiply(0.5));
+        assert_eq!(coverage, 1.0);
+
+        // Without colors below, it is just the coverage:
+        let (mut color, mut coverage) = (Color32::TRANSPARENT, 0.0);
+        paint_foreground(&mut color, &mut coverage, 0.25);
+        assert_eq!((color, coverage), (Color32::TRANSPARENT, 0.25));
+    }
+}
@@ -1,9 +1,10 @@
 use crate::{
     mutex::{Mutex, RwLock},
-    TextureAtlas,
+    text::color_glyphs::{self, ColorLayers},
+    Color32, TextureAtlas,
 };
 use ahash::AHashMap;
-use emath::{vec2, Vec2};
+use emath::{pos2, vec2, Rect, Vec2};
 use std::collections::BTreeSet;
 use std::sync::Arc;
 
@@ -24,12 +25,39 @@ pub struct UvRect {
 
     /// Bottom right corner (exclusive).
     pub max: [u16; 2],
+
+    /// The glyph has its own colors (e.g. an emoji), and is painted without the text color.
+    ///
+    /// The colors are in [`crate::FontImage::colors`].
+    pub is_color: bool,
+
+    /// This color glyph also has layers that are painted in the text color.
+    ///
+    /// Their coverage is stored next to the colors, see [`Self::foreground`].
+    pub has_foreground: bool,
 }
 
 impl UvRect {
     pub fn is_nothing(&self) -> bool {
         self.min == self.max
     }
+
+    /// Where the coverage of the layers in the text color is, if [`Self::has_foreground`].
+    ///
+    /// It is just left of the colors, with a pixel of padding,
+    /// and should be painted in the text color below the colors.
+    pub fn foreground(&self) -> Option<Self> {
+        self.has_foreground.then(|| {
+            let shift = self.max[0] - self.min[0] + 1;
+            Self {
+                min: [self.min[0] - shift, self.min[1]],
+                max: [self.max[0] - shift, self.max[1]],
+                is_color: false,
+                has_foreground: false,
+                ..*self
+            }
+        })
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -55,6 +83,38 @@ impl Default for GlyphInfo {
 
 // ----------------------------------------------------------------------------
 
+/// A font file for shaping, with the values of its variation axes.
+#[cfg(feature = "shaping")]
+struct ShapingFace {
+    font_data: Arc<crate::text::FontData>,
+    variations: Vec<rustybuzz::Variation>,
+}
+
+#[cfg(feature = "shaping")]
+impl ShapingFace {
+    fn new(font_data: Arc<crate::text::FontData>, variations: &[([u8; 4], f32)]) -> Self {
+        let variations = variations
+            .iter()
+            .map(|(tag, value)| rustybuzz::Variation {
+                tag: rustybuzz::Tag::from_bytes(tag),
+                value: *value,
+            })
+            .collect();
+        Self {
+            font_data,
+            variations,
+        }
+    }
+
+    /// The face borrows the font file, so we can't keep it around.
+    /// That is fine, because the tables of the face are only parsed when they are used.
+    fn face(&self) -> Option<rustybuzz::Face<'_>> {
+        let mut face = rustybuzz::Face::from_slice(&self.font_data.font, self.font_data.index)?;
+        face.set_variations(&self.variations);
+        Some(face)
+    }
+}
+
 /// A specific font with a size.
 /// The interface uses points as the unit for everything.
 pub struct FontImpl {
@@ -68,6 +128,17 @@ pub struct FontImpl {
     pixels_per_point: f32,
     glyph_info_cache: RwLock<AHashMap<char, GlyphInfo>>, // TODO(emilk): standard Mutex
     atlas: Arc<Mutex<TextureAtlas>>,
+
+    /// The colored layers of the color glyphs, if this is a `COLR` font.
+    color_layers: Option<Arc<ColorLayers>>,
+
+    /// The font file, for shaping.
+    #[cfg(feature = "shaping")]
+    shaping_face: Option<ShapingFace>,
+
+    /// Images of shaped glyph clusters, keyed by glyph ids and positions (in font units).
+    #[cfg(feature = "shaping")]
+    cluster_cache: RwLock<AHashMap<ClusterGlyphs, UvRect>>,
 }
 
 impl FontImpl {
@@ -103,9 +174,32 @@ impl FontImpl {
             pixels_per_point,
             glyph_info_cache: Default::default(),
             atlas,
+            color_layers: None,
+            #[cfg(feature = "shaping")]
+            shaping_face: None,
+            #[cfg(feature = "shaping")]
+            cluster_cache: Default::default(),
         }
     }
 
+    /// The colored layers for color glyphs, from [`ColorLayers::from_font`].
+    pub(crate) fn with_color_layers(mut self, color_layers: Option<Arc<ColorLayers>>) -> Self {
+        self.color_layers = color_layers;
+        self
+    }
+
+    /// The font file this font was loaded from, and the values of its variation axes.
+    /// Without it, text using this font is not shaped.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn with_font_data(
+        mut self,
+        font_data: Arc<crate::text::FontData>,
+        variations: &[([u8; 4], f32)],
+    ) -> Self {
+        self.shaping_face = Some(ShapingFace::new(font_data, variations));
+        self
+    }
+
     fn ignore_character(&self, chr: char) -> bool {
         if self.name == "emoji-icon-font" {
             // HACK: https://github.com/emilk/egui/issues/1284 https://github.com/jslegers/emoji-icon-font/issues/18
@@ -135,7 +229,7 @@ impl FontImpl {
     }
 
     /// `\n` will result in `None`
-    fn glyph_info(&self, c: char) -> Option<GlyphInfo> {
+    pub(crate) fn glyph_info(&self, c: char) -> Option<GlyphInfo> {
         {
             if let Some(glyph_info) = self.glyph_info_cache.read().get(&c) {
                 return Some(*glyph_info);
@@ -174,6 +268,7 @@ impl FontImpl {
             let glyph_info = allocate_glyph(
                 &mut self.atlas.lock(),
                 &self.ab_glyph_font,
+                self.color_layers.as_deref(),
                 glyph_id,
                 self.scale_in_pixels as f32,
                 self.y_offset,
@@ -198,6 +293,94 @@ impl FontImpl {
             / self.pixels_per_point
     }
 
+    /// Shape some text with this font, in the given direction.
+    ///
+    /// Returns the glyph clusters in logical order (the order of the text),
+    /// or `None` if this font can't be used for shaping.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn shape(&self, text: &str, is_rtl: bool) -> Option<Vec<ShapedCluster>> {
+        use ab_glyph::{Font as _, ScaleFont as _};
+
+        let face = self.shaping_face.as_ref()?.face()?;
+
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        buffer.push_str(text);
+        buffer.guess_segment_properties();
+        buffer.set_direction(if is_rtl {
+            rustybuzz::Direction::RightToLeft
+        } else {
+            rustybuzz::Direction::LeftToRight
+        });
+        let glyph_buffer = rustybuzz::shape(&face, &[], buffer);
+
+        // Group the glyphs by cluster, with positions relative to the start of the cluster:
+        let mut groups: Vec<(usize, i32, ClusterGlyphs)> = vec![];
+        for (info, pos) in glyph_buffer
+            .glyph_infos()
+            .iter()
+            .zip(glyph_buffer.glyph_positions())
+        {
+            let byte_start = info.cluster as usize;
+            if groups.last().map(|group| group.0) != Some(byte_start) {
+                groups.push((byte_start, 0, vec![]));
+            }
+            let (_, advance, glyphs) = groups.last_mut().unwrap();
+            glyphs.push((info.glyph_id as u16, *advance + pos.x_offset, pos.y_offset));
+            *advance += pos.x_advance;
+        }
+        if is_rtl {
+            groups.reverse();
+        }
+
+        let scale_in_pixels = self.scale_in_pixels as f32;
+        let pixels_per_unit = self
+            .ab_glyph_font
+            .as_scaled(scale_in_pixels)
+            .h_scale_factor();
+
+        let clusters = groups
+            .into_iter()
+            .map(|(byte_start, advance, glyphs)| ShapedCluster {
+                byte_start,
+                advance_width: advance as f32 * pixels_per_unit / self.pixels_per_point,
+                uv_rect: self.cluster_uv_rect(glyphs, pixels_per_unit),
+            })
+            .collect();
+        Some(clusters)
+    }
+
+    #[cfg(feature = "shaping")]
+    fn cluster_uv_rect(&self, glyphs: ClusterGlyphs, pixels_per_unit: f32) -> UvRect {
+        if let Some(uv_rect) = self.cluster_cache.read().get(&glyphs) {
+            return *uv_rect;
+        }
+
+        let positioned_glyphs: Vec<_> = glyphs
+            .iter()
+            .filter(|(glyph_id, _, _)| *glyph_id != 0)
+            .map(|&(glyph_id, x, y)| {
+                let position = ab_glyph::point(
+                    x as f32 * pixels_per_unit,
+                    -y as f32 * pixels_per_unit, // font units are y-up
+                );
+                ab_glyph::GlyphId(glyph_id)
+                    .with_scale_and_position(self.scale_in_pixels as f32, position)
+            })
+            .collect();
+
+        let uv_rect = allocate_glyph_cluster(
+            &mut self.atlas.lock(),
+            &self.ab_glyph_font,
+            self.color_layers.as_deref(),
+            positioned_glyphs,
+            self.scale_in_pixels as f32,
+            self.y_offset,
+            self.pixels_per_point,
+        );
+        self.cluster_cache.write().insert(glyphs, uv_rect);
+        uv_rect
+    }
+
     /// Height of one row of text. In points
     #[inline(always)]
     pub fn row_height(&self) -> f32 {
@@ -210,6 +393,26 @@ impl FontImpl {
     }
 }
 
+/// Glyph ids and their positions relative to the start of the cluster, in font units.
+#[cfg(feature = "shaping")]
+type ClusterGlyphs = Vec<(u16, i32, i32)>;
+
+/// One or more glyphs that together represent a part of a shaped text,
+/// e.g. a ligature, or a letter with its accents.
+#[cfg(feature = "shaping")]
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ShapedCluster {
+    /// Where the cluster starts in the shaped text.
+    /// It ends where the next cluster starts.
+    pub byte_start: usize,
+
+    /// Unit: points.
+    pub advance_width: f32,
+
+    /// All the glyphs of the cluster, rasterized together.
+    pub uv_rect: UvRect,
+}
+
 type FontIndex = usize;
 
 // TODO(emilk): rename?
@@ -222,6 +425,8 @@ pub struct Font {
     pixels_per_point: f32,
     row_height: f32,
     glyph_info_cache: AHashMap<char, (FontIndex, GlyphInfo)>,
+    /// Slant the glyphs, because there is no italic face.
+    synthetic_italics: bool,
 }
 
 impl Font {
@@ -234,6 +439,7 @@ impl Font {
                 pixels_per_point: 1.0,
                 row_height: 0.0,
                 glyph_info_cache: Default::default(),
+                synthetic_italics: false,
             };
         }
 
@@ -247,6 +453,7 @@ impl Font {
             pixels_per_point,
             row_height,
             glyph_info_cache: Default::default(),
+            synthetic_italics: false,
         };
 
         const PRIMARY_REPLACEMENT_CHAR: char = '◻'; // white medium square
@@ -266,6 +473,27 @@ impl Font {
         slf
     }
 
+    /// Set if the glyphs should be slanted, because the font has no italic face.
+    pub(crate) fn with_synthetic_italics(mut self, synthetic_italics: bool) -> Self {
+        self.synthetic_italics = synthetic_italics;
+        self
+    }
+
+    /// `true` if italic text with this font is made by slanting the glyphs
+    /// of an upright font.
+    #[inline]
+    pub fn synthetic_italics(&self) -> bool {
+        self.synthetic_italics
+    }
+
+    /// Add a font to use for the characters that none of the other fonts have.
+    #[cfg(feature = "system_fonts")]
+    pub(crate) fn push_fallback(&mut self, font: Arc<FontImpl>) {
+        let mut fonts = std::mem::take(&mut self.fonts);
+        fonts.push(font);
+        *self = Self::new(fonts).with_synthetic_italics(self.synthetic_italics);
+    }
+
     pub fn preload_common_characters(&mut self) {
         // Preload the printable ASCII characters [32, 126] (which excludes control codes):
         const FIRST_ASCII: usize = 32; // 32 == space
@@ -311,6 +539,29 @@ impl Font {
         self.glyph_info(c).1.advance_width
     }
 
+    /// Can we display this glyph, without falling back to the replacement character?
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.fonts.iter().any(|font| font.glyph_info(c).is_some())
+    }
+
+    /// Can we display all the glyphs in this text?
+    pub fn has_glyphs(&self, s: &str) -> bool {
+        s.chars().all(|c| self.has_glyph(c))
+    }
+
+    /// Which of the fonts (primary or fallbacks) has this character, if any.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn font_index_for_char(&self, c: char) -> Option<FontIndex> {
+        self.fonts
+            .iter()
+            .position(|font| font.glyph_info(c).is_some())
+    }
+
+    #[cfg(feature = "shaping")]
+    pub(crate) fn font_impl(&self, font_index: FontIndex) -> Arc<FontImpl> {
+        self.fonts[font_index].clone()
+    }
+
     /// `\n` will (intentionally) show up as the replacement character.
     fn glyph_info(&mut self, c: char) -> (FontIndex, GlyphInfo) {
         if let Some(font_index_glyph_info) = self.glyph_info_cache.get(&c) {
@@ -349,12 +600,14 @@ fn invisible_char(c: char) -> bool {
     // See https://github.com/emilk/egui/issues/336
 
     // From https://www.fileformat.info/info/unicode/category/Cf/list.htm
-    ('\u{200B}'..='\u{206F}').contains(&c) // TODO(emilk): heed bidi characters
+    // This includes the bidi control characters, which are used when laying out the text.
+    ('\u{200B}'..='\u{206F}').contains(&c)
 }
 
 fn allocate_glyph(
     atlas: &mut TextureAtlas,
     font: &ab_glyph::FontArc,
+    color_layers: Option<&ColorLayers>,
     glyph_id: ab_glyph::GlyphId,
     scale_in_pixels: f32,
     y_offset: f32,
@@ -366,34 +619,48 @@ fn allocate_glyph(
     let glyph =
         glyph_id.with_scale_and_position(scale_in_pixels, ab_glyph::Point { x: 0.0, y: 0.0 });
 
-    let uv_rect = font.outline_glyph(glyph).map(|glyph| {
-        let bb = glyph.px_bounds();
-        let glyph_width = bb.width() as usize;
-        let glyph_height = bb.height() as usize;
-        if glyph_width == 0 || glyph_height == 0 {
-            UvRect::default()
-        } else {
-            let (glyph_pos, image) = atlas.allocate((glyph_width, glyph_height));
-            glyph.draw(|x, y, v| {
-                if v > 0.0 {
-                    let px = glyph_pos.0 + x as usize;
-                    let py = glyph_pos.1 + y as usize;
-                    image[(px, py)] = v;
+    let color_uv_rect = allocate_color_glyphs(
+        atlas,
+        font,
+        color_layers,
+        std::slice::from_ref(&glyph),
+        scale_in_pixels,
+        y_offset,
+        pixels_per_point,
+    );
+
+    let uv_rect = color_uv_rect.or_else(|| {
+        font.outline_glyph(glyph).map(|glyph| {
+            let bb = glyph.px_bounds();
+            let glyph_width = bb.width() as usize;
+            let glyph_height = bb.height() as usize;
+            if glyph_width == 0 || glyph_height == 0 {
+                UvRect::default()
+            } else {
+                let (glyph_pos, image) = atlas.allocate((glyph_width, glyph_height));
+                glyph.draw(|x, y, v| {
+                    if v > 0.0 {
+                        let px = glyph_pos.0 + x as usize;
+                        let py = glyph_pos.1 + y as usize;
+                        image[(px, py)] = v;
+                    }
+                });
+
+                let offset_in_pixels = vec2(bb.min.x as f32, scale_in_pixels + bb.min.y as f32);
+                let offset = offset_in_pixels / pixels_per_point + y_offset * Vec2::Y;
+                UvRect {
+                    offset,
+                    size: vec2(glyph_width as f32, glyph_height as f32) / pixels_per_point,
+                    min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
+                    max: [
+                        (glyph_pos.0 + glyph_width) as u16,
+                        (glyph_pos.1 + glyph_height) as u16,
+                    ],
+                    is_color: false,
+                    has_foreground: false,
                 }
-            });
-
-            let offset_in_pixels = vec2(bb.min.x as f32, scale_in_pixels + bb.min.y as f32);
-            let offset = offset_in_pixels / pixels_per_point + y_offset * Vec2::Y;
-            UvRect {
-                offset,
-                size: vec2(glyph_width as f32, glyph_height as f32) / pixels_per_point,
-                min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
-                max: [
-                    (glyph_pos.0 + glyph_width) as u16,
-                    (glyph_pos.1 + glyph_height) as u16,
-                ],
             }
-        }
+        })
     });
     let uv_rect = uv_rect.unwrap_or_default();
 
@@ -406,3 +673,241 @@ fn allocate_glyph(
         uv_rect,
     }
 }
+
+/// Rasterize several glyphs (e.g. a base letter and its accents) into one image.
+#[cfg(feature = "shaping")]
+fn allocate_glyph_cluster(
+    atlas: &mut TextureAtlas,
+    font: &ab_glyph::FontArc,
+    color_layers: Option<&ColorLayers>,
+    glyphs: Vec<ab_glyph::Glyph>,
+    scale_in_pixels: f32,
+    y_offset: f32,
+    pixels_per_point: f32,
+) -> UvRect {
+    use ab_glyph::Font as _;
+
+    if let Some(uv_rect) = allocate_color_glyphs(
+        atlas,
+        font,
+        color_layers,
+        &glyphs,
+        scale_in_pixels,
+        y_offset,
+        pixels_per_point,
+    ) {
+        return uv_rect;
+    }
+
+    let outlined: Vec<_> = glyphs
+        .into_iter()
+        .filter_map(|glyph| font.outline_glyph(glyph))
+        .collect();
+
+    let bb = outlined
+        .iter()
+        .map(|glyph| glyph.px_bounds())
+        .reduce(|a, b| ab_glyph::Rect {
+            min: ab_glyph::point(a.min.x.min(b.min.x), a.min.y.min(b.min.y)),
+            max: ab_glyph::point(a.max.x.max(b.max.x), a.max.y.max(b.max.y)),
+        });
+    let bb = match bb {
+        Some(bb) => bb,
+        None => return UvRect::default(),
+    };
+
+    let cluster_width = bb.width() as usize;
+    let cluster_height = bb.height() as usize;
+    if cluster_width == 0 || cluster_height == 0 {
+        return UvRect::default();
+    }
+
+    let (cluster_pos, image) = atlas.allocate((cluster_width, cluster_height));
+    for glyph in &outlined {
+        // `px_bounds` are whole pixels:
+        let glyph_bb = glyph.px_bounds();
+        let dx = cluster_pos.0 + (glyph_bb.min.x - bb.min.x) as usize;
+        let dy = cluster_pos.1 + (glyph_bb.min.y - bb.min.y) as usize;
+        glyph.draw(|x, y, v| {
+            if v > 0.0 {
+                let pixel = (dx + x as usize, dy + y as usize);
+                image[pixel] = image[pixel].max(v);
+            }
+        });
+    }
+
+    let offset_in_pixels = vec2(bb.min.x, scale_in_pixels + bb.min.y);
+    let offset = offset_in_pixels / pixels_per_point + y_offset * Vec2::Y;
+    UvRect {
+        offset,
+        size: vec2(cluster_width as f32, cluster_height as f32) / pixels_per_point,
+        min: [cluster_pos.0 as u16, cluster_pos.1 as u16],
+        max: [
+            (cluster_pos.0 + cluster_width) as u16,
+            (cluster_pos.1 + cluster_height) as u16,
+        ],
+        is_color: false,
+        has_foreground: false,
+    }
+}
+
+/// Something to paint into the color layer of the atlas.
+enum ColorPaint {
+    /// A glyph in a color, or in the text color if `None`.
+    Outline(ab_glyph::OutlinedGlyph, Option<Color32>),
+    Image {
+        /// Top left corner, in whole pixels relative to the baseline.
+        min: (i32, i32),
+        image: crate::ColorImage,
+    },
+}
+
+impl ColorPaint {
+    fn pixel_bounds(&self) -> Rect {
+        match self {
+            Self::Outline(glyph, _) => {
+                let bb = glyph.px_bounds();
+                Rect::from_min_max(pos2(bb.min.x, bb.min.y), pos2(bb.max.x, bb.max.y))
+            }
+            Self::Image { min, image } => {
+                let min = pos2(min.0 as f32, min.1 as f32);
+                Rect::from_min_size(min, vec2(image.size[0] as f32, image.size[1] as f32))
+            }
+        }
+    }
+}
+
+/// The bitmap image of a color glyph (e.g. in an emoji font), scaled to the font size.
+fn raster_image_paint(
+    font: &ab_glyph::FontArc,
+    glyph: &ab_glyph::Glyph,
+    scale_in_pixels: f32,
+) -> Option<ColorPaint> {
+    use ab_glyph::Font as _;
+
+    let raster_image = font.glyph_raster_image2(glyph.id, scale_in_pixels.round() as u16)?;
+    if raster_image.pixels_per_em == 0 {
+        return None;
+    }
+    let image = color_glyphs::decode_raster_image(&raster_image)?;
+    if image.size.contains(&0) {
+        return None;
+    }
+
+    let scale = scale_in_pixels / raster_image.pixels_per_em as f32;
+    let size = [
+        ((image.size[0] as f32 * scale).round() as usize).max(1),
+        ((image.size[1] as f32 * scale).round() as usize).max(1),
+    ];
+    // The origin is the bottom left corner, with y up:
+    let left = glyph.position.x + raster_image.origin.x * scale;
+    let top = glyph.position.y - raster_image.origin.y * scale - size[1] as f32;
+    Some(ColorPaint::Image {
+        min: (left.round() as i32, top.round() as i32),
+        image: color_glyphs::resize_image(&image, size),
+    })
+}
+
+/// Rasterize glyphs with their own colors (e.g. emojis) into the color layer of the atlas.
+///
+/// Layers and glyphs without colors are painted in the text color,
+/// so their coverage goes next to the colors, see [`UvRect::foreground`].
+/// Returns `None` if none of the glyphs have colors.
+fn allocate_color_glyphs(
+    atlas: &mut TextureAtlas,
+    font: &ab_glyph::FontArc,
+    color_layers: Option<&ColorLayers>,
+    glyphs: &[ab_glyph::Glyph],
+    scale_in_pixels: f32,
+    y_offset: f32,
+    pixels_per_point: f32,
+) -> Option<UvRect> {
+    use ab_glyph::Font as _;
+
+    let mut paints = vec![];
+    let mut any_color = false;
+    for glyph in glyphs {
+        if let Some(layers) = color_layers.and_then(|color_layers| color_layers.layers(glyph.id)) {
+            any_color = true;
+            for (layer_id, color) in layers {
+                let layer = ab_glyph::Glyph {
+                    id: layer_id,
+                    ..glyph.clone()
+                };
+                if let Some(outlined) = font.outline_glyph(layer) {
+                    paints.push(ColorPaint::Outline(outlined, color));
+                }
+            }
+        } else if let Some(paint) = raster_image_paint(font, glyph, scale_in_pixels) {
+            any_color = true;
+            paints.push(paint);
+        } else if let Some(outlined) = font.outline_glyph(glyph.clone()) {
+            paints.push(ColorPaint::Outline(outlined, None));
+        }
+    }
+    if !any_color {
+        return None;
+    }
+
+    let bb = paints
+        .iter()
+        .map(ColorPaint::pixel_bounds)
+        .fold(Rect::NOTHING, |a, b| a.union(b));
+    if !bb.is_positive() {
+        return Some(UvRect::default());
+    }
+    let width = bb.width() as usize;
+    let height = bb.height() as usize;
+    let has_foreground = paints
+        .iter()
+        .any(|paint| matches!(paint, ColorPaint::Outline(_, None)));
+
+    // The coverage of the layers in the text color goes to the left of the colors:
+    let foreground_width = if has_foreground { width + 1 } else { 0 };
+    let (foreground_pos, image) = atlas.allocate((foreground_width + width, height));
+    let pos = (foreground_pos.0 + foreground_width, foreground_pos.1);
+    for paint in &paints {
+        let paint_bb = paint.pixel_bounds();
+        let dx = pos.0 + (paint_bb.min.x - bb.min.x) as usize;
+        let dy = pos.1 + (paint_bb.min.y - bb.min.y) as usize;
+        match paint {
+            ColorPaint::Outline(glyph, Some(color)) => {
+                glyph.draw(|x, y, v| {
+                    if v > 0.0 {
+                        let pixel = image.color_mut((dx + x as usize, dy + y as usize));
+                        *pixel = color_glyphs::blend_over(*pixel, color.linear_multiply(v));
+                    }
+                });
+            }
+            ColorPaint::Outline(glyph, None) => {
+                glyph.draw(|x, y, v| {
+                    if v > 0.0 {
+                        let (x, y) = (dx + x as usize, dy + y as usize);
+                        let mut coverage = image[(x - foreground_width, y)];
+                        color_glyphs::paint_foreground(image.color_mut((x, y)), &mut coverage, v);
+                        image[(x - foreground_width, y)] = coverage;
+                    }
+                });
+            }
+            ColorPaint::Image { image: src, .. } => {
+                for y in 0..src.size[1] {
+                    for x in 0..src.size[0] {
+                        let pixel = image.color_mut((dx + x, dy + y));
+                        *pixel = color_glyphs::blend_over(*pixel, src[(x, y)]);
+                    }
+                }
+            }
+        }
+    }
+
+    let offset_in_pixels = vec2(bb.min.x, scale_in_pixels + bb.min.y);
+    let offset = offset_in_pixels / pixels_per_point + y_offset * Vec2::Y;
+    Some(UvRect {
+        offset,
+        size: vec2(width as f32, height as f32) / pixels_per_point,
+        min: [pos.0 as u16, pos.1 as u16],
+        max: [(pos.0 + width) as u16, (pos.1 + height) as u16],
+        is_color: true,
+        has_foreground,
+    })
+}
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use crate::{
     mutex::{Mutex, MutexGuard},
     text::{
+        color_glyphs::ColorLayers,
         font::{Font, FontImpl},
         Galley, LayoutJob,
     },
@@ -16,13 +17,24 @@ use emath::NumExt as _;
 /// How to select a sized font.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct FontId {
     /// Height in points.
     pub size: f32,
 
     /// What font family to use.
     pub family: FontFamily,
-    // TODO(emilk): weight (bold), italics, …
+
+    /// How bold the font is.
+    ///
+    /// The closest weight in [`FontDefinitions::faces`] is used,
+    /// or the exact weight for variable fonts.
+    pub weight: FontWeight,
+
+    /// Upright or italic.
+    ///
+    /// If the family has no italic face, the glyphs are slanted.
+    pub style: FontStyle,
 }
 
 impl Default for FontId {
@@ -31,6 +43,8 @@ impl Default for FontId {
         Self {
             size: 14.0,
             family: FontFamily::Proportional,
+            weight: FontWeight::NORMAL,
+            style: FontStyle::Normal,
         }
     }
 }
@@ -38,7 +52,12 @@ impl Default for FontId {
 impl FontId {
     #[inline]
     pub const fn new(size: f32, family: FontFamily) -> Self {
-        Self { size, family }
+        Self {
+            size,
+            family,
+            weight: FontWeight::NORMAL,
+            style: FontStyle::Normal,
+        }
     }
 
     #[inline]
@@ -50,15 +69,85 @@ impl FontId {
     pub const fn monospace(size: f32) -> Self {
         Self::new(size, FontFamily::Monospace)
     }
+
+    #[inline]
+    pub const fn weight(mut self, weight: FontWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    #[inline]
+    pub const fn bold(self) -> Self {
+        self.weight(FontWeight::BOLD)
+    }
+
+    #[inline]
+    pub const fn style(mut self, style: FontStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    #[inline]
+    pub const fn italic(self) -> Self {
+        self.style(FontStyle::Italic)
+    }
 }
 
 #[allow(clippy::derive_hash_xor_eq)]
 impl std::hash::Hash for FontId {
     #[inline(always)]
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        let Self { size, family } = self;
+        let Self {
+            size,
+            family,
+            weight,
+            style,
+        } = self;
         crate::f32_hash(state, *size);
         family.hash(state);
+        weight.hash(state);
+        style.hash(state);
+    }
+}
+
+/// How thick the strokes of a font are, from 100 (thin) to 900 (black).
+///
+/// This is the same scale as used by CSS and the `wght` axis of variable fonts.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const THIN: Self = Self(100);
+    pub const EXTRA_LIGHT: Self = Self(200);
+    pub const LIGHT: Self = Self(300);
+    pub const NORMAL: Self = Self(400);
+    pub const MEDIUM: Self = Self(500);
+    pub const SEMI_BOLD: Self = Self(600);
+    pub const BOLD: Self = Self(700);
+    pub const EXTRA_BOLD: Self = Self(800);
+    pub const BLACK: Self = Self(900);
+}
+
+impl Default for FontWeight {
+    #[inline]
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+/// Upright or italic.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum FontStyle {
+    Normal,
+    Italic,
+}
+
+impl Default for FontStyle {
+    #[inline]
+    fn default() -> Self {
+        Self::Normal
     }
 }
 
@@ -124,6 +213,13 @@ pub struct FontData {
 
     /// Extra scale and vertical tweak to apply to all text of this font.
     pub tweak: FontTweak,
+
+    /// Fixed values for the axes of a variable font, e.g. `(*b"wdth", 75.0)`.
+    ///
+    /// The `wght` and `ital` axes are set from [`FontId::weight`] and [`FontId::style`],
+    /// unless they are given here.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub variations: Vec<([u8; 4], f32)>,
 }
 
 impl FontData {
@@ -132,6 +228,7 @@ impl FontData {
             font: std::borrow::Cow::Borrowed(font),
             index: 0,
             tweak: Default::default(),
+            variations: Default::default(),
         }
     }
 
@@ -140,12 +237,20 @@ impl FontData {
             font: std::borrow::Cow::Owned(font),
             index: 0,
             tweak: Default::default(),
+            variations: Default::default(),
         }
     }
 
     pub fn tweak(self, tweak: FontTweak) -> Self {
         Self { tweak, ..self }
     }
+
+    /// Set an axis of a variable font to a fixed value, e.g. `.variation(*b"wdth", 75.0)`.
+    pub fn variation(mut self, tag: [u8; 4], value: f32) -> Self {
+        self.variations.retain(|(t, _)| *t != tag);
+        self.variations.push((tag, value));
+        self
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -185,20 +290,43 @@ impl Default for FontTweak {
 
 // ----------------------------------------------------------------------------
 
-fn ab_glyph_font_from_font_data(name: &str, data: &FontData) -> ab_glyph::FontArc {
+/// Load a font, with the axes of variable fonts set to these values.
+fn ab_glyph_font_from_font_data(
+    name: &str,
+    data: &FontData,
+    variations: &[([u8; 4], f32)],
+) -> ab_glyph::FontArc {
+    use ab_glyph::VariableFont as _;
+
     match &data.font {
         std::borrow::Cow::Borrowed(bytes) => {
-            ab_glyph::FontRef::try_from_slice_and_index(bytes, data.index)
-                .map(ab_glyph::FontArc::from)
+            ab_glyph::FontRef::try_from_slice_and_index(bytes, data.index).map(|mut font| {
+                for (tag, value) in variations {
+                    font.set_variation(tag, *value);
+                }
+                ab_glyph::FontArc::from(font)
+            })
         }
         std::borrow::Cow::Owned(bytes) => {
-            ab_glyph::FontVec::try_from_vec_and_index(bytes.clone(), data.index)
-                .map(ab_glyph::FontArc::from)
+            ab_glyph::FontVec::try_from_vec_and_index(bytes.clone(), data.index).map(|mut font| {
+                for (tag, value) in variations {
+                    font.set_variation(tag, *value);
+                }
+                ab_glyph::FontArc::from(font)
+            })
         }
     }
     .unwrap_or_else(|err| panic!("Error parsing {:?} TTF/OTF font file: {}", name, err))
 }
 
+/// The variation axes of a font, empty for non-variable fonts.
+fn variation_axes(name: &str, data: &FontData) -> Vec<ab_glyph::VariationAxis> {
+    use ab_glyph::VariableFont as _;
+    ab_glyph::FontRef::try_from_slice_and_index(&data.font, data.index)
+        .unwrap_or_else(|err| panic!("Error parsing {:?} TTF/OTF font file: {}", name, err))
+        .variations()
+}
+
 /// Describes the font data and the sizes to use.
 ///
 /// Often you would start with [`FontDefinitions::default()`] and then add/change the contents.
@@ -240,7 +368,34 @@ pub struct FontDefinitions {
     /// When looking for a character glyph `epaint` will start with
     /// the first font and then move to the second, and so on.
     /// So the first font is the primary, and then comes a list of fallbacks in order of priority.
+    ///
+    /// These are the fonts for [`FontWeight::NORMAL`] and [`FontStyle::Normal`].
     pub families: BTreeMap<FontFamily, Vec<String>>,
+
+    /// Other weights and styles of the families, e.g. a bold or italic face.
+    ///
+    /// When asking for a [`FontId`] the face with the same style and the closest weight is used,
+    /// which may be the regular face in [`Self::families`].
+    /// Variable fonts are instanced at the exact weight.
+    ///
+    /// ```
+    /// # use epaint::text::{FontDefinitions, FontFace, FontFamily, FontWeight, FontStyle};
+    /// let mut fonts = FontDefinitions::default();
+    /// fonts.faces.entry(FontFamily::Proportional).or_default().push(FontFace {
+    ///     weight: FontWeight::BOLD,
+    ///     style: FontStyle::Normal,
+    ///     fonts: vec!["Hack".to_owned()],
+    /// });
+    /// ```
+    pub faces: BTreeMap<FontFamily, Vec<FontFace>>,
+
+    /// If set, characters that none of the fonts in [`Self::families`] have
+    /// are looked up among these fonts, which are then loaded as needed.
+    ///
+    /// Default: `None`.
+    #[cfg(feature = "system_fonts")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub system_fonts: Option<Arc<super::SystemFonts>>,
 }
 
 impl Default for FontDefinitions {
@@ -307,10 +462,87 @@ impl Default for FontDefinitions {
         Self {
             font_data,
             families,
+            faces: Default::default(),
+            #[cfg(feature = "system_fonts")]
+            system_fonts: None,
+        }
+    }
+}
+
+impl FontDefinitions {
+    /// The fonts to use for this family, weight and style.
+    ///
+    /// Returns the names of the fonts, and whether they are of the requested style.
+    fn face_fonts(
+        &self,
+        family: &FontFamily,
+        weight: FontWeight,
+        style: FontStyle,
+    ) -> (Vec<String>, bool) {
+        let regular = self
+            .families
+            .get(family)
+            .unwrap_or_else(|| panic!("FontFamily::{:?} is not bound to any fonts", family));
+        let regular_face = FontFace {
+            weight: FontWeight::NORMAL,
+            style: FontStyle::Normal,
+            fonts: vec![],
+        };
+
+        let faces = self.faces.get(family).into_iter().flatten();
+        let best = std::iter::once(&regular_face)
+            .chain(faces)
+            .min_by_key(|face| face.distance(weight, style))
+            .unwrap_or(&regular_face);
+
+        if std::ptr::eq(best, &regular_face) {
+            (regular.clone(), style == FontStyle::Normal)
+        } else {
+            // Use the regular fonts as fallbacks, e.g. for emojis:
+            let mut fonts = best.fonts.clone();
+            for name in regular {
+                if !fonts.contains(name) {
+                    fonts.push(name.clone());
+                }
+            }
+            (fonts, best.style == style)
         }
     }
 }
 
+/// The fonts for one weight and style of a [`FontFamily`].
+///
+/// See [`FontDefinitions::faces`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FontFace {
+    pub weight: FontWeight,
+    pub style: FontStyle,
+
+    /// Keys into [`FontDefinitions::font_data`], primary font first.
+    pub fonts: Vec<String>,
+}
+
+impl FontFace {
+    /// How bad of a match this face is. Lower is better.
+    ///
+    /// The style must match first, then the weight should be as close as possible.
+    /// For ties, bolder faces are preferred for bold text, and lighter for light text
+    /// (like in CSS).
+    fn distance(&self, weight: FontWeight, style: FontStyle) -> (bool, u16, bool) {
+        let wrong_direction = if weight >= FontWeight::MEDIUM {
+            self.weight < weight
+        } else {
+            self.weight > weight
+        };
+        (
+            self.style != style,
+            self.weight.0.abs_diff(weight.0),
+            wrong_direction,
+        )
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 /// The collection of fonts used by `epaint`.
@@ -322,6 +554,7 @@ impl Default for FontDefinitions {
 /// If you are using `egui`, use `egui::Context::set_fonts` and `egui::Context::fonts`.
 ///
 /// You need to call [`Self::begin_frame`] and [`Self::font_image_delta`] once every frame.
+#[derive(Clone)]
 pub struct Fonts(Arc<Mutex<FontsAndCache>>);
 
 impl Fonts {
@@ -368,9 +601,54 @@ impl Fonts {
             };
         }
 
+        #[cfg(feature = "system_fonts")]
+        if fonts_and_cache.fonts.system_fonts_became_ready() {
+            // Lay out the text with the missing characters again:
+            fonts_and_cache.galley_cache = Default::default();
+        }
+
         fonts_and_cache.galley_cache.flush_cache();
     }
 
+    /// Is some text showing replacement glyphs while
+    /// [`FontDefinitions::system_fonts`] are being read on a background thread?
+    ///
+    /// If so, you should repaint once they are ready, so it can be shown with the right font.
+    /// See [`Self::when_system_fonts_ready`].
+    pub fn is_waiting_for_system_fonts(&self) -> bool {
+        #[cfg(feature = "system_fonts")]
+        return self.lock().fonts.waiting_for_system_fonts;
+
+        #[cfg(not(feature = "system_fonts"))]
+        false
+    }
+
+    /// If [`Self::is_waiting_for_system_fonts`], call `wake_up` once the system fonts are ready,
+    /// from the background thread that reads them. Use it to request a repaint.
+    ///
+    /// While waiting, only the first `wake_up` is kept.
+    pub fn when_system_fonts_ready(&self, wake_up: impl FnOnce() + Send + 'static) {
+        #[cfg(feature = "system_fonts")]
+        {
+            let system_fonts = {
+                let mut fonts_and_cache = self.lock();
+                let fonts = &mut fonts_and_cache.fonts;
+                if !fonts.waiting_for_system_fonts || fonts.wake_up_registered {
+                    return;
+                }
+                fonts.wake_up_registered = true;
+                fonts.definitions.system_fonts.clone()
+            };
+            // Not holding the lock, in case `wake_up` is called right away:
+            if let Some(system_fonts) = system_fonts {
+                system_fonts.on_coverage_ready(wake_up);
+            }
+        }
+
+        #[cfg(not(feature = "system_fonts"))]
+        let _ = wake_up;
+    }
+
     /// Call at the end of each frame (before painting) to get the change to the font texture since last call.
     pub fn font_image_delta(&self) -> Option<crate::ImageDelta> {
         self.lock().fonts.atlas.lock().take_delta()
@@ -411,6 +689,16 @@ impl Fonts {
         self.lock().fonts.glyph_width(font_id, c)
     }
 
+    /// Can we display this glyph, without falling back to the replacement character?
+    pub fn has_glyph(&self, font_id: &FontId, c: char) -> bool {
+        self.lock().fonts.font(font_id).has_glyph(c)
+    }
+
+    /// Can we display all the glyphs in this text?
+    pub fn has_glyphs(&self, font_id: &FontId, s: &str) -> bool {
+        self.lock().fonts.font(font_id).has_glyphs(s)
+    }
+
     /// Height of one row of text in points
     #[inline]
     pub fn row_height(&self, font_id: &FontId) -> f32 {
@@ -521,7 +809,24 @@ pub struct FontsImpl {
     definitions: FontDefinitions,
     atlas: Arc<Mutex<TextureAtlas>>,
     font_impl_cache: FontImplCache,
-    sized_family: ahash::AHashMap<(u32, FontFamily), Font>,
+    sized_family: ahash::AHashMap<(u32, FontFamily, FontWeight, FontStyle), Font>,
+
+    /// Fonts loaded from [`FontDefinitions::system_fonts`], used as fallbacks for all families.
+    #[cfg(feature = "system_fonts")]
+    system_fallbacks: Vec<String>,
+
+    /// Characters that are not in any of the [`FontDefinitions::system_fonts`].
+    #[cfg(feature = "system_fonts")]
+    chars_without_system_font: ahash::AHashSet<char>,
+
+    /// Some text was laid out with missing characters
+    /// while the [`FontDefinitions::system_fonts`] were still being read.
+    #[cfg(feature = "system_fonts")]
+    waiting_for_system_fonts: bool,
+
+    /// Someone will be woken up when we are no longer [`Self::waiting_for_system_fonts`].
+    #[cfg(feature = "system_fonts")]
+    wake_up_registered: bool,
 }
 
 impl FontsImpl {
@@ -554,6 +859,14 @@ impl FontsImpl {
             atlas,
             font_impl_cache,
             sized_family: Default::default(),
+            #[cfg(feature = "system_fonts")]
+            system_fallbacks: Default::default(),
+            #[cfg(feature = "system_fonts")]
+            chars_without_system_font: Default::default(),
+            #[cfg(feature = "system_fonts")]
+            waiting_for_system_fonts: false,
+            #[cfg(feature = "system_fonts")]
+            wake_up_registered: false,
         }
     }
 
@@ -567,25 +880,37 @@ impl FontsImpl {
         &self.definitions
     }
 
-    /// Get the right font implementation from size and [`FontFamily`].
+    /// Get the right font implementation from size, [`FontFamily`], weight and style.
     pub fn font(&mut self, font_id: &FontId) -> &mut Font {
-        let FontId { size, family } = font_id;
+        let FontId {
+            size,
+            family,
+            weight,
+            style,
+        } = font_id;
+        let (weight, style) = (*weight, *style);
         let scale_in_pixels = self.font_impl_cache.scale_as_pixels(*size);
 
         self.sized_family
-            .entry((scale_in_pixels, family.clone()))
+            .entry((scale_in_pixels, family.clone(), weight, style))
             .or_insert_with(|| {
-                let fonts = &self.definitions.families.get(family);
-                let fonts = fonts.unwrap_or_else(|| {
-                    panic!("FontFamily::{:?} is not bound to any fonts", family)
-                });
+                let (fonts, is_right_style) = self.definitions.face_fonts(family, weight, style);
+                let has_italic_axis = fonts
+                    .first()
+                    .map_or(false, |name| self.font_impl_cache.has_italic_axis(name));
+
+                let fonts = fonts.iter();
+                #[cfg(feature = "system_fonts")]
+                let fonts = fonts.chain(&self.system_fallbacks);
 
                 let fonts: Vec<Arc<FontImpl>> = fonts
-                    .iter()
-                    .map(|font_name| self.font_impl_cache.font_impl(scale_in_pixels, font_name))
+                    .map(|font_name| {
+                        self.font_impl_cache
+                            .font_impl(scale_in_pixels, font_name, weight, style)
+                    })
                     .collect();
 
-                Font::new(fonts)
+                Font::new(fonts).with_synthetic_italics(!is_right_style && !has_italic_axis)
             })
     }
 
@@ -598,6 +923,88 @@ impl FontsImpl {
     fn row_height(&mut self, font_id: &FontId) -> f32 {
         self.font(font_id).row_height()
     }
+
+    /// Load fonts from [`FontDefinitions::system_fonts`] for the characters in `text`
+    /// that the font doesn't have.
+    #[cfg(feature = "system_fonts")]
+    pub(crate) fn load_system_fallbacks(&mut self, font_id: &FontId, text: &str) {
+        use super::system_fonts::FontForChar;
+
+        let system_fonts = match &self.definitions.system_fonts {
+            Some(system_fonts) => system_fonts.clone(),
+            None => return,
+        };
+
+        let font = self.font(font_id);
+        let mut missing: Vec<char> = text
+            .chars()
+            .filter(|&c| !c.is_whitespace() && !c.is_control() && !font.has_glyph(c))
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+        missing.sort_unstable();
+        missing.dedup();
+
+        for c in missing {
+            if self.chars_without_system_font.contains(&c) {
+                continue;
+            }
+
+            match system_fonts.font_for_char(c) {
+                FontForChar::Found(name, id) => {
+                    if !self.system_fallbacks.contains(&name) {
+                        match system_fonts.load_face(id) {
+                            Some(font_data) => self.add_system_fallback(name, &font_data),
+                            None => {
+                                self.chars_without_system_font.insert(c);
+                            }
+                        }
+                    }
+                }
+                FontForChar::Missing => {
+                    self.chars_without_system_font.insert(c);
+                }
+                FontForChar::NotReady => {
+                    // Show the replacement glyph for now:
+                    self.waiting_for_system_fonts = true;
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if some text was waiting for the system fonts, and they are now loaded.
+    #[cfg(feature = "system_fonts")]
+    pub(crate) fn system_fonts_became_ready(&mut self) -> bool {
+        if !self.waiting_for_system_fonts {
+            return false;
+        }
+        let ready = self
+            .definitions
+            .system_fonts
+            .as_ref()
+            .map_or(true, |system_fonts| !system_fonts.is_calculating_coverage());
+        if ready {
+            self.waiting_for_system_fonts = false;
+            self.wake_up_registered = false;
+        }
+        ready
+    }
+
+    #[cfg(feature = "system_fonts")]
+    fn add_system_fallback(&mut self, name: String, font_data: &FontData) {
+        self.font_impl_cache.add_font_data(&name, font_data);
+
+        for ((scale_in_pixels, _, weight, style), font) in &mut self.sized_family {
+            font.push_fallback(self.font_impl_cache.font_impl(
+                *scale_in_pixels,
+                &name,
+                *weight,
+                *style,
+            ));
+        }
+        self.system_fallbacks.push(name);
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -606,6 +1013,11 @@ struct CachedGalley {
     /// When it was last used
     last_used: u32,
     galley: Arc<Galley>,
+
+    /// The hashes of the cached paragraphs this galley was put together from, if any.
+    ///
+    /// They are kept alive together with it, so they can be reused when the text changes.
+    paragraphs: Vec<u64>,
 }
 
 #[derive(Default)]
@@ -618,23 +1030,56 @@ struct GalleyCache {
 impl GalleyCache {
     fn layout(&mut self, fonts: &mut FontsImpl, job: LayoutJob) -> Arc<Galley> {
         let hash = crate::util::hash(&job); // TODO(emilk): even faster hasher?
+        self.layout_with_hash(fonts, job, hash)
+    }
 
-        match self.cache.entry(hash) {
-            std::collections::hash_map::Entry::Occupied(entry) => {
-                let cached = entry.into_mut();
-                cached.last_used = self.generation;
-                cached.galley.clone()
-            }
-            std::collections::hash_map::Entry::Vacant(entry) => {
-                let galley = super::layout(fonts, job.into());
-                let galley = Arc::new(galley);
-                entry.insert(CachedGalley {
-                    last_used: self.generation,
-                    galley: galley.clone(),
-                });
-                galley
+    fn layout_with_hash(
+        &mut self,
+        fonts: &mut FontsImpl,
+        job: LayoutJob,
+        hash: u64,
+    ) -> Arc<Galley> {
+        if let Some(cached) = self.cache.get_mut(&hash) {
+            cached.last_used = self.generation;
+            let galley = cached.galley.clone();
+
+            let paragraphs = std::mem::take(&mut cached.paragraphs);
+            if !paragraphs.is_empty() {
+                for paragraph in &paragraphs {
+                    if let Some(cached) = self.cache.get_mut(paragraph) {
+                        cached.last_used = self.generation;
+                    }
+                }
+                if let Some(cached) = self.cache.get_mut(&hash) {
+                    cached.paragraphs = paragraphs;
+                }
             }
+            return galley;
         }
+
+        let lay_out_visible_paragraphs =
+            job.visible_y_range.is_some() && job.break_on_newline && job.wrap.max_rows == 0;
+        let mut paragraphs = vec![];
+        let galley = if lay_out_visible_paragraphs {
+            // Cache each paragraph on its own, so we only lay out the ones that changed:
+            super::text_layout::layout_visible_paragraphs(fonts, job.into(), |fonts, job| {
+                let hash = crate::util::hash(&job);
+                paragraphs.push(hash);
+                self.layout_with_hash(fonts, job, hash)
+            })
+        } else {
+            super::layout(fonts, job.into())
+        };
+        let galley = Arc::new(galley);
+        self.cache.insert(
+            hash,
+            CachedGalley {
+                last_used: self.generation,
+                galley: galley.clone(),
+                paragraphs,
+            },
+        );
+        galley
     }
 
     pub fn num_galleys_in_cache(&self) -> usize {
@@ -658,8 +1103,18 @@ struct FontImplCache {
     pixels_per_point: f32,
     ab_glyph_fonts: BTreeMap<String, (FontTweak, ab_glyph::FontArc)>,
 
-    /// Map font pixel sizes and names to the cached [`FontImpl`].
-    cache: ahash::AHashMap<(u32, String), Arc<FontImpl>>,
+    /// The fonts with variation axes, which are instanced for each weight and style.
+    variable_fonts: BTreeMap<String, VariableFont>,
+
+    /// The colored layers of the fonts that have them.
+    color_layers: BTreeMap<String, Arc<ColorLayers>>,
+
+    /// The font files, for shaping.
+    #[cfg(feature = "shaping")]
+    font_data: BTreeMap<String, Arc<FontData>>,
+
+    /// Map font pixel sizes, names and variable font instances to the cached [`FontImpl`].
+    cache: ahash::AHashMap<(u32, String, FontWeight, FontStyle), Arc<FontImpl>>,
 }
 
 impl FontImplCache {
@@ -668,20 +1123,46 @@ impl FontImplCache {
         pixels_per_point: f32,
         font_data: &BTreeMap<String, FontData>,
     ) -> Self {
-        let ab_glyph_fonts = font_data
-            .iter()
-            .map(|(name, font_data)| {
-                let tweak = font_data.tweak;
-                let ab_glyph = ab_glyph_font_from_font_data(name, font_data);
-                (name.clone(), (tweak, ab_glyph))
-            })
-            .collect();
-
-        Self {
+        let mut slf = Self {
             atlas,
             pixels_per_point,
-            ab_glyph_fonts,
+            ab_glyph_fonts: Default::default(),
+            variable_fonts: Default::default(),
+            color_layers: Default::default(),
+            #[cfg(feature = "shaping")]
+            font_data: Default::default(),
             cache: Default::default(),
+        };
+        for (name, font_data) in font_data {
+            slf.add_font_data(name, font_data);
+        }
+        slf
+    }
+
+    pub fn add_font_data(&mut self, name: &str, font_data: &FontData) {
+        let ab_glyph = ab_glyph_font_from_font_data(name, font_data, &font_data.variations);
+        self.ab_glyph_fonts
+            .insert(name.to_owned(), (font_data.tweak, ab_glyph));
+        if let Some(color_layers) = ColorLayers::from_font(&font_data.font, font_data.index) {
+            self.color_layers
+                .insert(name.to_owned(), Arc::new(color_layers));
+        }
+
+        // Variable fonts and shaping need the font file later. Copy it (if it is owned) only once, and share it:
+        let axes = variation_axes(name, font_data);
+        if !axes.is_empty() || cfg!(feature = "shaping") {
+            let font_data = Arc::new(font_data.clone());
+            if !axes.is_empty() {
+                self.variable_fonts.insert(
+                    name.to_owned(),
+                    VariableFont {
+                        data: font_data.clone(),
+                        axes,
+                    },
+                );
+            }
+            #[cfg(feature = "shaping")]
+            self.font_data.insert(name.to_owned(), font_data);
         }
     }
 
@@ -694,7 +1175,21 @@ impl FontImplCache {
         scale_in_pixels.round() as u32
     }
 
-    pub fn font_impl(&mut self, scale_in_pixels: u32, font_name: &str) -> Arc<FontImpl> {
+    /// Does this font have an `ital` axis that is set from the [`FontStyle`]?
+    pub fn has_italic_axis(&self, font_name: &str) -> bool {
+        self.variable_fonts.get(font_name).map_or(false, |font| {
+            font.instance_key(FontWeight::NORMAL, FontStyle::Italic).1 == FontStyle::Italic
+        })
+    }
+
+    /// The font at this size, instanced at this weight and style if it is a variable font.
+    pub fn font_impl(
+        &mut self,
+        scale_in_pixels: u32,
+        font_name: &str,
+        weight: FontWeight,
+        style: FontStyle,
+    ) -> Arc<FontImpl> {
         let (tweak, ab_glyph_font) = self
             .ab_glyph_fonts
             .get(font_name)
@@ -708,18 +1203,92 @@ impl FontImplCache {
             scale_in_points * tweak.y_offset_factor
         } + tweak.y_offset;
 
+        // Static fonts look the same for all weights and styles, so share them:
+        let variable_font = self.variable_fonts.get(font_name);
+        let (weight, style) = variable_font
+            .map_or((FontWeight::NORMAL, FontStyle::Normal), |font| {
+                font.instance_key(weight, style)
+            });
+
         self.cache
-            .entry((scale_in_pixels, font_name.to_owned()))
+            .entry((scale_in_pixels, font_name.to_owned(), weight, style))
             .or_insert_with(|| {
-                Arc::new(FontImpl::new(
+                let variations = variable_font.map(|font| font.variations(weight, style));
+                let ab_glyph_font = match (variable_font, &variations) {
+                    (Some(font), Some(variations)) => {
+                        ab_glyph_font_from_font_data(font_name, &font.data, variations)
+                    }
+                    _ => ab_glyph_font,
+                };
+
+                let font_impl = FontImpl::new(
                     self.atlas.clone(),
                     self.pixels_per_point,
                     font_name.to_owned(),
                     ab_glyph_font,
                     scale_in_pixels,
                     y_offset_points,
-                ))
+                )
+                .with_color_layers(self.color_layers.get(font_name).cloned());
+                #[cfg(feature = "shaping")]
+                let font_impl = {
+                    let font_data = self.font_data[font_name].clone();
+                    let variations = variations.unwrap_or_else(|| font_data.variations.clone());
+                    font_impl.with_font_data(font_data, &variations)
+                };
+                Arc::new(font_impl)
             })
             .clone()
     }
 }
+
+/// A font with variation axes, e.g. for weight (`wght`), width (`wdth`) and italics (`ital`).
+struct VariableFont {
+    /// Shared with the [`FontImpl`]s, for shaping.
+    data: Arc<FontData>,
+
+    /// Never empty.
+    axes: Vec<ab_glyph::VariationAxis>,
+}
+
+impl VariableFont {
+    /// The axis with this tag, unless it has a fixed value in [`FontData::variations`].
+    fn free_axis(&self, tag: &[u8; 4]) -> Option<&ab_glyph::VariationAxis> {
+        if self.data.variations.iter().any(|(t, _)| t == tag) {
+            None
+        } else {
+            self.axes.iter().find(|axis| &axis.tag == tag)
+        }
+    }
+
+    /// The weight and style that make a difference for this font,
+    /// so that we don't create identical instances.
+    fn instance_key(&self, weight: FontWeight, style: FontStyle) -> (FontWeight, FontStyle) {
+        let weight = match self.free_axis(b"wght") {
+            Some(_) => weight,
+            None => FontWeight::NORMAL,
+        };
+        let style = match self.free_axis(b"ital") {
+            Some(_) => style,
+            None => FontStyle::Normal,
+        };
+        (weight, style)
+    }
+
+    /// The axis values to use for this weight and style.
+    fn variations(&self, weight: FontWeight, style: FontStyle) -> Vec<([u8; 4], f32)> {
+        let mut variations = self.data.variations.clone();
+        if let Some(axis) = self.free_axis(b"wght") {
+            let value = (weight.0 as f32).clamp(axis.min_value, axis.max_value);
+            variations.push((*b"wght", value));
+        }
+        if let Some(axis) = self.free_axis(b"ital") {
+            let value = match style {
+                FontStyle::Normal => 0.0_f32,
+                FontStyle::Italic => 1.0,
+            };
+            variations.push((*b"ital", value.clamp(axis.min_value, axis.max_value)));
+        }
+        variations
+    }
+}
@@ -1,8 +1,11 @@
 //! Everything related to text, fonts, text layout, cursors etc.
 
+mod color_glyphs;
 pub mod cursor;
 mod font;
 mod fonts;
+#[cfg(feature = "system_fonts")]
+mod system_fonts;
 mod text_layout;
 mod text_layout_types;
 
@@ -10,10 +13,16 @@ mod text_layout_types;
 pub const TAB_SIZE: usize = 4;
 
 pub use {
-    fonts::{FontData, FontDefinitions, FontFamily, FontId, FontTweak, Fonts, FontsImpl},
+    fonts::{
+        FontData, FontDefinitions, FontFace, FontFamily, FontId, FontStyle, FontTweak, FontWeight,
+        Fonts, FontsImpl,
+    },
     text_layout::layout,
     text_layout_types::*,
 };
 
+#[cfg(feature = "system_fonts")]
+pub use system_fonts::SystemFonts;
+
 /// Suggested character to use to replace those in password text fields.
 pub const PASSWORD_REPLACEMENT_CHAR: char = '•';
@@ -0,0 +1,331 @@
+use std::{path::Path, sync::Arc};
+
+use crate::mutex::Mutex;
+
+use super::FontData;
+
+/// Fonts installed on the system, found by scanning the standard font directories.
+///
+/// Use [`Self::font_data`] to look up a font by family name,
+/// or put it in [`super::FontDefinitions::system_fonts`] to automatically
+/// load fonts for characters that none of your own fonts have.
+///
+/// ```no_run
+/// # use epaint::text::{FontDefinitions, FontFamily, SystemFonts};
+/// let system_fonts = SystemFonts::load();
+///
+/// let mut fonts = FontDefinitions::default();
+/// if let Some(font_data) = system_fonts.font_data("DejaVu Sans") {
+///     fonts.font_data.insert("DejaVu Sans".to_owned(), font_data);
+///     fonts.families.get_mut(&FontFamily::Proportional).unwrap()
+///         .push("DejaVu Sans".to_owned());
+/// }
+/// fonts.system_fonts = Some(std::sync::Arc::new(system_fonts));
+/// ```
+pub struct SystemFonts {
+    db: fontdb::Database,
+
+    /// Which characters each face has.
+    ///
+    /// Calculated on a background thread, since it requires reading all the font files.
+    /// Replaced when new fonts are added, so an old calculation can't overwrite it.
+    coverage: Arc<Mutex<Coverage>>,
+}
+
+enum Coverage {
+    NotStarted,
+
+    /// With the callbacks to call when it is ready, see [`SystemFonts::on_coverage_ready`].
+    Calculating(Vec<Box<dyn FnOnce() + Send>>),
+
+    Ready(Vec<FaceCoverage>),
+}
+
+/// The result of [`SystemFonts::font_for_char`].
+pub(crate) enum FontForChar {
+    /// The name of a font that has the character, and its id for [`SystemFonts::load_face`].
+    Found(String, fontdb::ID),
+
+    /// None of the system fonts have the character.
+    Missing,
+
+    /// We are still reading the system fonts on a background thread. Try again later.
+    NotReady,
+}
+
+/// The characters a font face has, as sorted, non-overlapping, inclusive ranges.
+struct FaceCoverage {
+    id: fontdb::ID,
+    ranges: Vec<(char, char)>,
+}
+
+impl Default for SystemFonts {
+    /// No fonts. Use [`Self::load_fonts_dir`] to add some.
+    fn default() -> Self {
+        Self {
+            db: fontdb::Database::new(),
+            coverage: Arc::new(Mutex::new(Coverage::NotStarted)),
+        }
+    }
+}
+
+impl std::fmt::Debug for SystemFonts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SystemFonts")
+            .field("num_faces", &self.db.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Compares by identity, since comparing all the fonts would be slow.
+impl PartialEq for SystemFonts {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl SystemFonts {
+    /// Find the fonts in the system font directories.
+    ///
+    /// On Linux this is `/usr/share/fonts`, `/usr/local/share/fonts`,
+    /// `~/.fonts` and `~/.local/share/fonts`.
+    /// This only reads the font names, and is fairly fast.
+    /// The rest of the font files are read on a background thread.
+    pub fn load() -> Self {
+        let mut slf = Self::default();
+        slf.db.load_system_fonts();
+        slf.start_coverage_calculation();
+        slf
+    }
+
+    /// Add all fonts in this directory, recursively.
+    pub fn load_fonts_dir(&mut self, dir: impl AsRef<Path>) {
+        self.db.load_fonts_dir(dir);
+        self.coverage = Arc::new(Mutex::new(Coverage::NotStarted));
+    }
+
+    /// The number of font faces found.
+    pub fn len(&self) -> usize {
+        self.db.len()
+    }
+
+    /// No font faces were found.
+    pub fn is_empty(&self) -> bool {
+        self.db.is_empty()
+    }
+
+    /// Names of all the font families found, sorted and without duplicates.
+    pub fn family_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .db
+            .faces()
+            .iter()
+            .map(|face| face.family.clone())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Load the regular face of the font family with this name (e.g. `"Noto Sans"`).
+    ///
+    /// Returns `None` if there is no such font, or it could not be read.
+    pub fn font_data(&self, family_name: &str) -> Option<FontData> {
+        let id = self.db.query(&fontdb::Query {
+            families: &[fontdb::Family::Name(family_name)],
+            ..Default::default()
+        })?;
+        self.load_face(id)
+    }
+
+    /// Are we still reading the font files to find out which characters they have?
+    ///
+    /// Until we are done, [`Self::font_for_char`] returns [`FontForChar::NotReady`].
+    pub fn is_calculating_coverage(&self) -> bool {
+        !matches!(*self.coverage.lock(), Coverage::Ready(_))
+    }
+
+    /// Call `callback` once [`Self::is_calculating_coverage`] is done,
+    /// from the background thread that does it.
+    ///
+    /// If we are not calculating it, `callback` is called right away.
+    pub fn on_coverage_ready(&self, callback: impl FnOnce() + Send + 'static) {
+        {
+            let mut coverage = self.coverage.lock();
+            if let Coverage::Calculating(callbacks) = &mut *coverage {
+                callbacks.push(Box::new(callback));
+                return;
+            }
+        }
+        callback();
+    }
+
+    /// Find a font that has this character, preferring regular faces.
+    ///
+    /// The first call starts reading all the system fonts on a background thread
+    /// (unless [`Self::load`] already did), and we return [`FontForChar::NotReady`] until that is done.
+    pub(crate) fn font_for_char(&self, c: char) -> FontForChar {
+        let id = {
+            let coverage = self.coverage.lock();
+            let faces = match &*coverage {
+                Coverage::Ready(faces) => faces,
+                Coverage::NotStarted => {
+                    drop(coverage);
+                    self.start_coverage_calculation();
+                    return FontForChar::NotReady;
+                }
+                Coverage::Calculating(_) => return FontForChar::NotReady,
+            };
+            match faces.iter().find(|face| face.has_char(c)) {
+                Some(face) => face.id,
+                None => return FontForChar::Missing,
+            }
+        };
+
+        match self.db.face(id) {
+            Some(face) => FontForChar::Found(face.post_script_name.clone(), id),
+            None => FontForChar::Missing,
+        }
+    }
+
+    fn start_coverage_calculation(&self) {
+        {
+            let mut coverage = self.coverage.lock();
+            if !matches!(*coverage, Coverage::NotStarted) {
+                return;
+            }
+            *coverage = Coverage::Calculating(vec![]);
+        }
+
+        let db = self.db.clone();
+        let coverage = self.coverage.clone();
+        let calculate = move || {
+            let faces = calc_coverage(&db);
+            let previous = std::mem::replace(&mut *coverage.lock(), Coverage::Ready(faces));
+            if let Coverage::Calculating(callbacks) = previous {
+                for callback in callbacks {
+                    callback();
+                }
+            }
+        };
+
+        let spawned = std::thread::Builder::new()
+            .name("epaint_system_fonts".to_owned())
+            .spawn(calculate);
+        if spawned.is_err() {
+            // No threads (e.g. on the web), so do it now. Nobody has been waiting for it yet.
+            *self.coverage.lock() = Coverage::Ready(calc_coverage(&self.db));
+        }
+    }
+
+    /// Read the font file of a face.
+    pub(crate) fn load_face(&self, id: fontdb::ID) -> Option<FontData> {
+        let (font, index) = self
+            .db
+            .with_face_data(id, |data, index| (data.to_vec(), index))?;
+        Some(FontData {
+            index,
+            ..FontData::from_owned(font)
+        })
+    }
+}
+
+fn calc_coverage(db: &fontdb::Database) -> Vec<FaceCoverage> {
+    let mut faces: Vec<&fontdb::FaceInfo> = db.faces().iter().collect();
+    // Regular faces first, so we don't fall back to italic or bold unless we have to:
+    faces.sort_by_key(|face| {
+        (
+            face.style != fontdb::Style::Normal,
+            face.weight != fontdb::Weight::NORMAL,
+        )
+    });
+
+    faces
+        .iter()
+        .filter_map(|face| {
+            let ranges = db.with_face_data(face.id, |data, index| {
+                use ab_glyph::Font as _;
+                let font = ab_glyph::FontRef::try_from_slice_and_index(data, index).ok()?;
+                let mut chars: Vec<char> = font.codepoint_ids().map(|(_, chr)| chr).collect();
+                chars.sort_unstable();
+                Some(char_ranges(&chars))
+            })??;
+            Some(FaceCoverage {
+                id: face.id,
+                ranges,
+            })
+        })
+        .collect()
+}
+
+impl FaceCoverage {
+    fn has_char(&self, c: char) -> bool {
+        // Find the last range starting at or before `c`:
+        let i = self.ranges.partition_point(|&(start, _)| start <= c);
+        i > 0 && c <= self.ranges[i - 1].1
+    }
+}
+
+/// Compress sorted characters into inclusive ranges.
+fn char_ranges(chars: &[char]) -> Vec<(char, char)> {
+    let mut ranges: Vec<(char, char)> = vec![];
+    for &c in chars {
+        match ranges.last_mut() {
+            Some((_, end)) if *end == c || char::from_u32(*end as u32 + 1) == Some(c) => {
+                *end = c;
+            }
+            _ => ranges.push((c, c)),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_ranges() {
+        let ranges = char_ranges(&['a', 'b', 'b', 'c', 'x', 'z']);
+        assert_eq!(ranges, vec![('a', 'c'), ('x', 'x'), ('z', 'z')]);
+
+        let face = FaceCoverage {
+            id: fontdb::ID::dummy(),
+            ranges,
+        };
+        assert!(face.has_char('a'));
+        assert!(face.has_char('b'));
+        assert!(face.has_char('x'));
+        assert!(!face.has_char('d'));
+        assert!(!face.has_char('y'));
+        assert!(!face.has_char(' '));
+    }
+
+    #[test]
+    fn test_load_fonts_dir() {
+        let mut system_fonts = SystemFonts::default();
+        system_fonts.load_fonts_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/fonts"));
+        assert!(system_fonts.family_names().contains(&"Hack".to_owned()));
+        assert!(system_fonts.font_data("Hack").is_some());
+
+        assert!(matches!(
+            system_fonts.font_for_char('😀'),
+            FontForChar::NotReady
+        ));
+        let (sender, receiver) = std::sync::mpsc::channel();
+        system_fonts.on_coverage_ready(move || sender.send(()).unwrap());
+        receiver.recv().unwrap();
+        assert!(!system_fonts.is_calculating_coverage());
+
+        match system_fonts.font_for_char('😀') {
+            FontForChar::Found(_name, id) => {
+                assert!(!system_fonts.load_face(id).unwrap().font.is_empty());
+            }
+            _ => panic!("Expected a font for the emoji"),
+        }
+        assert!(matches!(
+            system_fonts.font_for_char('\u{10FFFD}'),
+            FontForChar::Missing
+        ));
+    }
+}
@@ -1,10 +1,18 @@
-use std::ops::RangeInclusive;
+use std::ops::{Range, RangeInclusive};
 use std::sync::Arc;
 
-use super::{FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, Row, RowVisuals};
+#[cfg(feature = "shaping")]
+use super::font::ShapedCluster;
+use super::{
+    font::Font, ElidedParagraphs, FontId, FontStyle, FontsImpl, Galley, Glyph, HyphenationLanguage,
+    LayoutJob, LayoutSection, Row, RowVisuals, TextFormat,
+};
 use crate::{Color32, Mesh, Stroke, Vertex};
 use emath::*;
 
+/// U+00AD: an invisible place to break a word, which shows a hyphen if the row ends there.
+const SOFT_HYPHEN: char = '\u{AD}';
+
 // ----------------------------------------------------------------------------
 
 /// Represents GUI scale and convenience methods for rounding to pixels.
@@ -52,15 +60,30 @@ struct Paragraph {
 /// In most cases you should use [`crate::Fonts::layout_job`] instead
 /// since that memoizes the input, making subsequent layouting of the same text much faster.
 pub fn layout(fonts: &mut FontsImpl, job: Arc<LayoutJob>) -> Galley {
+    let bidi_levels = bidi_levels(&job.text);
+
     let mut paragraphs = vec![Paragraph::default()];
     for (section_index, section) in job.sections.iter().enumerate() {
-        layout_section(fonts, &job, section_index as u32, section, &mut paragraphs);
+        layout_section(
+            fonts,
+            &job,
+            &bidi_levels,
+            section_index as u32,
+            section,
+            &mut paragraphs,
+        );
     }
 
     let point_scale = PointScale::new(fonts.pixels_per_point());
 
     let mut rows = rows_from_paragraphs(fonts, paragraphs, &job);
 
+    if !bidi_levels.is_empty() {
+        for row in &mut rows {
+            reorder_row_visually(row);
+        }
+    }
+
     let justify = job.justify && job.wrap.max_width.is_finite();
 
     if justify || job.halign != Align::LEFT {
@@ -78,12 +101,212 @@ pub fn layout(fonts: &mut FontsImpl, job: Arc<LayoutJob>) -> Galley {
         }
     }
 
-    galley_from_rows(point_scale, job, rows)
+    let format_summary = format_summary(fonts, &job);
+    galley_from_rows(point_scale, job, &format_summary, rows)
+}
+
+/// Lay out only the paragraphs within [`LayoutJob::visible_y_range`],
+/// each with `layout_paragraph`, so that they can be cached on their own.
+///
+/// The other paragraphs get an estimated height, and are replaced with placeholder rows
+/// (see [`Row::elided`]). The last paragraph is always laid out,
+/// so that the last row never ends with a newline.
+pub(crate) fn layout_visible_paragraphs(
+    fonts: &mut FontsImpl,
+    job: Arc<LayoutJob>,
+    mut layout_paragraph: impl FnMut(&mut FontsImpl, LayoutJob) -> Arc<Galley>,
+) -> Galley {
+    let point_scale = PointScale::new(fonts.pixels_per_point());
+    let visible_y_range = job
+        .visible_y_range
+        .clone()
+        .unwrap_or(f32::NEG_INFINITY..=f32::INFINITY);
+
+    // For estimating the height of the paragraphs we don't lay out:
+    let (row_height, char_width) = match job.sections.first() {
+        Some(section) => {
+            let font = fonts.font(&section_font_id(&section.format));
+            (font.row_height(), font.glyph_width('n'))
+        }
+        None => (0.0, 0.0),
+    };
+    let estimated_height = |num_chars: usize| {
+        let num_rows = if job.wrap.max_width.is_finite() && job.wrap.max_width > 0.0 {
+            (num_chars as f32 * char_width / job.wrap.max_width).ceil()
+        } else {
+            1.0
+        };
+        point_scale.round_to_pixel(num_rows.at_least(1.0) * row_height)
+    };
+
+    let mut rows = vec![];
+    let mut elided: Option<(ElidedParagraphs, f32)> = None;
+    let mut cursor_y = 0.0;
+    let mut paragraph_start = 0;
+
+    for (paragraph_index, paragraph) in job.text.split('\n').enumerate() {
+        let byte_range = paragraph_start..paragraph_start + paragraph.len();
+        paragraph_start = byte_range.end + 1;
+        let is_last_paragraph = byte_range.end == job.text.len();
+        let num_chars = paragraph.chars().count();
+        let height = estimated_height(num_chars);
+
+        let is_visible =
+            cursor_y <= *visible_y_range.end() && *visible_y_range.start() <= cursor_y + height;
+        if !is_visible && !is_last_paragraph {
+            if let Some((elided, _)) = &mut elided {
+                elided.num_paragraphs += 1;
+                elided.num_chars += 1 + num_chars; // the newline before, and the paragraph
+                elided.byte_range.end = byte_range.end;
+            } else {
+                let paragraphs = ElidedParagraphs {
+                    num_paragraphs: 1,
+                    num_chars,
+                    byte_range,
+                };
+                elided = Some((paragraphs, cursor_y));
+            }
+            cursor_y += height;
+            continue;
+        }
+
+        if let Some((elided, min_y)) = elided.take() {
+            rows.push(Row {
+                glyphs: vec![],
+                rect: Rect::from_min_max(pos2(0.0, min_y), pos2(0.0, cursor_y)),
+                visuals: Default::default(),
+                ends_with_newline: true,
+                hyphen: None,
+                elided: Some(elided),
+            });
+        }
+
+        let galley = layout_paragraph(fonts, paragraph_job(&job, byte_range, paragraph_index == 0));
+        let offset = vec2(0.0, cursor_y);
+        let num_rows = galley.rows.len();
+        rows.extend(galley.rows.iter().enumerate().map(|(i, row)| {
+            let mut row = row.clone();
+            row.rect = row.rect.translate(offset);
+            for glyph in row.glyphs.iter_mut().chain(&mut row.hyphen) {
+                glyph.pos += offset;
+            }
+            row.visuals.mesh.translate(offset);
+            if row.visuals.mesh_bounds.is_finite() {
+                row.visuals.mesh_bounds = row.visuals.mesh_bounds.translate(offset);
+            }
+            if i + 1 == num_rows {
+                row.ends_with_newline = !is_last_paragraph;
+            }
+            row
+        }));
+        cursor_y = point_scale.round_to_pixel(cursor_y + galley.size().y);
+    }
+
+    let mut rect = Rect::from_min_max(Pos2::ZERO, pos2(0.0, cursor_y));
+    let mut mesh_bounds = Rect::NOTHING;
+    let mut num_vertices = 0;
+    let mut num_indices = 0;
+    for row in &rows {
+        if row.elided.is_none() {
+            rect.min.x = rect.min.x.min(row.rect.min.x);
+            rect.max.x = rect.max.x.max(row.rect.max.x);
+        }
+        mesh_bounds = mesh_bounds.union(row.visuals.mesh_bounds);
+        num_vertices += row.visuals.mesh.vertices.len();
+        num_indices += row.visuals.mesh.indices.len();
+    }
+
+    Galley {
+        job,
+        rows,
+        rect,
+        mesh_bounds,
+        num_vertices,
+        num_indices,
+    }
+}
+
+/// The part of the job for one paragraph.
+fn paragraph_job(job: &LayoutJob, byte_range: Range<usize>, is_first: bool) -> LayoutJob {
+    let text = job.text[byte_range.clone()].to_owned();
+    let sections = job
+        .sections
+        .iter()
+        .filter(|section| {
+            section.byte_range.start <= byte_range.end && byte_range.start <= section.byte_range.end
+        })
+        .map(|section| {
+            let start = section.byte_range.start.max(byte_range.start);
+            let end = section.byte_range.end.min(byte_range.end);
+            LayoutSection {
+                // Only the paragraph where the section starts gets the leading space:
+                leading_space: if section.byte_range.start >= byte_range.start {
+                    section.leading_space
+                } else {
+                    0.0
+                },
+                byte_range: start - byte_range.start..end.max(start) - byte_range.start,
+                format: section.format.clone(),
+            }
+        })
+        .collect();
+
+    LayoutJob {
+        text,
+        sections,
+        wrap: job.wrap.clone(),
+        first_row_min_height: if is_first {
+            job.first_row_min_height
+        } else {
+            0.0
+        },
+        break_on_newline: true,
+        halign: job.halign,
+        justify: job.justify,
+        visible_y_range: None,
+    }
+}
+
+/// The font to use for a section, which is italic if [`TextFormat::italics`] is set.
+fn section_font_id(format: &TextFormat) -> std::borrow::Cow<'_, FontId> {
+    if format.italics {
+        std::borrow::Cow::Owned(format.font_id.clone().style(FontStyle::Italic))
+    } else {
+        std::borrow::Cow::Borrowed(&format.font_id)
+    }
+}
+
+/// The bidirectional embedding level of each byte of the text,
+/// using the Unicode Bidirectional Algorithm.
+///
+/// Empty if the text is all left-to-right.
+fn bidi_levels(text: &str) -> Vec<u8> {
+    use unicode_bidi::BidiClass;
+
+    let has_rtl = text.chars().any(|chr| {
+        chr >= '\u{0590}' // quick check for the common case
+            && matches!(
+                unicode_bidi::bidi_class(chr),
+                BidiClass::R | BidiClass::AL | BidiClass::RLE | BidiClass::RLO | BidiClass::RLI
+            )
+    });
+
+    if has_rtl {
+        let bidi_info = unicode_bidi::BidiInfo::new(text, None);
+        bidi_info
+            .levels
+            .iter()
+            .map(|level| level.number())
+            .collect()
+    } else {
+        vec![]
+    }
 }
 
 fn layout_section(
     fonts: &mut FontsImpl,
     job: &LayoutJob,
+    bidi_levels: &[u8],
     section_index: u32,
     section: &LayoutSection,
     out_paragraphs: &mut Vec<Paragraph>,
@@ -93,7 +316,13 @@ fn layout_section(
         byte_range,
         format,
     } = section;
-    let font = fonts.font(&format.font_id);
+
+    let font_id = section_font_id(format);
+
+    #[cfg(feature = "system_fonts")]
+    fonts.load_system_fallbacks(&font_id, &job.text[byte_range.clone()]);
+
+    let font = fonts.font(&font_id);
     let font_height = font.row_height();
 
     let mut paragraph = out_paragraphs.last_mut().unwrap();
@@ -103,36 +332,236 @@ fn layout_section(
 
     paragraph.cursor_x += leading_space;
 
-    let mut last_glyph_id = None;
-
-    for chr in job.text[byte_range.clone()].chars() {
-        if job.break_on_newline && chr == '\n' {
+    let text = &job.text[byte_range.clone()];
+    let mut line_start = byte_range.start;
+    for (i, line) in text
+        .split(|chr| job.break_on_newline && chr == '\n')
+        .enumerate()
+    {
+        let line_range = line_start..line_start + line.len();
+        line_start = line_range.end + 1; // skip the newline
+        let levels = bidi_levels.get(line_range).unwrap_or_default();
+
+        if i > 0 {
             out_paragraphs.push(Paragraph::default());
             paragraph = out_paragraphs.last_mut().unwrap();
             paragraph.empty_paragraph_height = font_height; // TODO(emilk): replace this hack with actually including `\n` in the glyphs?
-        } else {
-            let (font_impl, glyph_info) = font.glyph_info_and_font_impl(chr);
-            if let Some(font_impl) = font_impl {
-                if let Some(last_glyph_id) = last_glyph_id {
-                    paragraph.cursor_x += font_impl.pair_kerning(last_glyph_id, glyph_info.id);
-                }
-            }
+        }
+
+        #[cfg(feature = "shaping")]
+        layout_shaped(font, line, levels, section_index, paragraph);
+
+        #[cfg(not(feature = "shaping"))]
+        layout_chars(font, line, levels, section_index, paragraph);
+    }
+}
 
+/// One glyph per character, each with its own advance width.
+///
+/// `levels` are the bidi levels of each byte of `text`, or empty.
+fn layout_chars(
+    font: &mut Font,
+    text: &str,
+    levels: &[u8],
+    section_index: u32,
+    paragraph: &mut Paragraph,
+) {
+    let font_height = font.row_height();
+    let mut last_glyph_id = None;
+
+    for (byte_index, chr) in text.char_indices() {
+        if chr == SOFT_HYPHEN {
+            // Invisible, unless the row is broken there (see `Row::hyphen`):
             paragraph.glyphs.push(Glyph {
                 chr,
                 pos: pos2(paragraph.cursor_x, f32::NAN),
-                size: vec2(glyph_info.advance_width, font_height),
-                uv_rect: glyph_info.uv_rect,
+                size: vec2(0.0, font_height),
+                uv_rect: Default::default(),
                 section_index,
+                bidi_level: levels.get(byte_index).copied().unwrap_or_default(),
             });
+            continue;
+        }
+
+        let (font_impl, glyph_info) = font.glyph_info_and_font_impl(chr);
+        if let Some(font_impl) = font_impl {
+            if let Some(last_glyph_id) = last_glyph_id {
+                paragraph.cursor_x += font_impl.pair_kerning(last_glyph_id, glyph_info.id);
+            }
+        }
+
+        paragraph.glyphs.push(Glyph {
+            chr,
+            pos: pos2(paragraph.cursor_x, f32::NAN),
+            size: vec2(glyph_info.advance_width, font_height),
+            uv_rect: glyph_info.uv_rect,
+            section_index,
+            bidi_level: levels.get(byte_index).copied().unwrap_or_default(),
+        });
+
+        paragraph.cursor_x += glyph_info.advance_width;
+        paragraph.cursor_x = font.round_to_pixel(paragraph.cursor_x);
+        last_glyph_id = Some(glyph_info.id);
+    }
+}
+
+/// Shape each run of text that uses the same font (primary or fallback) with `rustybuzz`.
+///
+/// Each character still gets one [`Glyph`], so that cursors work as before:
+/// one character of a cluster (e.g. a ligature) carries the image of the whole cluster,
+/// and the advance width of the cluster is split evenly between its grapheme clusters.
+/// Characters that continue a grapheme cluster (e.g. combining accents) get zero width.
+///
+/// Clusters are placed in logical order, and reordered visually after line breaking.
+#[cfg(feature = "shaping")]
+fn layout_shaped(
+    font: &mut Font,
+    text: &str,
+    levels: &[u8],
+    section_index: u32,
+    paragraph: &mut Paragraph,
+) {
+    use unicode_segmentation::UnicodeSegmentation as _;
+
+    // Split into runs by font and direction.
+    // A font index of `None` means we don't shape it (tabs and missing characters):
+    let mut runs: Vec<(Option<usize>, bool, usize)> = vec![];
+    for (byte_index, grapheme) in text.grapheme_indices(true) {
+        let chr = grapheme.chars().next().unwrap_or_default();
+        let font_index = if chr == '\t' {
+            None
+        } else {
+            font.font_index_for_char(chr)
+        };
+        let is_rtl = levels.get(byte_index).map_or(false, |level| level % 2 == 1);
+        if runs.last().map(|run| (run.0, run.1)) != Some((font_index, is_rtl)) {
+            runs.push((font_index, is_rtl, byte_index));
+        }
+    }
+
+    for (run_index, &(font_index, is_rtl, run_start)) in runs.iter().enumerate() {
+        let run_end = runs.get(run_index + 1).map_or(text.len(), |run| run.2);
+        let run = &text[run_start..run_end];
+        let run_levels = levels.get(run_start..run_end).unwrap_or_default();
+
+        let clusters =
+            font_index.and_then(|font_index| font.font_impl(font_index).shape(run, is_rtl));
+        if let Some(clusters) = clusters {
+            layout_clusters(font, run, run_levels, &clusters, section_index, paragraph);
+        } else {
+            layout_chars(font, run, run_levels, section_index, paragraph);
+        }
+    }
+}
+
+/// See [`layout_shaped`].
+#[cfg(feature = "shaping")]
+fn layout_clusters(
+    font: &Font,
+    text: &str,
+    levels: &[u8],
+    clusters: &[ShapedCluster],
+    section_index: u32,
+    paragraph: &mut Paragraph,
+) {
+    use unicode_segmentation::UnicodeSegmentation as _;
+
+    let font_height = font.row_height();
 
-            paragraph.cursor_x += glyph_info.advance_width;
-            paragraph.cursor_x = font.round_to_pixel(paragraph.cursor_x);
-            last_glyph_id = Some(glyph_info.id);
+    for (cluster_index, cluster) in clusters.iter().enumerate() {
+        let cluster_end = clusters
+            .get(cluster_index + 1)
+            .map_or(text.len(), |next| next.byte_start);
+        let cluster_text = &text[cluster.byte_start..cluster_end];
+
+        let num_graphemes = cluster_text.graphemes(true).count().max(1);
+        let grapheme_width = cluster.advance_width / num_graphemes as f32;
+
+        // Right-to-left text is reversed when reordered visually,
+        // so the last grapheme cluster ends up on the left, where the image starts:
+        let is_rtl = levels
+            .get(cluster.byte_start)
+            .map_or(false, |level| level % 2 == 1);
+        let image_grapheme_index = if is_rtl { num_graphemes - 1 } else { 0 };
+
+        for (grapheme_index, (grapheme_start, grapheme)) in
+            cluster_text.grapheme_indices(true).enumerate()
+        {
+            let grapheme_x = paragraph.cursor_x + grapheme_index as f32 * grapheme_width;
+            for (char_start, chr) in grapheme.char_indices() {
+                let is_first_char = char_start == 0;
+                let byte_index = cluster.byte_start + grapheme_start + char_start;
+                paragraph.glyphs.push(Glyph {
+                    chr,
+                    pos: pos2(
+                        if is_first_char {
+                            grapheme_x
+                        } else {
+                            grapheme_x + grapheme_width
+                        },
+                        f32::NAN,
+                    ),
+                    size: vec2(
+                        if is_first_char { grapheme_width } else { 0.0 },
+                        font_height,
+                    ),
+                    uv_rect: if is_first_char && grapheme_index == image_grapheme_index {
+                        cluster.uv_rect
+                    } else {
+                        Default::default()
+                    },
+                    section_index,
+                    bidi_level: levels.get(byte_index).copied().unwrap_or_default(),
+                });
+            }
         }
+
+        paragraph.cursor_x += cluster.advance_width;
+        paragraph.cursor_x = font.round_to_pixel(paragraph.cursor_x);
     }
 }
 
+/// Move the glyphs of a row to where they should be shown in bidirectional text,
+/// e.g. so that Hebrew words inside English text are shown right-to-left.
+///
+/// The glyphs stay in logical order, so that cursors keep working.
+fn reorder_row_visually(row: &mut Row) {
+    if !row.glyphs.iter().any(|glyph| glyph.is_rtl()) {
+        return;
+    }
+
+    let levels: Vec<unicode_bidi::Level> = row
+        .glyphs
+        .iter()
+        .map(|glyph| {
+            unicode_bidi::Level::new(glyph.bidi_level)
+                .unwrap_or_else(|_| unicode_bidi::Level::ltr())
+        })
+        .collect();
+
+    // Keep the advance of each glyph (including kerning and rounding),
+    // i.e. the distance to where the next glyph started before reordering:
+    let advances: Vec<f32> = row
+        .glyphs
+        .iter()
+        .enumerate()
+        .map(|(i, glyph)| {
+            let next_x = row
+                .glyphs
+                .get(i + 1)
+                .map_or(row.rect.max.x, |next| next.pos.x);
+            next_x - glyph.pos.x
+        })
+        .collect();
+
+    let mut x = row.rect.min.x;
+    for logical_index in unicode_bidi::BidiInfo::reorder_visual(&levels) {
+        row.glyphs[logical_index].pos.x = x;
+        x += advances[logical_index];
+    }
+    row.rect.max.x = x;
+}
+
 /// We ignore y at this stage
 fn rect_from_x_range(x_range: RangeInclusive<f32>) -> Rect {
     Rect::from_x_y_ranges(x_range, 0.0..=0.0)
@@ -159,6 +588,8 @@ fn rows_from_paragraphs(
                     vec2(0.0, paragraph.empty_paragraph_height),
                 ),
                 ends_with_newline: !is_last_paragraph,
+                hyphen: None,
+                elided: None,
             });
         } else {
             let paragraph_max_x = paragraph.glyphs.last().unwrap().max_x();
@@ -170,6 +601,8 @@ fn rows_from_paragraphs(
                     visuals: Default::default(),
                     rect: rect_from_x_range(paragraph_min_x..=paragraph_max_x),
                     ends_with_newline: !is_last_paragraph,
+                    hyphen: None,
+                    elided: None,
                 });
             } else {
                 line_break(fonts, &paragraph, job, &mut rows);
@@ -187,6 +620,8 @@ fn line_break(
     job: &LayoutJob,
     out_rows: &mut Vec<Row>,
 ) {
+    let break_opportunities = break_opportunities(&paragraph.glyphs, job.wrap.hyphenation);
+
     // Keeps track of good places to insert row break if we exceed `wrap_width`.
     let mut row_break_candidates = RowBreakCandidates::default();
 
@@ -195,7 +630,7 @@ fn line_break(
     let mut row_start_idx = 0;
     let mut non_empty_rows = 0;
 
-    for i in 0..paragraph.glyphs.len() {
+    for (i, &opportunity) in break_opportunities.iter().enumerate() {
         let potential_row_width = paragraph.glyphs[i].max_x() - row_start_x;
 
         if job.wrap.max_rows > 0 && non_empty_rows >= job.wrap.max_rows {
@@ -213,10 +648,13 @@ fn line_break(
                     visuals: Default::default(),
                     rect: rect_from_x_range(first_row_indentation..=first_row_indentation),
                     ends_with_newline: false,
+                    hyphen: None,
+                    elided: None,
                 });
                 row_start_x += first_row_indentation;
                 first_row_indentation = 0.0;
-            } else if let Some(last_kept_index) = row_break_candidates.get(job.wrap.break_anywhere)
+            } else if let Some((last_kept_index, hyphenate)) =
+                row_break_candidates.get(job.wrap.break_anywhere)
             {
                 let glyphs: Vec<Glyph> = paragraph.glyphs[row_start_idx..=last_kept_index]
                     .iter()
@@ -227,14 +665,19 @@ fn line_break(
                     })
                     .collect();
 
+                let last_glyph = glyphs.last().unwrap();
+                let hyphen = hyphenate.then(|| hyphen_glyph(fonts, job, last_glyph));
+
                 let paragraph_min_x = glyphs[0].pos.x;
-                let paragraph_max_x = glyphs.last().unwrap().max_x();
+                let paragraph_max_x = hyphen.map_or(last_glyph.max_x(), |hyphen| hyphen.max_x());
 
                 out_rows.push(Row {
                     glyphs,
                     visuals: Default::default(),
                     rect: rect_from_x_range(paragraph_min_x..=paragraph_max_x),
                     ends_with_newline: false,
+                    hyphen,
+                    elided: None,
                 });
 
                 row_start_idx = last_kept_index + 1;
@@ -246,7 +689,20 @@ fn line_break(
             }
         }
 
-        row_break_candidates.add(i, &paragraph.glyphs[i..]);
+        let glyph = &paragraph.glyphs[i];
+        let opportunity = match opportunity {
+            BreakOpportunity::Hyphen => {
+                // Only if there is room for the hyphen:
+                let hyphen_max_x = glyph.max_x() + hyphen_glyph(fonts, job, glyph).size.x;
+                if hyphen_max_x - row_start_x <= job.wrap.max_width {
+                    BreakOpportunity::Hyphen
+                } else {
+                    BreakOpportunity::None
+                }
+            }
+            opportunity => opportunity,
+        };
+        row_break_candidates.add(i, opportunity);
     }
 
     if row_start_idx < paragraph.glyphs.len() {
@@ -272,11 +728,28 @@ fn line_break(
                 visuals: Default::default(),
                 rect: rect_from_x_range(paragraph_min_x..=paragraph_max_x),
                 ends_with_newline: false,
+                hyphen: None,
+                elided: None,
             });
         }
     }
 }
 
+/// The hyphen to show after `glyph` when a word is broken there.
+fn hyphen_glyph(fonts: &mut FontsImpl, job: &LayoutJob, glyph: &Glyph) -> Glyph {
+    let format = &job.sections[glyph.section_index as usize].format;
+    let font = fonts.font(&section_font_id(format));
+    let (_, glyph_info) = font.glyph_info_and_font_impl('-');
+    Glyph {
+        chr: '-',
+        pos: pos2(glyph.max_x(), glyph.pos.y),
+        size: vec2(glyph_info.advance_width, font.row_height()),
+        uv_rect: glyph_info.uv_rect,
+        section_index: glyph.section_index,
+        bidi_level: glyph.bidi_level,
+    }
+}
+
 fn replace_last_glyph_with_overflow_character(
     fonts: &mut FontsImpl,
     job: &LayoutJob,
@@ -287,6 +760,9 @@ fn replace_last_glyph_with_overflow_character(
         None => return,
     };
 
+    // The overflow character replaces the hyphen:
+    row.hyphen = None;
+
     loop {
         let (prev_glyph, last_glyph) = match row.glyphs.as_mut_slice() {
             [.., prev, last] => (Some(prev), last),
@@ -295,7 +771,7 @@ fn replace_last_glyph_with_overflow_character(
         };
 
         let section = &job.sections[last_glyph.section_index as usize];
-        let font = fonts.font(&section.format.font_id);
+        let font = fonts.font(&section_font_id(&section.format));
         let font_height = font.row_height();
 
         let prev_glyph_id = prev_glyph.map(|prev_glyph| {
@@ -368,7 +844,8 @@ fn halign_and_jusitfy_row(
     assert!(num_glyphs_in_range > 0);
 
     let original_min_x = row.glyphs[glyph_range.0].logical_rect().min.x;
-    let original_max_x = row.glyphs[glyph_range.1 - 1].logical_rect().max.x;
+    let hyphen_width = row.hyphen.map_or(0.0, |hyphen| hyphen.size.x);
+    let original_max_x = row.glyphs[glyph_range.1 - 1].logical_rect().max.x + hyphen_width;
     let original_width = original_max_x - original_min_x;
 
     let target_width = if justify && num_glyphs_in_range > 1 {
@@ -425,7 +902,12 @@ fn halign_and_jusitfy_row(
 }
 
 /// Calculate the Y positions and tessellate the text.
-fn galley_from_rows(point_scale: PointScale, job: Arc<LayoutJob>, mut rows: Vec<Row>) -> Galley {
+fn galley_from_rows(
+    point_scale: PointScale,
+    job: Arc<LayoutJob>,
+    format_summary: &FormatSummary,
+    mut rows: Vec<Row>,
+) -> Galley {
     let mut first_row_min_height = job.first_row_min_height;
     let mut cursor_y = 0.0;
     let mut min_x: f32 = 0.0;
@@ -445,6 +927,19 @@ fn galley_from_rows(point_scale: PointScale, job: Arc<LayoutJob>, mut rows: Vec<
             glyph.pos.y = point_scale.round_to_pixel(glyph.pos.y);
         }
 
+        // The hyphen goes after the last glyph:
+        let hyphen_x = row
+            .glyphs
+            .iter()
+            .map(|glyph| glyph.max_x())
+            .fold(0.0, f32::max);
+        if let Some(hyphen) = &mut row.hyphen {
+            let format = &job.sections[hyphen.section_index as usize].format;
+            hyphen.pos.x = hyphen_x;
+            hyphen.pos.y = cursor_y + format.valign.to_factor() * (row_height - hyphen.size.y);
+            hyphen.pos.y = point_scale.round_to_pixel(hyphen.pos.y);
+        }
+
         row.rect.min.y = cursor_y;
         row.rect.max.y = cursor_y + row_height;
 
@@ -454,14 +949,12 @@ fn galley_from_rows(point_scale: PointScale, job: Arc<LayoutJob>, mut rows: Vec<
         cursor_y = point_scale.round_to_pixel(cursor_y);
     }
 
-    let format_summary = format_summary(&job);
-
     let mut mesh_bounds = Rect::NOTHING;
     let mut num_vertices = 0;
     let mut num_indices = 0;
 
     for row in &mut rows {
-        row.visuals = tessellate_row(point_scale, &job, &format_summary, row);
+        row.visuals = tessellate_row(point_scale, &job, format_summary, row);
         mesh_bounds = mesh_bounds.union(row.visuals.mesh_bounds);
         num_vertices += row.visuals.mesh.vertices.len();
         num_indices += row.visuals.mesh.indices.len();
@@ -484,14 +977,22 @@ struct FormatSummary {
     any_background: bool,
     any_underline: bool,
     any_strikethrough: bool,
+
+    /// For each section: slant the glyphs, because the font has no italic face.
+    synthetic_italics: Vec<bool>,
 }
 
-fn format_summary(job: &LayoutJob) -> FormatSummary {
+fn format_summary(fonts: &mut FontsImpl, job: &LayoutJob) -> FormatSummary {
     let mut format_summary = FormatSummary::default();
     for section in &job.sections {
         format_summary.any_background |= section.format.background != Color32::TRANSPARENT;
         format_summary.any_underline |= section.format.underline != Stroke::none();
         format_summary.any_strikethrough |= section.format.strikethrough != Stroke::none();
+
+        let font_id = section_font_id(&section.format);
+        let synthetic_italics =
+            font_id.style == FontStyle::Italic && fonts.font(&font_id).synthetic_italics();
+        format_summary.synthetic_italics.push(synthetic_italics);
     }
     format_summary
 }
@@ -516,9 +1017,12 @@ fn tessellate_row(
     }
 
     let glyph_vertex_start = mesh.vertices.len();
-    tessellate_glyphs(point_scale, job, row, &mut mesh);
+    tessellate_glyphs(point_scale, job, format_summary, row, &mut mesh, false);
     let glyph_vertex_end = mesh.vertices.len();
 
+    // After the `glyph_vertex_range`, so they are not affected by `override_text_color`:
+    tessellate_glyphs(point_scale, job, format_summary, row, &mut mesh, true);
+
     if format_summary.any_underline {
         add_row_hline(point_scale, row, &mut mesh, |glyph| {
             let format = &job.sections[glyph.section_index as usize].format;
@@ -591,9 +1095,36 @@ fn add_row_backgrounds(job: &LayoutJob, row: &Row, mesh: &mut Mesh) {
     end_run(run_start.take(), last_rect.right());
 }
 
-fn tessellate_glyphs(point_scale: PointScale, job: &LayoutJob, row: &Row, mesh: &mut Mesh) {
-    for glyph in &row.glyphs {
-        let uv_rect = glyph.uv_rect;
+/// Add either the monochrome glyphs, or the color glyphs (e.g. emojis).
+///
+/// The parts of color glyphs in the text color are added with the monochrome glyphs,
+/// so they end up below the colors.
+fn tessellate_glyphs(
+    point_scale: PointScale,
+    job: &LayoutJob,
+    format_summary: &FormatSummary,
+    row: &Row,
+    mesh: &mut Mesh,
+    color_glyphs: bool,
+) {
+    for glyph in row.glyphs.iter().chain(&row.hyphen) {
+        let format = &job.sections[glyph.section_index as usize].format;
+
+        let (uv_rect, color) = if color_glyphs {
+            if !glyph.uv_rect.is_color {
+                continue;
+            }
+            // Paint the colors of the glyph as they are:
+            (glyph.uv_rect, Color32::from_white_alpha(format.color.a()))
+        } else if glyph.uv_rect.is_color {
+            match glyph.uv_rect.foreground() {
+                Some(foreground) => (foreground, format.color),
+                None => continue,
+            }
+        } else {
+            (glyph.uv_rect, format.color)
+        };
+
         if !uv_rect.is_nothing() {
             let mut left_top = glyph.pos + uv_rect.offset;
             left_top.x = point_scale.round_to_pixel(left_top.x);
@@ -605,11 +1136,7 @@ fn tessellate_glyphs(point_scale: PointScale, job: &LayoutJob, row: &Row, mesh:
                 pos2(uv_rect.max[0] as f32, uv_rect.max[1] as f32),
             );
 
-            let format = &job.sections[glyph.section_index as usize].format;
-
-            let color = format.color;
-
-            if format.italics {
+            if format_summary.synthetic_italics[glyph.section_index as usize] {
                 let idx = mesh.vertices.len() as u32;
                 mesh.add_triangle(idx, idx + 1, idx + 2);
                 mesh.add_triangle(idx + 2, idx + 1, idx + 3);
@@ -708,99 +1235,627 @@ fn add_hline(point_scale: PointScale, [start, stop]: [Pos2; 2], stroke: Stroke,
 
 // ----------------------------------------------------------------------------
 
+/// Where a row may end, after a glyph.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BreakOpportunity {
+    None,
+
+    /// A break allowed by the Unicode line breaking algorithm (UAX #14),
+    /// e.g. after a space or a dash, between two CJK characters, or after a `/` in a path.
+    Word,
+
+    /// Inside a word, with a hyphen at the end of the row:
+    /// at a soft hyphen, or between two syllables found with [`TextWrapping::hyphenation`].
+    Hyphen,
+}
+
+/// Where each row may end, for each glyph of a paragraph.
+fn break_opportunities(
+    glyphs: &[Glyph],
+    hyphenation: Option<HyphenationLanguage>,
+) -> Vec<BreakOpportunity> {
+    let text: String = glyphs.iter().map(|glyph| glyph.chr).collect();
+    let char_starts: Vec<usize> = text
+        .char_indices()
+        .map(|(byte_index, _)| byte_index)
+        .collect();
+
+    let mut opportunities = vec![BreakOpportunity::None; glyphs.len()];
+    for (byte_index, _) in unicode_linebreak::linebreaks(&text) {
+        // The break is before the character at `byte_index`:
+        if let Ok(next_index) = char_starts.binary_search(&byte_index) {
+            if next_index > 0 {
+                opportunities[next_index - 1] = BreakOpportunity::Word;
+            }
+        }
+    }
+
+    for (glyph, opportunity) in glyphs.iter().zip(&mut opportunities) {
+        if glyph.chr == SOFT_HYPHEN {
+            *opportunity = BreakOpportunity::Hyphen;
+        }
+    }
+
+    #[cfg(feature = "hyphenation")]
+    if let Some(language) = hyphenation {
+        hyphenate_words(glyphs, language, &mut opportunities);
+    }
+    #[cfg(not(feature = "hyphenation"))]
+    let _ = hyphenation;
+
+    opportunities
+}
+
+/// Add [`BreakOpportunity::Hyphen`] between the syllables of all words.
+#[cfg(feature = "hyphenation")]
+fn hyphenate_words(
+    glyphs: &[Glyph],
+    language: HyphenationLanguage,
+    opportunities: &mut [BreakOpportunity],
+) {
+    let language = match language {
+        HyphenationLanguage::Danish => hypher::Lang::Danish,
+        HyphenationLanguage::Dutch => hypher::Lang::Dutch,
+        HyphenationLanguage::English => hypher::Lang::English,
+        HyphenationLanguage::Finnish => hypher::Lang::Finnish,
+        HyphenationLanguage::French => hypher::Lang::French,
+        HyphenationLanguage::German => hypher::Lang::German,
+        HyphenationLanguage::Italian => hypher::Lang::Italian,
+        HyphenationLanguage::Norwegian => hypher::Lang::Norwegian,
+        HyphenationLanguage::Portuguese => hypher::Lang::Portuguese,
+        HyphenationLanguage::Spanish => hypher::Lang::Spanish,
+        HyphenationLanguage::Swedish => hypher::Lang::Swedish,
+    };
+
+    let mut word_start = 0;
+    while word_start < glyphs.len() {
+        let word_len = glyphs[word_start..]
+            .iter()
+            .take_while(|glyph| glyph.chr.is_alphabetic())
+            .count();
+        if word_len == 0 {
+            word_start += 1;
+            continue;
+        }
+
+        let word: String = glyphs[word_start..word_start + word_len]
+            .iter()
+            .map(|glyph| glyph.chr)
+            .collect();
+        let mut syllable_end = word_start;
+        let mut syllables = hypher::hyphenate(&word, language).peekable();
+        while let Some(syllable) = syllables.next() {
+            syllable_end += syllable.chars().count();
+            if syllables.peek().is_some()
+                && opportunities[syllable_end - 1] == BreakOpportunity::None
+            {
+                opportunities[syllable_end - 1] = BreakOpportunity::Hyphen;
+            }
+        }
+
+        word_start += word_len;
+    }
+}
+
 /// Keeps track of good places to break a long row of text.
 /// Will focus primarily on spaces, secondarily on things like `-`
-#[derive(Clone, Copy, Default)]
+#[derive(Default)]
 struct RowBreakCandidates {
-    /// Breaking at ` ` or other whitespace
-    /// is always the primary candidate.
-    space: Option<usize>,
-    /// Logograms (single character representing a whole word) are good candidates for line break.
-    logogram: Option<usize>,
-    /// Kana (Japanese hiragana and katakana) may be line broken unless before a gyōtō kinsoku character.
-    kana: Option<usize>,
-    /// Breaking at a dash is a super-
-    /// good idea.
-    dash: Option<usize>,
-    /// This is nicer for things like URLs, e.g. www.
-    /// example.com.
-    punctuation: Option<usize>,
+    /// The last [`BreakOpportunity::Word`].
+    word: Option<usize>,
+
+    /// The last [`BreakOpportunity::Hyphen`] where the hyphen fits on the row.
+    hyphen: Option<usize>,
+
     /// Breaking after just random character is some
     /// times necessary.
     any: Option<usize>,
 }
 
 impl RowBreakCandidates {
-    fn add(&mut self, index: usize, glyphs: &[Glyph]) {
-        let chr = glyphs[0].chr;
-        const NON_BREAKING_SPACE: char = '\u{A0}';
-        if chr.is_whitespace() && chr != NON_BREAKING_SPACE {
-            self.space = Some(index);
-        } else if is_cjk_ideograph(chr) {
-            self.logogram = Some(index);
-        } else if chr == '-' {
-            self.dash = Some(index);
-        } else if chr.is_ascii_punctuation() {
-            self.punctuation = Some(index);
-        } else if is_kana(chr) && (glyphs.len() == 1 || !is_gyoto_kinsoku(glyphs[1].chr)) {
-            self.kana = Some(index);
+    fn add(&mut self, index: usize, opportunity: BreakOpportunity) {
+        match opportunity {
+            BreakOpportunity::None => {}
+            BreakOpportunity::Word => self.word = Some(index),
+            BreakOpportunity::Hyphen => self.hyphen = Some(index),
         }
         self.any = Some(index);
     }
 
-    fn has_word_boundary(&self) -> bool {
-        self.space.is_some() || self.logogram.is_some()
-    }
-
     fn has_good_candidate(&self, break_anywhere: bool) -> bool {
         if break_anywhere {
             self.any.is_some()
         } else {
-            self.has_word_boundary()
+            self.word.is_some() || self.hyphen.is_some()
         }
     }
 
-    fn get(&self, break_anywhere: bool) -> Option<usize> {
+    /// The index of the last glyph to keep on the row, and if a hyphen should be added after it.
+    fn get(&self, break_anywhere: bool) -> Option<(usize, bool)> {
         if break_anywhere {
-            self.any
-        } else {
-            self.space
-                .or(self.kana)
-                .or(self.logogram)
-                .or(self.dash)
-                .or(self.punctuation)
-                .or(self.any)
+            return self.any.map(|index| (index, false));
+        }
+        match (self.word, self.hyphen) {
+            (Some(word), Some(hyphen)) if hyphen > word => Some((hyphen, true)),
+            (Some(word), _) => Some((word, false)),
+            (None, Some(hyphen)) => Some((hyphen, true)),
+            (None, None) => self.any.map(|index| (index, false)),
         }
     }
 }
 
-#[inline]
-fn is_cjk_ideograph(c: char) -> bool {
-    ('\u{4E00}' <= c && c <= '\u{9FFF}')
-        || ('\u{3400}' <= c && c <= '\u{4DBF}')
-        || ('\u{2B740}' <= c && c <= '\u{2B81F}')
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_zero_max_width() {
+    let mut fonts = FontsImpl::new(1.0, 1024, super::FontDefinitions::default());
+    let mut layout_job = LayoutJob::single_section("W".into(), super::TextFormat::default());
+    layout_job.wrap.max_width = 0.0;
+    let galley = super::layout(&mut fonts, layout_job.into());
+    assert_eq!(galley.rows.len(), 1);
 }
 
-#[inline]
-fn is_kana(c: char) -> bool {
-    ('\u{3040}' <= c && c <= '\u{309F}') // Hiragana block
-        || ('\u{30A0}' <= c && c <= '\u{30FF}') // Katakana block
+#[cfg(test)]
+fn layout_no_wrap(text: &str) -> Galley {
+    let mut fonts = FontsImpl::new(1.0, 1024, super::FontDefinitions::default());
+    let layout_job = LayoutJob::single_section(text.into(), super::TextFormat::default());
+    super::layout(&mut fonts, layout_job.into())
 }
 
-#[inline]
-fn is_gyoto_kinsoku(c: char) -> bool {
-    // Gyōtō (meaning "beginning of line") kinsoku characters in Japanese typesetting are characters that may not appear at the start of a line, according to kinsoku shori rules.
-    // The list of gyōtō kinsoku characters can be found at https://en.wikipedia.org/wiki/Line_breaking_rules_in_East_Asian_languages#Characters_not_permitted_on_the_start_of_a_line.
-    ")]｝〕〉》」』】〙〗〟'\"｠»ヽヾーァィゥェォッャュョヮヵヶぁぃぅぇぉっゃゅょゎゕゖㇰㇱㇲㇳㇴㇵㇶㇷㇸㇹㇺㇻㇼㇽㇾㇿ々〻‐゠–〜?!‼⁇⁈⁉・、:;,。.".contains(c)
+#[test]
+fn test_cursor_moves_over_grapheme_clusters() {
+    use super::cursor::CCursor;
+
+    // "e" + combining acute accent, then a family emoji made of three emojis joined by ZWJ:
+    let galley = layout_no_wrap("e\u{301}👨\u{200d}👩\u{200d}👧!");
+
+    let mut cursor = Default::default();
+    let mut indices = vec![];
+    for _ in 0..4 {
+        cursor = galley.cursor_right_one_character(&cursor);
+        indices.push(cursor.ccursor.index);
+    }
+    assert_eq!(indices, vec![2, 7, 8, 8]);
+
+    cursor = galley.cursor_left_one_character(&cursor);
+    assert_eq!(cursor.ccursor.index, 7);
+    cursor = galley.cursor_left_one_character(&cursor);
+    assert_eq!(cursor.ccursor.index, 2);
+
+    assert!(galley.is_grapheme_boundary(CCursor::new(2)));
+    assert!(!galley.is_grapheme_boundary(CCursor::new(1)));
+    assert!(!galley.is_grapheme_boundary(CCursor::new(4)));
 }
 
-// ----------------------------------------------------------------------------
+#[test]
+fn test_cursor_from_pos_snaps_to_grapheme_clusters() {
+    let galley = layout_no_wrap("e\u{301}e\u{301}e\u{301}");
+    for x in 0..=(galley.size().x.ceil() as i32) {
+        let cursor = galley.cursor_from_pos(vec2(x as f32, 1.0));
+        assert!(
+            cursor.ccursor.index % 2 == 0,
+            "x = {}: cursor inside a grapheme cluster: {:?}",
+            x,
+            cursor
+        );
+    }
+}
 
+#[cfg(feature = "shaping")]
 #[test]
-fn test_zero_max_width() {
+fn test_shaping_combines_accents() {
+    let galley = layout_no_wrap("e\u{301}x");
+    let glyphs = &galley.rows[0].glyphs;
+    assert_eq!(glyphs.len(), 3, "still one glyph per char");
+
+    // The accent is drawn together with the "e":
+    assert!(!glyphs[0].uv_rect.is_nothing());
+    assert!(glyphs[1].uv_rect.is_nothing());
+    assert_eq!(glyphs[1].size.x, 0.0);
+    assert_eq!(glyphs[1].pos.x, glyphs[0].max_x());
+    assert!(glyphs[2].pos.x >= glyphs[0].max_x());
+}
+
+#[test]
+fn test_bidi_layout() {
+    use super::cursor::CCursor;
+
+    // "ab " followed by three hebrew letters (alef, bet, gimel):
+    let galley = layout_no_wrap("ab \u{5D0}\u{5D1}\u{5D2}");
+    let row = &galley.rows[0];
+    let glyphs = &row.glyphs;
+    assert_eq!(glyphs.len(), 6);
+    assert!(!glyphs[2].is_rtl());
+    assert!(glyphs[3].is_rtl());
+
+    // Latin left-to-right, hebrew right-to-left:
+    assert!(glyphs[0].pos.x < glyphs[1].pos.x);
+    assert!(glyphs[1].pos.x < glyphs[5].pos.x);
+    assert!(glyphs[5].pos.x < glyphs[4].pos.x);
+    assert!(glyphs[4].pos.x < glyphs[3].pos.x);
+
+    // The glyphs keep their advances, so the hebrew starts where it would after "ab ":
+    let ltr_galley = layout_no_wrap("ab x");
+    assert_eq!(glyphs[5].pos.x, ltr_galley.rows[0].glyphs[3].pos.x);
+
+    // Selecting "b" and alef gives two separate ranges:
+    assert_eq!(row.x_ranges(3..6).len(), 1);
+    assert_eq!(row.x_ranges(1..4).len(), 2);
+
+    // Moving left in the hebrew is moving forward in the text:
+    let cursor = galley.from_ccursor(CCursor::new(4));
+    assert_eq!(galley.cursor_left_one_character(&cursor).ccursor.index, 5);
+    assert_eq!(galley.cursor_right_one_character(&cursor).ccursor.index, 3);
+}
+
+#[cfg(feature = "system_fonts")]
+#[test]
+fn test_system_font_fallback() {
+    use super::FontId;
+
+    // Only "Hack", which has no emojis:
+    let mut definitions = super::FontDefinitions::default();
+    definitions.font_data.retain(|name, _| name == "Hack");
+    for fonts in definitions.families.values_mut() {
+        *fonts = vec!["Hack".to_owned()];
+    }
+
+    let mut system_fonts = super::SystemFonts::default();
+    system_fonts.load_fonts_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/fonts"));
+    let system_fonts = Arc::new(system_fonts);
+    definitions.system_fonts = Some(system_fonts.clone());
+
+    let mut fonts = FontsImpl::new(1.0, 1024, definitions);
+    let font_id = FontId::proportional(14.0);
+    assert!(!fonts.font(&font_id).has_glyph('😀'));
+
+    // The system fonts are read on a background thread, so the first layout has to do without:
+    let layout_job = LayoutJob::simple_singleline("a😀".into(), font_id.clone(), Color32::WHITE);
+    super::layout(&mut fonts, layout_job.clone().into());
+    assert!(!fonts.font(&font_id).has_glyph('😀'));
+    while system_fonts.is_calculating_coverage() {
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+    assert!(fonts.system_fonts_became_ready());
+
+    super::layout(&mut fonts, layout_job.into());
+    assert!(fonts.font(&font_id).has_glyph('😀'));
+    assert!(fonts.font(&FontId::monospace(10.0)).has_glyph('😀'));
+}
+
+#[test]
+fn test_font_faces() {
+    use super::{FontDefinitions, FontFace, FontId, FontWeight};
+
+    // Use the monospace "Hack" as the bold face of the proportional font, so it is easy to tell apart:
+    let mut definitions = FontDefinitions::default();
+    definitions.faces.insert(
+        super::FontFamily::Proportional,
+        vec![FontFace {
+            weight: FontWeight::BOLD,
+            style: FontStyle::Normal,
+            fonts: vec!["Hack".to_owned()],
+        }],
+    );
+    let mut fonts = FontsImpl::new(1.0, 1024, definitions);
+
+    let regular = FontId::proportional(14.0);
+    let mut width = |font_id: &FontId| fonts.font(font_id).glyph_width('i');
+    let regular_width = width(&regular);
+    let bold_width = width(&regular.clone().bold());
+    assert_ne!(regular_width, bold_width);
+    assert_eq!(
+        width(&regular.clone().weight(FontWeight::SEMI_BOLD)),
+        bold_width
+    );
+    assert_eq!(
+        width(&regular.clone().weight(FontWeight::LIGHT)),
+        regular_width
+    );
+    assert_eq!(width(&regular.clone().bold().italic()), bold_width);
+
+    // There is no bold monospace, so we get the regular one:
+    let monospace = FontId::monospace(14.0);
+    assert_eq!(width(&monospace.clone().bold()), width(&monospace));
+
+    // There are no italic faces, so the glyphs are slanted:
+    assert!(fonts.font(&regular.clone().italic()).synthetic_italics());
+    assert!(!fonts.font(&regular).synthetic_italics());
+}
+
+#[test]
+fn test_color_glyph_foreground_layers() {
+    use super::{color_glyphs, font::FontImpl};
+    use crate::{mutex::Mutex, TextureAtlas};
+    use ab_glyph::Font as _;
+
+    // Hack, where glyph 5 is drawn as glyph 6 in red and then glyph 7 in the text color:
+    let font =
+        ab_glyph::FontArc::try_from_slice(include_bytes!("../../fonts/Hack-Regular.ttf")).unwrap();
+    let chr = font
+        .codepoint_ids()
+        .find(|(id, _)| id.0 == 5)
+        .map(|(_, chr)| chr)
+        .unwrap();
+    let color_layers = color_glyphs::ColorLayers::from_font(&color_glyphs::tests::test_font(), 0);
+    let atlas = Arc::new(Mutex::new(TextureAtlas::new([1024, 64])));
+    let font_impl = FontImpl::new(atlas.clone(), 1.0, "Hack".to_owned(), font, 32, 0.0)
+        .with_color_layers(color_layers.map(Arc::new));
+
+    let uv_rect = font_impl.glyph_info(chr).unwrap().uv_rect;
+    assert!(uv_rect.is_color);
+    let foreground = uv_rect.foreground().unwrap();
+
+    // The foreground layer is only coverage, and the red layer only colors:
+    let image = match atlas.lock().take_delta().unwrap().image {
+        crate::ImageData::Font(image) => image,
+        crate::ImageData::Color(_) => panic!("Expected the font image"),
+    };
+    let texels = |uv: super::font::UvRect| {
+        (uv.min[1]..uv.max[1])
+            .flat_map(move |y| (uv.min[0]..uv.max[0]).map(move |x| (x as usize, y as usize)))
+    };
+    assert!(texels(foreground).any(|pos| image[pos] > 0.0));
+    assert!(texels(uv_rect).all(|pos| image[pos] == 0.0));
+    assert!(texels(uv_rect).any(|(x, y)| image.colors[y * image.width() + x].r() > 0));
+
+    let text_color = Color32::from_rgb(0, 0, 255);
+    let job = LayoutJob::simple_singleline(chr.to_string(), FontId::monospace(32.0), text_color);
+    let row = Row {
+        glyphs: vec![Glyph {
+            chr,
+            pos: Pos2::ZERO,
+            size: uv_rect.size,
+            uv_rect,
+            section_index: 0,
+            bidi_level: 0,
+        }],
+        rect: Rect::NOTHING,
+        visuals: Default::default(),
+        ends_with_newline: false,
+        hyphen: None,
+        elided: None,
+    };
+    let format_summary = FormatSummary {
+        synthetic_italics: vec![false],
+        ..Default::default()
+    };
+    let point_scale = PointScale::new(1.0);
+
+    // The foreground layer is tinted with the text color, below the colors:
+    let mut mesh = Mesh::default();
+    tessellate_glyphs(point_scale, &job, &format_summary, &row, &mut mesh, false);
+    assert_eq!(mesh.vertices.len(), 4);
+    assert!(mesh
+        .vertices
+        .iter()
+        .all(|vertex| vertex.color == text_color));
+    assert_eq!(
+        mesh.vertices[0].uv,
+        pos2(foreground.min[0] as f32, foreground.min[1] as f32)
+    );
+
+    let mut mesh = Mesh::default();
+    tessellate_glyphs(point_scale, &job, &format_summary, &row, &mut mesh, true);
+    assert_eq!(mesh.vertices.len(), 4);
+    assert!(mesh
+        .vertices
+        .iter()
+        .all(|vertex| vertex.color == Color32::WHITE));
+    assert_eq!(
+        mesh.vertices[0].uv,
+        pos2(uv_rect.min[0] as f32, uv_rect.min[1] as f32)
+    );
+}
+
+#[cfg(test)]
+fn layout_wrapped(text: &str, max_width: f32, wrap: &super::TextWrapping) -> Galley {
     let mut fonts = FontsImpl::new(1.0, 1024, super::FontDefinitions::default());
-    let mut layout_job = LayoutJob::single_section("W".into(), super::TextFormat::default());
-    layout_job.wrap.max_width = 0.0;
-    let galley = super::layout(&mut fonts, layout_job.into());
+    let mut layout_job = LayoutJob::single_section(text.into(), super::TextFormat::default());
+    layout_job.wrap = super::TextWrapping {
+        max_width,
+        ..wrap.clone()
+    };
+    super::layout(&mut fonts, layout_job.into())
+}
+
+#[cfg(test)]
+fn row_texts(galley: &Galley) -> Vec<String> {
+    galley
+        .rows
+        .iter()
+        .map(|row| {
+            row.glyphs
+                .iter()
+                .chain(&row.hyphen)
+                .map(|glyph| glyph.chr)
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_wrap_at_spaces() {
+    let width = layout_no_wrap("Hello world").size().x;
+    let galley = layout_wrapped("Hello world", width - 1.0, &Default::default());
+    assert_eq!(row_texts(&galley), vec!["Hello ", "world"]);
+}
+
+#[test]
+fn test_wrap_cjk_between_characters() {
+    // Chinese has no spaces, but may be broken between any two ideographs:
+    let text = "你好世界";
+    let width = layout_no_wrap("你好世").size().x;
+    let galley = layout_wrapped(text, width, &Default::default());
+    assert_eq!(row_texts(&galley), vec!["你好世", "界"]);
+}
+
+#[test]
+fn test_wrap_path_after_slash() {
+    let text = "/usr/local/share";
+    let width = layout_no_wrap("/usr/local/sh").size().x;
+    let galley = layout_wrapped(text, width, &Default::default());
+    assert_eq!(row_texts(&galley), vec!["/usr/local/", "share"]);
+}
+
+#[test]
+fn test_soft_hyphen() {
+    let text = "hyper\u{AD}link";
+
+    // Invisible when not at the end of a row:
+    let galley = layout_no_wrap(text);
+    assert_eq!(galley.size().x, layout_no_wrap("hyperlink").size().x);
+    assert!(galley.rows[0].hyphen.is_none());
+
+    let width = layout_no_wrap("hyperli").size().x;
+    let galley = layout_wrapped(text, width, &Default::default());
+    assert_eq!(row_texts(&galley), vec!["hyper\u{AD}-", "link"]);
+    assert_eq!(galley.text(), text, "the hyphen is not part of the text");
+    let hyphen = galley.rows[0].hyphen.unwrap();
+    assert!(!hyphen.uv_rect.is_nothing());
+    assert_eq!(galley.rows[0].rect.max.x, hyphen.max_x());
+}
+
+#[cfg(feature = "hyphenation")]
+#[test]
+fn test_hyphenation() {
+    let wrap = super::TextWrapping {
+        hyphenation: Some(super::HyphenationLanguage::English),
+        ..Default::default()
+    };
+    let width = layout_no_wrap("The extraordinar").size().x;
+    let galley = layout_wrapped("The extraordinary", width, &wrap);
+    assert_eq!(galley.rows.len(), 2);
+    assert!(galley.rows[0].hyphen.is_some());
+    assert!(row_texts(&galley)[0].starts_with("The extra"));
+
+    // Without hyphenation we break at the space:
+    let galley = layout_wrapped("The extraordinary", width, &Default::default());
+    assert_eq!(row_texts(&galley), vec!["The ", "extraordinary"]);
+}
+
+#[test]
+fn test_wrap_max_rows_with_overflow_character() {
+    let wrap = super::TextWrapping {
+        max_rows: 1,
+        ..Default::default()
+    };
+    let width = layout_no_wrap("你好世").size().x;
+    let galley = layout_wrapped("你好世界", width, &wrap);
     assert_eq!(galley.rows.len(), 1);
+    assert!(row_texts(&galley)[0].ends_with('…'));
+}
+
+#[cfg(test)]
+fn layout_visible(
+    fonts: &super::Fonts,
+    text: &str,
+    visible_y_range: Option<RangeInclusive<f32>>,
+) -> Arc<Galley> {
+    let mut layout_job = LayoutJob::single_section(text.into(), super::TextFormat::default());
+    layout_job.wrap.max_width = 200.0;
+    layout_job.visible_y_range = visible_y_range;
+    fonts.layout_job(layout_job)
+}
+
+#[test]
+fn test_layout_visible_paragraphs() {
+    use super::cursor::{CCursor, PCursor};
+
+    // Share the font atlas, so the glyphs are the same:
+    let fonts = super::Fonts::new(1.0, 1024, super::FontDefinitions::default());
+    let text: String = (0..100).map(|i| format!("Paragraph {}\n", i)).collect();
+    let full = layout_visible(&fonts, &text, None);
+    let row_height = full.rows[0].height();
+
+    // Everything visible gives the same rows:
+    let all = layout_visible(&fonts, &text, Some(f32::NEG_INFINITY..=f32::INFINITY));
+    assert_eq!(all.rows, full.rows);
+    assert_eq!(all.rect, full.rect);
+
+    // Only some paragraphs are laid out:
+    let galley = layout_visible(&fonts, &text, Some(10.5 * row_height..=20.5 * row_height));
+    assert_eq!(
+        galley.size().y,
+        full.size().y,
+        "estimated heights are exact without wrapping"
+    );
+    let placeholders: Vec<_> = galley
+        .rows
+        .iter()
+        .filter_map(|row| row.elided.as_ref())
+        .collect();
+    assert_eq!(placeholders.len(), 2);
+    assert_eq!(placeholders[0].num_paragraphs, 10);
+    assert_eq!(placeholders[1].num_paragraphs, 100 - 21);
+    assert_eq!(
+        galley.rows[1].glyphs[0].pos.y,
+        full.rows[10].glyphs[0].pos.y
+    );
+    assert_eq!(galley.end().ccursor, full.end().ccursor);
+    assert_eq!(galley.end().pcursor, full.end().pcursor);
+
+    // Cursors work the same, also in the paragraphs that were not laid out:
+    for index in [0, 5, 13, 130, 140, 160, 900, text.chars().count()] {
+        let ccursor = CCursor::new(index);
+        assert_eq!(
+            galley.from_ccursor(ccursor).pcursor,
+            full.from_ccursor(ccursor).pcursor
+        );
+    }
+    let pcursor = PCursor {
+        paragraph: 50,
+        offset: 3,
+        prefer_next_row: false,
+    };
+    assert_eq!(
+        galley.from_pcursor(pcursor).ccursor,
+        full.from_pcursor(pcursor).ccursor
+    );
+    let pos = full.pos_from_pcursor(pcursor);
+    assert_eq!(galley.pos_from_pcursor(pcursor).min.y, pos.min.y);
+    assert_eq!(
+        galley
+            .cursor_from_pos(pos.center().to_vec2())
+            .pcursor
+            .paragraph,
+        50
+    );
+
+    // Moving down from the last visible paragraph:
+    let cursor = galley.from_pcursor(PCursor {
+        paragraph: 20,
+        offset: 2,
+        prefer_next_row: false,
+    });
+    assert_eq!(galley.cursor_down_one_row(&cursor).pcursor.paragraph, 21);
+}
+
+#[test]
+fn test_layout_visible_paragraphs_is_cached() {
+    let fonts = super::Fonts::new(1.0, 1024, super::FontDefinitions::default());
+    let text: String = (0..100).map(|i| format!("Paragraph {}\n", i)).collect();
+    let mut layout_job = LayoutJob::single_section(text, super::TextFormat::default());
+    layout_job.visible_y_range = Some(0.0..=100.0);
+    let galley = fonts.layout_job(layout_job.clone());
+
+    // Scrolling reuses the paragraphs that were laid out before:
+    let num_laid_out = galley
+        .rows
+        .iter()
+        .filter(|row| row.elided.is_none())
+        .count();
+    let num_galleys = fonts.num_galleys_in_cache();
+    assert_eq!(num_galleys, 1 + num_laid_out);
+
+    // The paragraphs stay cached while the whole galley is reused, frame after frame:
+    for _ in 0..2 {
+        fonts.begin_frame(1.0, 1024);
+        fonts.layout_job(layout_job.clone());
+    }
+    assert_eq!(fonts.num_galleys_in_cache(), num_galleys);
+
+    layout_job.visible_y_range = Some(1.0..=101.0);
+    fonts.layout_job(layout_job);
+    assert_eq!(fonts.num_galleys_in_cache(), num_galleys + 1);
 }
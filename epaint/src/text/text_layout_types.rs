@@ -1,11 +1,12 @@
 #![allow(clippy::derive_hash_xor_eq)] // We need to impl Hash for f32, but we don't implement Eq, which is fine
 
-use std::ops::Range;
+use std::ops::{Range, RangeInclusive};
 use std::sync::Arc;
 
 use super::{cursor::*, font::UvRect};
 use crate::{Color32, FontId, Mesh, Stroke};
 use emath::*;
+use unicode_segmentation::GraphemeCursor;
 
 /// Describes the task of laying out text.
 ///
@@ -68,6 +69,18 @@ pub struct LayoutJob {
 
     /// Justify text so that word-wrapped rows fill the whole [`TextWrapping::max_width`]
     pub justify: bool,
+
+    /// If set, only lay out the paragraphs (pieces of text separated with `\n`)
+    /// within this range of y coordinates, relative to the top of the galley.
+    ///
+    /// The paragraphs outside of it are replaced with placeholder rows of an estimated height
+    /// (see [`Row::elided`]). Each paragraph is laid out and cached on its own,
+    /// so that editing one paragraph only lays out that paragraph again.
+    ///
+    /// Use this for huge texts in a `ScrollArea`, with the visible part of it.
+    /// Ignored with [`TextWrapping::max_rows`] or without [`Self::break_on_newline`].
+    /// Default: `None` (lay out all the text).
+    pub visible_y_range: Option<RangeInclusive<f32>>,
 }
 
 impl Default for LayoutJob {
@@ -81,6 +94,7 @@ impl Default for LayoutJob {
             break_on_newline: true,
             halign: Align::LEFT,
             justify: false,
+            visible_y_range: None,
         }
     }
 }
@@ -174,6 +188,7 @@ impl std::hash::Hash for LayoutJob {
             break_on_newline,
             halign,
             justify,
+            visible_y_range,
         } = self;
 
         text.hash(state);
@@ -183,6 +198,10 @@ impl std::hash::Hash for LayoutJob {
         break_on_newline.hash(state);
         halign.hash(state);
         justify.hash(state);
+        if let Some(visible_y_range) = visible_y_range {
+            crate::f32_hash(state, *visible_y_range.start());
+            crate::f32_hash(state, *visible_y_range.end());
+        }
     }
 }
 
@@ -221,6 +240,10 @@ pub struct TextFormat {
     /// Text color
     pub color: Color32,
     pub background: Color32,
+
+    /// Use the italic face of the font, or slant the glyphs if there is none.
+    ///
+    /// Same as setting [`FontId::style`] to [`crate::text::FontStyle::Italic`].
     pub italics: bool,
     pub underline: Stroke,
     pub strikethrough: Stroke,
@@ -271,10 +294,21 @@ pub struct TextWrapping {
     pub max_rows: usize,
 
     /// Don't try to break text at an appropriate place.
+    ///
+    /// By default text is broken where the Unicode line breaking algorithm allows it:
+    /// after spaces and dashes, between most CJK characters, after the `/` in paths, etc.
     pub break_anywhere: bool,
 
     /// Character to use to represent clipped text, `…` for example, which is the default.
     pub overflow_character: Option<char>,
+
+    /// Hyphenate words in this language when breaking them gives a better fit.
+    ///
+    /// Soft hyphens (`\u{AD}`) in the text are always used as places to hyphenate words,
+    /// even when this is `None`.
+    ///
+    /// Requires the `hyphenation` feature, and is ignored otherwise.
+    pub hyphenation: Option<HyphenationLanguage>,
 }
 
 impl std::hash::Hash for TextWrapping {
@@ -285,11 +319,13 @@ impl std::hash::Hash for TextWrapping {
             max_rows,
             break_anywhere,
             overflow_character,
+            hyphenation,
         } = self;
         crate::f32_hash(state, *max_width);
         max_rows.hash(state);
         break_anywhere.hash(state);
         overflow_character.hash(state);
+        hyphenation.hash(state);
     }
 }
 
@@ -300,10 +336,28 @@ impl Default for TextWrapping {
             max_rows: 0,
             break_anywhere: false,
             overflow_character: Some('…'),
+            hyphenation: None,
         }
     }
 }
 
+/// A language to hyphenate words in, see [`TextWrapping::hyphenation`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum HyphenationLanguage {
+    Danish,
+    Dutch,
+    English,
+    Finnish,
+    French,
+    German,
+    Italian,
+    Norwegian,
+    Portuguese,
+    Spanish,
+    Swedish,
+}
+
 // ----------------------------------------------------------------------------
 
 /// Text that has been layed out, ready for painting.
@@ -363,6 +417,33 @@ pub struct Row {
     /// so that text that ends with `\n` has an empty [`Row`] last.
     /// This also implies that the last [`Row`] in a [`Galley`] always has `ends_with_newline == false`.
     pub ends_with_newline: bool,
+
+    /// A hyphen painted after [`Self::glyphs`], because a word was broken at the end of this row.
+    ///
+    /// This is not part of the text, so it is not included in [`Self::glyphs`] or any cursor.
+    pub hyphen: Option<Glyph>,
+
+    /// If set, this row is a placeholder for whole paragraphs that were not laid out,
+    /// because they were outside of [`LayoutJob::visible_y_range`].
+    ///
+    /// A placeholder row has no glyphs and an estimated height,
+    /// and always ends with a newline.
+    pub elided: Option<ElidedParagraphs>,
+}
+
+/// Paragraphs that were not laid out, see [`Row::elided`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ElidedParagraphs {
+    /// How many paragraphs there are, at least one.
+    pub num_paragraphs: usize,
+
+    /// The number of characters in them, including the `\n` between them,
+    /// but not the last one (see [`Row::ends_with_newline`]).
+    pub num_chars: usize,
+
+    /// Where they are in [`LayoutJob::text`], excluding the last `\n`.
+    pub byte_range: Range<usize>,
 }
 
 /// The tessellated output of a row.
@@ -377,8 +458,8 @@ pub struct RowVisuals {
     /// Does NOT include leading or trailing whitespace glyphs!!
     pub mesh_bounds: Rect,
 
-    /// The range of vertices in the mesh the contain glyphs.
-    /// Before comes backgrounds (if any), and after any underlines and strikethrough.
+    /// The range of vertices in the mesh the contain glyphs, except the color glyphs (e.g. emojis).
+    /// Before comes backgrounds (if any), and after the color glyphs, underlines and strikethrough.
     pub glyph_vertex_range: Range<usize>,
 }
 
@@ -405,6 +486,8 @@ pub struct Glyph {
     pub uv_rect: UvRect,
     /// Index into [`LayoutJob::sections`]. Decides color etc.
     pub section_index: u32,
+    /// The bidirectional embedding level: even for left-to-right text, odd for right-to-left text.
+    pub bidi_level: u8,
 }
 
 impl Glyph {
@@ -412,6 +495,33 @@ impl Glyph {
         self.pos.x + self.size.x
     }
 
+    /// Is this part of right-to-left text (e.g. Arabic or Hebrew)?
+    #[inline]
+    pub fn is_rtl(&self) -> bool {
+        self.bidi_level % 2 == 1
+    }
+
+    /// Where a cursor placed before this character is:
+    /// to the left of it for left-to-right text, and to the right of it for right-to-left text.
+    #[inline]
+    pub fn leading_x(&self) -> f32 {
+        if self.is_rtl() {
+            self.max_x()
+        } else {
+            self.pos.x
+        }
+    }
+
+    /// Where a cursor placed after this character is.
+    #[inline]
+    pub fn trailing_x(&self) -> f32 {
+        if self.is_rtl() {
+            self.pos.x
+        } else {
+            self.max_x()
+        }
+    }
+
     /// Same y range for all characters with the same [`TextFormat`].
     #[inline]
     pub fn logical_rect(&self) -> Rect {
@@ -425,13 +535,30 @@ impl Row {
     /// Excludes the implicit `\n` after the [`Row`], if any.
     #[inline]
     pub fn char_count_excluding_newline(&self) -> usize {
-        self.glyphs.len()
+        self.glyphs.len() + self.elided.as_ref().map_or(0, |elided| elided.num_chars)
     }
 
     /// Includes the implicit `\n` after the [`Row`], if any.
     #[inline]
     pub fn char_count_including_newline(&self) -> usize {
-        self.glyphs.len() + (self.ends_with_newline as usize)
+        self.char_count_excluding_newline() + (self.ends_with_newline as usize)
+    }
+
+    /// How many paragraphs end with this row: more than one for placeholder rows.
+    #[inline]
+    fn num_paragraph_ends(&self) -> usize {
+        if self.ends_with_newline {
+            self.elided
+                .as_ref()
+                .map_or(1, |elided| elided.num_paragraphs)
+        } else {
+            0
+        }
+    }
+
+    /// The height of each paragraph in a placeholder row.
+    fn elided_paragraph_height(&self, elided: &ElidedParagraphs) -> f32 {
+        self.height() / elided.num_paragraphs as f32
     }
 
     #[inline]
@@ -452,21 +579,70 @@ impl Row {
     /// Closest char at the desired x coordinate.
     /// Returns something in the range `[0, char_count_excluding_newline()]`.
     pub fn char_at(&self, desired_x: f32) -> usize {
+        // The glyphs are in logical order, which is not the visual order for bidirectional text.
+        let mut closest = None;
+        let mut closest_dist = f32::INFINITY;
         for (i, glyph) in self.glyphs.iter().enumerate() {
-            if desired_x < glyph.logical_rect().center().x {
-                return i;
+            let dist = if desired_x < glyph.pos.x {
+                glyph.pos.x - desired_x
+            } else {
+                (desired_x - glyph.max_x()).at_least(0.0)
+            };
+            if dist < closest_dist {
+                closest = Some(i);
+                closest_dist = dist;
             }
         }
-        self.char_count_excluding_newline()
+
+        if let Some(i) = closest {
+            let glyph = &self.glyphs[i];
+            let before_center = desired_x < glyph.logical_rect().center().x;
+            if before_center != glyph.is_rtl() {
+                i
+            } else {
+                i + 1
+            }
+        } else {
+            self.char_count_excluding_newline()
+        }
     }
 
+    /// Where a cursor at the given column is.
     pub fn x_offset(&self, column: usize) -> f32 {
         if let Some(glyph) = self.glyphs.get(column) {
-            glyph.pos.x
+            glyph.leading_x()
+        } else if let Some(glyph) = self.glyphs.last() {
+            glyph.trailing_x()
         } else {
             self.rect.right()
         }
     }
+
+    /// The x ranges covered by the characters in the given range of columns, left to right.
+    ///
+    /// This is one range, except for bidirectional text, where a range of characters
+    /// can be split up visually. Use this for painting a selection.
+    pub fn x_ranges(&self, columns: Range<usize>) -> Vec<RangeInclusive<f32>> {
+        let end = columns.end.at_most(self.glyphs.len());
+        let start = columns.start.at_most(end);
+        let mut glyph_ranges: Vec<(f32, f32)> = self.glyphs[start..end]
+            .iter()
+            .map(|glyph| (glyph.pos.x, glyph.max_x()))
+            .collect();
+        glyph_ranges.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut ranges: Vec<RangeInclusive<f32>> = vec![];
+        for (min, max) in glyph_ranges {
+            match ranges.last_mut() {
+                // Allow for a small gap, e.g. from kerning:
+                Some(last) if min <= *last.end() + 0.5 => {
+                    *last = *last.start()..=last.end().max(max);
+                }
+                _ => ranges.push(min..=max),
+            }
+        }
+        ranges
+    }
 }
 
 impl Galley {
@@ -505,7 +681,17 @@ impl Galley {
         let mut it = PCursor::default();
 
         for row in &self.rows {
-            if it.paragraph == pcursor.paragraph {
+            if let Some(elided) = &row.elided {
+                if it.paragraph <= pcursor.paragraph
+                    && pcursor.paragraph < it.paragraph + elided.num_paragraphs
+                {
+                    // Estimate where the paragraph is:
+                    let height = row.elided_paragraph_height(elided);
+                    let y = row.min_y() + (pcursor.paragraph - it.paragraph) as f32 * height;
+                    let x = row.rect.left();
+                    return Rect::from_min_max(pos2(x, y), pos2(x, y + height));
+                }
+            } else if it.paragraph == pcursor.paragraph {
                 // Right paragraph, but is it the right row in the paragraph?
 
                 if it.offset <= pcursor.offset
@@ -525,7 +711,7 @@ impl Galley {
             }
 
             if row.ends_with_newline {
-                it.paragraph += 1;
+                it.paragraph += row.num_paragraph_ends();
                 it.offset = 0;
             } else {
                 it.offset += row.char_count_including_newline();
@@ -553,7 +739,16 @@ impl Galley {
             let y_dist = (row.min_y() - pos.y).abs().min((row.max_y() - pos.y).abs());
             if is_pos_within_row || y_dist < best_y_dist {
                 best_y_dist = y_dist;
-                let column = row.char_at(pos.x);
+                let column = if let Some(elided) = &row.elided {
+                    // The start of the paragraph at the estimated position:
+                    let paragraph = (pos.y - row.min_y()) / row.elided_paragraph_height(elided);
+                    let paragraph =
+                        (paragraph.max(0.0) as usize).at_most(elided.num_paragraphs - 1);
+                    self.elided_column(elided, paragraph, 0)
+                } else {
+                    let column = row.char_at(pos.x);
+                    self.snap_column_to_grapheme(ccursor_index, row, column)
+                };
                 let prefer_next_row = column < row.char_count_excluding_newline();
                 cursor = Cursor {
                     ccursor: CCursor {
@@ -564,11 +759,14 @@ impl Galley {
                         row: row_nr,
                         column,
                     },
-                    pcursor: PCursor {
-                        paragraph: pcursor_it.paragraph,
-                        offset: pcursor_it.offset + column,
-                        prefer_next_row,
-                    },
+                    pcursor: self.pcursor_in_row(
+                        row,
+                        PCursor {
+                            prefer_next_row,
+                            ..pcursor_it
+                        },
+                        column,
+                    ),
                 };
 
                 if is_pos_within_row {
@@ -577,7 +775,7 @@ impl Galley {
             }
             ccursor_index += row.char_count_including_newline();
             if row.ends_with_newline {
-                pcursor_it.paragraph += 1;
+                pcursor_it.paragraph += row.num_paragraph_ends();
                 pcursor_it.offset = 0;
             } else {
                 pcursor_it.offset += row.char_count_including_newline();
@@ -607,7 +805,7 @@ impl Galley {
             let row_char_count = row.char_count_including_newline();
             ccursor.index += row_char_count;
             if row.ends_with_newline {
-                pcursor.paragraph += 1;
+                pcursor.paragraph += row.num_paragraph_ends();
                 pcursor.offset = 0;
             } else {
                 pcursor.offset += row_char_count;
@@ -660,20 +858,19 @@ impl Galley {
                     && !row.ends_with_newline
                     && column >= row.char_count_excluding_newline();
                 if !select_next_row_instead {
-                    pcursor_it.offset += column;
                     return Cursor {
                         ccursor,
                         rcursor: RCursor {
                             row: row_nr,
                             column,
                         },
-                        pcursor: pcursor_it,
+                        pcursor: self.pcursor_in_row(row, pcursor_it, column),
                     };
                 }
             }
             ccursor_it.index += row.char_count_including_newline();
             if row.ends_with_newline {
-                pcursor_it.paragraph += 1;
+                pcursor_it.paragraph += row.num_paragraph_ends();
                 pcursor_it.offset = 0;
             } else {
                 pcursor_it.offset += row.char_count_including_newline();
@@ -708,7 +905,13 @@ impl Galley {
             if row_nr == rcursor.row {
                 ccursor_it.index += rcursor.column.at_most(row.char_count_excluding_newline());
 
-                if row.ends_with_newline {
+                if row.elided.is_some() {
+                    pcursor_it = self.pcursor_in_row(
+                        row,
+                        pcursor_it,
+                        rcursor.column.at_most(row.char_count_excluding_newline()),
+                    );
+                } else if row.ends_with_newline {
                     // Allow offset to go beyond the end of the paragraph
                     pcursor_it.offset += rcursor.column;
                 } else {
@@ -722,7 +925,7 @@ impl Galley {
             }
             ccursor_it.index += row.char_count_including_newline();
             if row.ends_with_newline {
-                pcursor_it.paragraph += 1;
+                pcursor_it.paragraph += row.num_paragraph_ends();
                 pcursor_it.offset = 0;
             } else {
                 pcursor_it.offset += row.char_count_including_newline();
@@ -749,7 +952,26 @@ impl Galley {
         };
 
         for (row_nr, row) in self.rows.iter().enumerate() {
-            if pcursor_it.paragraph == pcursor.paragraph {
+            if let Some(elided) = &row.elided {
+                if pcursor_it.paragraph <= pcursor.paragraph
+                    && pcursor.paragraph < pcursor_it.paragraph + elided.num_paragraphs
+                {
+                    let column = self.elided_column(
+                        elided,
+                        pcursor.paragraph - pcursor_it.paragraph,
+                        pcursor.offset,
+                    );
+                    ccursor_it.index += column;
+                    return Cursor {
+                        ccursor: ccursor_it,
+                        rcursor: RCursor {
+                            row: row_nr,
+                            column,
+                        },
+                        pcursor,
+                    };
+                }
+            } else if pcursor_it.paragraph == pcursor.paragraph {
                 // Right paragraph, but is it the right row in the paragraph?
 
                 if pcursor_it.offset <= pcursor.offset
@@ -779,7 +1001,7 @@ impl Galley {
 
             ccursor_it.index += row.char_count_including_newline();
             if row.ends_with_newline {
-                pcursor_it.paragraph += 1;
+                pcursor_it.paragraph += row.num_paragraph_ends();
                 pcursor_it.offset = 0;
             } else {
                 pcursor_it.offset += row.char_count_including_newline();
@@ -793,9 +1015,170 @@ impl Galley {
     }
 }
 
+/// ## Placeholder rows
+///
+/// The text of paragraphs that were not laid out (see [`Row::elided`])
+/// is scanned to find the paragraph and offset of a cursor in them.
+impl Galley {
+    /// The [`PCursor`] at the given column of the row that starts at `row_start`.
+    fn pcursor_in_row(&self, row: &Row, row_start: PCursor, column: usize) -> PCursor {
+        let elided = match &row.elided {
+            Some(elided) => elided,
+            None => {
+                return PCursor {
+                    offset: row_start.offset + column,
+                    ..row_start
+                }
+            }
+        };
+
+        let mut pcursor = row_start;
+        let mut paragraph_start = 0;
+        for paragraph in self.job.text[elided.byte_range.clone()].split('\n') {
+            let paragraph_end = paragraph_start + paragraph.chars().count();
+            if column <= paragraph_end {
+                pcursor.offset = column - paragraph_start;
+                return pcursor;
+            }
+            pcursor.paragraph += 1;
+            paragraph_start = paragraph_end + 1;
+        }
+        pcursor
+    }
+
+    /// The column in a placeholder row of the given paragraph (counted from the first one in the row)
+    /// and offset in it, clamped to the end of that paragraph.
+    fn elided_column(&self, elided: &ElidedParagraphs, paragraph: usize, offset: usize) -> usize {
+        let mut paragraph_start = 0;
+        for (i, text) in self.job.text[elided.byte_range.clone()]
+            .split('\n')
+            .enumerate()
+        {
+            let num_chars = text.chars().count();
+            if i == paragraph {
+                return paragraph_start + offset.at_most(num_chars);
+            }
+            paragraph_start += num_chars + 1;
+        }
+        elided.num_chars
+    }
+}
+
+/// ## Grapheme clusters
+///
+/// A grapheme cluster is what a user thinks of as one character,
+/// e.g. a letter with its accents, or an emoji made up of several emojis joined together.
+/// Cursors move over whole grapheme clusters, even though they count `char`s.
+impl Galley {
+    /// The start of the grapheme cluster before the given character index.
+    pub fn previous_grapheme_boundary(&self, ccursor: CCursor) -> CCursor {
+        let text = self.text();
+        let mut grapheme_cursor = GraphemeCursor::new(
+            byte_index_from_char_index(text, ccursor.index),
+            text.len(),
+            true,
+        );
+        let index = match grapheme_cursor.prev_boundary(text, 0) {
+            Ok(Some(byte_index)) => text[..byte_index].chars().count(),
+            _ => 0,
+        };
+        CCursor { index, ..ccursor }
+    }
+
+    /// The end of the grapheme cluster after the given character index.
+    pub fn next_grapheme_boundary(&self, ccursor: CCursor) -> CCursor {
+        let text = self.text();
+        let mut grapheme_cursor = GraphemeCursor::new(
+            byte_index_from_char_index(text, ccursor.index),
+            text.len(),
+            true,
+        );
+        let index = match grapheme_cursor.next_boundary(text, 0) {
+            Ok(Some(byte_index)) => text[..byte_index].chars().count(),
+            _ => text.chars().count(),
+        };
+        CCursor { index, ..ccursor }
+    }
+
+    /// Is the given character index between two grapheme clusters (and not inside one)?
+    pub fn is_grapheme_boundary(&self, ccursor: CCursor) -> bool {
+        let text = self.text();
+        let byte_index = byte_index_from_char_index(text, ccursor.index);
+        GraphemeCursor::new(byte_index, text.len(), true)
+            .is_boundary(text, 0)
+            .unwrap_or(true)
+    }
+
+    /// If the column is inside a grapheme cluster, move it to the end of it (but not past the row).
+    fn snap_column_to_grapheme(
+        &self,
+        row_start_ccursor_index: usize,
+        row: &Row,
+        column: usize,
+    ) -> usize {
+        let ccursor = CCursor::new(row_start_ccursor_index + column);
+        if self.is_grapheme_boundary(ccursor) {
+            column
+        } else {
+            let next = self.next_grapheme_boundary(ccursor).index - row_start_ccursor_index;
+            next.at_most(row.char_count_excluding_newline())
+        }
+    }
+
+    /// Like [`Row::char_at`], but never inside a grapheme cluster.
+    fn char_at_in_row(&self, row_nr: usize, x: f32) -> usize {
+        let row_start_ccursor_index = self
+            .from_rcursor(RCursor {
+                row: row_nr,
+                column: 0,
+            })
+            .ccursor
+            .index;
+        let row = &self.rows[row_nr];
+        self.snap_column_to_grapheme(row_start_ccursor_index, row, row.char_at(x))
+    }
+}
+
+fn byte_index_from_char_index(text: &str, char_index: usize) -> usize {
+    text.char_indices()
+        .nth(char_index)
+        .map_or(text.len(), |(byte_index, _)| byte_index)
+}
+
 /// ## Cursor positions
 impl Galley {
+    /// Is the text at the cursor right-to-left?
+    fn is_rtl_at(&self, cursor: &Cursor) -> bool {
+        self.rows.get(cursor.rcursor.row).map_or(false, |row| {
+            row.glyphs
+                .get(cursor.rcursor.column)
+                .or_else(|| row.glyphs.last())
+                .map_or(false, |glyph| glyph.is_rtl())
+        })
+    }
+
+    /// Moves visually left over one grapheme cluster,
+    /// which is forward in right-to-left text.
     pub fn cursor_left_one_character(&self, cursor: &Cursor) -> Cursor {
+        if self.is_rtl_at(cursor) {
+            self.cursor_next_grapheme(cursor)
+        } else {
+            self.cursor_previous_grapheme(cursor)
+        }
+    }
+
+    /// Moves visually right over one grapheme cluster,
+    /// which is backwards in right-to-left text.
+    pub fn cursor_right_one_character(&self, cursor: &Cursor) -> Cursor {
+        if self.is_rtl_at(cursor) {
+            self.cursor_previous_grapheme(cursor)
+        } else {
+            self.cursor_next_grapheme(cursor)
+        }
+    }
+
+    /// Moves back over one grapheme cluster.
+    pub fn cursor_previous_grapheme(&self, cursor: &Cursor) -> Cursor {
         if cursor.ccursor.index == 0 {
             Default::default()
         } else {
@@ -803,16 +1186,17 @@ impl Galley {
                 index: cursor.ccursor.index,
                 prefer_next_row: true, // default to this when navigating. It is more often useful to put cursor at the begging of a row than at the end.
             };
-            self.from_ccursor(ccursor - 1)
+            self.from_ccursor(self.previous_grapheme_boundary(ccursor))
         }
     }
 
-    pub fn cursor_right_one_character(&self, cursor: &Cursor) -> Cursor {
+    /// Moves forward over one grapheme cluster.
+    pub fn cursor_next_grapheme(&self, cursor: &Cursor) -> Cursor {
         let ccursor = CCursor {
             index: cursor.ccursor.index,
             prefer_next_row: true, // default to this when navigating. It is more often useful to put cursor at the begging of a row than at the end.
         };
-        self.from_ccursor(ccursor + 1)
+        self.from_ccursor(self.next_grapheme_boundary(ccursor))
     }
 
     pub fn cursor_up_one_row(&self, cursor: &Cursor) -> Cursor {
@@ -821,6 +1205,16 @@ impl Galley {
         } else {
             let new_row = cursor.rcursor.row - 1;
 
+            if self.rows[cursor.rcursor.row].elided.is_some() || self.rows[new_row].elided.is_some()
+            {
+                // Move by whole paragraphs in text that is not laid out:
+                return self.from_pcursor(PCursor {
+                    paragraph: cursor.pcursor.paragraph.saturating_sub(1),
+                    offset: 0,
+                    prefer_next_row: true,
+                });
+            }
+
             let cursor_is_beyond_end_of_current_row = cursor.rcursor.column
                 >= self.rows[cursor.rcursor.row].char_count_excluding_newline();
 
@@ -837,7 +1231,7 @@ impl Galley {
                     // beyond the end of this row - keep same colum
                     cursor.rcursor.column
                 } else {
-                    self.rows[new_row].char_at(x)
+                    self.char_at_in_row(new_row, x)
                 };
                 RCursor {
                     row: new_row,
@@ -852,6 +1246,16 @@ impl Galley {
         if cursor.rcursor.row + 1 < self.rows.len() {
             let new_row = cursor.rcursor.row + 1;
 
+            if self.rows[cursor.rcursor.row].elided.is_some() || self.rows[new_row].elided.is_some()
+            {
+                // Move by whole paragraphs in text that is not laid out:
+                return self.from_pcursor(PCursor {
+                    paragraph: cursor.pcursor.paragraph + 1,
+                    offset: 0,
+                    prefer_next_row: true,
+                });
+            }
+
             let cursor_is_beyond_end_of_current_row = cursor.rcursor.column
                 >= self.rows[cursor.rcursor.row].char_count_excluding_newline();
 
@@ -868,7 +1272,7 @@ impl Galley {
                     // beyond the end of the next row - keep same column
                     cursor.rcursor.column
                 } else {
-                    self.rows[new_row].char_at(x)
+                    self.char_at_in_row(new_row, x)
                 };
                 RCursor {
                     row: new_row,
@@ -18,7 +18,7 @@ cargo test --workspace --all-targets --all-features
 cargo test --workspace --doc # slow - checks all doc-tests
 cargo fmt --all -- --check
 
-cargo doc -p eframe -p egui -p egui_demo_lib -p egui_extras -p egui_glium -p egui_glow -p egui-winit -p emath -p epaint --lib --no-deps --all-features
+cargo doc -p eframe -p egui -p egui_demo_lib -p egui_extras -p egui_glium -p egui_glow -p egui_software -p egui-winit -p emath -p epaint --lib --no-deps --all-features
 cargo doc --document-private-items --no-deps --all-features
 
 (cd eframe && cargo check --no-default-features --features "glow")
@@ -30,6 +30,7 @@ cargo doc --document-private-items --no-deps --all-features
 (cd egui_extras && cargo check --no-default-features)
 (cd egui_glium && cargo check --no-default-features)
 (cd egui_glow && cargo check --no-default-features)
+(cd egui_software && cargo check --no-default-features)
 (cd egui-winit && cargo check --no-default-features)
 (cd emath && cargo check --no-default-features)
 (cd epaint && cargo check --no-default-features --release)
@@ -41,6 +42,7 @@ cargo doc --document-private-items --no-deps --all-features
 (cd egui_extras && cargo check --all-features)
 (cd egui_glium && cargo check --all-features)
 (cd egui_glow && cargo check --all-features)
+(cd egui_software && cargo check --all-features)
 (cd egui-winit && cargo check --all-features)
 (cd emath && cargo check --all-features)
 (cd epaint && cargo check --all-features)